@@ -1,13 +1,207 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+//! Benchmarks for request hot paths.
+//!
+//! `post_hydration` compares building a page of posts one post at a time
+//! (an author lookup, a like check and a poll lookup per post) with the
+//! batched `PostHydrationService`. It needs a migrated database and is
+//! skipped unless `BENCH_DATABASE_URL` is set:
+//!
+//! ```text
+//! BENCH_DATABASE_URL=postgres://localhost/view_social_bench cargo bench
+//! ```
+//!
+//! The benchmark seeds its own users and posts and deletes them afterwards.
 
-fn benchmark_placeholder(c: &mut Criterion) {
-    c.bench_function("placeholder", |b| {
-        b.iter(|| {
-            // Placeholder benchmark - will be implemented in later tasks
-            1 + 1
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use sqlx::postgres::PgPoolOptions;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+use view_social_backend::application::services::{
+    NotificationService, PollService, PostHydrationService, PostVisibilityService,
+};
+use view_social_backend::domain::entities::{
+    CreatePollRequest, CreatePostRequest, CreateUserRequest, Poll, Post, PostContentType,
+    PostVisibility, User,
+};
+use view_social_backend::domain::repositories::{PostRepository, UserRepository};
+use view_social_backend::infrastructure::database::repositories::{
+    InMemoryNotificationPreferencesRepository, PostgresDeviceTokenRepository,
+    PostgresNotificationRepository, PostgresPollRepository, PostgresPostRepository,
+    PostgresUserRepository,
+};
+
+const PAGE_SIZES: [usize; 3] = [10, 20, 50];
+
+struct Fixture {
+    post_repo: Arc<PostgresPostRepository>,
+    user_repo: Arc<PostgresUserRepository>,
+    poll_service: Arc<PollService>,
+    hydration_service: PostHydrationService,
+    viewer: User,
+    authors: Vec<User>,
+    posts: Vec<Post>,
+}
+
+fn bench_user() -> User {
+    let username = format!("bench_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    User::new(CreateUserRequest {
+        email: format!("{}@example.com", username),
+        username,
+        phone_number: None,
+        password_hash: "not-a-real-hash".to_string(),
+        display_name: None,
+        bio: None,
+    })
+    .expect("valid bench user")
+}
+
+/// Seed one post per author, every fifth post a poll, with the viewer liking
+/// every other post and voting in every poll
+async fn seed(database_url: &str, post_count: usize) -> Fixture {
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(database_url)
+        .await
+        .expect("connect to BENCH_DATABASE_URL");
+
+    let post_repo = Arc::new(PostgresPostRepository::new(pool.clone()));
+    let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
+    let notification_service = Arc::new(NotificationService::new(
+        Arc::new(PostgresNotificationRepository::new(pool.clone())),
+        Arc::new(PostgresDeviceTokenRepository::new(pool.clone())),
+        Arc::new(InMemoryNotificationPreferencesRepository::new()),
+        user_repo.clone(),
+    ));
+    let poll_service = Arc::new(PollService::new(
+        Arc::new(PostgresPollRepository::new(pool.clone())),
+        post_repo.clone(),
+        Arc::new(PostVisibilityService::new(
+            post_repo.clone(),
+            user_repo.clone(),
+        )),
+        notification_service,
+    ));
+    let hydration_service =
+        PostHydrationService::new(post_repo.clone(), user_repo.clone(), poll_service.clone());
+
+    let viewer = user_repo.create(&bench_user()).await.unwrap();
+    let mut authors = Vec::with_capacity(post_count);
+    let mut posts = Vec::with_capacity(post_count);
+
+    for i in 0..post_count {
+        let author = user_repo.create(&bench_user()).await.unwrap();
+        let poll_request = (i % 5 == 0).then(|| CreatePollRequest {
+            options: vec!["Yes".to_string(), "No".to_string()],
+            closes_at: chrono::Utc::now() + chrono::Duration::days(1),
+        });
+
+        let post = Post::new(CreatePostRequest {
+            user_id: author.id,
+            text_content: Some(format!("Benchmark post {}", i)),
+            media_attachments: vec![],
+            is_reel: false,
+            visibility: PostVisibility::Public,
+            poll: poll_request.clone(),
         })
-    });
+        .unwrap();
+
+        let post = match poll_request {
+            Some(poll_request) => {
+                let poll = Poll::new(post.id, poll_request).unwrap();
+                let (post, _) = poll_service.create_poll_post(&post, &poll).await.unwrap();
+                poll_service.vote(viewer.id, post.id, 0).await.unwrap();
+                post
+            }
+            None => post_repo.create(&post).await.unwrap(),
+        };
+
+        if i % 2 == 0 {
+            post_repo.like_post(viewer.id, post.id).await.unwrap();
+        }
+
+        authors.push(author);
+        posts.push(post);
+    }
+
+    Fixture {
+        post_repo,
+        user_repo,
+        poll_service,
+        hydration_service,
+        viewer,
+        authors,
+        posts,
+    }
+}
+
+/// How feed handlers loaded a page before batching: three round trips per post
+async fn hydrate_one_by_one(fixture: &Fixture, posts: &[Post]) -> usize {
+    let mut hydrated = 0;
+    for post in posts {
+        let author = fixture.user_repo.find_by_id(post.user_id).await.unwrap();
+        let is_liked = fixture
+            .post_repo
+            .has_user_liked(fixture.viewer.id, post.id)
+            .await
+            .unwrap();
+        let poll = if post.content_type == PostContentType::Poll {
+            fixture
+                .poll_service
+                .find_poll_view(Some(fixture.viewer.id), post.id)
+                .await
+                .unwrap()
+        } else {
+            None
+        };
+
+        black_box((author, is_liked, poll));
+        hydrated += 1;
+    }
+    hydrated
+}
+
+async fn cleanup(fixture: &Fixture) {
+    // Posts, likes, polls and votes cascade with their users
+    for user in fixture.authors.iter().chain([&fixture.viewer]) {
+        fixture.user_repo.delete(user.id).await.unwrap();
+    }
+}
+
+fn benchmark_post_hydration(c: &mut Criterion) {
+    let Ok(database_url) = std::env::var("BENCH_DATABASE_URL") else {
+        eprintln!("Skipping post_hydration: BENCH_DATABASE_URL is not set");
+        return;
+    };
+
+    let rt = Runtime::new().unwrap();
+    let max_page = PAGE_SIZES.iter().copied().max().unwrap();
+    let fixture = rt.block_on(seed(&database_url, max_page));
+
+    let mut group = c.benchmark_group("post_hydration");
+    for page_size in PAGE_SIZES {
+        let page = &fixture.posts[..page_size];
+
+        group.bench_with_input(
+            BenchmarkId::new("one_by_one", page_size),
+            page,
+            |b, page| b.to_async(&rt).iter(|| hydrate_one_by_one(&fixture, page)),
+        );
+
+        group.bench_with_input(BenchmarkId::new("batched", page_size), page, |b, page| {
+            b.to_async(&rt).iter(|| async {
+                let hydrated = fixture
+                    .hydration_service
+                    .hydrate(Some(fixture.viewer.id), page.to_vec())
+                    .await
+                    .unwrap();
+                black_box(hydrated.len())
+            })
+        });
+    }
+    group.finish();
+
+    rt.block_on(cleanup(&fixture));
 }
 
-criterion_group!(benches, benchmark_placeholder);
+criterion_group!(benches, benchmark_post_hydration);
 criterion_main!(benches);
@@ -0,0 +1,21 @@
+-- @mentions in posts, comments and messages
+
+-- Resolved mention spans rendered by clients
+ALTER TABLE posts ADD COLUMN mentions JSONB NOT NULL DEFAULT '[]'::jsonb;
+ALTER TABLE comments ADD COLUMN mentions JSONB NOT NULL DEFAULT '[]'::jsonb;
+ALTER TABLE messages ADD COLUMN mentions JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+-- One row per mentioned user per source, used for "posts that mention me"
+CREATE TABLE mentions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    mentioned_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    author_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source_type VARCHAR(20) NOT NULL CHECK (source_type IN ('post', 'comment', 'message')),
+    source_id UUID NOT NULL,
+    post_id UUID REFERENCES posts(id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE(source_type, source_id, mentioned_user_id)
+);
+
+CREATE INDEX idx_mentions_mentioned_user ON mentions(mentioned_user_id, created_at DESC);
+CREATE INDEX idx_mentions_post_id ON mentions(post_id) WHERE post_id IS NOT NULL;
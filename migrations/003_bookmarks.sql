@@ -0,0 +1,25 @@
+-- Bookmarks and private saved collections
+
+CREATE TABLE bookmark_collections (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE(user_id, name)
+);
+
+-- Removing a collection keeps its bookmarks in the unsorted list
+CREATE TABLE bookmarks (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    collection_id UUID REFERENCES bookmark_collections(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE(user_id, post_id)
+);
+
+CREATE INDEX idx_bookmark_collections_user_id ON bookmark_collections(user_id);
+CREATE INDEX idx_bookmarks_user_created ON bookmarks(user_id, created_at DESC, id DESC);
+CREATE INDEX idx_bookmarks_collection ON bookmarks(collection_id, created_at DESC, id DESC)
+    WHERE collection_id IS NOT NULL;
@@ -0,0 +1,43 @@
+-- Drafts and scheduled posts
+
+ALTER TABLE posts ADD COLUMN status VARCHAR(20) NOT NULL DEFAULT 'published'
+    CHECK (status IN ('draft', 'scheduled', 'published'));
+ALTER TABLE posts ADD COLUMN publish_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE posts ADD CONSTRAINT chk_scheduled_has_publish_at CHECK (
+    status <> 'scheduled' OR publish_at IS NOT NULL
+);
+
+-- Scheduler lookup for due posts
+CREATE INDEX idx_posts_scheduled_publish_at ON posts(publish_at) WHERE status = 'scheduled';
+CREATE INDEX idx_posts_user_status ON posts(user_id, status, created_at DESC);
+
+-- Unpublished posts must never reach the precomputed feed
+DROP MATERIALIZED VIEW user_feed_cache;
+
+CREATE MATERIALIZED VIEW user_feed_cache AS
+SELECT
+    f.follower_id as user_id,
+    p.id as post_id,
+    p.user_id as post_author_id,
+    p.content_type,
+    p.text_content,
+    p.media_attachments,
+    p.is_reel,
+    p.visibility,
+    p.like_count,
+    p.comment_count,
+    p.reshare_count,
+    p.created_at,
+    u.username as author_username,
+    u.display_name as author_display_name,
+    u.avatar_url as author_avatar_url,
+    u.is_verified as author_is_verified
+FROM follows f
+JOIN posts p ON f.following_id = p.user_id
+JOIN users u ON p.user_id = u.id
+WHERE p.visibility IN ('public', 'followers') AND p.status = 'published'
+ORDER BY p.created_at DESC;
+
+CREATE INDEX idx_user_feed_cache_user_id ON user_feed_cache(user_id);
+CREATE INDEX idx_user_feed_cache_created_at ON user_feed_cache(created_at DESC);
+CREATE INDEX idx_user_feed_cache_is_reel ON user_feed_cache(is_reel);
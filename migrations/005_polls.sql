@@ -0,0 +1,28 @@
+-- Polls as a post content type
+
+ALTER TYPE post_content_type ADD VALUE 'poll';
+ALTER TYPE notification_type ADD VALUE 'poll_closed';
+
+-- vote_counts[i] holds the votes for options[i]
+CREATE TABLE polls (
+    post_id UUID PRIMARY KEY REFERENCES posts(id) ON DELETE CASCADE,
+    options TEXT[] NOT NULL,
+    vote_counts INTEGER[] NOT NULL,
+    closes_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    closed_notified_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CONSTRAINT chk_poll_option_count CHECK (array_length(options, 1) BETWEEN 2 AND 4),
+    CONSTRAINT chk_poll_vote_counts CHECK (array_length(vote_counts, 1) = array_length(options, 1))
+);
+
+-- One vote per user per poll
+CREATE TABLE poll_votes (
+    post_id UUID NOT NULL REFERENCES polls(post_id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    option_index INTEGER NOT NULL CHECK (option_index >= 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (post_id, user_id)
+);
+
+-- Scheduler lookup for polls that closed but have not been announced
+CREATE INDEX idx_polls_pending_close ON polls(closes_at) WHERE closed_notified_at IS NULL;
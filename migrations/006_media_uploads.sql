@@ -0,0 +1,21 @@
+-- Upload sessions for media stored in object storage
+
+CREATE TABLE media_uploads (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    storage_key VARCHAR(512) NOT NULL UNIQUE,
+    media_type VARCHAR(100) NOT NULL,
+    size BIGINT NOT NULL CHECK (size > 0),
+    width INTEGER,
+    height INTEGER,
+    duration INTEGER,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    url TEXT,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    completed_at TIMESTAMP WITH TIME ZONE,
+    CONSTRAINT chk_media_upload_status CHECK (status IN ('pending', 'completed')),
+    CONSTRAINT chk_media_upload_url CHECK (status = 'pending' OR url IS NOT NULL)
+);
+
+CREATE INDEX idx_media_uploads_user_id ON media_uploads(user_id);
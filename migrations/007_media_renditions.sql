@@ -0,0 +1,4 @@
+-- Processed image details recorded when an upload completes
+
+ALTER TABLE media_uploads ADD COLUMN renditions JSONB NOT NULL DEFAULT '[]'::jsonb;
+ALTER TABLE media_uploads ADD COLUMN blurhash VARCHAR(64);
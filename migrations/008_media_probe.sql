@@ -0,0 +1,4 @@
+-- Codecs probed from video and audio uploads when they complete
+
+ALTER TABLE media_uploads ADD COLUMN video_codec VARCHAR(32);
+ALTER TABLE media_uploads ADD COLUMN audio_codec VARCHAR(32);
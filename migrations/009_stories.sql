@@ -0,0 +1,30 @@
+-- Ephemeral stories with per-viewer seen state
+
+-- media holds the attachment built from the story's upload, which the
+-- cleanup job deletes together with the story once it expires
+CREATE TABLE stories (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    media JSONB NOT NULL,
+    caption TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT chk_story_expiry CHECK (expires_at > created_at)
+);
+
+-- One row per viewer; authors never appear in their own viewer list
+CREATE TABLE story_views (
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    viewer_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    viewed_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (story_id, viewer_id)
+);
+
+-- Direct messages sent as a reply to a story keep a link to it while it lives
+ALTER TABLE messages ADD COLUMN story_id UUID REFERENCES stories(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_stories_user_expires ON stories(user_id, expires_at);
+CREATE INDEX idx_stories_expires_at ON stories(expires_at);
+CREATE INDEX idx_story_views_story_viewed ON story_views(story_id, viewed_at DESC, viewer_id DESC);
+CREATE INDEX idx_story_views_viewer ON story_views(viewer_id);
+CREATE INDEX idx_messages_story_id ON messages(story_id) WHERE story_id IS NOT NULL;
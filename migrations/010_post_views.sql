@@ -0,0 +1,17 @@
+-- View counts and watch time, rolled up periodically from Redis
+
+-- Running totals per post; averages are derived from the sums
+CREATE TABLE post_view_stats (
+    post_id UUID PRIMARY KEY REFERENCES posts(id) ON DELETE CASCADE,
+    impression_count BIGINT NOT NULL DEFAULT 0,
+    view_count BIGINT NOT NULL DEFAULT 0,
+    replay_count BIGINT NOT NULL DEFAULT 0,
+    completed_count BIGINT NOT NULL DEFAULT 0,
+    total_watch_ms BIGINT NOT NULL DEFAULT 0,
+    completion_percent_sum DOUBLE PRECISION NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- Copied from post_view_stats on every rollup so feeds can rank without a join
+ALTER TABLE posts ADD COLUMN view_count BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE posts ADD COLUMN avg_completion_percent DOUBLE PRECISION NOT NULL DEFAULT 0;
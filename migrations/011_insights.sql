@@ -0,0 +1,32 @@
+-- Daily rollups behind the creator insights API
+
+-- One row per post and day; view counters arrive with the periodic view
+-- rollup, likes and comments as they happen. author_id is copied from the
+-- post so an author's insights never need to join posts.
+CREATE TABLE post_daily_stats (
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    author_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    impressions BIGINT NOT NULL DEFAULT 0,
+    follower_impressions BIGINT NOT NULL DEFAULT 0,
+    reach BIGINT NOT NULL DEFAULT 0,
+    follower_reach BIGINT NOT NULL DEFAULT 0,
+    likes BIGINT NOT NULL DEFAULT 0,
+    comments BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (post_id, day)
+);
+
+CREATE TABLE user_daily_stats (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    followers_gained BIGINT NOT NULL DEFAULT 0,
+    followers_lost BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, day)
+);
+
+-- Audience split of the all-time view totals
+ALTER TABLE post_view_stats ADD COLUMN reach_count BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE post_view_stats ADD COLUMN follower_impression_count BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE post_view_stats ADD COLUMN follower_reach_count BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_post_daily_stats_author_day ON post_daily_stats(author_id, day);
@@ -0,0 +1,60 @@
+-- Content reports and the moderation queue
+
+-- Users allowed to work the moderation queue
+CREATE TABLE moderators (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One case per reported target; repeated reports add to the open case, and a
+-- resolved case is reopened when the target is reported again
+CREATE TABLE moderation_cases (
+    id UUID PRIMARY KEY,
+    target_type VARCHAR(20) NOT NULL,
+    target_id UUID NOT NULL,
+    target_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'open',
+    report_count INTEGER NOT NULL DEFAULT 0,
+    is_hidden BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    UNIQUE (target_type, target_id)
+);
+
+CREATE INDEX idx_moderation_cases_queue ON moderation_cases(status, report_count DESC, created_at);
+
+-- A reporter can only report the same target once
+CREATE TABLE reports (
+    id UUID PRIMARY KEY,
+    case_id UUID NOT NULL REFERENCES moderation_cases(id) ON DELETE CASCADE,
+    reporter_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_type VARCHAR(20) NOT NULL,
+    target_id UUID NOT NULL,
+    reason VARCHAR(30) NOT NULL,
+    details TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (reporter_id, target_type, target_id)
+);
+
+CREATE INDEX idx_reports_case ON reports(case_id, created_at);
+
+-- Audit log of every decision taken on a case
+CREATE TABLE moderation_actions (
+    id UUID PRIMARY KEY,
+    case_id UUID NOT NULL REFERENCES moderation_cases(id) ON DELETE CASCADE,
+    moderator_id UUID NOT NULL REFERENCES users(id),
+    action VARCHAR(20) NOT NULL,
+    suspended_until TIMESTAMPTZ,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_moderation_actions_case ON moderation_actions(case_id, created_at);
+
+-- Hidden content stays in place for review but is left out of reads
+ALTER TABLE posts ADD COLUMN is_hidden BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE comments ADD COLUMN is_hidden BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE messages ADD COLUMN is_hidden BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE users ADD COLUMN suspended_until TIMESTAMPTZ;
@@ -0,0 +1,5 @@
+-- Content filter rules that flag text for review instead of rejecting it
+
+-- Names of the filter rules that sent the target to the queue; a case can be
+-- opened by the filter alone, without any reports
+ALTER TABLE moderation_cases ADD COLUMN flagged_rules TEXT[] NOT NULL DEFAULT '{}';
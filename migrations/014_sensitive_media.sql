@@ -0,0 +1,16 @@
+-- Sensitive-content warnings, alt text search and the viewer's sensitive media setting
+
+ALTER TABLE posts ADD COLUMN is_sensitive BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE posts ADD COLUMN content_warning VARCHAR(200);
+
+-- 'show', 'blur' or 'hide'
+ALTER TABLE users ADD COLUMN sensitive_media VARCHAR(10) NOT NULL DEFAULT 'blur';
+
+-- Alt text of every attachment, kept in step with media_attachments so post
+-- search can match image descriptions as well as the post text
+ALTER TABLE posts ADD COLUMN media_alt_text TEXT
+    GENERATED ALWAYS AS (jsonb_path_query_array(media_attachments, 'strict $[*].alt_text')::text) STORED;
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+CREATE INDEX idx_posts_text_content_trgm ON posts USING GIN (text_content gin_trgm_ops);
+CREATE INDEX idx_posts_media_alt_text_trgm ON posts USING GIN (media_alt_text gin_trgm_ops);
@@ -0,0 +1,27 @@
+-- Posts pinned to the top of their author's profile
+
+ALTER TABLE posts ADD COLUMN pinned_at TIMESTAMPTZ;
+
+CREATE INDEX idx_posts_pinned ON posts(user_id, pinned_at DESC) WHERE pinned_at IS NOT NULL;
+
+-- A pin only holds for the audience the post had when it was pinned, so
+-- changing the visibility or hiding the post through moderation unpins it.
+-- Deleted posts take their pin with them.
+CREATE OR REPLACE FUNCTION unpin_post_on_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.pinned_at IS NOT NULL AND (
+        NEW.visibility IS DISTINCT FROM OLD.visibility
+        OR (NEW.is_hidden AND NOT OLD.is_hidden)
+    ) THEN
+        NEW.pinned_at = NULL;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_unpin_post_on_change
+    BEFORE UPDATE ON posts
+    FOR EACH ROW
+    EXECUTE FUNCTION unpin_post_on_change();
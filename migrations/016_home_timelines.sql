@@ -0,0 +1,7 @@
+-- Home timelines moved to Redis sorted sets written on publish
+
+-- Never refreshed, so it only ever served stale feeds
+DROP MATERIALIZED VIEW IF EXISTS user_feed_cache;
+
+-- Fan-out-on-read lookup of followed authors too popular to fan out to
+CREATE INDEX idx_users_follower_count ON users(follower_count DESC);
@@ -0,0 +1,9 @@
+-- Keyset pagination: lists are ordered and continued on (timestamp, id)
+
+CREATE INDEX idx_posts_user_created_id ON posts(user_id, created_at DESC, id DESC);
+CREATE INDEX idx_comments_post_created_id ON comments(post_id, created_at, id);
+CREATE INDEX idx_follows_following_created ON follows(following_id, created_at DESC, follower_id DESC);
+CREATE INDEX idx_follows_follower_created ON follows(follower_id, created_at DESC, following_id DESC);
+CREATE INDEX idx_transactions_sender_created_id ON transactions(sender_wallet_id, created_at DESC, id DESC);
+CREATE INDEX idx_transactions_receiver_created_id ON transactions(receiver_wallet_id, created_at DESC, id DESC);
+CREATE INDEX idx_notifications_user_created_id ON notifications(user_id, created_at DESC, id DESC);
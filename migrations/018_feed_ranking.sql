@@ -0,0 +1,3 @@
+-- Author affinity: a viewer's recent likes and comments, looked up per viewer
+CREATE INDEX idx_post_likes_user_created ON post_likes(user_id, created_at DESC);
+CREATE INDEX idx_comments_user_created ON comments(user_id, created_at DESC);
@@ -0,0 +1,14 @@
+-- Explore feed: public posts from accounts the viewer does not follow
+
+-- Muted accounts stay followed but are left out of the muter's feeds
+CREATE TABLE mutes (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    muted_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, muted_user_id),
+    CHECK (user_id <> muted_user_id)
+);
+
+-- Recent public posts scanned for explore candidates
+CREATE INDEX idx_posts_public_published_created ON posts(created_at DESC)
+    WHERE visibility = 'public' AND status = 'published';
@@ -0,0 +1,15 @@
+-- Negative feedback on feed posts: hide, not interested, show fewer from author
+
+-- The author and hashtags are copied from the post so feedback outlives it
+CREATE TABLE feed_feedback (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    post_id UUID NOT NULL,
+    author_id UUID NOT NULL,
+    kind VARCHAR(32) NOT NULL
+        CHECK (kind IN ('hide', 'not_interested', 'show_fewer_from_author')),
+    hashtags TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, post_id, kind)
+);
+
+CREATE INDEX idx_feed_feedback_user_created ON feed_feedback(user_id, created_at DESC);
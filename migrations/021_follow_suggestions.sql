@@ -0,0 +1,13 @@
+-- Follow suggestions ("people you may know")
+
+-- Users a user no longer wants suggested
+CREATE TABLE dismissed_suggestions (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    dismissed_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, dismissed_user_id)
+);
+
+-- Matching contacts saved by phone number to accounts
+CREATE INDEX idx_contacts_user_phone ON contacts(user_id, phone_number)
+    WHERE phone_number IS NOT NULL AND contact_user_id IS NULL;
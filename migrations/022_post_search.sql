@@ -0,0 +1,17 @@
+-- Full-text post search over the post text and attachment alt text
+
+-- Text outweighs alt text when ranking matches. Generated columns cannot
+-- reference media_alt_text, so the alt text is extracted again here.
+ALTER TABLE posts ADD COLUMN search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(text_content, '')), 'A')
+        || setweight(
+            to_tsvector(
+                'english',
+                coalesce(jsonb_path_query_array(media_attachments, 'strict $[*].alt_text')::text, '')
+            ),
+            'B'
+        )
+    ) STORED;
+
+CREATE INDEX idx_posts_search_vector ON posts USING GIN (search_vector);
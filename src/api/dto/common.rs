@@ -43,23 +43,42 @@ pub struct PaginatedResponse<T> {
 
 #[derive(Debug, Serialize)]
 pub struct PaginationMeta {
-    pub total: i64,
+    /// Only reported by lists that know their size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
     pub limit: i64,
+    /// Deprecated: page with `next_cursor` where it is returned
     pub offset: i64,
     pub has_more: bool,
+    /// Pass back as `cursor` to get the next page
+    pub next_cursor: Option<String>,
 }
 
 impl<T> PaginatedResponse<T> {
     pub fn new(data: Vec<T>, total: i64, limit: i64, offset: i64) -> Self {
         let has_more = offset + (data.len() as i64) < total;
+        let mut response = Self::page(data, limit, offset, has_more, None);
+        response.pagination.total = Some(total);
+        response
+    }
+
+    /// Page of a list whose total is not known
+    pub fn page(
+        data: Vec<T>,
+        limit: i64,
+        offset: i64,
+        has_more: bool,
+        next_cursor: Option<String>,
+    ) -> Self {
         Self {
             success: true,
             data,
             pagination: PaginationMeta {
-                total,
+                total: None,
                 limit,
                 offset,
                 has_more,
+                next_cursor,
             },
         }
     }
@@ -0,0 +1,72 @@
+// Creator insights DTOs
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct EngagementTotalsDTO {
+    pub impressions: i64,
+    pub reach: i64,
+    pub likes: i64,
+    pub comments: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyEngagementDTO {
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub stats: EngagementTotalsDTO,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AudienceSegmentDTO {
+    pub impressions: i64,
+    pub reach: i64,
+}
+
+/// Impressions and reach split by whether the viewer follows the author
+#[derive(Debug, Serialize)]
+pub struct AudienceBreakdownDTO {
+    pub followers: AudienceSegmentDTO,
+    pub non_followers: AudienceSegmentDTO,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostInsightsDTO {
+    pub post_id: Uuid,
+    pub days: i64,
+    pub totals: EngagementTotalsDTO,
+    pub audience: AudienceBreakdownDTO,
+    pub daily: Vec<DailyEngagementDTO>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FollowerGrowthDTO {
+    pub date: NaiveDate,
+    pub gained: i64,
+    pub lost: i64,
+    pub net: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TopPostDTO {
+    pub post_id: Uuid,
+    pub content_type: String,
+    pub text_content: Option<String>,
+    pub is_reel: bool,
+    pub created_at: DateTime<Utc>,
+    pub stats: EngagementTotalsDTO,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserInsightsDTO {
+    pub days: i64,
+    pub follower_count: i32,
+    /// Followers gained minus followers lost over the range
+    pub follower_change: i64,
+    pub totals: EngagementTotalsDTO,
+    pub audience: AudienceBreakdownDTO,
+    pub daily: Vec<DailyEngagementDTO>,
+    pub follower_growth: Vec<FollowerGrowthDTO>,
+    pub top_posts: Vec<TopPostDTO>,
+}
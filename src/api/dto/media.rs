@@ -0,0 +1,54 @@
+// Media upload DTOs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMediaUploadRequest {
+    pub media_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MediaUploadDTO {
+    pub id: Uuid,
+    pub media_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration: Option<i32>,
+    pub status: String,
+    pub url: Option<String>,
+    pub renditions: Vec<MediaRenditionDTO>,
+    pub blurhash: Option<String>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaRenditionDTO {
+    /// "thumbnail", "feed", "full" or, for videos, "poster"
+    pub kind: String,
+    pub url: String,
+    pub media_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size: i64,
+}
+
+/// Where to send the file for a new upload. Direct uploads must be confirmed
+/// with POST /media/uploads/:id/complete once the file is stored.
+#[derive(Debug, Serialize)]
+pub struct MediaUploadTargetDTO {
+    pub upload: MediaUploadDTO,
+    pub upload_url: String,
+    pub method: String,
+    pub headers: HashMap<String, String>,
+    pub direct: bool,
+}
@@ -3,7 +3,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{PaymentDataDTO, UserDTO};
+use super::{MentionSpanDTO, PaymentDataDTO, UserDTO};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateConversationRequest {
@@ -28,6 +28,9 @@ pub struct SendMessageRequest {
     pub message_type: String,
     pub content: Option<String>,
     pub media_url: Option<String>,
+    /// ID of a completed media upload, used instead of media_url
+    #[serde(default)]
+    pub media_id: Option<Uuid>,
     pub reply_to_id: Option<Uuid>,
 }
 
@@ -41,6 +44,9 @@ pub struct MessageDTO {
     pub media_url: Option<String>,
     pub payment_data: Option<PaymentDataDTO>,
     pub reply_to_id: Option<Uuid>,
+    /// Set when the message replies to a story that has not expired yet
+    pub story_id: Option<Uuid>,
+    pub mentions: Vec<MentionSpanDTO>,
     pub is_read: bool,
     pub created_at: DateTime<Utc>,
 }
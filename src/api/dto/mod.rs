@@ -1,15 +1,25 @@
 // Data Transfer Objects module
 pub mod auth;
 pub mod common;
+pub mod insights;
+pub mod media;
 pub mod messaging;
+pub mod moderation;
 pub mod payment;
 pub mod post;
+pub mod search;
+pub mod story;
 pub mod user;
 
 // Re-export commonly used DTOs
 pub use auth::*;
 pub use common::*;
+pub use insights::*;
+pub use media::*;
 pub use messaging::*;
+pub use moderation::*;
 pub use payment::*;
 pub use post::*;
+pub use search::*;
+pub use story::*;
 pub use user::*;
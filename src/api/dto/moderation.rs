@@ -0,0 +1,69 @@
+// Reporting and moderation DTOs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReportRequest {
+    /// One of post, comment, message or user
+    pub target_type: String,
+    pub target_id: Uuid,
+    /// One of spam, harassment, hate_speech, violence, nudity, scam,
+    /// misinformation, self_harm or other
+    pub reason: String,
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ModerationActionRequest {
+    /// One of dismiss, remove_content, warn, suspend_user or lock_wallet
+    pub action: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Required for suspend_user
+    #[serde(default)]
+    pub suspend_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModerationCaseDTO {
+    pub id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub target_user_id: Uuid,
+    pub status: String,
+    pub report_count: i32,
+    pub is_hidden: bool,
+    pub flagged_rules: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportDTO {
+    pub id: Uuid,
+    pub reporter_id: Uuid,
+    pub reason: String,
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModerationActionDTO {
+    pub id: Uuid,
+    pub moderator_id: Uuid,
+    pub action: String,
+    pub suspended_until: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModerationCaseDetailDTO {
+    #[serde(flatten)]
+    pub case: ModerationCaseDTO,
+    pub reports: Vec<ReportDTO>,
+    pub actions: Vec<ModerationActionDTO>,
+}
@@ -1,16 +1,44 @@
 // Post-related DTOs
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use super::common::PaginatedResponse;
+use super::media::MediaRenditionDTO;
 use super::UserDTO;
 
 #[derive(Debug, Deserialize)]
 pub struct CreatePostRequest {
     pub text_content: Option<String>,
     pub media_attachments: Vec<MediaAttachmentDTO>,
+    /// IDs of completed media uploads to attach, after any media_attachments
+    #[serde(default)]
+    pub media_ids: Vec<Uuid>,
+    /// Alt text for attached uploads, keyed by upload ID
+    #[serde(default)]
+    pub media_alt_text: HashMap<Uuid, String>,
     pub is_reel: bool,
     pub visibility: String,
+    /// "draft", "scheduled" or "published" (default)
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Required when status is "scheduled"
+    #[serde(default)]
+    pub publish_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub poll: Option<CreatePollRequest>,
+    #[serde(default)]
+    pub is_sensitive: bool,
+    /// Marks the post as sensitive when set
+    #[serde(default)]
+    pub content_warning: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePollRequest {
+    pub options: Vec<String>,
+    pub closes_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +49,26 @@ pub struct MediaAttachmentDTO {
     pub width: Option<i32>,
     pub height: Option<i32>,
     pub duration: Option<i32>,
+    /// Resized copies of processed images; ignored on requests
+    #[serde(default)]
+    pub renditions: Vec<MediaRenditionDTO>,
+    #[serde(default)]
+    pub blurhash: Option<String>,
+    /// Probed from uploaded videos and audio; ignored on requests
+    #[serde(default)]
+    pub video_codec: Option<String>,
+    #[serde(default)]
+    pub audio_codec: Option<String>,
+    #[serde(default)]
+    pub alt_text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MentionSpanDTO {
+    pub user_id: Uuid,
+    pub username: String,
+    pub start: usize,
+    pub end: usize,
 }
 
 #[derive(Debug, Serialize)]
@@ -30,15 +78,70 @@ pub struct PostDTO {
     pub content_type: String,
     pub text_content: Option<String>,
     pub media_attachments: Vec<MediaAttachmentDTO>,
+    pub mentions: Vec<MentionSpanDTO>,
     pub is_reel: bool,
     pub visibility: String,
+    pub status: String,
+    pub publish_at: Option<DateTime<Utc>>,
     pub like_count: i32,
     pub comment_count: i32,
     pub reshare_count: i32,
+    pub view_count: i64,
+    pub is_sensitive: bool,
+    pub content_warning: Option<String>,
+    pub is_pinned: bool,
     pub is_liked: bool,
+    pub poll: Option<PollDTO>,
     pub created_at: DateTime<Utc>,
 }
 
+/// A page of a feed
+#[derive(Debug, Serialize)]
+pub struct FeedResponse {
+    #[serde(flatten)]
+    pub page: PaginatedResponse<PostDTO>,
+    /// Index in `data` of the first post the viewer had already seen, where
+    /// the client shows that they are all caught up
+    pub caught_up_index: Option<usize>,
+    /// IDs of posts by the same author folded into a post in `data`, by the
+    /// ID of the post shown
+    pub collapsed: HashMap<Uuid, Vec<Uuid>>,
+    /// Index in `data` of the first suggested post from accounts the viewer
+    /// does not follow, filling out a short feed
+    pub suggested_index: Option<usize>,
+}
+
+/// Vote counts are only included once the user has voted or the poll has closed
+#[derive(Debug, Serialize)]
+pub struct PollDTO {
+    pub options: Vec<PollOptionDTO>,
+    pub closes_at: DateTime<Utc>,
+    pub is_closed: bool,
+    pub user_vote: Option<i32>,
+    pub total_votes: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PollOptionDTO {
+    pub index: i32,
+    pub text: String,
+    pub vote_count: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PollVoteRequest {
+    pub option_index: i32,
+}
+
+/// One viewing session of a video post, sent when the viewer moves on
+#[derive(Debug, Deserialize)]
+pub struct RecordViewRequest {
+    pub watch_time_ms: i64,
+    pub completion_percent: f64,
+    #[serde(default)]
+    pub replays: i32,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateCommentRequest {
     pub content: String,
@@ -51,7 +154,33 @@ pub struct CommentDTO {
     pub post_id: Uuid,
     pub user: UserDTO,
     pub content: String,
+    pub mentions: Vec<MentionSpanDTO>,
     pub like_count: i32,
     pub parent_comment_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBookmarkRequest {
+    pub collection_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBookmarkCollectionRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookmarkDTO {
+    pub id: Uuid,
+    pub collection_id: Option<Uuid>,
+    pub post: PostDTO,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookmarkCollectionDTO {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,32 @@
+// Search DTOs
+use crate::api::dto::post::PostDTO;
+use crate::api::dto::user::UserDTO;
+use serde::Serialize;
+
+/// An excerpt of a matching post. Highlights are `[start, end)` character
+/// offsets of the matched terms in `text`.
+#[derive(Debug, Serialize)]
+pub struct SearchSnippetDTO {
+    pub text: String,
+    pub highlights: Vec<[usize; 2]>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostSearchHitDTO {
+    pub post: PostDTO,
+    pub rank: f32,
+    pub snippet: SearchSnippetDTO,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HashtagDTO {
+    pub tag: String,
+    pub post_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResultsDTO {
+    pub users: Vec<UserDTO>,
+    pub posts: Vec<PostSearchHitDTO>,
+    pub hashtags: Vec<HashtagDTO>,
+}
@@ -0,0 +1,46 @@
+// Story DTOs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::post::MediaAttachmentDTO;
+use super::UserDTO;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateStoryRequest {
+    /// ID of a completed image or video upload
+    pub media_id: Uuid,
+    #[serde(default)]
+    pub caption: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StoryReplyRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoryDTO {
+    pub id: Uuid,
+    pub media: MediaAttachmentDTO,
+    pub caption: Option<String>,
+    pub seen: bool,
+    /// Only included for the author's own stories
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view_count: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoryGroupDTO {
+    pub user: UserDTO,
+    pub has_unseen: bool,
+    pub stories: Vec<StoryDTO>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoryViewerDTO {
+    pub user: UserDTO,
+    pub viewed_at: DateTime<Utc>,
+}
@@ -17,12 +17,28 @@ pub struct UserDTO {
     pub phone_verified: bool,
     pub follower_count: i32,
     pub following_count: i32,
+    /// "show", "blur" or "hide" for sensitive media in feeds
+    pub sensitive_media: String,
     pub created_at: DateTime<Utc>,
 }
 
+/// A user suggested to follow, with why they were suggested
+#[derive(Debug, Serialize)]
+pub struct SuggestionDTO {
+    pub user: UserDTO,
+    /// Accounts the viewer follows that follow this user
+    pub mutual_follows: i64,
+    pub follows_you: bool,
+    pub in_contacts: bool,
+    pub shared_conversations: i64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdateProfileRequest {
     pub display_name: Option<String>,
     pub bio: Option<String>,
     pub avatar_url: Option<String>,
+    /// "show", "blur" or "hide"
+    #[serde(default)]
+    pub sensitive_media: Option<String>,
 }
@@ -3,18 +3,21 @@ use crate::api::dto::auth::{
     VerifyCodeRequest,
 };
 use crate::api::dto::common::SuccessResponse;
+use crate::application::services::ContentFilterService;
 use crate::application::verification::VerificationService;
 use crate::domain::auth::JwtService;
 use crate::domain::entities::{CreateUserRequest, User};
 use crate::domain::errors::AppError;
 use crate::domain::password::PasswordService;
 use crate::domain::repositories::UserRepository;
+use crate::infrastructure::content_filter::FilterField;
 use axum::{
     extract::State,
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::Utc;
 use std::sync::Arc;
 use tracing::info;
 use uuid::Uuid;
@@ -26,6 +29,7 @@ pub struct AuthState {
     pub password_service: PasswordService,
     pub jwt_service: JwtService,
     pub verification_service: Arc<VerificationService>,
+    pub content_filter: Arc<ContentFilterService>,
 }
 
 impl AuthState {
@@ -33,12 +37,14 @@ impl AuthState {
         user_repo: Arc<dyn UserRepository>,
         jwt_service: JwtService,
         verification_service: Arc<VerificationService>,
+        content_filter: Arc<ContentFilterService>,
     ) -> Self {
         Self {
             user_repo,
             password_service: PasswordService::new(),
             jwt_service,
             verification_service,
+            content_filter,
         }
     }
 }
@@ -90,6 +96,12 @@ pub async fn register(
         ));
     }
 
+    // Display names are screened as in profile updates. Flagged names are not
+    // queued for review, since the user is only saved once verified.
+    let (display_name, _) = state
+        .content_filter
+        .screen_optional(FilterField::DisplayName, payload.display_name)?;
+
     // Hash password
     let password_hash = state.password_service.hash_password(&payload.password)?;
 
@@ -101,7 +113,7 @@ pub async fn register(
             .unwrap_or_else(|| format!("{}@temp.local", Uuid::new_v4())), // Temporary email for phone registration
         phone_number: phone_number.clone(),
         password_hash,
-        display_name: payload.display_name,
+        display_name,
         bio: None,
     };
 
@@ -265,6 +277,17 @@ pub async fn login(
         ));
     }
 
+    // Suspended users cannot sign in until the suspension ends
+    if let Some(until) = user
+        .suspended_until
+        .filter(|_| user.is_suspended(Utc::now()))
+    {
+        return Err(AppError::AuthenticationError(format!(
+            "Account suspended until {}",
+            until.format("%Y-%m-%d %H:%M UTC")
+        )));
+    }
+
     // Generate JWT token
     let token = state.jwt_service.generate_access_token(user.id)?;
 
@@ -348,16 +371,73 @@ pub async fn refresh_token(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::services::{
+        FeedGenerationService, ModerationService, NotificationService, PostVisibilityService,
+    };
     use crate::domain::repositories::MockUserRepository;
+    use crate::infrastructure::content_filter::{
+        ContentFilter, FilterAction, FilterRule, FilterRuleSet,
+    };
+    use crate::infrastructure::database::repositories::{
+        InMemoryNotificationPreferencesRepository, PostgresCommentRepository,
+        PostgresConversationRepository, PostgresDeviceTokenRepository, PostgresMessageRepository,
+        PostgresModerationRepository, PostgresNotificationRepository, PostgresPostRepository,
+        PostgresWalletRepository,
+    };
     use std::sync::Arc;
 
+    /// Content filter with the given rules. Its moderation repositories use a
+    /// lazily connected pool that registration never queries.
+    fn create_test_content_filter(rules: FilterRuleSet) -> Arc<ContentFilterService> {
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let user_repo = Arc::new(MockUserRepository::new());
+        let post_repo = Arc::new(PostgresPostRepository::new(pool.clone()));
+        let notification_service = Arc::new(NotificationService::new(
+            Arc::new(PostgresNotificationRepository::new(pool.clone())),
+            Arc::new(PostgresDeviceTokenRepository::new(pool.clone())),
+            Arc::new(InMemoryNotificationPreferencesRepository::new()),
+            user_repo.clone(),
+        ));
+        let moderation_service = Arc::new(ModerationService::new(
+            Arc::new(PostgresModerationRepository::new(pool.clone())),
+            Arc::new(PostVisibilityService::new(
+                post_repo.clone(),
+                user_repo.clone(),
+            )),
+            post_repo.clone(),
+            Arc::new(FeedGenerationService::new(
+                post_repo,
+                user_repo.clone(),
+                None,
+                None,
+            )),
+            Arc::new(PostgresCommentRepository::new(pool.clone())),
+            Arc::new(PostgresConversationRepository::new(pool.clone())),
+            Arc::new(PostgresMessageRepository::new(pool.clone())),
+            user_repo,
+            Arc::new(PostgresWalletRepository::new(pool)),
+            notification_service,
+            3,
+        ));
+
+        Arc::new(ContentFilterService::new(
+            Arc::new(ContentFilter::new(rules).unwrap()),
+            moderation_service,
+        ))
+    }
+
     fn create_test_auth_state() -> AuthState {
         let user_repo = Arc::new(MockUserRepository::new());
         let jwt_service = JwtService::new("test-secret");
         let verification_service =
             Arc::new(VerificationService::new().expect("Failed to create verification service"));
 
-        AuthState::new(user_repo, jwt_service, verification_service)
+        AuthState::new(
+            user_repo,
+            jwt_service,
+            verification_service,
+            create_test_content_filter(FilterRuleSet::default()),
+        )
     }
 
     #[tokio::test]
@@ -377,4 +457,29 @@ mod tests {
         let result = register(State(state), Json(request)).await;
         // In a real test, we'd assert the result
     }
+
+    #[tokio::test]
+    async fn test_register_rejects_filtered_display_name() {
+        let mut state = create_test_auth_state();
+        state.content_filter = create_test_content_filter(FilterRuleSet {
+            rules: vec![FilterRule {
+                name: "slurs".to_string(),
+                action: FilterAction::Reject,
+                words: vec!["badword".to_string()],
+                domains: Vec::new(),
+                fields: vec![FilterField::DisplayName],
+            }],
+        });
+
+        let request = RegisterRequest {
+            username: "testuser".to_string(),
+            password: "password123".to_string(),
+            identifier: "test@example.com".to_string(),
+            registration_type: "email".to_string(),
+            display_name: Some("Badword Fan".to_string()),
+        };
+
+        let result = register(State(state), Json(request)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
 }
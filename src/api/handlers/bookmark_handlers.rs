@@ -0,0 +1,219 @@
+use crate::api::dto::common::{PaginatedResponse, SuccessResponse};
+use crate::api::dto::post::{
+    BookmarkCollectionDTO, BookmarkDTO, CreateBookmarkCollectionRequest, CreateBookmarkRequest,
+    PostDTO,
+};
+use crate::api::handlers::post_handlers::posts_to_dtos;
+use crate::api::middleware::auth::AuthUser;
+use crate::application::services::{BookmarkService, PostHydrationService};
+use crate::domain::entities::BookmarkCollection;
+use crate::domain::errors::AppError;
+use crate::domain::value_objects::Cursor;
+use crate::infrastructure::storage::MediaUrlSigner;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Application state for bookmark handlers
+#[derive(Clone)]
+pub struct BookmarkState {
+    pub bookmark_service: Arc<BookmarkService>,
+    pub hydration_service: Arc<PostHydrationService>,
+    pub media_urls: MediaUrlSigner,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BookmarkQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    pub cursor: Option<String>,
+    pub collection_id: Option<Uuid>,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+// GET /bookmarks - Get the current user's bookmarked posts
+pub async fn get_bookmarks(
+    auth_user: AuthUser,
+    Query(query): Query<BookmarkQuery>,
+    State(state): State<BookmarkState>,
+) -> Result<Response, AppError> {
+    let limit = query.limit.clamp(1, 100);
+    let cursor = query.cursor.as_deref().map(Cursor::decode).transpose()?;
+
+    let page = state
+        .bookmark_service
+        .get_bookmarks(auth_user.user_id, query.collection_id, cursor, limit)
+        .await?;
+
+    let (bookmarks, posts): (Vec<_>, Vec<_>) = page.items.into_iter().unzip();
+    let mut post_dtos: HashMap<Uuid, PostDTO> = posts_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        Some(auth_user.user_id),
+        posts,
+    )
+    .await?
+    .into_iter()
+    .map(|post_dto| (post_dto.id, post_dto))
+    .collect();
+
+    // Posts whose author no longer exists were dropped during hydration
+    let bookmark_dtos: Vec<BookmarkDTO> = bookmarks
+        .into_iter()
+        .filter_map(|bookmark| {
+            let post = post_dtos.remove(&bookmark.post_id)?;
+            Some(BookmarkDTO {
+                id: bookmark.id,
+                collection_id: bookmark.collection_id,
+                post,
+                created_at: bookmark.created_at,
+            })
+        })
+        .collect();
+
+    let response = PaginatedResponse::page(
+        bookmark_dtos,
+        limit,
+        0,
+        page.next_cursor.is_some(),
+        page.next_cursor.map(|c| c.encode()),
+    );
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// POST /posts/:id/bookmark - Bookmark a post
+pub async fn bookmark_post(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<BookmarkState>,
+    payload: Option<Json<CreateBookmarkRequest>>,
+) -> Result<Response, AppError> {
+    let collection_id = payload.and_then(|Json(p)| p.collection_id);
+
+    let bookmark = state
+        .bookmark_service
+        .add_bookmark(auth_user.user_id, post_id, collection_id)
+        .await?;
+
+    let response = serde_json::json!({
+        "id": bookmark.id,
+        "post_id": bookmark.post_id,
+        "collection_id": bookmark.collection_id,
+        "created_at": bookmark.created_at,
+    });
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Post bookmarked successfully".to_string(),
+            Some(response),
+        )),
+    )
+        .into_response())
+}
+
+// DELETE /posts/:id/bookmark - Remove a bookmark
+pub async fn remove_bookmark(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<BookmarkState>,
+) -> Result<Response, AppError> {
+    state
+        .bookmark_service
+        .remove_bookmark(auth_user.user_id, post_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Bookmark removed successfully".to_string(),
+            None,
+        )),
+    )
+        .into_response())
+}
+
+// GET /bookmarks/collections - List the current user's collections
+pub async fn get_collections(
+    auth_user: AuthUser,
+    State(state): State<BookmarkState>,
+) -> Result<Response, AppError> {
+    let collections = state
+        .bookmark_service
+        .get_collections(auth_user.user_id)
+        .await?;
+
+    let collection_dtos: Vec<BookmarkCollectionDTO> =
+        collections.iter().map(collection_to_dto).collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Collections retrieved successfully".to_string(),
+            Some(serde_json::to_value(collection_dtos).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// POST /bookmarks/collections - Create a collection
+pub async fn create_collection(
+    auth_user: AuthUser,
+    State(state): State<BookmarkState>,
+    Json(payload): Json<CreateBookmarkCollectionRequest>,
+) -> Result<Response, AppError> {
+    let collection = state
+        .bookmark_service
+        .create_collection(auth_user.user_id, payload.name)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SuccessResponse::new(
+            "Collection created successfully".to_string(),
+            Some(serde_json::to_value(collection_to_dto(&collection)).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// DELETE /bookmarks/collections/:id - Delete a collection
+pub async fn delete_collection(
+    auth_user: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(state): State<BookmarkState>,
+) -> Result<Response, AppError> {
+    state
+        .bookmark_service
+        .delete_collection(auth_user.user_id, collection_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Collection deleted successfully".to_string(),
+            None,
+        )),
+    )
+        .into_response())
+}
+
+// Helper function to convert BookmarkCollection entity to BookmarkCollectionDTO
+fn collection_to_dto(collection: &BookmarkCollection) -> BookmarkCollectionDTO {
+    BookmarkCollectionDTO {
+        id: collection.id,
+        name: collection.name.clone(),
+        created_at: collection.created_at,
+    }
+}
@@ -0,0 +1,168 @@
+use crate::api::dto::common::SuccessResponse;
+use crate::api::dto::insights::{
+    AudienceBreakdownDTO, AudienceSegmentDTO, DailyEngagementDTO, EngagementTotalsDTO,
+    FollowerGrowthDTO, PostInsightsDTO, TopPostDTO, UserInsightsDTO,
+};
+use crate::api::middleware::auth::AuthUser;
+use crate::application::services::{InsightsService, DEFAULT_INSIGHTS_DAYS, MAX_INSIGHTS_DAYS};
+use crate::domain::entities::{DailyEngagement, EngagementTotals, Post, PostContentType};
+use crate::domain::errors::AppError;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Application state for insights handlers
+#[derive(Clone)]
+pub struct InsightsState {
+    pub insights_service: Arc<InsightsService>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InsightsQuery {
+    #[serde(default = "default_days")]
+    pub days: i64,
+}
+
+fn default_days() -> i64 {
+    DEFAULT_INSIGHTS_DAYS
+}
+
+// GET /posts/:id/insights - Get how one of your posts performed
+pub async fn get_post_insights(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    Query(query): Query<InsightsQuery>,
+    State(state): State<InsightsState>,
+) -> Result<Response, AppError> {
+    let days = query.days.clamp(1, MAX_INSIGHTS_DAYS);
+    let insights = state
+        .insights_service
+        .get_post_insights(auth_user.user_id, post_id, days)
+        .await?;
+
+    let insights_dto = PostInsightsDTO {
+        post_id: insights.post.id,
+        days,
+        totals: totals_to_dto(&insights.totals),
+        audience: audience_to_dto(&insights.totals),
+        daily: daily_to_dto(&insights.daily),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Post insights retrieved successfully".to_string(),
+            Some(serde_json::to_value(insights_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// GET /users/me/insights - Get how all of your posts performed
+pub async fn get_user_insights(
+    auth_user: AuthUser,
+    Query(query): Query<InsightsQuery>,
+    State(state): State<InsightsState>,
+) -> Result<Response, AppError> {
+    let days = query.days.clamp(1, MAX_INSIGHTS_DAYS);
+    let insights = state
+        .insights_service
+        .get_user_insights(auth_user.user_id, days)
+        .await?;
+
+    let insights_dto = UserInsightsDTO {
+        days,
+        follower_count: insights.follower_count,
+        follower_change: insights
+            .follower_growth
+            .iter()
+            .map(|day| day.net_change())
+            .sum(),
+        totals: totals_to_dto(&insights.totals),
+        audience: audience_to_dto(&insights.totals),
+        daily: daily_to_dto(&insights.daily),
+        follower_growth: insights
+            .follower_growth
+            .iter()
+            .map(|day| FollowerGrowthDTO {
+                date: day.day,
+                gained: day.followers_gained,
+                lost: day.followers_lost,
+                net: day.net_change(),
+            })
+            .collect(),
+        top_posts: insights
+            .top_posts
+            .iter()
+            .map(|(post, totals)| top_post_to_dto(post, totals))
+            .collect(),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Insights retrieved successfully".to_string(),
+            Some(serde_json::to_value(insights_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// Helper function to convert EngagementTotals to EngagementTotalsDTO
+fn totals_to_dto(totals: &EngagementTotals) -> EngagementTotalsDTO {
+    EngagementTotalsDTO {
+        impressions: totals.impressions,
+        reach: totals.reach,
+        likes: totals.likes,
+        comments: totals.comments,
+    }
+}
+
+// Helper function to split EngagementTotals by follower and non-follower viewers
+fn audience_to_dto(totals: &EngagementTotals) -> AudienceBreakdownDTO {
+    AudienceBreakdownDTO {
+        followers: AudienceSegmentDTO {
+            impressions: totals.follower_impressions,
+            reach: totals.follower_reach,
+        },
+        non_followers: AudienceSegmentDTO {
+            impressions: totals.non_follower_impressions(),
+            reach: totals.non_follower_reach(),
+        },
+    }
+}
+
+fn daily_to_dto(daily: &[DailyEngagement]) -> Vec<DailyEngagementDTO> {
+    daily
+        .iter()
+        .map(|day| DailyEngagementDTO {
+            date: day.day,
+            stats: totals_to_dto(&day.totals),
+        })
+        .collect()
+}
+
+fn top_post_to_dto(post: &Post, totals: &EngagementTotals) -> TopPostDTO {
+    let content_type = match post.content_type {
+        PostContentType::Text => "text",
+        PostContentType::Image => "image",
+        PostContentType::Video => "video",
+        PostContentType::Mixed => "mixed",
+        PostContentType::Poll => "poll",
+    };
+
+    TopPostDTO {
+        post_id: post.id,
+        content_type: content_type.to_string(),
+        text_content: post.text_content.clone(),
+        is_reel: post.is_reel,
+        created_at: post.created_at,
+        stats: totals_to_dto(totals),
+    }
+}
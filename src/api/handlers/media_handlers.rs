@@ -0,0 +1,233 @@
+use crate::api::dto::common::SuccessResponse;
+use crate::api::dto::media::{
+    CreateMediaUploadRequest, MediaRenditionDTO, MediaUploadDTO, MediaUploadTargetDTO,
+};
+use crate::api::middleware::auth::AuthUser;
+use crate::application::services::MediaService;
+use crate::domain::entities::{MediaRendition, MediaUpload};
+use crate::domain::errors::AppError;
+use crate::infrastructure::storage::MediaUrlSigner;
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Application state for media handlers
+#[derive(Clone)]
+pub struct MediaState {
+    pub media_service: Arc<MediaService>,
+    pub media_urls: MediaUrlSigner,
+}
+
+// Signature query parameters of a media file URL
+#[derive(Debug, Deserialize)]
+pub struct MediaFileQuery {
+    pub expires: Option<i64>,
+    pub signature: Option<String>,
+}
+
+// POST /media/uploads - Start an upload session
+pub async fn create_media_upload(
+    auth_user: AuthUser,
+    State(state): State<MediaState>,
+    Json(payload): Json<CreateMediaUploadRequest>,
+) -> Result<Response, AppError> {
+    let target = state
+        .media_service
+        .create_upload(
+            auth_user.user_id,
+            payload.media_type,
+            payload.size,
+            payload.width,
+            payload.height,
+            payload.duration,
+        )
+        .await?;
+
+    // Without a presigned URL the file is sent through the API
+    let target_dto = match target.presigned {
+        Some(presigned) => MediaUploadTargetDTO {
+            upload: upload_to_dto(&target.upload, &state.media_urls),
+            upload_url: presigned.url,
+            method: presigned.method,
+            headers: presigned.headers.into_iter().collect(),
+            direct: true,
+        },
+        None => MediaUploadTargetDTO {
+            upload: upload_to_dto(&target.upload, &state.media_urls),
+            upload_url: format!("/api/v1/media/uploads/{}/content", target.upload.id),
+            method: "PUT".to_string(),
+            headers: HashMap::from([(
+                header::CONTENT_TYPE.to_string(),
+                target.upload.media_type.clone(),
+            )]),
+            direct: false,
+        },
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SuccessResponse::new(
+            "Upload created successfully".to_string(),
+            Some(serde_json::to_value(target_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// GET /media/uploads/:id - Get an upload session
+pub async fn get_media_upload(
+    auth_user: AuthUser,
+    Path(upload_id): Path<Uuid>,
+    State(state): State<MediaState>,
+) -> Result<Response, AppError> {
+    let upload = state
+        .media_service
+        .get_upload(auth_user.user_id, upload_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Upload retrieved successfully".to_string(),
+            Some(serde_json::to_value(upload_to_dto(&upload, &state.media_urls)).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// PUT /media/uploads/:id/content - Upload the file through the API
+pub async fn upload_media_content(
+    auth_user: AuthUser,
+    Path(upload_id): Path<Uuid>,
+    State(state): State<MediaState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    // Ignore parameters such as charset
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_lowercase())
+        .ok_or_else(|| AppError::ValidationError("Content-Type header is required".to_string()))?;
+
+    let upload = state
+        .media_service
+        .upload_content(auth_user.user_id, upload_id, &content_type, body.to_vec())
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Upload completed successfully".to_string(),
+            Some(serde_json::to_value(upload_to_dto(&upload, &state.media_urls)).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// POST /media/uploads/:id/complete - Complete a direct upload
+pub async fn complete_media_upload(
+    auth_user: AuthUser,
+    Path(upload_id): Path<Uuid>,
+    State(state): State<MediaState>,
+) -> Result<Response, AppError> {
+    let upload = state
+        .media_service
+        .complete_upload(auth_user.user_id, upload_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Upload completed successfully".to_string(),
+            Some(serde_json::to_value(upload_to_dto(&upload, &state.media_urls)).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// GET /media/files/*key - Serve a stored media file for a signed URL
+pub async fn get_media_file(
+    Path(key): Path<String>,
+    Query(query): Query<MediaFileQuery>,
+    State(state): State<MediaState>,
+) -> Result<Response, AppError> {
+    let now = Utc::now();
+    let expires = match (query.expires, query.signature.as_deref()) {
+        (Some(expires), Some(signature))
+            if state.media_urls.verify(&key, expires, signature, now) =>
+        {
+            expires
+        }
+        _ => return Err(AppError::Forbidden),
+    };
+
+    // Object storage serves the bytes itself when it can
+    if let Some(url) = state.media_service.download_url(&key).await? {
+        return Ok(Redirect::temporary(&url).into_response());
+    }
+
+    let (data, media_type) = state.media_service.read_file(&key).await?;
+
+    // Stored objects never change, so they can be cached until the URL expires
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, media_type.to_string()),
+            (
+                header::CACHE_CONTROL,
+                format!("private, max-age={}", expires - now.timestamp()),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+// Helper function to convert MediaUpload to MediaUploadDTO
+fn upload_to_dto(upload: &MediaUpload, media_urls: &MediaUrlSigner) -> MediaUploadDTO {
+    MediaUploadDTO {
+        id: upload.id,
+        media_type: upload.media_type.clone(),
+        size: upload.size,
+        width: upload.width,
+        height: upload.height,
+        duration: upload.duration,
+        status: upload.status.to_string(),
+        url: upload.url.as_deref().map(|url| media_urls.sign(url)),
+        renditions: renditions_to_dto(&upload.renditions, media_urls),
+        blurhash: upload.blurhash.clone(),
+        video_codec: upload.video_codec.clone(),
+        audio_codec: upload.audio_codec.clone(),
+        expires_at: upload.expires_at,
+        created_at: upload.created_at,
+    }
+}
+
+// Helper function to convert image renditions to DTOs with signed URLs
+pub fn renditions_to_dto(
+    renditions: &[MediaRendition],
+    media_urls: &MediaUrlSigner,
+) -> Vec<MediaRenditionDTO> {
+    renditions
+        .iter()
+        .map(|rendition| MediaRenditionDTO {
+            kind: rendition.kind.to_string(),
+            url: media_urls.sign(&rendition.url),
+            media_type: rendition.media_type.clone(),
+            width: rendition.width,
+            height: rendition.height,
+            size: rendition.size,
+        })
+        .collect()
+}
@@ -3,12 +3,19 @@ use crate::api::dto::messaging::{
     ConversationDTO, CreateConversationRequest, MessageDTO, SendMessageRequest,
 };
 use crate::api::dto::payment::PaymentDataDTO;
+use crate::api::handlers::post_handlers::mentions_to_dto;
 use crate::api::handlers::user_handlers::user_to_dto;
 use crate::api::middleware::auth::AuthUser;
 use crate::api::websocket::{ConnectionManager, WebSocketEvent};
-use crate::domain::entities::{CreateMessageRequest, Message, MessageType};
+use crate::application::services::{ContentFilterService, MediaService, MentionService};
+use crate::domain::entities::{
+    CreateMessageRequest, MentionSource, Message, MessageType, ReportTargetType,
+};
 use crate::domain::errors::AppError;
 use crate::domain::repositories::{ConversationRepository, MessageRepository, UserRepository};
+use crate::domain::value_objects::{Cursor, PageStart};
+use crate::infrastructure::content_filter::FilterField;
+use crate::infrastructure::storage::MediaUrlSigner;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
@@ -26,13 +33,19 @@ pub struct MessageState {
     pub conversation_repo: Arc<dyn ConversationRepository>,
     pub message_repo: Arc<dyn MessageRepository>,
     pub user_repo: Arc<dyn UserRepository>,
+    pub mention_service: Arc<MentionService>,
+    pub media_service: Arc<MediaService>,
+    pub content_filter: Arc<ContentFilterService>,
     pub connection_manager: ConnectionManager,
+    pub media_urls: MediaUrlSigner,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ConversationQuery {
     #[serde(default = "default_limit")]
     pub limit: i64,
+    pub cursor: Option<String>,
+    /// Deprecated: page with `cursor`
     #[serde(default)]
     pub offset: i64,
 }
@@ -70,20 +83,23 @@ pub async fn get_conversations(
 ) -> Result<Response, AppError> {
     // Validate pagination parameters
     let limit = query.limit.min(100).max(1);
-    let offset = query.offset.max(0);
+    let page = PageStart::from_query(query.cursor.as_deref(), query.offset)?;
 
     // Get conversations for the user
-    let conversations = state
+    let mut conversations = state
         .conversation_repo
-        .find_by_user(auth_user.user_id, limit, offset)
+        .find_by_user(auth_user.user_id, page, limit + 1)
         .await?;
+    let next_cursor = Cursor::for_next_page(&mut conversations, limit, |conversation| {
+        Cursor::new(conversation.last_activity_at, conversation.id)
+    });
 
     // Convert to DTOs
     let mut conversation_dtos = Vec::new();
-    for (conv_id, participant_ids, is_group, group_name, created_at) in conversations {
+    for conversation in conversations {
         // Get participant users
         let mut participants = Vec::new();
-        for participant_id in &participant_ids {
+        for participant_id in &conversation.participant_ids {
             if let Some(user) = state.user_repo.find_by_id(*participant_id).await? {
                 participants.push(user_to_dto(&user));
             }
@@ -92,10 +108,10 @@ pub async fn get_conversations(
         // Get last message
         let last_message = state
             .message_repo
-            .find_latest_in_conversation(conv_id)
+            .find_latest_in_conversation(conversation.id)
             .await?;
         let last_message_dto = if let Some(msg) = last_message {
-            Some(message_to_dto(&msg, &state).await?)
+            Some(message_to_dto(&msg, state.user_repo.as_ref(), &state.media_urls).await?)
         } else {
             None
         };
@@ -103,21 +119,27 @@ pub async fn get_conversations(
         // Get unread count
         let unread_count = state
             .message_repo
-            .get_unread_count(conv_id, auth_user.user_id)
+            .get_unread_count(conversation.id, auth_user.user_id)
             .await?;
 
         conversation_dtos.push(ConversationDTO {
-            id: conv_id,
+            id: conversation.id,
             participants,
-            is_group,
-            group_name,
+            is_group: conversation.is_group,
+            group_name: conversation.group_name,
             last_message: last_message_dto,
             unread_count,
-            created_at,
+            created_at: conversation.created_at,
         });
     }
 
-    let response = PaginatedResponse::new(conversation_dtos, 0, limit, offset);
+    let response = PaginatedResponse::page(
+        conversation_dtos,
+        limit,
+        page.offset(),
+        next_cursor.is_some(),
+        next_cursor.map(|c| c.encode()),
+    );
 
     Ok((StatusCode::OK, Json(response)).into_response())
 }
@@ -268,18 +290,21 @@ pub async fn get_messages(
     let limit = query.limit.min(100).max(1);
 
     // Get messages
-    let messages = state
+    let mut messages = state
         .message_repo
-        .find_by_conversation(conversation_id, limit, query.before_id)
+        .find_by_conversation(conversation_id, limit + 1, query.before_id)
         .await?;
+    let has_more = messages.len() as i64 > limit;
+    messages.truncate(limit as usize);
 
     // Convert to DTOs
     let mut message_dtos = Vec::new();
     for message in messages {
-        message_dtos.push(message_to_dto(&message, &state).await?);
+        message_dtos
+            .push(message_to_dto(&message, state.user_repo.as_ref(), &state.media_urls).await?);
     }
 
-    let response = PaginatedResponse::new(message_dtos, 0, limit, 0);
+    let response = PaginatedResponse::page(message_dtos, limit, 0, has_more, None);
 
     Ok((StatusCode::OK, Json(response)).into_response())
 }
@@ -315,29 +340,113 @@ pub async fn send_message(
         }
     };
 
+    // Resolve an uploaded media ID to its URL
+    let media_url = match payload.media_id {
+        Some(media_id) => {
+            if payload.media_url.is_some() {
+                return Err(AppError::ValidationError(
+                    "Provide either media_url or media_id, not both".to_string(),
+                ));
+            }
+
+            let media = state
+                .media_service
+                .resolve_attachment(auth_user.user_id, media_id)
+                .await?;
+
+            let matches_type = match message_type {
+                MessageType::Image => media.is_image(),
+                MessageType::Video => media.is_video(),
+                MessageType::Audio => media.is_audio(),
+                _ => false,
+            };
+            if !matches_type {
+                return Err(AppError::ValidationError(
+                    "Media does not match the message type".to_string(),
+                ));
+            }
+
+            Some(media.url)
+        }
+        None => payload.media_url,
+    };
+
+    let (content, flagged_rules) = state
+        .content_filter
+        .screen_optional(FilterField::Message, payload.content)?;
+
     // Create message
     let message_request = CreateMessageRequest {
         conversation_id,
         sender_id: Some(auth_user.user_id),
         message_type,
-        content: payload.content,
-        media_url: payload.media_url,
+        content,
+        media_url,
         payment_data: None, // Payment data would be set by payment service
         reply_to_id: payload.reply_to_id,
     };
 
-    let message = Message::new(message_request)?;
-    let created_message = state.message_repo.create(&message).await?;
-
-    let message_dto = message_to_dto(&created_message, &state).await?;
+    let mut message = Message::new(message_request)?;
 
-    // Broadcast message to conversation participants via WebSocket
     let (_, participant_ids, _, _, _) = state
         .conversation_repo
         .find_by_id(conversation_id)
         .await?
         .ok_or_else(|| AppError::NotFound("Conversation not found".to_string()))?;
 
+    // Only participants can be mentioned in a conversation
+    if let Some(ref content) = message.content {
+        message.mentions = state
+            .mention_service
+            .resolve_mentions(auth_user.user_id, content)
+            .await?
+            .into_iter()
+            .filter(|span| participant_ids.contains(&span.user_id))
+            .collect();
+    }
+
+    let created_message = state.message_repo.create(&message).await?;
+    state
+        .content_filter
+        .flag(
+            &flagged_rules,
+            ReportTargetType::Message,
+            created_message.id,
+            auth_user.user_id,
+        )
+        .await;
+
+    if !created_message.mentions.is_empty() {
+        if let Some(sender) = state.user_repo.find_by_id(auth_user.user_id).await? {
+            if let Err(e) = state
+                .mention_service
+                .record_mentions(
+                    &sender,
+                    MentionSource::Message,
+                    created_message.id,
+                    None,
+                    &created_message.mentions,
+                )
+                .await
+            {
+                tracing::warn!(
+                    "Failed to record mentions for message {}: {}",
+                    created_message.id,
+                    e
+                );
+            }
+        }
+    }
+
+    let message_dto = message_to_dto(
+        &created_message,
+        state.user_repo.as_ref(),
+        &state.media_urls,
+    )
+    .await?;
+
+    // Broadcast message to conversation participants via WebSocket
+
     // Send WebSocket event to all participants except sender
     let recipients: Vec<Uuid> = participant_ids
         .into_iter()
@@ -509,7 +618,11 @@ pub async fn mark_messages_read(
 }
 
 // Helper function to convert Message entity to MessageDTO
-async fn message_to_dto(message: &Message, state: &MessageState) -> Result<MessageDTO, AppError> {
+pub async fn message_to_dto(
+    message: &Message,
+    user_repo: &dyn UserRepository,
+    media_urls: &MediaUrlSigner,
+) -> Result<MessageDTO, AppError> {
     let message_type = match message.message_type {
         MessageType::Text => "text",
         MessageType::Image => "image",
@@ -520,8 +633,7 @@ async fn message_to_dto(message: &Message, state: &MessageState) -> Result<Messa
     };
 
     let sender = if let Some(sender_id) = message.sender_id {
-        state
-            .user_repo
+        user_repo
             .find_by_id(sender_id)
             .await?
             .map(|u| user_to_dto(&u))
@@ -546,9 +658,11 @@ async fn message_to_dto(message: &Message, state: &MessageState) -> Result<Messa
         sender,
         message_type: message_type.to_string(),
         content: message.content.clone(),
-        media_url: message.media_url.clone(),
+        media_url: message.media_url.as_deref().map(|url| media_urls.sign(url)),
         payment_data,
         reply_to_id: message.reply_to_id,
+        story_id: message.story_id,
+        mentions: mentions_to_dto(&message.mentions),
         is_read,
         created_at: message.created_at,
     })
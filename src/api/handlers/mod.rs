@@ -8,16 +8,28 @@
 //! - Error handling and response formatting
 
 pub mod auth_handlers;
+pub mod bookmark_handlers;
+pub mod insights_handlers;
+pub mod media_handlers;
 pub mod message_handlers;
+pub mod moderation_handlers;
 pub mod notification_handlers;
 pub mod payment_handlers;
 pub mod post_handlers;
+pub mod search_handlers;
+pub mod story_handlers;
 pub mod user_handlers;
 
 // Re-export commonly used types
 pub use auth_handlers::*;
+pub use bookmark_handlers::*;
+pub use insights_handlers::*;
+pub use media_handlers::*;
 pub use message_handlers::*;
+pub use moderation_handlers::*;
 pub use notification_handlers::*;
 pub use payment_handlers::*;
 pub use post_handlers::*;
+pub use search_handlers::*;
+pub use story_handlers::*;
 pub use user_handlers::*;
@@ -0,0 +1,188 @@
+use crate::api::dto::common::SuccessResponse;
+use crate::api::dto::moderation::{
+    CreateReportRequest, ModerationActionDTO, ModerationActionRequest, ModerationCaseDTO,
+    ModerationCaseDetailDTO, ReportDTO,
+};
+use crate::api::middleware::auth::AuthUser;
+use crate::application::services::ModerationService;
+use crate::domain::entities::{ModerationAction, ModerationActionType, ModerationCase, Report};
+use crate::domain::errors::AppError;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Application state for reporting and moderation handlers
+#[derive(Clone)]
+pub struct ModerationState {
+    pub moderation_service: Arc<ModerationService>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ModerationQueueQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+// POST /reports - Report a post, comment, message or user
+pub async fn create_report(
+    auth_user: AuthUser,
+    State(state): State<ModerationState>,
+    Json(payload): Json<CreateReportRequest>,
+) -> Result<Response, AppError> {
+    let is_new = state
+        .moderation_service
+        .file_report(
+            auth_user.user_id,
+            payload.target_type.parse()?,
+            payload.target_id,
+            payload.reason.parse()?,
+            payload.details,
+        )
+        .await?;
+
+    // Reporting the same target again is accepted but not counted twice
+    let (status, message) = if is_new {
+        (StatusCode::CREATED, "Report submitted")
+    } else {
+        (StatusCode::OK, "You have already reported this")
+    };
+
+    Ok((
+        status,
+        Json(SuccessResponse::new(message.to_string(), None)),
+    )
+        .into_response())
+}
+
+// GET /moderation/queue - Get open cases, most reported first
+pub async fn get_moderation_queue(
+    auth_user: AuthUser,
+    Query(query): Query<ModerationQueueQuery>,
+    State(state): State<ModerationState>,
+) -> Result<Response, AppError> {
+    let cases = state
+        .moderation_service
+        .get_queue(auth_user.user_id, query.limit, query.offset)
+        .await?;
+
+    let case_dtos: Vec<ModerationCaseDTO> = cases.iter().map(case_to_dto).collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Moderation queue retrieved successfully".to_string(),
+            Some(serde_json::to_value(case_dtos).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// GET /moderation/cases/:id - Get a case with its reports and actions
+pub async fn get_moderation_case(
+    auth_user: AuthUser,
+    Path(case_id): Path<Uuid>,
+    State(state): State<ModerationState>,
+) -> Result<Response, AppError> {
+    let detail = state
+        .moderation_service
+        .get_case(auth_user.user_id, case_id)
+        .await?;
+
+    let detail_dto = ModerationCaseDetailDTO {
+        case: case_to_dto(&detail.case),
+        reports: detail.reports.iter().map(report_to_dto).collect(),
+        actions: detail.actions.iter().map(action_to_dto).collect(),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Moderation case retrieved successfully".to_string(),
+            Some(serde_json::to_value(detail_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// POST /moderation/cases/:id/actions - Act on a case and resolve it
+pub async fn create_moderation_action(
+    auth_user: AuthUser,
+    Path(case_id): Path<Uuid>,
+    State(state): State<ModerationState>,
+    Json(payload): Json<ModerationActionRequest>,
+) -> Result<Response, AppError> {
+    let action = match payload.action.as_str() {
+        "suspend_user" => {
+            let days = payload.suspend_days.ok_or_else(|| {
+                AppError::ValidationError("suspend_days is required to suspend a user".to_string())
+            })?;
+            ModerationActionType::suspend_for(days, Utc::now())?
+        }
+        name => ModerationActionType::from_parts(name, None)?,
+    };
+
+    let action = state
+        .moderation_service
+        .take_action(auth_user.user_id, case_id, action, payload.note)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SuccessResponse::new(
+            "Moderation action recorded".to_string(),
+            Some(serde_json::to_value(action_to_dto(&action)).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// Helper function to convert ModerationCase to ModerationCaseDTO
+fn case_to_dto(case: &ModerationCase) -> ModerationCaseDTO {
+    ModerationCaseDTO {
+        id: case.id,
+        target_type: case.target_type.to_string(),
+        target_id: case.target_id,
+        target_user_id: case.target_user_id,
+        status: case.status.to_string(),
+        report_count: case.report_count,
+        is_hidden: case.is_hidden,
+        flagged_rules: case.flagged_rules.clone(),
+        created_at: case.created_at,
+        updated_at: case.updated_at,
+        resolved_at: case.resolved_at,
+    }
+}
+
+fn report_to_dto(report: &Report) -> ReportDTO {
+    ReportDTO {
+        id: report.id,
+        reporter_id: report.reporter_id,
+        reason: report.reason.to_string(),
+        details: report.details.clone(),
+        created_at: report.created_at,
+    }
+}
+
+fn action_to_dto(action: &ModerationAction) -> ModerationActionDTO {
+    ModerationActionDTO {
+        id: action.id,
+        moderator_id: action.moderator_id,
+        action: action.action.name().to_string(),
+        suspended_until: action.action.suspended_until(),
+        note: action.note.clone(),
+        created_at: action.created_at,
+    }
+}
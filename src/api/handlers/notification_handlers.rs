@@ -2,6 +2,7 @@ use crate::api::middleware::auth::AuthUser;
 use crate::application::services::NotificationService;
 use crate::domain::entities::{CreateDeviceTokenRequest, DevicePlatform, NotificationPreferences};
 use crate::domain::errors::AppError;
+use crate::domain::value_objects::PageStart;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
@@ -19,6 +20,8 @@ pub type NotificationState = Arc<NotificationService>;
 #[derive(Debug, Deserialize)]
 pub struct NotificationQuery {
     pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    /// Deprecated: page with `cursor`
     pub offset: Option<i64>,
 }
 
@@ -40,6 +43,7 @@ pub struct UpdatePreferencesRequest {
     pub follow_notifications: Option<bool>,
     pub payment_notifications: Option<bool>,
     pub mention_notifications: Option<bool>,
+    pub poll_notifications: Option<bool>,
     pub system_notifications: Option<bool>,
 }
 
@@ -49,6 +53,8 @@ pub struct NotificationListResponse {
     pub notifications: Vec<NotificationResponse>,
     pub total_count: i64,
     pub unread_count: i64,
+    pub has_more: bool,
+    pub next_cursor: Option<String>,
 }
 
 /// Response for single notification
@@ -86,23 +92,26 @@ pub async fn get_notifications(
     State(service): State<NotificationState>,
 ) -> Result<Response, AppError> {
     let limit = query.limit.unwrap_or(20).min(100).max(1);
-    let offset = query.offset.unwrap_or(0).max(0);
+    let page = PageStart::from_query(query.cursor.as_deref(), query.offset.unwrap_or(0))?;
 
     // Get notifications
-    let notifications = service
-        .get_user_notifications(auth_user.user_id, limit, offset)
+    let page = service
+        .get_user_notifications(auth_user.user_id, limit, page)
         .await?;
 
     // Get stats for counts
     let stats = service.get_notification_stats(auth_user.user_id).await?;
 
     let response = NotificationListResponse {
-        notifications: notifications
+        notifications: page
+            .notifications
             .into_iter()
             .map(NotificationResponse::from)
             .collect(),
         total_count: stats.total_notifications,
         unread_count: stats.unread_count,
+        has_more: page.next_cursor.is_some(),
+        next_cursor: page.next_cursor.map(|c| c.encode()),
     };
 
     Ok(Response::builder()
@@ -289,6 +298,9 @@ pub async fn update_notification_preferences(
     if let Some(mention_enabled) = payload.mention_notifications {
         preferences.mention_notifications = mention_enabled;
     }
+    if let Some(poll_enabled) = payload.poll_notifications {
+        preferences.poll_notifications = poll_enabled;
+    }
     if let Some(system_enabled) = payload.system_notifications {
         preferences.system_notifications = system_enabled;
     }
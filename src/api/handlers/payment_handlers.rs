@@ -3,9 +3,12 @@ use crate::api::dto::payment::{SetPinRequest, TransactionDTO, TransferRequest, W
 use crate::api::handlers::user_handlers::user_to_dto;
 use crate::api::middleware::auth::AuthUser;
 use crate::api::websocket::{ConnectionManager, WebSocketEvent};
-use crate::domain::entities::{CreateTransactionRequest, Transaction, TransactionType, Wallet};
+use crate::domain::entities::{
+    CreateTransactionRequest, Transaction, TransactionType, User, Wallet,
+};
 use crate::domain::errors::AppError;
 use crate::domain::repositories::{UserRepository, WalletRepository};
+use crate::domain::value_objects::{Cursor, PageStart};
 use axum::{
     extract::{Query, State},
     http::StatusCode,
@@ -14,8 +17,10 @@ use axum::{
 };
 use rust_decimal::Decimal;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
+use uuid::Uuid;
 
 // Application state for payment handlers
 #[derive(Clone)]
@@ -30,6 +35,8 @@ pub struct PaymentState {
 pub struct TransactionQuery {
     #[serde(default = "default_limit")]
     pub limit: i64,
+    pub cursor: Option<String>,
+    /// Deprecated: page with `cursor`
     #[serde(default)]
     pub offset: i64,
 }
@@ -233,50 +240,68 @@ pub async fn get_transaction_history(
         .ok_or_else(|| AppError::NotFound("Wallet not found".to_string()))?;
 
     // Get transaction history
-    let transactions = state
+    let limit = params.limit.clamp(1, 100);
+    let page = PageStart::from_query(params.cursor.as_deref(), params.offset)?;
+    let mut transactions = state
         .wallet_repo
-        .get_transaction_history(wallet.id, params.limit, params.offset)
+        .get_transaction_history(wallet.id, page, limit + 1)
         .await?;
+    let next_cursor = Cursor::for_next_page(&mut transactions, limit, |transaction| {
+        Cursor::new(transaction.created_at, transaction.id)
+    });
+
+    // Load the wallets and users on the other side of each transaction in two queries
+    let mut wallet_ids: Vec<Uuid> = transactions
+        .iter()
+        .flat_map(|t| [t.sender_wallet_id, t.receiver_wallet_id])
+        .flatten()
+        .collect();
+    wallet_ids.sort_unstable();
+    wallet_ids.dedup();
+    let wallet_owners: HashMap<Uuid, Uuid> = state
+        .wallet_repo
+        .find_wallets_by_ids(&wallet_ids)
+        .await?
+        .into_iter()
+        .map(|w| (w.id, w.user_id))
+        .collect();
+
+    let mut user_ids: Vec<Uuid> = wallet_owners.values().copied().collect();
+    user_ids.sort_unstable();
+    user_ids.dedup();
+    let users: HashMap<Uuid, User> = state
+        .user_repo
+        .find_by_ids(&user_ids)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let user_for_wallet = |wallet_id: Option<Uuid>| {
+        wallet_id
+            .and_then(|id| wallet_owners.get(&id))
+            .and_then(|user_id| users.get(user_id))
+    };
 
     // Convert transactions to DTOs with user information
-    let mut transaction_dtos = Vec::new();
-    for transaction in &transactions {
-        // Get sender user if exists
-        let sender_user = if let Some(sender_wallet_id) = transaction.sender_wallet_id {
-            let sender_wallet = state.wallet_repo.find_by_id(sender_wallet_id).await?;
-            if let Some(sw) = sender_wallet {
-                state.user_repo.find_by_id(sw.user_id).await?
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        // Get receiver user if exists
-        let receiver_user = if let Some(receiver_wallet_id) = transaction.receiver_wallet_id {
-            let receiver_wallet = state.wallet_repo.find_by_id(receiver_wallet_id).await?;
-            if let Some(rw) = receiver_wallet {
-                state.user_repo.find_by_id(rw.user_id).await?
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        transaction_dtos.push(transaction_to_dto(
-            transaction,
-            sender_user.as_ref(),
-            receiver_user.as_ref(),
-        ));
-    }
-
-    // For pagination, we would need to count total transactions
-    // For now, we'll use a simple approach
-    let total = transaction_dtos.len() as i64;
-
-    let response = PaginatedResponse::new(transaction_dtos, total, params.limit, params.offset);
+    let transaction_dtos: Vec<_> = transactions
+        .iter()
+        .map(|transaction| {
+            transaction_to_dto(
+                transaction,
+                user_for_wallet(transaction.sender_wallet_id),
+                user_for_wallet(transaction.receiver_wallet_id),
+            )
+        })
+        .collect();
+
+    let response = PaginatedResponse::page(
+        transaction_dtos,
+        limit,
+        page.offset(),
+        next_cursor.is_some(),
+        next_cursor.map(|c| c.encode()),
+    );
 
     Ok((StatusCode::OK, Json(response)).into_response())
 }
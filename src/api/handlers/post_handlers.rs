@@ -1,14 +1,28 @@
 use crate::api::dto::common::{PaginatedResponse, SuccessResponse};
-use crate::api::dto::post::{CreatePostRequest, MediaAttachmentDTO, PostDTO};
+use crate::api::dto::post::{
+    CommentDTO, CreateCommentRequest, CreatePostRequest, FeedResponse, MediaAttachmentDTO,
+    MentionSpanDTO, PollDTO, PollOptionDTO, PollVoteRequest, PostDTO, RecordViewRequest,
+};
+use crate::api::handlers::media_handlers::renditions_to_dto;
 use crate::api::handlers::user_handlers::user_to_dto;
 use crate::api::middleware::auth::AuthUser;
 use crate::api::websocket::{ConnectionManager, WebSocketEvent};
+use crate::application::services::{
+    ContentFilterService, FeedFilters, FeedGenerationService, FeedSortStrategy, HydratedPost,
+    InsightsService, MediaService, MentionService, PinnedPostService, PollService, PollView,
+    PostHydrationService, PostSchedulerService, PostViewService, PostVisibilityService,
+};
 use crate::domain::entities::{
-    CreatePostRequest as DomainCreatePostRequest, MediaAttachment, Post, PostContentType,
-    PostVisibility,
+    Comment, CreateCommentRequest as DomainCreateCommentRequest,
+    CreatePollRequest as DomainCreatePollRequest, CreatePostRequest as DomainCreatePostRequest,
+    EngagementEvent, FeedFeedbackKind, MediaAttachment, MentionSource, MentionSpan, Poll, Post,
+    PostContentType, PostStatus, PostVisibility, ReportTargetType, User,
 };
 use crate::domain::errors::AppError;
-use crate::domain::repositories::{PostRepository, UserRepository};
+use crate::domain::repositories::{CommentRepository, PostRepository, UserRepository};
+use crate::domain::value_objects::{Cursor, PageStart};
+use crate::infrastructure::content_filter::FilterField;
+use crate::infrastructure::storage::MediaUrlSigner;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
@@ -24,7 +38,20 @@ use uuid::Uuid;
 pub struct PostState {
     pub post_repo: Arc<dyn PostRepository>,
     pub user_repo: Arc<dyn UserRepository>,
+    pub comment_repo: Arc<dyn CommentRepository>,
+    pub feed_service: Arc<FeedGenerationService>,
+    pub hydration_service: Arc<PostHydrationService>,
+    pub mention_service: Arc<MentionService>,
+    pub scheduler_service: Arc<PostSchedulerService>,
+    pub poll_service: Arc<PollService>,
+    pub visibility_service: Arc<PostVisibilityService>,
+    pub media_service: Arc<MediaService>,
+    pub view_service: Arc<PostViewService>,
+    pub insights_service: Arc<InsightsService>,
+    pub content_filter: Arc<ContentFilterService>,
+    pub pinned_post_service: Arc<PinnedPostService>,
     pub connection_manager: ConnectionManager,
+    pub media_urls: MediaUrlSigner,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,44 +66,183 @@ fn default_limit() -> i64 {
     20
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CursorQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    pub cursor: Option<String>,
+    /// Deprecated: page with `cursor`
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostFeedQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    /// Continues a chronological feed; ranked feeds are paged by offset
+    pub cursor: Option<String>,
+    /// Deprecated for chronological feeds: page with `cursor`
+    #[serde(default)]
+    pub offset: i64,
+    /// "chronological" (default) or "algorithmic"
+    pub strategy: Option<String>,
+    #[serde(default)]
+    pub reels_only: bool,
+    #[serde(default)]
+    pub exclude_reels: bool,
+    /// Comma-separated, e.g. "image,video"
+    pub content_types: Option<String>,
+    pub min_engagement: Option<i32>,
+}
+
 // GET /posts/feed - Get user feed
 pub async fn get_feed(
+    auth_user: AuthUser,
+    Query(query): Query<PostFeedQuery>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    // Validate pagination parameters
+    let limit = query.limit.clamp(1, 100);
+    let page = PageStart::from_query(query.cursor.as_deref(), query.offset)?;
+
+    let strategy = match query.strategy.as_deref() {
+        Some(strategy) => strategy.parse::<FeedSortStrategy>()?,
+        None => FeedSortStrategy::Chronological,
+    };
+
+    let content_types = query.content_types.as_deref().map(|types| {
+        types
+            .split(',')
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<String>>()
+    });
+
+    let filters = FeedFilters {
+        reels_only: query.reels_only,
+        exclude_reels: query.exclude_reels,
+        content_types,
+        min_engagement: query.min_engagement,
+    };
+    filters.validate()?;
+
+    let feed = state
+        .feed_service
+        .generate_feed(auth_user.user_id, strategy, filters, limit, page)
+        .await?;
+
+    let post_dtos = posts_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        Some(auth_user.user_id),
+        feed.posts,
+    )
+    .await?;
+    let response = FeedResponse {
+        page: PaginatedResponse::page(
+            post_dtos,
+            limit,
+            page.offset(),
+            feed.has_more,
+            feed.next_cursor.map(|c| c.encode()),
+        ),
+        caught_up_index: feed.caught_up_index,
+        collapsed: feed.collapsed,
+        suggested_index: feed.suggested_index,
+    };
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// GET /reels - Get reels from followed users, most engaging first
+pub async fn get_reels(
     auth_user: AuthUser,
     Query(query): Query<FeedQuery>,
     State(state): State<PostState>,
 ) -> Result<Response, AppError> {
     // Validate pagination parameters
-    let limit = query.limit.min(100).max(1);
+    let limit = query.limit.clamp(1, 100);
     let offset = query.offset.max(0);
 
-    // Get posts from followed users
-    let posts = state
-        .post_repo
-        .find_feed(auth_user.user_id, limit, offset)
+    let feed = state
+        .feed_service
+        .get_reels_feed(auth_user.user_id, limit, offset)
         .await?;
 
-    // Convert posts to DTOs
-    let mut post_dtos = Vec::new();
-    for post in posts {
-        // Get post author
-        let author = state
-            .user_repo
-            .find_by_id(post.user_id)
-            .await?
-            .ok_or_else(|| AppError::NotFound("Post author not found".to_string()))?;
+    let post_dtos = posts_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        Some(auth_user.user_id),
+        feed.posts,
+    )
+    .await?;
+    let response = FeedResponse {
+        page: PaginatedResponse::page(post_dtos, limit, offset, feed.has_more, None),
+        caught_up_index: feed.caught_up_index,
+        collapsed: feed.collapsed,
+        suggested_index: feed.suggested_index,
+    };
 
-        // Check if current user has liked the post
-        let is_liked = state
-            .post_repo
-            .has_user_liked(auth_user.user_id, post.id)
-            .await?;
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
 
-        post_dtos.push(post_to_dto(&post, &author, is_liked));
-    }
+// GET /posts/explore - Get public posts from accounts the user does not follow
+pub async fn get_explore(
+    auth_user: AuthUser,
+    Query(query): Query<FeedQuery>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    // Validate pagination parameters
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
 
-    // For simplicity, we'll return the posts without total count
-    // In a real implementation, we'd query the total count separately
-    let response = PaginatedResponse::new(post_dtos, 0, limit, offset);
+    let feed = state
+        .feed_service
+        .generate_explore_feed(auth_user.user_id, limit, offset)
+        .await?;
+
+    let post_dtos = posts_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        Some(auth_user.user_id),
+        feed.posts,
+    )
+    .await?;
+    let response = FeedResponse {
+        page: PaginatedResponse::page(post_dtos, limit, offset, feed.has_more, None),
+        caught_up_index: feed.caught_up_index,
+        collapsed: feed.collapsed,
+        suggested_index: feed.suggested_index,
+    };
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// GET /posts/trending - Get public posts with high engagement
+pub async fn get_trending(
+    auth_user: Option<AuthUser>,
+    Query(query): Query<FeedQuery>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    // Validate pagination parameters
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+    let viewer_id = auth_user.map(|u| u.user_id);
+
+    let feed = state
+        .feed_service
+        .get_trending_posts(viewer_id, limit, offset)
+        .await?;
+
+    let post_dtos = posts_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        viewer_id,
+        feed.posts,
+    )
+    .await?;
+    let response = PaginatedResponse::page(post_dtos, limit, offset, feed.has_more, None);
 
     Ok((StatusCode::OK, Json(response)).into_response())
 }
@@ -90,7 +256,7 @@ pub async fn create_post(
     // Convert DTO media attachments to domain entities
     let mut media_attachments = Vec::new();
     for media_dto in payload.media_attachments {
-        let media = MediaAttachment::new(
+        let mut media = MediaAttachment::new(
             media_dto.url,
             media_dto.media_type,
             media_dto.size,
@@ -98,9 +264,25 @@ pub async fn create_post(
             media_dto.height,
             media_dto.duration,
         )?;
+        media.set_alt_text(media_dto.alt_text)?;
         media_attachments.push(media);
     }
 
+    let mut media_alt_text = payload.media_alt_text;
+    for mut media in state
+        .media_service
+        .resolve_attachments(auth_user.user_id, &payload.media_ids)
+        .await?
+    {
+        media.set_alt_text(media_alt_text.remove(&media.id))?;
+        media_attachments.push(media);
+    }
+    if !media_alt_text.is_empty() {
+        return Err(AppError::ValidationError(
+            "Alt text was given for media that is not attached".to_string(),
+        ));
+    }
+
     // Parse visibility
     let visibility = match payload.visibility.as_str() {
         "public" => PostVisibility::Public,
@@ -113,17 +295,78 @@ pub async fn create_post(
         }
     };
 
+    // Parse status
+    let status = match payload.status.as_deref() {
+        Some(status) => status.parse()?,
+        None => PostStatus::Published,
+    };
+
+    let poll_request = payload.poll.map(|poll| DomainCreatePollRequest {
+        options: poll.options,
+        closes_at: poll.closes_at,
+    });
+
+    let (text_content, flagged_rules) = state
+        .content_filter
+        .screen_optional(FilterField::Post, payload.text_content)?;
+
     // Create post
     let post_request = DomainCreatePostRequest {
         user_id: auth_user.user_id,
-        text_content: payload.text_content,
+        text_content,
         media_attachments,
         is_reel: payload.is_reel,
         visibility,
+        poll: poll_request.clone(),
     };
 
-    let post = Post::new(post_request)?;
-    let created_post = state.post_repo.create(&post).await?;
+    let mut post = Post::new(post_request)?;
+    post.set_sensitivity(payload.is_sensitive, payload.content_warning)?;
+    if let Some(ref text) = post.text_content {
+        post.mentions = state
+            .mention_service
+            .resolve_mentions(auth_user.user_id, text)
+            .await?;
+    }
+
+    match status {
+        PostStatus::Draft => post.save_as_draft(),
+        PostStatus::Scheduled => {
+            let publish_at = payload.publish_at.ok_or_else(|| {
+                AppError::ValidationError("Scheduled posts require publish_at".to_string())
+            })?;
+            post.schedule(publish_at)?;
+        }
+        PostStatus::Published => {}
+    }
+
+    // A poll has to stay open for a while after it goes live
+    if let (Some(ref poll), Some(publish_at)) = (&poll_request, post.publish_at) {
+        if poll.closes_at <= publish_at {
+            return Err(AppError::ValidationError(
+                "Poll must close after the post is published".to_string(),
+            ));
+        }
+    }
+
+    let (created_post, created_poll) = match poll_request {
+        Some(poll_request) => {
+            let poll = Poll::new(post.id, poll_request)?;
+            let (created_post, created_poll) =
+                state.poll_service.create_poll_post(&post, &poll).await?;
+            (created_post, Some(created_poll))
+        }
+        None => (state.post_repo.create(&post).await?, None),
+    };
+    state
+        .content_filter
+        .flag(
+            &flagged_rules,
+            ReportTargetType::Post,
+            created_post.id,
+            auth_user.user_id,
+        )
+        .await;
 
     // Get post author
     let author = state
@@ -132,7 +375,37 @@ pub async fn create_post(
         .await?
         .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
 
-    let post_dto = post_to_dto(&created_post, &author, false);
+    // Drafts and scheduled posts are fanned out and their mentions recorded
+    // when they are published
+    if created_post.is_published() {
+        state.feed_service.fan_out_to_timelines(&created_post);
+
+        if let Err(e) = state
+            .mention_service
+            .record_mentions(
+                &author,
+                MentionSource::Post,
+                created_post.id,
+                Some(created_post.id),
+                &created_post.mentions,
+            )
+            .await
+        {
+            tracing::warn!(
+                "Failed to record mentions for post {}: {}",
+                created_post.id,
+                e
+            );
+        }
+    }
+
+    let mut post_dto = post_to_dto(&created_post, &author, false, &state.media_urls);
+    post_dto.poll = created_poll.map(|poll| {
+        poll_to_dto(&PollView {
+            poll,
+            user_vote: None,
+        })
+    });
 
     Ok((
         StatusCode::CREATED,
@@ -150,12 +423,11 @@ pub async fn like_post(
     Path(post_id): Path<Uuid>,
     State(state): State<PostState>,
 ) -> Result<Response, AppError> {
-    // Check if post exists
+    // Check if post exists and is visible to the user
     let post = state
-        .post_repo
-        .find_by_id(post_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+        .visibility_service
+        .find_visible_post(Some(auth_user.user_id), post_id)
+        .await?;
 
     // Check if already liked
     if state
@@ -172,6 +444,10 @@ pub async fn like_post(
         .like_post(auth_user.user_id, post_id)
         .await?;
     state.post_repo.increment_like_count(post_id).await?;
+    state
+        .insights_service
+        .record_event(EngagementEvent::PostLiked(post_id))
+        .await;
 
     // Send WebSocket notification to post author (if not self-like)
     if post.user_id != auth_user.user_id {
@@ -206,12 +482,11 @@ pub async fn unlike_post(
     Path(post_id): Path<Uuid>,
     State(state): State<PostState>,
 ) -> Result<Response, AppError> {
-    // Check if post exists
-    let _post = state
-        .post_repo
-        .find_by_id(post_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+    // Check if post exists and is visible to the user
+    state
+        .visibility_service
+        .find_visible_post(Some(auth_user.user_id), post_id)
+        .await?;
 
     // Check if currently liked
     if !state
@@ -228,6 +503,10 @@ pub async fn unlike_post(
         .unlike_post(auth_user.user_id, post_id)
         .await?;
     state.post_repo.decrement_like_count(post_id).await?;
+    state
+        .insights_service
+        .record_event(EngagementEvent::PostUnliked(post_id))
+        .await;
 
     let response = serde_json::json!({
         "success": true,
@@ -237,56 +516,646 @@ pub async fn unlike_post(
     Ok((StatusCode::OK, Json(response)).into_response())
 }
 
-// GET /posts/:id/comments - Get post comments (placeholder)
+// GET /posts/:id/comments - Get post comments
 pub async fn get_post_comments(
+    auth_user: Option<AuthUser>,
+    Path(post_id): Path<Uuid>,
+    Query(query): Query<CursorQuery>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    // Validate pagination parameters
+    let limit = query.limit.clamp(1, 100);
+    let page = PageStart::from_query(query.cursor.as_deref(), query.offset)?;
+
+    // Check if post exists and is visible to the viewer
+    state
+        .visibility_service
+        .find_visible_post(auth_user.map(|u| u.user_id), post_id)
+        .await?;
+
+    let mut comments = state
+        .comment_repo
+        .find_by_post(post_id, page, limit + 1)
+        .await?;
+    let next_cursor = Cursor::for_next_page(&mut comments, limit, |comment| {
+        Cursor::new(comment.created_at, comment.id)
+    });
+
+    let mut comment_dtos = Vec::new();
+    for comment in comments {
+        // Skip comments whose author no longer exists
+        if let Some(author) = state.user_repo.find_by_id(comment.user_id).await? {
+            comment_dtos.push(comment_to_dto(&comment, &author));
+        }
+    }
+
+    let response = PaginatedResponse::page(
+        comment_dtos,
+        limit,
+        page.offset(),
+        next_cursor.is_some(),
+        next_cursor.map(|c| c.encode()),
+    );
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// POST /posts/:id/comments - Add a comment
+pub async fn create_comment(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<Response, AppError> {
+    // Check if post exists and is visible to the user
+    state
+        .visibility_service
+        .find_visible_post(Some(auth_user.user_id), post_id)
+        .await?;
+
+    // Replies must belong to the same post
+    if let Some(parent_id) = payload.parent_comment_id {
+        let parent = state
+            .comment_repo
+            .find_by_id(parent_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Parent comment not found".to_string()))?;
+
+        if parent.post_id != post_id {
+            return Err(AppError::ValidationError(
+                "Parent comment belongs to a different post".to_string(),
+            ));
+        }
+    }
+
+    let screened = state
+        .content_filter
+        .screen(FilterField::Comment, &payload.content)?;
+    let mut comment = Comment::new(DomainCreateCommentRequest {
+        post_id,
+        user_id: auth_user.user_id,
+        parent_comment_id: payload.parent_comment_id,
+        content: screened.text,
+    })?;
+    comment.mentions = state
+        .mention_service
+        .resolve_mentions(auth_user.user_id, &comment.content)
+        .await?;
+
+    let created_comment = state.comment_repo.create(&comment).await?;
+    state
+        .content_filter
+        .flag(
+            &screened.flagged_rules,
+            ReportTargetType::Comment,
+            created_comment.id,
+            auth_user.user_id,
+        )
+        .await;
+    state.post_repo.increment_comment_count(post_id).await?;
+    state
+        .insights_service
+        .record_event(EngagementEvent::PostCommented(post_id))
+        .await;
+
+    let author = state
+        .user_repo
+        .find_by_id(auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if let Err(e) = state
+        .mention_service
+        .record_mentions(
+            &author,
+            MentionSource::Comment,
+            created_comment.id,
+            Some(post_id),
+            &created_comment.mentions,
+        )
+        .await
+    {
+        tracing::warn!(
+            "Failed to record mentions for comment {}: {}",
+            created_comment.id,
+            e
+        );
+    }
+
+    let comment_dto = comment_to_dto(&created_comment, &author);
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SuccessResponse::new(
+            "Comment created successfully".to_string(),
+            Some(serde_json::to_value(comment_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// GET /posts/mentions - Get posts that mention the current user
+pub async fn get_mentions(
+    auth_user: AuthUser,
+    Query(query): Query<CursorQuery>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    // Validate pagination parameters
+    let limit = query.limit.clamp(1, 100);
+    let page = PageStart::from_query(query.cursor.as_deref(), query.offset)?;
+
+    let mut posts = state
+        .mention_service
+        .get_mentioning_posts(auth_user.user_id, page, limit + 1)
+        .await?;
+    let next_cursor = Cursor::for_next_page(&mut posts, limit, |post| {
+        Cursor::new(post.created_at, post.id)
+    });
+    let posts = state
+        .visibility_service
+        .filter_visible(Some(auth_user.user_id), posts)
+        .await?;
+
+    let post_dtos = posts_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        Some(auth_user.user_id),
+        posts,
+    )
+    .await?;
+
+    let response = PaginatedResponse::page(
+        post_dtos,
+        limit,
+        page.offset(),
+        next_cursor.is_some(),
+        next_cursor.map(|c| c.encode()),
+    );
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// GET /posts/drafts - Get the current user's drafts and scheduled posts
+pub async fn get_drafts(
+    auth_user: AuthUser,
+    Query(query): Query<FeedQuery>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    // Offset paging on purpose: drafts are ordered by their publish or edit
+    // time, which changes as the author works on them, so a keyset cursor
+    // would not stay stable. Only the author writes to this list.
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+
+    let mut posts = state
+        .scheduler_service
+        .get_unpublished_posts(auth_user.user_id, limit + 1, offset)
+        .await?;
+    let has_more = posts.len() as i64 > limit;
+    posts.truncate(limit as usize);
+
+    let post_dtos = posts_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        Some(auth_user.user_id),
+        posts,
+    )
+    .await?;
+
+    let response = PaginatedResponse::page(post_dtos, limit, offset, has_more, None);
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// POST /posts/:id/publish - Publish a draft or scheduled post now
+pub async fn publish_post(
+    auth_user: AuthUser,
     Path(post_id): Path<Uuid>,
     State(state): State<PostState>,
 ) -> Result<Response, AppError> {
-    // Check if post exists
-    let _post = state
+    let published = state
+        .scheduler_service
+        .publish_now(auth_user.user_id, post_id)
+        .await?;
+
+    // Let online followers know about the new post
+    let publish_event = WebSocketEvent::PostPublished {
+        post_id,
+        user_id: auth_user.user_id,
+    };
+    state
+        .connection_manager
+        .send_to_users(&published.follower_ids, publish_event)
+        .await;
+
+    let author = state
+        .user_repo
+        .find_by_id(auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let mut post_dto = post_to_dto(&published.post, &author, false, &state.media_urls);
+    post_dto.poll = load_poll_dto(
+        &state.poll_service,
+        Some(auth_user.user_id),
+        &published.post,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Post published successfully".to_string(),
+            Some(serde_json::to_value(post_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// DELETE /posts/:id - Delete one of your posts
+pub async fn delete_post(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    // Other users' posts are reported as missing so their existence is not leaked
+    let post = state
         .post_repo
         .find_by_id(post_id)
         .await?
+        .filter(|post| post.user_id == auth_user.user_id)
         .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
 
-    // For MVP, return empty comments list
-    // Full comment implementation would be in a later task
-    let response = PaginatedResponse::new(Vec::<serde_json::Value>::new(), 0, 20, 0);
+    state.post_repo.delete(post.id).await?;
+    state.feed_service.remove_from_timelines(&post);
+    state.feed_service.invalidate_trending_cache().await?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "message": "Post deleted successfully"
+    });
 
     Ok((StatusCode::OK, Json(response)).into_response())
 }
 
-// POST /posts/:id/comments - Add a comment (placeholder)
-pub async fn create_comment(
-    _auth_user: AuthUser,
-    Path(_post_id): Path<Uuid>,
+// GET /posts/:id - Get a single post
+pub async fn get_post(
+    auth_user: Option<AuthUser>,
+    Path(post_id): Path<Uuid>,
     State(state): State<PostState>,
-    Json(_payload): Json<serde_json::Value>,
 ) -> Result<Response, AppError> {
-    // Check if post exists
-    let _post = state
-        .post_repo
-        .find_by_id(_post_id)
+    let viewer_id = auth_user.map(|u| u.user_id);
+
+    let post = state
+        .visibility_service
+        .find_visible_post(viewer_id, post_id)
+        .await?;
+
+    let author = state
+        .user_repo
+        .find_by_id(post.user_id)
         .await?
-        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound("Post author not found".to_string()))?;
+
+    let is_liked = match viewer_id {
+        Some(viewer_id) => state.post_repo.has_user_liked(viewer_id, post.id).await?,
+        None => false,
+    };
+
+    let mut post_dto = post_to_dto(&post, &author, is_liked, &state.media_urls);
+    post_dto.poll = load_poll_dto(&state.poll_service, viewer_id, &post).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Post retrieved successfully".to_string(),
+            Some(serde_json::to_value(post_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// GET /posts/:id/poll - Get a post's poll
+pub async fn get_poll(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    let view = state
+        .poll_service
+        .get_poll(auth_user.user_id, post_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Poll retrieved successfully".to_string(),
+            Some(serde_json::to_value(poll_to_dto(&view)).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// POST /posts/:id/poll/vote - Vote in a post's poll
+pub async fn vote_poll(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+    Json(payload): Json<PollVoteRequest>,
+) -> Result<Response, AppError> {
+    let view = state
+        .poll_service
+        .vote(auth_user.user_id, post_id, payload.option_index)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Vote recorded successfully".to_string(),
+            Some(serde_json::to_value(poll_to_dto(&view)).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// POST /posts/:id/views - Report a viewing session of a video post
+pub async fn record_post_view(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+    Json(payload): Json<RecordViewRequest>,
+) -> Result<Response, AppError> {
+    let counted = state
+        .view_service
+        .record_view(
+            auth_user.user_id,
+            post_id,
+            payload.watch_time_ms,
+            payload.completion_percent,
+            payload.replays,
+        )
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(SuccessResponse::new(
+            "View recorded".to_string(),
+            Some(serde_json::json!({ "counted": counted })),
+        )),
+    )
+        .into_response())
+}
+
+// POST /posts/:id/pin - Pin a post to the top of the current user's profile
+pub async fn pin_post(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    let post = state
+        .pinned_post_service
+        .pin_post(auth_user.user_id, post_id)
+        .await?;
+
+    let author = state
+        .user_repo
+        .find_by_id(auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let is_liked = state
+        .post_repo
+        .has_user_liked(auth_user.user_id, post.id)
+        .await?;
+
+    let mut post_dto = post_to_dto(&post, &author, is_liked, &state.media_urls);
+    post_dto.poll = load_poll_dto(&state.poll_service, Some(auth_user.user_id), &post).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Post pinned successfully".to_string(),
+            Some(serde_json::to_value(post_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// DELETE /posts/:id/pin - Unpin a post from the current user's profile
+pub async fn unpin_post(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    state
+        .pinned_post_service
+        .unpin_post(auth_user.user_id, post_id)
+        .await?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "message": "Post unpinned successfully"
+    });
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// POST /posts/:id/hide - Hide a post from the current user's feeds
+pub async fn hide_post(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    give_feed_feedback(&state, auth_user.user_id, post_id, FeedFeedbackKind::Hide).await
+}
+
+// POST /posts/:id/not-interested - Hide a post and show fewer posts like it
+pub async fn mark_not_interested(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    give_feed_feedback(
+        &state,
+        auth_user.user_id,
+        post_id,
+        FeedFeedbackKind::NotInterested,
+    )
+    .await
+}
+
+// POST /posts/:id/show-fewer - Show fewer posts from the post's author
+pub async fn show_fewer_from_author(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    give_feed_feedback(
+        &state,
+        auth_user.user_id,
+        post_id,
+        FeedFeedbackKind::ShowFewerFromAuthor,
+    )
+    .await
+}
+
+async fn give_feed_feedback(
+    state: &PostState,
+    user_id: Uuid,
+    post_id: Uuid,
+    kind: FeedFeedbackKind,
+) -> Result<Response, AppError> {
+    state
+        .feed_service
+        .give_feedback(user_id, post_id, kind)
+        .await?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "message": "Feedback recorded",
+        "feedback": kind
+    });
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// DELETE /posts/:id/feedback - Take back feedback given on a post
+pub async fn undo_feed_feedback(
+    auth_user: AuthUser,
+    Path(post_id): Path<Uuid>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    state
+        .feed_service
+        .undo_feedback(auth_user.user_id, post_id)
+        .await?;
 
-    // For MVP, return a placeholder response
-    // Full comment implementation would be in a later task
     let response = serde_json::json!({
         "success": true,
-        "message": "Comment feature coming soon"
+        "message": "Feedback removed"
     });
 
     Ok((StatusCode::OK, Json(response)).into_response())
 }
 
+// GET /users/:id/posts - Get a user's posts, pinned posts first
+pub async fn get_user_posts(
+    auth_user: Option<AuthUser>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<FeedQuery>,
+    State(state): State<PostState>,
+) -> Result<Response, AppError> {
+    // Validate pagination parameters
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+    let viewer_id = auth_user.map(|u| u.user_id);
+
+    state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let mut posts = state
+        .pinned_post_service
+        .get_profile_posts(viewer_id, user_id, limit + 1, offset)
+        .await?;
+    let has_more = posts.len() as i64 > limit;
+    posts.truncate(limit as usize);
+
+    let post_dtos = posts_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        viewer_id,
+        posts,
+    )
+    .await?;
+
+    let response = PaginatedResponse::page(post_dtos, limit, offset, has_more, None);
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// Helper function to convert a page of posts to DTOs as seen by the viewer,
+// loading authors, likes and polls in batches
+pub async fn posts_to_dtos(
+    hydration_service: &PostHydrationService,
+    media_urls: &MediaUrlSigner,
+    viewer_id: Option<Uuid>,
+    posts: Vec<Post>,
+) -> Result<Vec<PostDTO>, AppError> {
+    let hydrated = hydration_service.hydrate(viewer_id, posts).await?;
+    Ok(hydrated
+        .iter()
+        .map(|hydrated| hydrated_post_to_dto(hydrated, media_urls))
+        .collect())
+}
+
+// Helper function to convert a HydratedPost to PostDTO
+pub fn hydrated_post_to_dto(hydrated: &HydratedPost, media_urls: &MediaUrlSigner) -> PostDTO {
+    let mut post_dto = post_to_dto(
+        &hydrated.post,
+        &hydrated.author,
+        hydrated.is_liked,
+        media_urls,
+    );
+    post_dto.poll = hydrated.poll.as_ref().map(poll_to_dto);
+    post_dto
+}
+
+// Helper function to load the poll for a poll post as seen by the viewer
+pub async fn load_poll_dto(
+    poll_service: &PollService,
+    viewer_id: Option<Uuid>,
+    post: &Post,
+) -> Result<Option<PollDTO>, AppError> {
+    if post.content_type != PostContentType::Poll {
+        return Ok(None);
+    }
+
+    let view = poll_service.find_poll_view(viewer_id, post.id).await?;
+    Ok(view.as_ref().map(poll_to_dto))
+}
+
+// Helper function to convert a PollView to PollDTO, hiding counts until results are visible
+fn poll_to_dto(view: &PollView) -> PollDTO {
+    let results_visible = view.results_visible();
+
+    let options = view
+        .poll
+        .options
+        .iter()
+        .zip(view.poll.vote_counts.iter())
+        .enumerate()
+        .map(|(index, (text, count))| PollOptionDTO {
+            index: index as i32,
+            text: text.clone(),
+            vote_count: results_visible.then_some(*count),
+        })
+        .collect();
+
+    PollDTO {
+        options,
+        closes_at: view.poll.closes_at,
+        is_closed: view.poll.is_closed(),
+        user_vote: view.user_vote,
+        total_votes: results_visible.then(|| view.poll.total_votes()),
+    }
+}
+
 // Helper function to convert Post entity to PostDTO
-fn post_to_dto(post: &Post, author: &crate::domain::entities::User, is_liked: bool) -> PostDTO {
+pub fn post_to_dto(
+    post: &Post,
+    author: &User,
+    is_liked: bool,
+    media_urls: &MediaUrlSigner,
+) -> PostDTO {
     let content_type = match post.content_type {
         PostContentType::Text => "text",
         PostContentType::Image => "image",
         PostContentType::Video => "video",
         PostContentType::Mixed => "mixed",
+        PostContentType::Poll => "poll",
     };
 
     let visibility = match post.visibility {
@@ -298,14 +1167,7 @@ fn post_to_dto(post: &Post, author: &crate::domain::entities::User, is_liked: bo
     let media_attachments = post
         .media_attachments
         .iter()
-        .map(|media| MediaAttachmentDTO {
-            url: media.url.clone(),
-            media_type: media.media_type.clone(),
-            size: media.size,
-            width: media.width,
-            height: media.height,
-            duration: media.duration,
-        })
+        .map(|media| media_attachment_to_dto(media, media_urls))
         .collect();
 
     PostDTO {
@@ -314,12 +1176,67 @@ fn post_to_dto(post: &Post, author: &crate::domain::entities::User, is_liked: bo
         content_type: content_type.to_string(),
         text_content: post.text_content.clone(),
         media_attachments,
+        mentions: mentions_to_dto(&post.mentions),
         is_reel: post.is_reel,
         visibility: visibility.to_string(),
+        status: post.status.to_string(),
+        publish_at: post.publish_at,
         like_count: post.like_count,
         comment_count: post.comment_count,
         reshare_count: post.reshare_count,
+        view_count: post.view_count,
+        is_sensitive: post.is_sensitive,
+        content_warning: post.content_warning.clone(),
+        is_pinned: post.is_pinned(),
         is_liked,
+        poll: None,
         created_at: post.created_at,
     }
 }
+
+// Helper function to convert a MediaAttachment to MediaAttachmentDTO with signed URLs
+pub fn media_attachment_to_dto(
+    media: &MediaAttachment,
+    media_urls: &MediaUrlSigner,
+) -> MediaAttachmentDTO {
+    MediaAttachmentDTO {
+        url: media_urls.sign(&media.url),
+        media_type: media.media_type.clone(),
+        size: media.size,
+        width: media.width,
+        height: media.height,
+        duration: media.duration,
+        renditions: renditions_to_dto(&media.renditions, media_urls),
+        blurhash: media.blurhash.clone(),
+        video_codec: media.video_codec.clone(),
+        audio_codec: media.audio_codec.clone(),
+        alt_text: media.alt_text.clone(),
+    }
+}
+
+// Helper function to convert Comment entity to CommentDTO
+fn comment_to_dto(comment: &Comment, author: &User) -> CommentDTO {
+    CommentDTO {
+        id: comment.id,
+        post_id: comment.post_id,
+        user: user_to_dto(author),
+        content: comment.content.clone(),
+        mentions: mentions_to_dto(&comment.mentions),
+        like_count: comment.like_count,
+        parent_comment_id: comment.parent_comment_id,
+        created_at: comment.created_at,
+    }
+}
+
+// Helper function to convert mention spans to DTOs
+pub fn mentions_to_dto(mentions: &[MentionSpan]) -> Vec<MentionSpanDTO> {
+    mentions
+        .iter()
+        .map(|span| MentionSpanDTO {
+            user_id: span.user_id,
+            username: span.username.clone(),
+            start: span.start,
+            end: span.end,
+        })
+        .collect()
+}
@@ -0,0 +1,174 @@
+use crate::api::dto::common::{PaginatedResponse, SuccessResponse};
+use crate::api::dto::search::{HashtagDTO, PostSearchHitDTO, SearchResultsDTO, SearchSnippetDTO};
+use crate::api::handlers::post_handlers::posts_to_dtos;
+use crate::api::handlers::user_handlers::user_to_dto;
+use crate::api::middleware::auth::AuthUser;
+use crate::application::services::{PostHydrationService, SearchService};
+use crate::domain::entities::{PostSearchHit, PostSearchQuery};
+use crate::domain::errors::AppError;
+use crate::infrastructure::storage::MediaUrlSigner;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Application state for search handlers
+#[derive(Clone)]
+pub struct SearchState {
+    pub search_service: Arc<SearchService>,
+    pub hydration_service: Arc<PostHydrationService>,
+    pub media_urls: MediaUrlSigner,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostSearchParams {
+    pub q: String,
+    pub author_id: Option<Uuid>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub reels_only: bool,
+    pub hashtag: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    pub q: String,
+    #[serde(default = "default_section_limit")]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+fn default_section_limit() -> i64 {
+    5
+}
+
+// GET /search/posts - Full-text search of posts visible to the viewer
+pub async fn search_posts(
+    auth_user: Option<AuthUser>,
+    Query(params): Query<PostSearchParams>,
+    State(state): State<SearchState>,
+) -> Result<Response, AppError> {
+    let limit = params.limit.clamp(1, 100);
+    let offset = params.offset.max(0);
+    let viewer_id = auth_user.map(|u| u.user_id);
+
+    let query = PostSearchQuery {
+        text: params.q,
+        author_id: params.author_id,
+        since: params.since,
+        until: params.until,
+        content_type: params.content_type.as_deref().map(str::parse).transpose()?,
+        reels_only: params.reels_only,
+        hashtag: params.hashtag,
+    };
+    let page = state
+        .search_service
+        .search_posts(viewer_id, query, limit, offset)
+        .await?;
+
+    let hits = hits_to_dtos(
+        &state.hydration_service,
+        &state.media_urls,
+        viewer_id,
+        page.hits,
+    )
+    .await?;
+    let response = PaginatedResponse::page(hits, limit, offset, page.has_more, None);
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// GET /search - Search users, posts and hashtags at once
+pub async fn search(
+    auth_user: Option<AuthUser>,
+    Query(params): Query<SearchParams>,
+    State(state): State<SearchState>,
+) -> Result<Response, AppError> {
+    let limit = params.limit.clamp(1, 20);
+    let viewer_id = auth_user.map(|u| u.user_id);
+
+    let results = state
+        .search_service
+        .search(viewer_id, &params.q, limit)
+        .await?;
+
+    let results_dto = SearchResultsDTO {
+        users: results.users.iter().map(user_to_dto).collect(),
+        posts: hits_to_dtos(
+            &state.hydration_service,
+            &state.media_urls,
+            viewer_id,
+            results.posts,
+        )
+        .await?,
+        hashtags: results
+            .hashtags
+            .into_iter()
+            .map(|hashtag| HashtagDTO {
+                tag: hashtag.tag,
+                post_count: hashtag.post_count,
+            })
+            .collect(),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Search results retrieved successfully".to_string(),
+            Some(serde_json::to_value(results_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// Helper function to convert search hits to DTOs, hydrating their posts in
+// one batch and keeping the ranking order
+async fn hits_to_dtos(
+    hydration_service: &PostHydrationService,
+    media_urls: &MediaUrlSigner,
+    viewer_id: Option<Uuid>,
+    hits: Vec<PostSearchHit>,
+) -> Result<Vec<PostSearchHitDTO>, AppError> {
+    let posts = hits.iter().map(|hit| hit.post.clone()).collect();
+    let mut post_dtos: HashMap<Uuid, _> =
+        posts_to_dtos(hydration_service, media_urls, viewer_id, posts)
+            .await?
+            .into_iter()
+            .map(|dto| (dto.id, dto))
+            .collect();
+
+    Ok(hits
+        .into_iter()
+        .filter_map(|hit| {
+            post_dtos.remove(&hit.post.id).map(|post| PostSearchHitDTO {
+                post,
+                rank: hit.rank,
+                snippet: SearchSnippetDTO {
+                    text: hit.snippet.text,
+                    highlights: hit
+                        .snippet
+                        .highlights
+                        .into_iter()
+                        .map(|(start, end)| [start, end])
+                        .collect(),
+                },
+            })
+        })
+        .collect())
+}
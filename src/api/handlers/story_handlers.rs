@@ -0,0 +1,250 @@
+use crate::api::dto::common::{PaginatedResponse, SuccessResponse};
+use crate::api::dto::story::{
+    CreateStoryRequest, StoryDTO, StoryGroupDTO, StoryReplyRequest, StoryViewerDTO,
+};
+use crate::api::handlers::message_handlers::message_to_dto;
+use crate::api::handlers::post_handlers::media_attachment_to_dto;
+use crate::api::handlers::user_handlers::user_to_dto;
+use crate::api::middleware::auth::AuthUser;
+use crate::api::websocket::{ConnectionManager, WebSocketEvent};
+use crate::application::services::{ContentFilterService, StoryService};
+use crate::domain::entities::{ReportTargetType, Story};
+use crate::domain::errors::AppError;
+use crate::domain::repositories::UserRepository;
+use crate::domain::value_objects::Cursor;
+use crate::infrastructure::content_filter::FilterField;
+use crate::infrastructure::storage::MediaUrlSigner;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Application state for story handlers
+#[derive(Clone)]
+pub struct StoryState {
+    pub story_service: Arc<StoryService>,
+    pub user_repo: Arc<dyn UserRepository>,
+    pub content_filter: Arc<ContentFilterService>,
+    pub connection_manager: ConnectionManager,
+    pub media_urls: MediaUrlSigner,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StoryViewerQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    pub cursor: Option<String>,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+// POST /stories - Post a story from a completed upload
+pub async fn create_story(
+    auth_user: AuthUser,
+    State(state): State<StoryState>,
+    Json(payload): Json<CreateStoryRequest>,
+) -> Result<Response, AppError> {
+    let story = state
+        .story_service
+        .create_story(auth_user.user_id, payload.media_id, payload.caption)
+        .await?;
+
+    let story_dto = story_to_dto(&story, true, Some(0), &state.media_urls);
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SuccessResponse::new(
+            "Story created successfully".to_string(),
+            Some(serde_json::to_value(story_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// GET /stories/feed - Get stories from followed users, grouped by author
+pub async fn get_story_feed(
+    auth_user: AuthUser,
+    State(state): State<StoryState>,
+) -> Result<Response, AppError> {
+    let groups = state.story_service.get_feed(auth_user.user_id).await?;
+
+    let mut group_dtos = Vec::with_capacity(groups.len());
+    for group in groups {
+        // Skip authors who no longer exist
+        let author = match state.user_repo.find_by_id(group.author_id).await? {
+            Some(author) => author,
+            None => continue,
+        };
+
+        group_dtos.push(StoryGroupDTO {
+            user: user_to_dto(&author),
+            has_unseen: group.has_unseen(),
+            stories: group
+                .stories
+                .iter()
+                .map(|item| {
+                    story_to_dto(&item.story, item.seen, item.view_count, &state.media_urls)
+                })
+                .collect(),
+        });
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Story feed retrieved successfully".to_string(),
+            Some(serde_json::to_value(group_dtos).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// POST /stories/:id/view - Mark a story as seen
+pub async fn view_story(
+    auth_user: AuthUser,
+    Path(story_id): Path<Uuid>,
+    State(state): State<StoryState>,
+) -> Result<Response, AppError> {
+    state
+        .story_service
+        .mark_seen(auth_user.user_id, story_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Story marked as seen".to_string(),
+            None,
+        )),
+    )
+        .into_response())
+}
+
+// GET /stories/:id/viewers - List who has seen one of your stories
+pub async fn get_story_viewers(
+    auth_user: AuthUser,
+    Path(story_id): Path<Uuid>,
+    Query(query): Query<StoryViewerQuery>,
+    State(state): State<StoryState>,
+) -> Result<Response, AppError> {
+    let limit = query.limit.clamp(1, 100);
+    let cursor = query.cursor.as_deref().map(Cursor::decode).transpose()?;
+
+    let page = state
+        .story_service
+        .get_viewers(auth_user.user_id, story_id, cursor, limit)
+        .await?;
+
+    let viewer_dtos: Vec<StoryViewerDTO> = page
+        .items
+        .iter()
+        .map(|(view, viewer)| StoryViewerDTO {
+            user: user_to_dto(viewer),
+            viewed_at: view.viewed_at,
+        })
+        .collect();
+
+    let response = PaginatedResponse::page(
+        viewer_dtos,
+        limit,
+        0,
+        page.next_cursor.is_some(),
+        page.next_cursor.map(|c| c.encode()),
+    );
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// POST /stories/:id/replies - Reply to a story with a direct message
+pub async fn reply_to_story(
+    auth_user: AuthUser,
+    Path(story_id): Path<Uuid>,
+    State(state): State<StoryState>,
+    Json(payload): Json<StoryReplyRequest>,
+) -> Result<Response, AppError> {
+    let screened = state
+        .content_filter
+        .screen(FilterField::Message, &payload.content)?;
+    let reply = state
+        .story_service
+        .reply(auth_user.user_id, story_id, screened.text)
+        .await?;
+    state
+        .content_filter
+        .flag(
+            &screened.flagged_rules,
+            ReportTargetType::Message,
+            reply.message.id,
+            auth_user.user_id,
+        )
+        .await;
+
+    let message_dto =
+        message_to_dto(&reply.message, state.user_repo.as_ref(), &state.media_urls).await?;
+
+    let ws_event = WebSocketEvent::MessageSent {
+        conversation_id: reply.message.conversation_id,
+        message_id: reply.message.id,
+        sender_id: auth_user.user_id,
+        content: reply.message.content.clone().unwrap_or_default(),
+    };
+    state
+        .connection_manager
+        .send_to_users(&[reply.recipient_id], ws_event)
+        .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SuccessResponse::new(
+            "Reply sent successfully".to_string(),
+            Some(serde_json::to_value(message_dto).unwrap()),
+        )),
+    )
+        .into_response())
+}
+
+// DELETE /stories/:id - Delete one of your stories
+pub async fn delete_story(
+    auth_user: AuthUser,
+    Path(story_id): Path<Uuid>,
+    State(state): State<StoryState>,
+) -> Result<Response, AppError> {
+    state
+        .story_service
+        .delete_story(auth_user.user_id, story_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(SuccessResponse::new(
+            "Story deleted successfully".to_string(),
+            None,
+        )),
+    )
+        .into_response())
+}
+
+// Helper function to convert Story entity to StoryDTO
+fn story_to_dto(
+    story: &Story,
+    seen: bool,
+    view_count: Option<i64>,
+    media_urls: &MediaUrlSigner,
+) -> StoryDTO {
+    StoryDTO {
+        id: story.id,
+        media: media_attachment_to_dto(&story.media, media_urls),
+        caption: story.caption.clone(),
+        seen,
+        view_count,
+        created_at: story.created_at,
+        expires_at: story.expires_at,
+    }
+}
@@ -1,15 +1,21 @@
-use crate::api::dto::common::SuccessResponse;
-use crate::api::dto::user::{UpdateProfileRequest, UserDTO};
+use crate::api::dto::common::{PaginatedResponse, SuccessResponse};
+use crate::api::dto::user::{SuggestionDTO, UpdateProfileRequest, UserDTO};
 use crate::api::middleware::auth::AuthUser;
-use crate::application::services::UserManagementService;
-use crate::domain::entities::{UpdateUserRequest, User};
+use crate::application::services::{
+    ContentFilterService, FeedGenerationService, InsightsService, SuggestionService,
+    UserManagementService,
+};
+use crate::domain::entities::{EngagementEvent, ReportTargetType, UpdateUserRequest, User};
 use crate::domain::errors::AppError;
+use crate::domain::value_objects::PageStart;
+use crate::infrastructure::content_filter::FilterField;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -17,6 +23,34 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct UserState {
     pub user_service: Arc<UserManagementService>,
+    pub insights_service: Arc<InsightsService>,
+    pub content_filter: Arc<ContentFilterService>,
+    pub feed_service: Arc<FeedGenerationService>,
+    pub suggestion_service: Arc<SuggestionService>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FollowListQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    pub cursor: Option<String>,
+    /// Deprecated: page with `cursor`
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestionQuery {
+    #[serde(default = "default_suggestion_limit")]
+    pub limit: usize,
+}
+
+fn default_suggestion_limit() -> usize {
+    20
 }
 
 // GET /users/me - Get current user profile
@@ -47,17 +81,38 @@ pub async fn update_current_user(
     State(state): State<UserState>,
     Json(payload): Json<UpdateProfileRequest>,
 ) -> Result<Response, AppError> {
+    let (display_name, mut flagged_rules) = state
+        .content_filter
+        .screen_optional(FilterField::DisplayName, payload.display_name)?;
+    let (bio, bio_rules) = state
+        .content_filter
+        .screen_optional(FilterField::Bio, payload.bio)?;
+    flagged_rules.extend(bio_rules);
+
     // Update user with new data using the service
     let update_request = UpdateUserRequest {
-        display_name: payload.display_name,
-        bio: payload.bio,
+        display_name,
+        bio,
         avatar_url: payload.avatar_url,
+        sensitive_media: payload
+            .sensitive_media
+            .map(|preference| preference.parse())
+            .transpose()?,
     };
 
     let updated_user = state
         .user_service
         .update_profile(auth_user.user_id, update_request)
         .await?;
+    state
+        .content_filter
+        .flag(
+            &flagged_rules,
+            ReportTargetType::User,
+            auth_user.user_id,
+            auth_user.user_id,
+        )
+        .await;
 
     let user_dto = user_to_dto(&updated_user);
 
@@ -101,6 +156,14 @@ pub async fn follow_user(
         .user_service
         .follow_user(auth_user.user_id, user_id)
         .await?;
+    state
+        .insights_service
+        .record_event(EngagementEvent::UserFollowed(user_id))
+        .await;
+    state
+        .feed_service
+        .follow_in_timeline(auth_user.user_id, user_id)
+        .await;
 
     let response = serde_json::json!({
         "success": true,
@@ -121,6 +184,14 @@ pub async fn unfollow_user(
         .user_service
         .unfollow_user(auth_user.user_id, user_id)
         .await?;
+    state
+        .insights_service
+        .record_event(EngagementEvent::UserUnfollowed(user_id))
+        .await;
+    state
+        .feed_service
+        .unfollow_in_timeline(auth_user.user_id, user_id)
+        .await;
 
     let response = serde_json::json!({
         "success": true,
@@ -130,48 +201,147 @@ pub async fn unfollow_user(
     Ok((StatusCode::OK, Json(response)).into_response())
 }
 
-// GET /users/:id/followers - Get user's followers
-pub async fn get_user_followers(
+// POST /users/:id/mute - Mute a user
+pub async fn mute_user(
+    auth_user: AuthUser,
     Path(user_id): Path<Uuid>,
     State(state): State<UserState>,
 ) -> Result<Response, AppError> {
-    let followers = state
+    state
+        .user_service
+        .mute_user(auth_user.user_id, user_id)
+        .await?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "message": "Successfully muted user"
+    });
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// DELETE /users/:id/mute - Unmute a user
+pub async fn unmute_user(
+    auth_user: AuthUser,
+    Path(user_id): Path<Uuid>,
+    State(state): State<UserState>,
+) -> Result<Response, AppError> {
+    state
         .user_service
-        .get_user_followers(user_id, 50, 0) // Default pagination
+        .unmute_user(auth_user.user_id, user_id)
+        .await?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "message": "Successfully unmuted user"
+    });
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// GET /users/suggestions - Get users the current user may want to follow
+pub async fn get_suggestions(
+    auth_user: AuthUser,
+    Query(query): Query<SuggestionQuery>,
+    State(state): State<UserState>,
+) -> Result<Response, AppError> {
+    let limit = query.limit.clamp(1, 50);
+
+    let suggestions = state
+        .suggestion_service
+        .get_suggestions(auth_user.user_id, limit)
         .await?;
 
-    let follower_dtos: Vec<UserDTO> = followers.iter().map(user_to_dto).collect();
+    let suggestion_dtos: Vec<SuggestionDTO> = suggestions
+        .iter()
+        .map(|(user, suggestion)| SuggestionDTO {
+            user: user_to_dto(user),
+            mutual_follows: suggestion.mutual_follows,
+            follows_you: suggestion.follows_you,
+            in_contacts: suggestion.in_contacts,
+            shared_conversations: suggestion.shared_conversations,
+        })
+        .collect();
 
     Ok((
         StatusCode::OK,
         Json(SuccessResponse::new(
-            "Followers retrieved successfully".to_string(),
-            Some(serde_json::to_value(follower_dtos).unwrap()),
+            "Suggestions retrieved successfully".to_string(),
+            Some(serde_json::to_value(suggestion_dtos).unwrap()),
         )),
     )
         .into_response())
 }
 
+// POST /users/suggestions/:id/dismiss - Stop suggesting a user
+pub async fn dismiss_suggestion(
+    auth_user: AuthUser,
+    Path(user_id): Path<Uuid>,
+    State(state): State<UserState>,
+) -> Result<Response, AppError> {
+    state
+        .suggestion_service
+        .dismiss_suggestion(auth_user.user_id, user_id)
+        .await?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "message": "Suggestion dismissed"
+    });
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+// GET /users/:id/followers - Get user's followers
+pub async fn get_user_followers(
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<FollowListQuery>,
+    State(state): State<UserState>,
+) -> Result<Response, AppError> {
+    let limit = query.limit.clamp(1, 100);
+    let page = PageStart::from_query(query.cursor.as_deref(), query.offset)?;
+
+    let followers = state
+        .user_service
+        .get_user_followers(user_id, limit, page)
+        .await?;
+
+    let follower_dtos: Vec<UserDTO> = followers.users.iter().map(user_to_dto).collect();
+    let response = PaginatedResponse::page(
+        follower_dtos,
+        limit,
+        page.offset(),
+        followers.next_cursor.is_some(),
+        followers.next_cursor.map(|c| c.encode()),
+    );
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
 // GET /users/:id/following - Get users that a user is following
 pub async fn get_user_following(
     Path(user_id): Path<Uuid>,
+    Query(query): Query<FollowListQuery>,
     State(state): State<UserState>,
 ) -> Result<Response, AppError> {
+    let limit = query.limit.clamp(1, 100);
+    let page = PageStart::from_query(query.cursor.as_deref(), query.offset)?;
+
     let following = state
         .user_service
-        .get_user_following(user_id, 50, 0) // Default pagination
+        .get_user_following(user_id, limit, page)
         .await?;
 
-    let following_dtos: Vec<UserDTO> = following.iter().map(user_to_dto).collect();
+    let following_dtos: Vec<UserDTO> = following.users.iter().map(user_to_dto).collect();
+    let response = PaginatedResponse::page(
+        following_dtos,
+        limit,
+        page.offset(),
+        following.next_cursor.is_some(),
+        following.next_cursor.map(|c| c.encode()),
+    );
 
-    Ok((
-        StatusCode::OK,
-        Json(SuccessResponse::new(
-            "Following list retrieved successfully".to_string(),
-            Some(serde_json::to_value(following_dtos).unwrap()),
-        )),
-    )
-        .into_response())
+    Ok((StatusCode::OK, Json(response)).into_response())
 }
 
 // GET /users/search?q=query - Search for users
@@ -233,6 +403,7 @@ pub fn user_to_dto(user: &User) -> UserDTO {
         phone_verified: user.phone_verified,
         follower_count: user.follower_count,
         following_count: user.following_count,
+        sensitive_media: user.sensitive_media.to_string(),
         created_at: user.created_at,
     }
 }
@@ -8,6 +8,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::{DateTime, Utc};
 use serde_json::json;
 use uuid::Uuid;
 
@@ -41,6 +42,7 @@ pub async fn auth_middleware(
         .jwt_service
         .validate_access_token(token)
         .map_err(|_| AuthError::InvalidToken)?;
+    ensure_not_suspended(&auth_state, user_id).await?;
 
     // Insert authenticated user into request extensions
     request
@@ -66,8 +68,11 @@ pub async fn optional_auth_middleware(
         if auth_header.starts_with("Bearer ") {
             let token = &auth_header[7..];
 
-            // Try to validate token
+            // Try to validate token. Suspended users are treated as signed out.
             if let Ok(user_id) = auth_state.jwt_service.validate_access_token(token) {
+                if ensure_not_suspended(&auth_state, user_id).await.is_err() {
+                    return next.run(request).await;
+                }
                 request
                     .extensions_mut()
                     .insert(AuthenticatedUser { user_id });
@@ -78,11 +83,29 @@ pub async fn optional_auth_middleware(
     next.run(request).await
 }
 
+// Tokens stay valid until they expire, so a suspension is checked on every
+// request rather than only at login
+async fn ensure_not_suspended(auth_state: &AuthState, user_id: Uuid) -> Result<(), AuthError> {
+    let user = auth_state
+        .user_repo
+        .find_by_id(user_id)
+        .await
+        .map_err(AuthError::Lookup)?
+        .ok_or(AuthError::InvalidToken)?;
+
+    match user.suspended_until {
+        Some(until) if user.is_suspended(Utc::now()) => Err(AuthError::Suspended(until)),
+        _ => Ok(()),
+    }
+}
+
 #[derive(Debug)]
 pub enum AuthError {
     MissingToken,
     InvalidTokenFormat,
     InvalidToken,
+    Suspended(DateTime<Utc>),
+    Lookup(AppError),
 }
 
 impl IntoResponse for AuthError {
@@ -91,18 +114,27 @@ impl IntoResponse for AuthError {
             AuthError::MissingToken => (
                 StatusCode::UNAUTHORIZED,
                 "MISSING_TOKEN",
-                "Authorization token is required",
+                "Authorization token is required".to_string(),
             ),
             AuthError::InvalidTokenFormat => (
                 StatusCode::UNAUTHORIZED,
                 "INVALID_TOKEN_FORMAT",
-                "Authorization header must be in format: Bearer <token>",
+                "Authorization header must be in format: Bearer <token>".to_string(),
             ),
             AuthError::InvalidToken => (
                 StatusCode::UNAUTHORIZED,
                 "INVALID_TOKEN",
-                "Invalid or expired token",
+                "Invalid or expired token".to_string(),
+            ),
+            AuthError::Suspended(until) => (
+                StatusCode::FORBIDDEN,
+                "ACCOUNT_SUSPENDED",
+                format!(
+                    "Account suspended until {}",
+                    until.format("%Y-%m-%d %H:%M UTC")
+                ),
             ),
+            AuthError::Lookup(e) => return e.into_response(),
         };
 
         let body = Json(json!({
@@ -0,0 +1,35 @@
+use crate::api::handlers::bookmark_handlers::{
+    bookmark_post, create_collection, delete_collection, get_bookmarks, get_collections,
+    remove_bookmark,
+};
+use crate::api::middleware::auth::auth_middleware;
+use crate::server::AppState;
+use axum::{
+    middleware,
+    routing::{delete, get, post},
+    Router,
+};
+
+/// Create bookmark-related routes
+///
+/// All routes require authentication:
+/// - GET /bookmarks - Get bookmarked posts (cursor paginated, optional collection filter)
+/// - POST /posts/:id/bookmark - Bookmark a post, optionally into a collection
+/// - DELETE /posts/:id/bookmark - Remove a bookmark
+/// - GET /bookmarks/collections - List collections
+/// - POST /bookmarks/collections - Create a collection
+/// - DELETE /bookmarks/collections/:id - Delete a collection
+pub fn create_router(state: AppState) -> Router {
+    Router::new()
+        .route("/bookmarks", get(get_bookmarks))
+        .route("/posts/:id/bookmark", post(bookmark_post))
+        .route("/posts/:id/bookmark", delete(remove_bookmark))
+        .route("/bookmarks/collections", get(get_collections))
+        .route("/bookmarks/collections", post(create_collection))
+        .route("/bookmarks/collections/:id", delete(delete_collection))
+        .layer(middleware::from_fn_with_state(
+            state.auth_state.clone(),
+            auth_middleware,
+        ))
+        .with_state(state.bookmark_state)
+}
@@ -0,0 +1,20 @@
+use crate::api::handlers::insights_handlers::{get_post_insights, get_user_insights};
+use crate::api::middleware::auth::auth_middleware;
+use crate::server::AppState;
+use axum::{middleware, routing::get, Router};
+
+/// Create creator insights routes
+///
+/// All routes require authentication and accept `?days=` (default 28, max 90):
+/// - GET /posts/:id/insights - Impressions, reach, likes and comments per day for one of your posts
+/// - GET /users/me/insights - Totals per day, follower growth, top posts and audience breakdown
+pub fn create_router(state: AppState) -> Router {
+    Router::new()
+        .route("/posts/:id/insights", get(get_post_insights))
+        .route("/users/me/insights", get(get_user_insights))
+        .layer(middleware::from_fn_with_state(
+            state.auth_state.clone(),
+            auth_middleware,
+        ))
+        .with_state(state.insights_state)
+}
@@ -0,0 +1,45 @@
+use crate::api::handlers::media_handlers::{
+    complete_media_upload, create_media_upload, get_media_file, get_media_upload,
+    upload_media_content,
+};
+use crate::api::middleware::auth::auth_middleware;
+use crate::domain::entities::MAX_MEDIA_SIZE;
+use crate::server::AppState;
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+
+/// Create media-related routes
+///
+/// Protected routes (require authentication):
+/// - POST /media/uploads - Start an upload session (returns a presigned URL when supported)
+/// - GET /media/uploads/:id - Get an upload session
+/// - PUT /media/uploads/:id/content - Upload the file through the API
+/// - POST /media/uploads/:id/complete - Complete an upload sent directly to storage
+///
+/// Public routes (authorized by the URL signature instead):
+/// - GET /media/files/*key?expires=..&signature=.. - Serve a stored media file
+pub fn create_router(state: AppState) -> Router {
+    let protected = Router::new()
+        .route("/media/uploads", post(create_media_upload))
+        .route("/media/uploads/:id", get(get_media_upload))
+        .route(
+            "/media/uploads/:id/content",
+            put(upload_media_content).layer(DefaultBodyLimit::max(MAX_MEDIA_SIZE as usize)),
+        )
+        .route("/media/uploads/:id/complete", post(complete_media_upload))
+        .layer(middleware::from_fn_with_state(
+            state.auth_state.clone(),
+            auth_middleware,
+        ))
+        .with_state(state.media_state.clone());
+
+    let public = Router::new()
+        .route("/media/files/*key", get(get_media_file))
+        .with_state(state.media_state);
+
+    Router::new().merge(protected).merge(public)
+}
@@ -1,10 +1,17 @@
 pub mod auth;
+pub mod bookmarks;
 pub mod health;
 pub mod home;
+pub mod insights;
+pub mod media;
 pub mod messages;
+pub mod moderation;
 pub mod notifications;
 pub mod payments;
 pub mod posts;
+pub mod search;
+pub mod stories;
+pub mod users;
 pub mod websocket;
 
 use crate::server::AppState;
@@ -16,7 +23,14 @@ pub fn create_router(state: AppState) -> Router {
         .merge(health::create_router())
         .nest("/auth", auth::create_router(state.clone()))
         .merge(posts::create_router(state.clone()))
+        .merge(search::create_router(state.clone()))
+        .merge(users::create_router(state.clone()))
+        .merge(bookmarks::create_router(state.clone()))
+        .merge(stories::create_router(state.clone()))
+        .merge(insights::create_router(state.clone()))
+        .merge(media::create_router(state.clone()))
         .merge(messages::create_router(state.clone()))
+        .merge(moderation::create_router(state.clone()))
         .merge(payments::create_router(state.clone()))
         .nest(
             "/notifications",
@@ -0,0 +1,34 @@
+use crate::api::handlers::moderation_handlers::{
+    create_moderation_action, create_report, get_moderation_case, get_moderation_queue,
+};
+use crate::api::middleware::auth::auth_middleware;
+use crate::server::AppState;
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+/// Create reporting and moderation routes
+///
+/// All routes require authentication; the moderation routes also require the
+/// caller to be a moderator:
+/// - POST /reports - Report a post, comment, message or user
+/// - GET /moderation/queue - Open cases, most reported first (`?limit=&offset=`)
+/// - GET /moderation/cases/:id - A case with its reports and past actions
+/// - POST /moderation/cases/:id/actions - Dismiss, remove content, warn, suspend the user or lock their wallet
+pub fn create_router(state: AppState) -> Router {
+    Router::new()
+        .route("/reports", post(create_report))
+        .route("/moderation/queue", get(get_moderation_queue))
+        .route("/moderation/cases/:id", get(get_moderation_case))
+        .route(
+            "/moderation/cases/:id/actions",
+            post(create_moderation_action),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.auth_state.clone(),
+            auth_middleware,
+        ))
+        .with_state(state.moderation_state)
+}
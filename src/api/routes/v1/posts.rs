@@ -1,7 +1,10 @@
 use crate::api::handlers::post_handlers::{
-    create_comment, create_post, get_feed, get_post_comments, like_post, unlike_post,
+    create_comment, create_post, delete_post, get_drafts, get_explore, get_feed, get_mentions,
+    get_poll, get_post, get_post_comments, get_reels, get_trending, get_user_posts, hide_post,
+    like_post, mark_not_interested, pin_post, publish_post, record_post_view,
+    show_fewer_from_author, undo_feed_feedback, unlike_post, unpin_post, vote_poll,
 };
-use crate::api::middleware::auth::auth_middleware;
+use crate::api::middleware::auth::{auth_middleware, optional_auth_middleware};
 use crate::server::AppState;
 use axum::{
     middleware,
@@ -13,20 +16,56 @@ use axum::{
 ///
 /// Protected routes (require authentication):
 /// - GET /posts/feed - Get user's personalized feed
-/// - POST /posts - Create a new post
+///   (?strategy=chronological|algorithmic&reels_only=&exclude_reels=&content_types=image,video&min_engagement=)
+///   (short first pages are filled out with explore posts from `suggested_index` on)
+/// - GET /posts/explore - Get public posts from accounts you do not follow,
+///   ranked by trending, hashtags you like and likes from accounts you follow
+/// - GET /reels - Get reels from followed users, most engaging first
+/// - GET /posts/mentions - Get posts that mention the current user
+/// - GET /posts/drafts - Get the current user's drafts and scheduled posts
+/// - POST /posts - Create a new post (optionally as a draft or scheduled)
+/// - DELETE /posts/:id - Delete one of your posts
+/// - POST /posts/:id/publish - Publish a draft or scheduled post now
 /// - POST /posts/:id/like - Like a post
 /// - DELETE /posts/:id/like - Unlike a post
 /// - POST /posts/:id/comments - Add a comment to a post
+/// - GET /posts/:id/poll - Get a poll (counts hidden until voted or closed)
+/// - POST /posts/:id/poll/vote - Vote in a poll
+/// - POST /posts/:id/views - Report watch time, completion and replays of a video post
+/// - POST /posts/:id/pin - Pin a post to the top of your profile (at most three)
+/// - DELETE /posts/:id/pin - Unpin a post
+/// - POST /posts/:id/hide - Hide a post from your feeds
+/// - POST /posts/:id/not-interested - Hide a post and show fewer with its author or hashtags
+/// - POST /posts/:id/show-fewer - Show fewer posts from the post's author
+/// - DELETE /posts/:id/feedback - Take back feedback given on a post
 ///
-/// Public routes:
+/// Public routes (optional authentication, only posts visible to the viewer):
+/// - GET /posts/trending - Get public posts with high engagement
+/// - GET /posts/:id - Get a single post
 /// - GET /posts/:id/comments - Get comments for a post
+/// - GET /users/:id/posts - Get a user's posts, pinned posts first
 pub fn create_router(state: AppState) -> Router {
     let protected = Router::new()
         .route("/posts/feed", get(get_feed))
+        .route("/posts/explore", get(get_explore))
+        .route("/reels", get(get_reels))
+        .route("/posts/mentions", get(get_mentions))
+        .route("/posts/drafts", get(get_drafts))
         .route("/posts", post(create_post))
+        .route("/posts/:id", delete(delete_post))
+        .route("/posts/:id/publish", post(publish_post))
         .route("/posts/:id/like", post(like_post))
         .route("/posts/:id/like", delete(unlike_post))
         .route("/posts/:id/comments", post(create_comment))
+        .route("/posts/:id/poll", get(get_poll))
+        .route("/posts/:id/poll/vote", post(vote_poll))
+        .route("/posts/:id/views", post(record_post_view))
+        .route("/posts/:id/pin", post(pin_post))
+        .route("/posts/:id/pin", delete(unpin_post))
+        .route("/posts/:id/hide", post(hide_post))
+        .route("/posts/:id/not-interested", post(mark_not_interested))
+        .route("/posts/:id/show-fewer", post(show_fewer_from_author))
+        .route("/posts/:id/feedback", delete(undo_feed_feedback))
         .layer(middleware::from_fn_with_state(
             state.auth_state.clone(),
             auth_middleware,
@@ -34,7 +73,14 @@ pub fn create_router(state: AppState) -> Router {
         .with_state(state.post_state.clone());
 
     let public = Router::new()
+        .route("/posts/trending", get(get_trending))
+        .route("/posts/:id", get(get_post))
         .route("/posts/:id/comments", get(get_post_comments))
+        .route("/users/:id/posts", get(get_user_posts))
+        .layer(middleware::from_fn_with_state(
+            state.auth_state.clone(),
+            optional_auth_middleware,
+        ))
         .with_state(state.post_state);
 
     Router::new().merge(protected).merge(public)
@@ -0,0 +1,24 @@
+use crate::api::handlers::search_handlers::{search, search_posts};
+use crate::api::middleware::auth::optional_auth_middleware;
+use crate::server::AppState;
+use axum::{middleware, routing::get, Router};
+
+/// Create search routes
+///
+/// Public routes (optional authentication, only posts visible to the viewer):
+/// - GET /search?q=&limit= - Search users, posts and hashtags at once
+/// - GET /search/posts - Full-text post search, most relevant first, with
+///   highlighted snippets
+///   (?q=&author_id=&since=&until=&content_type=&reels_only=&hashtag=&limit=&offset=)
+/// - GET /posts/search - Same as /search/posts
+pub fn create_router(state: AppState) -> Router {
+    Router::new()
+        .route("/search", get(search))
+        .route("/search/posts", get(search_posts))
+        .route("/posts/search", get(search_posts))
+        .layer(middleware::from_fn_with_state(
+            state.auth_state.clone(),
+            optional_auth_middleware,
+        ))
+        .with_state(state.search_state)
+}
@@ -0,0 +1,34 @@
+use crate::api::handlers::story_handlers::{
+    create_story, delete_story, get_story_feed, get_story_viewers, reply_to_story, view_story,
+};
+use crate::api::middleware::auth::auth_middleware;
+use crate::server::AppState;
+use axum::{
+    middleware,
+    routing::{delete, get, post},
+    Router,
+};
+
+/// Create story-related routes
+///
+/// All routes require authentication:
+/// - POST /stories - Post a story from a completed image or video upload
+/// - GET /stories/feed - Get stories from followed users, grouped by author, unseen first
+/// - POST /stories/:id/view - Mark a story as seen
+/// - GET /stories/:id/viewers - List who has seen one of your stories (cursor paginated)
+/// - POST /stories/:id/replies - Reply to a story with a direct message
+/// - DELETE /stories/:id - Delete one of your stories
+pub fn create_router(state: AppState) -> Router {
+    Router::new()
+        .route("/stories", post(create_story))
+        .route("/stories/feed", get(get_story_feed))
+        .route("/stories/:id/view", post(view_story))
+        .route("/stories/:id/viewers", get(get_story_viewers))
+        .route("/stories/:id/replies", post(reply_to_story))
+        .route("/stories/:id", delete(delete_story))
+        .layer(middleware::from_fn_with_state(
+            state.auth_state.clone(),
+            auth_middleware,
+        ))
+        .with_state(state.story_state)
+}
@@ -0,0 +1,57 @@
+use crate::api::handlers::user_handlers::{
+    check_following_status, dismiss_suggestion, follow_user, get_current_user, get_suggestions,
+    get_user_by_id, get_user_followers, get_user_following, mute_user, search_users, unfollow_user,
+    unmute_user, update_current_user,
+};
+use crate::api::middleware::auth::auth_middleware;
+use crate::server::AppState;
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+/// Create user-related routes
+///
+/// Protected routes (require authentication):
+/// - GET /users/me - Get the current user's profile
+/// - PUT /users/me - Update the current user's profile
+/// - GET /users/suggestions?limit= - Get people you may know, best first
+/// - POST /users/suggestions/:id/dismiss - Stop suggesting a user
+/// - POST /users/:id/follow - Follow a user
+/// - DELETE /users/:id/follow - Unfollow a user
+/// - POST /users/:id/mute - Mute a user, keeping their posts out of your feeds
+/// - DELETE /users/:id/mute - Unmute a user
+///
+/// Public routes:
+/// - GET /users/search?q= - Search users by username or display name
+/// - GET /users/:id - Get a user's public profile
+/// - GET /users/:id/followers - Get a user's followers
+/// - GET /users/:id/following - Get the users a user follows
+/// - GET /users/:id/following/:following_id - Check whether a user follows another
+pub fn create_router(state: AppState) -> Router {
+    let protected = Router::new()
+        .route("/users/me", get(get_current_user).put(update_current_user))
+        .route("/users/suggestions", get(get_suggestions))
+        .route("/users/suggestions/:id/dismiss", post(dismiss_suggestion))
+        .route("/users/:id/follow", post(follow_user).delete(unfollow_user))
+        .route("/users/:id/mute", post(mute_user).delete(unmute_user))
+        .layer(middleware::from_fn_with_state(
+            state.auth_state.clone(),
+            auth_middleware,
+        ))
+        .with_state(state.user_state.clone());
+
+    let public = Router::new()
+        .route("/users/search", get(search_users))
+        .route("/users/:id", get(get_user_by_id))
+        .route("/users/:id/followers", get(get_user_followers))
+        .route("/users/:id/following", get(get_user_following))
+        .route(
+            "/users/:id/following/:following_id",
+            get(check_following_status),
+        )
+        .with_state(state.user_state);
+
+    Router::new().merge(protected).merge(public)
+}
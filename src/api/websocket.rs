@@ -43,6 +43,10 @@ pub enum WebSocketEvent {
         post_id: Uuid,
         user_id: Uuid,
     },
+    PostPublished {
+        post_id: Uuid,
+        user_id: Uuid,
+    },
     UserOnline {
         user_id: Uuid,
     },
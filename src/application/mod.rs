@@ -1,5 +1,6 @@
 // Application layer - Use cases and services
 pub mod commands;
 pub mod queries;
+pub mod ranking;
 pub mod services;
 pub mod verification;
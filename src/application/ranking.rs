@@ -0,0 +1,752 @@
+//! Feed ranking
+//!
+//! Candidate posts are described by [`PostFeatures`]: how much engagement
+//! they have and how fast it came in, how old they are, what kind of post
+//! they are, and how the viewer relates to them, including feedback asking
+//! for fewer posts from the author or with the post's hashtags. A
+//! [`FeedRanker`] turns
+//! features into a score. [`EngagementRanker`] is the original engagement
+//! formula; [`WeightedRanker`] combines every feature with weights read from
+//! a JSON file; [`ExploreRanker`] ranks posts from accounts the viewer does
+//! not follow. [`evaluate`] replays logged feed sessions so rankers can be
+//! compared offline before one is switched on.
+use crate::domain::entities::{
+    extract_hashtags, FeedFeedback, FeedFeedbackKind, Post, PostContentType,
+};
+use crate::domain::errors::{AppError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// How much a single view counts towards engagement, relative to a like
+pub const VIEW_ENGAGEMENT_WEIGHT: f64 = 0.1;
+
+/// How far back the viewer's likes and comments count towards author affinity
+pub const AUTHOR_AFFINITY_WINDOW_DAYS: i64 = 90;
+
+/// How far back feedback on posts counts towards showing fewer like them
+pub const FEEDBACK_WINDOW_DAYS: i64 = 180;
+
+/// How much "show fewer from this author" counts against the author
+const SHOW_FEWER_AUTHOR_FEEDBACK: f64 = 1.0;
+
+/// How much "not interested" counts against the post's author and each of
+/// its hashtags
+const NOT_INTERESTED_AUTHOR_FEEDBACK: f64 = 0.5;
+const NOT_INTERESTED_HASHTAG_FEEDBACK: f64 = 1.0;
+
+/// Tracing target feed sessions are logged under for offline evaluation
+pub const SESSION_LOG_TARGET: &str = "feed_ranking";
+
+/// What a ranker knows about a candidate post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostFeatures {
+    /// Likes and comments, reshares counted twice, views at a tenth
+    pub engagement: f64,
+    pub age_hours: f64,
+    /// Decays from 1 for a new post, falling to 1/e after a day
+    pub recency: f64,
+    /// Engagement per hour since the post was published
+    pub engagement_velocity: f64,
+    /// The viewer's recent likes and comments on the author's posts
+    pub author_affinity: f64,
+    /// How often the viewer recently liked posts with the post's hashtags
+    #[serde(default)]
+    pub hashtag_affinity: f64,
+    /// Accounts the viewer follows that liked the post
+    #[serde(default)]
+    pub followed_likes: f64,
+    /// How much the viewer asked to see fewer posts from the author
+    #[serde(default)]
+    pub author_feedback: f64,
+    /// How much the viewer marked posts with the post's hashtags not interesting
+    #[serde(default)]
+    pub hashtag_feedback: f64,
+    pub content_type: PostContentType,
+    pub is_reel: bool,
+    /// Average share of the video watched, from 0 to 1, once it has views
+    pub completion_rate: Option<f64>,
+    pub already_seen: bool,
+    /// Posts by the same author ranked above this one
+    #[serde(default)]
+    pub author_position: u32,
+}
+
+impl PostFeatures {
+    /// Features of a post that do not depend on who is looking at it
+    pub fn of_post(post: &Post, now: DateTime<Utc>) -> Self {
+        let total_engagement = post.like_count + post.comment_count + (post.reshare_count * 2);
+        let engagement = total_engagement as f64 + post.view_count as f64 * VIEW_ENGAGEMENT_WEIGHT;
+        let age_hours = ((now - post.created_at).num_seconds() as f64 / 3600.0).max(0.0);
+
+        Self {
+            engagement,
+            age_hours,
+            recency: (-age_hours / 24.0).exp(),
+            engagement_velocity: engagement / age_hours.max(1.0),
+            author_affinity: 0.0,
+            hashtag_affinity: 0.0,
+            followed_likes: 0.0,
+            author_feedback: 0.0,
+            hashtag_feedback: 0.0,
+            content_type: post.content_type.clone(),
+            is_reel: post.is_reel,
+            completion_rate: (post.view_count > 0).then(|| post.avg_completion_percent / 100.0),
+            already_seen: false,
+            author_position: 0,
+        }
+    }
+}
+
+/// What is known about the viewer when ranking their feed
+#[derive(Debug, Clone, Default)]
+pub struct ViewerSignals {
+    /// Interactions with each author within the affinity window
+    pub author_affinity: HashMap<Uuid, i64>,
+    /// Recently liked posts carrying each hashtag
+    pub hashtag_affinity: HashMap<String, i64>,
+    /// Likes on each post from accounts the viewer follows
+    pub followed_likes: HashMap<Uuid, i64>,
+    /// Posts the viewer has already been shown
+    pub seen_post_ids: HashSet<Uuid>,
+    /// Posts the viewer hid or marked not interesting
+    pub hidden_post_ids: HashSet<Uuid>,
+    /// Feedback against each author
+    pub author_feedback: HashMap<Uuid, f64>,
+    /// Feedback against each hashtag
+    pub hashtag_feedback: HashMap<String, f64>,
+}
+
+impl ViewerSignals {
+    pub fn features(&self, post: &Post, now: DateTime<Utc>) -> PostFeatures {
+        let hashtags = match &post.text_content {
+            Some(text)
+                if !self.hashtag_affinity.is_empty() || !self.hashtag_feedback.is_empty() =>
+            {
+                extract_hashtags(text)
+            }
+            _ => Vec::new(),
+        };
+
+        PostFeatures {
+            author_affinity: self
+                .author_affinity
+                .get(&post.user_id)
+                .copied()
+                .unwrap_or(0) as f64,
+            hashtag_affinity: hashtags
+                .iter()
+                .filter_map(|tag| self.hashtag_affinity.get(tag))
+                .sum::<i64>() as f64,
+            followed_likes: self.followed_likes.get(&post.id).copied().unwrap_or(0) as f64,
+            author_feedback: self
+                .author_feedback
+                .get(&post.user_id)
+                .copied()
+                .unwrap_or(0.0),
+            hashtag_feedback: hashtags
+                .iter()
+                .filter_map(|tag| self.hashtag_feedback.get(tag))
+                .sum(),
+            already_seen: self.seen_post_ids.contains(&post.id),
+            ..PostFeatures::of_post(post, now)
+        }
+    }
+
+    /// Take in the viewer's feedback on posts: hidden posts are dropped, and
+    /// the authors and hashtags of posts they wanted fewer of count against
+    /// similar posts
+    pub fn add_feedback(&mut self, feedback: &[FeedFeedback]) {
+        for item in feedback {
+            if item.kind.drops_post() {
+                self.hidden_post_ids.insert(item.post_id);
+            }
+            match item.kind {
+                FeedFeedbackKind::Hide => {}
+                FeedFeedbackKind::NotInterested => {
+                    *self.author_feedback.entry(item.author_id).or_default() +=
+                        NOT_INTERESTED_AUTHOR_FEEDBACK;
+                    for tag in &item.hashtags {
+                        *self.hashtag_feedback.entry(tag.clone()).or_default() +=
+                            NOT_INTERESTED_HASHTAG_FEEDBACK;
+                    }
+                }
+                FeedFeedbackKind::ShowFewerFromAuthor => {
+                    *self.author_feedback.entry(item.author_id).or_default() +=
+                        SHOW_FEWER_AUTHOR_FEEDBACK;
+                }
+            }
+        }
+    }
+}
+
+/// Scores candidate posts; higher scores are shown first
+pub trait FeedRanker: Send + Sync {
+    /// Name used in logs and evaluation reports
+    fn name(&self) -> &str;
+
+    fn score(&self, features: &PostFeatures) -> f64;
+}
+
+/// The original formula: engagement with a one-day exponential decay over
+/// whole hours, reels boosted by half and scaled by how far they are watched.
+/// Each unit of feedback against the author or hashtags halves the score.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EngagementRanker;
+
+impl FeedRanker for EngagementRanker {
+    fn name(&self) -> &str {
+        "engagement"
+    }
+
+    fn score(&self, features: &PostFeatures) -> f64 {
+        let time_decay = (-features.age_hours.trunc() / 24.0).exp();
+        let mut score = features.engagement * time_decay;
+
+        if features.is_reel {
+            score *= 1.5;
+            if let Some(completion_rate) = features.completion_rate {
+                score *= 0.5 + completion_rate;
+            }
+        }
+
+        score * 0.5f64.powf(features.author_feedback + features.hashtag_feedback)
+    }
+}
+
+/// Weights of the weighted ranker. Counts are log-scaled so one viral post
+/// does not drown out every other signal; penalties are subtracted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RankingWeights {
+    pub engagement: f64,
+    pub engagement_velocity: f64,
+    pub recency: f64,
+    pub author_affinity: f64,
+    pub hashtag_affinity: f64,
+    pub followed_likes: f64,
+    pub reel: f64,
+    pub completion: f64,
+    /// Added per content type, keyed by the names feeds filter on
+    pub content_types: HashMap<String, f64>,
+    pub already_seen: f64,
+    /// Subtracted for each post by the same author ranked above
+    pub author_repeat: f64,
+    pub author_feedback: f64,
+    pub hashtag_feedback: f64,
+}
+
+impl Default for RankingWeights {
+    fn default() -> Self {
+        Self {
+            engagement: 1.0,
+            engagement_velocity: 1.0,
+            recency: 2.0,
+            author_affinity: 1.5,
+            hashtag_affinity: 1.0,
+            followed_likes: 1.0,
+            reel: 0.5,
+            completion: 1.0,
+            content_types: HashMap::new(),
+            already_seen: 3.0,
+            author_repeat: 0.75,
+            author_feedback: 2.0,
+            hashtag_feedback: 1.0,
+        }
+    }
+}
+
+/// Linear combination of every feature
+#[derive(Debug, Clone, Default)]
+pub struct WeightedRanker {
+    weights: RankingWeights,
+}
+
+impl WeightedRanker {
+    pub fn new(weights: RankingWeights) -> Self {
+        Self { weights }
+    }
+
+    /// Load weights from a JSON file; weights it leaves out keep their defaults
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            AppError::ConfigurationError(format!(
+                "Failed to read ranking weights {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let weights = serde_json::from_str(&contents).map_err(|e| {
+            AppError::ConfigurationError(format!(
+                "Invalid ranking weights {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(Self::new(weights))
+    }
+
+    pub fn weights(&self) -> &RankingWeights {
+        &self.weights
+    }
+}
+
+impl FeedRanker for WeightedRanker {
+    fn name(&self) -> &str {
+        "weighted"
+    }
+
+    fn score(&self, features: &PostFeatures) -> f64 {
+        let w = &self.weights;
+        let mut score = w.engagement * features.engagement.max(0.0).ln_1p()
+            + w.engagement_velocity * features.engagement_velocity.max(0.0).ln_1p()
+            + w.recency * features.recency
+            + w.author_affinity * features.author_affinity.max(0.0).ln_1p()
+            + w.hashtag_affinity * features.hashtag_affinity.max(0.0).ln_1p()
+            + w.followed_likes * features.followed_likes.max(0.0).ln_1p()
+            + w.content_types
+                .get(content_type_name(&features.content_type))
+                .copied()
+                .unwrap_or(0.0)
+            - w.author_repeat * features.author_position as f64
+            - w.author_feedback * features.author_feedback.max(0.0).ln_1p()
+            - w.hashtag_feedback * features.hashtag_feedback.max(0.0).ln_1p();
+
+        if features.is_reel {
+            score += w.reel + w.completion * features.completion_rate.unwrap_or(0.0);
+        }
+        if features.already_seen {
+            score -= w.already_seen;
+        }
+
+        score
+    }
+}
+
+/// Ranks the explore feed: how much a post is trending under the engagement
+/// formula, boosted by hashtags the viewer likes and by likes from accounts
+/// they follow, with repeated authors and feedback against the author or
+/// hashtags pushing it down
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExploreRanker;
+
+impl FeedRanker for ExploreRanker {
+    fn name(&self) -> &str {
+        "explore"
+    }
+
+    fn score(&self, features: &PostFeatures) -> f64 {
+        EngagementRanker.score(features).max(0.0).ln_1p()
+            + features.hashtag_affinity.max(0.0).ln_1p()
+            + 1.5 * features.followed_likes.max(0.0).ln_1p()
+            - 0.75 * features.author_position as f64
+            - 2.0 * features.author_feedback.max(0.0).ln_1p()
+            - features.hashtag_feedback.max(0.0).ln_1p()
+    }
+}
+
+/// Pick a ranker by name, as set in `FEED_RANKER`
+pub fn ranker_by_name(name: &str, weights_path: Option<&str>) -> Result<Arc<dyn FeedRanker>> {
+    match name {
+        "engagement" => Ok(Arc::new(EngagementRanker)),
+        "weighted" => Ok(Arc::new(match weights_path {
+            Some(path) => WeightedRanker::from_file(path)?,
+            None => WeightedRanker::default(),
+        })),
+        _ => Err(AppError::ConfigurationError(format!(
+            "Unknown feed ranker: {}",
+            name
+        ))),
+    }
+}
+
+fn content_type_name(content_type: &PostContentType) -> &'static str {
+    match content_type {
+        PostContentType::Text => "text",
+        PostContentType::Image => "image",
+        PostContentType::Video => "video",
+        PostContentType::Mixed => "mixed",
+        PostContentType::Poll => "poll",
+    }
+}
+
+/// A post up for ranking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankingCandidate {
+    pub post_id: Uuid,
+    pub author_id: Uuid,
+    pub features: PostFeatures,
+}
+
+/// Order candidates best first. Candidates are picked one at a time so each
+/// is scored knowing how many posts by its author are already above it;
+/// equal scores keep their original order.
+pub fn rank(ranker: &dyn FeedRanker, candidates: Vec<RankingCandidate>) -> Vec<RankingCandidate> {
+    let mut remaining = candidates;
+    let mut ranked = Vec::with_capacity(remaining.len());
+    let mut ranked_by_author: HashMap<Uuid, u32> = HashMap::new();
+
+    while !remaining.is_empty() {
+        let mut best = 0;
+        let mut best_score = f64::NEG_INFINITY;
+        for (index, candidate) in remaining.iter_mut().enumerate() {
+            candidate.features.author_position = ranked_by_author
+                .get(&candidate.author_id)
+                .copied()
+                .unwrap_or(0);
+            let score = ranker.score(&candidate.features);
+            if score > best_score {
+                best = index;
+                best_score = score;
+            }
+        }
+
+        let candidate = remaining.remove(best);
+        *ranked_by_author.entry(candidate.author_id).or_default() += 1;
+        ranked.push(candidate);
+    }
+
+    ranked
+}
+
+/// Rank a viewer's candidate posts, logging the session for offline evaluation
+pub fn rank_posts(
+    ranker: &dyn FeedRanker,
+    viewer_id: Uuid,
+    posts: Vec<Post>,
+    signals: &ViewerSignals,
+    now: DateTime<Utc>,
+) -> Vec<Post> {
+    let candidates = posts
+        .iter()
+        .map(|post| RankingCandidate {
+            post_id: post.id,
+            author_id: post.user_id,
+            features: signals.features(post, now),
+        })
+        .collect();
+    let ranked = rank(ranker, candidates);
+
+    if tracing::enabled!(target: SESSION_LOG_TARGET, tracing::Level::DEBUG) {
+        let session = LoggedSession {
+            viewer_id,
+            served_at: now,
+            ranker: ranker.name().to_string(),
+            candidates: ranked.clone(),
+            engaged_post_ids: Vec::new(),
+        };
+        if let Ok(session) = serde_json::to_string(&session) {
+            tracing::debug!(target: SESSION_LOG_TARGET, %session, "ranked feed");
+        }
+    }
+
+    let mut posts: HashMap<Uuid, Post> = posts.into_iter().map(|post| (post.id, post)).collect();
+    ranked
+        .iter()
+        .filter_map(|candidate| posts.remove(&candidate.post_id))
+        .collect()
+}
+
+/// A feed as it was served: the candidates in the order shown, and the posts
+/// the viewer went on to engage with. Sessions are logged with no engaged
+/// posts; those are joined in from likes, comments and views afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedSession {
+    pub viewer_id: Uuid,
+    pub served_at: DateTime<Utc>,
+    /// Ranker that served the session
+    pub ranker: String,
+    pub candidates: Vec<RankingCandidate>,
+    #[serde(default)]
+    pub engaged_post_ids: Vec<Uuid>,
+}
+
+/// How well a ranker put engaged posts near the top of replayed sessions
+#[derive(Debug, Clone, Serialize)]
+pub struct RankerEvaluation {
+    pub ranker: String,
+    /// Sessions with at least one engaged post; the rest are skipped
+    pub sessions: usize,
+    pub k: usize,
+    /// Normalized discounted cumulative gain of the top `k`
+    pub ndcg_at_k: f64,
+    /// Share of the top `k` the viewer engaged with
+    pub precision_at_k: f64,
+    /// Mean of one over the position of the first engaged post
+    pub mean_reciprocal_rank: f64,
+}
+
+/// Re-rank each logged session's candidates and score the new order against
+/// what the viewer actually engaged with
+pub fn evaluate(ranker: &dyn FeedRanker, sessions: &[LoggedSession], k: usize) -> RankerEvaluation {
+    let k = k.max(1);
+    let mut evaluated = 0;
+    let mut ndcg_sum = 0.0;
+    let mut precision_sum = 0.0;
+    let mut reciprocal_rank_sum = 0.0;
+
+    for session in sessions {
+        let engaged: HashSet<Uuid> = session.engaged_post_ids.iter().copied().collect();
+        let relevant = session
+            .candidates
+            .iter()
+            .filter(|candidate| engaged.contains(&candidate.post_id))
+            .count();
+        if relevant == 0 {
+            continue;
+        }
+        evaluated += 1;
+
+        let ranked = rank(ranker, session.candidates.clone());
+        let hits: Vec<bool> = ranked
+            .iter()
+            .map(|candidate| engaged.contains(&candidate.post_id))
+            .collect();
+
+        let discount = |position: usize| 1.0 / (position as f64 + 2.0).log2();
+        let dcg: f64 = hits
+            .iter()
+            .take(k)
+            .enumerate()
+            .filter(|(_, hit)| **hit)
+            .map(|(position, _)| discount(position))
+            .sum();
+        let ideal_dcg: f64 = (0..relevant.min(k)).map(discount).sum();
+        ndcg_sum += dcg / ideal_dcg;
+
+        precision_sum += hits.iter().take(k).filter(|hit| **hit).count() as f64 / k as f64;
+
+        if let Some(position) = hits.iter().position(|hit| *hit) {
+            reciprocal_rank_sum += 1.0 / (position as f64 + 1.0);
+        }
+    }
+
+    let mean = |sum: f64| {
+        if evaluated == 0 {
+            0.0
+        } else {
+            sum / evaluated as f64
+        }
+    };
+
+    RankerEvaluation {
+        ranker: ranker.name().to_string(),
+        sessions: evaluated,
+        k,
+        ndcg_at_k: mean(ndcg_sum),
+        precision_at_k: mean(precision_sum),
+        mean_reciprocal_rank: mean(reciprocal_rank_sum),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{CreatePostRequest, PostVisibility};
+    use chrono::Duration;
+
+    fn features(engagement: f64, age_hours: f64) -> PostFeatures {
+        PostFeatures {
+            engagement,
+            age_hours,
+            recency: (-age_hours / 24.0).exp(),
+            engagement_velocity: engagement / age_hours.max(1.0),
+            author_affinity: 0.0,
+            hashtag_affinity: 0.0,
+            followed_likes: 0.0,
+            author_feedback: 0.0,
+            hashtag_feedback: 0.0,
+            content_type: PostContentType::Text,
+            is_reel: false,
+            completion_rate: None,
+            already_seen: false,
+            author_position: 0,
+        }
+    }
+
+    fn candidate(author_id: Uuid, features: PostFeatures) -> RankingCandidate {
+        RankingCandidate {
+            post_id: Uuid::new_v4(),
+            author_id,
+            features,
+        }
+    }
+
+    #[test]
+    fn test_engagement_ranker_matches_original_formula() {
+        let now = Utc::now();
+        let mut post = Post::new(CreatePostRequest {
+            user_id: Uuid::new_v4(),
+            text_content: Some("reel".to_string()),
+            media_attachments: vec![],
+            is_reel: false,
+            visibility: PostVisibility::Public,
+            poll: None,
+        })
+        .unwrap();
+        post.is_reel = true;
+        post.created_at = now - Duration::minutes(150);
+        post.like_count = 10;
+        post.comment_count = 4;
+        post.reshare_count = 3;
+        post.view_count = 50;
+        post.avg_completion_percent = 80.0;
+
+        let expected = (20.0 + 50.0 * 0.1) * (-2.0f64 / 24.0).exp() * 1.5 * 1.3;
+        let score = EngagementRanker.score(&PostFeatures::of_post(&post, now));
+        assert!((score - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weighted_ranker_prefers_authors_the_viewer_engages_with() {
+        let ranker = WeightedRanker::default();
+        let stranger = features(10.0, 2.0);
+        let friend = PostFeatures {
+            author_affinity: 8.0,
+            ..features(10.0, 2.0)
+        };
+        assert!(ranker.score(&friend) > ranker.score(&stranger));
+
+        let seen = PostFeatures {
+            already_seen: true,
+            ..features(10.0, 2.0)
+        };
+        assert!(ranker.score(&seen) < ranker.score(&stranger));
+    }
+
+    #[test]
+    fn test_weighted_ranker_spreads_out_authors() {
+        let prolific = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let candidates = vec![
+            candidate(prolific, features(12.0, 1.0)),
+            candidate(prolific, features(11.0, 1.0)),
+            candidate(other, features(10.0, 1.0)),
+        ];
+
+        let weighted = rank(&WeightedRanker::default(), candidates.clone());
+        let authors: Vec<Uuid> = weighted.iter().map(|c| c.author_id).collect();
+        assert_eq!(authors, vec![prolific, other, prolific]);
+
+        // The original formula ignores authors entirely
+        let engagement = rank(&EngagementRanker, candidates);
+        let authors: Vec<Uuid> = engagement.iter().map(|c| c.author_id).collect();
+        assert_eq!(authors, vec![prolific, prolific, other]);
+    }
+
+    #[test]
+    fn test_feedback_counts_against_authors_and_hashtags() {
+        let now = Utc::now();
+        let author_id = Uuid::new_v4();
+        let post = |user_id: Uuid, text: &str| {
+            Post::new(CreatePostRequest {
+                user_id,
+                text_content: Some(text.to_string()),
+                media_attachments: vec![],
+                is_reel: false,
+                visibility: PostVisibility::Public,
+                poll: None,
+            })
+            .unwrap()
+        };
+        let disliked = post(Uuid::new_v4(), "Big match tonight #football");
+        let mut signals = ViewerSignals::default();
+        signals.add_feedback(&[
+            FeedFeedback::new(Uuid::new_v4(), &disliked, FeedFeedbackKind::NotInterested),
+            FeedFeedback::new(
+                Uuid::new_v4(),
+                &post(author_id, "Anything"),
+                FeedFeedbackKind::ShowFewerFromAuthor,
+            ),
+        ]);
+
+        assert!(signals.hidden_post_ids.contains(&disliked.id));
+        let same_hashtag = signals.features(&post(Uuid::new_v4(), "#Football again"), now);
+        assert_eq!(same_hashtag.hashtag_feedback, 1.0);
+        let same_author = signals.features(&post(author_id, "Hello"), now);
+        assert_eq!(same_author.author_feedback, 1.0);
+
+        let neutral = features(10.0, 1.0);
+        for penalized in [same_hashtag, same_author] {
+            let penalized = PostFeatures {
+                engagement: 10.0,
+                ..penalized
+            };
+            for ranker in [
+                &EngagementRanker as &dyn FeedRanker,
+                &WeightedRanker::default(),
+                &ExploreRanker,
+            ] {
+                assert!(ranker.score(&penalized) < ranker.score(&neutral));
+            }
+        }
+    }
+
+    #[test]
+    fn test_explore_ranker_boosts_liked_hashtags_and_friends_likes() {
+        let trending = features(40.0, 1.0);
+        let liked_hashtags = PostFeatures {
+            hashtag_affinity: 12.0,
+            ..features(10.0, 1.0)
+        };
+        let liked_by_friends = PostFeatures {
+            followed_likes: 3.0,
+            ..features(10.0, 1.0)
+        };
+
+        assert!(ExploreRanker.score(&liked_hashtags) > ExploreRanker.score(&trending));
+        assert!(ExploreRanker.score(&liked_by_friends) > ExploreRanker.score(&trending));
+    }
+
+    #[test]
+    fn test_weights_file_keeps_defaults_for_missing_weights() {
+        let weights: RankingWeights =
+            serde_json::from_str(r#"{"recency": 5.0, "content_types": {"poll": 1.0}}"#).unwrap();
+        assert_eq!(weights.recency, 5.0);
+        assert_eq!(weights.content_types.get("poll"), Some(&1.0));
+        assert_eq!(
+            weights.author_repeat,
+            RankingWeights::default().author_repeat
+        );
+
+        assert!(ranker_by_name("engagement", None).is_ok());
+        assert!(ranker_by_name("weighted", None).is_ok());
+        assert!(ranker_by_name("random", None).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_replays_sessions() {
+        let author = Uuid::new_v4();
+        let popular = candidate(author, features(50.0, 1.0));
+        let engaged = candidate(Uuid::new_v4(), features(1.0, 1.0));
+        let session = LoggedSession {
+            viewer_id: Uuid::new_v4(),
+            served_at: Utc::now(),
+            ranker: "engagement".to_string(),
+            candidates: vec![popular.clone(), engaged.clone()],
+            engaged_post_ids: vec![engaged.post_id],
+        };
+        let unengaged = LoggedSession {
+            engaged_post_ids: vec![],
+            ..session.clone()
+        };
+
+        let evaluation = evaluate(&EngagementRanker, &[session.clone(), unengaged], 1);
+        assert_eq!(evaluation.sessions, 1);
+        assert_eq!(evaluation.ndcg_at_k, 0.0);
+        assert_eq!(evaluation.precision_at_k, 0.0);
+        assert_eq!(evaluation.mean_reciprocal_rank, 0.5);
+
+        let engaged_session = LoggedSession {
+            engaged_post_ids: vec![popular.post_id],
+            ..session
+        };
+        let evaluation = evaluate(&EngagementRanker, &[engaged_session], 1);
+        assert_eq!(evaluation.ndcg_at_k, 1.0);
+        assert_eq!(evaluation.mean_reciprocal_rank, 1.0);
+    }
+}
@@ -0,0 +1,401 @@
+//! Private bookmark collections and paging through saved posts.
+use super::PostVisibilityService;
+use crate::domain::entities::{Bookmark, BookmarkCollection, Post};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{BookmarkRepository, PostRepository};
+use crate::domain::value_objects::Cursor;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Bookmark service for saving posts into private collections
+pub struct BookmarkService {
+    bookmark_repository: Arc<dyn BookmarkRepository>,
+    post_repository: Arc<dyn PostRepository>,
+    visibility_service: Arc<PostVisibilityService>,
+}
+
+/// A page of bookmarks hydrated with their posts
+#[derive(Debug)]
+pub struct BookmarkPage {
+    pub items: Vec<(Bookmark, Post)>,
+    pub next_cursor: Option<Cursor>,
+}
+
+impl BookmarkService {
+    pub fn new(
+        bookmark_repository: Arc<dyn BookmarkRepository>,
+        post_repository: Arc<dyn PostRepository>,
+        visibility_service: Arc<PostVisibilityService>,
+    ) -> Self {
+        Self {
+            bookmark_repository,
+            post_repository,
+            visibility_service,
+        }
+    }
+
+    /// Bookmark a post, optionally filing it into one of the user's collections
+    pub async fn add_bookmark(
+        &self,
+        user_id: Uuid,
+        post_id: Uuid,
+        collection_id: Option<Uuid>,
+    ) -> Result<Bookmark> {
+        self.visibility_service
+            .find_visible_post(Some(user_id), post_id)
+            .await?;
+
+        if let Some(collection_id) = collection_id {
+            self.get_owned_collection(user_id, collection_id).await?;
+        }
+
+        let bookmark = Bookmark::new(user_id, post_id, collection_id);
+        self.bookmark_repository.upsert(&bookmark).await
+    }
+
+    /// Remove a bookmark
+    pub async fn remove_bookmark(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+        self.bookmark_repository.remove(user_id, post_id).await
+    }
+
+    /// Get a page of bookmarked posts.
+    ///
+    /// Posts that were deleted or are no longer visible to the user are skipped
+    /// rather than failing the whole page.
+    pub async fn get_bookmarks(
+        &self,
+        user_id: Uuid,
+        collection_id: Option<Uuid>,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<BookmarkPage> {
+        if let Some(collection_id) = collection_id {
+            self.get_owned_collection(user_id, collection_id).await?;
+        }
+
+        let mut bookmarks = self
+            .bookmark_repository
+            .find_by_user(user_id, collection_id, after, limit + 1)
+            .await?;
+
+        let has_more = bookmarks.len() as i64 > limit;
+        bookmarks.truncate(limit.max(0) as usize);
+        let next_cursor = if has_more {
+            bookmarks.last().map(|b| Cursor::new(b.created_at, b.id))
+        } else {
+            None
+        };
+
+        let post_ids: Vec<Uuid> = bookmarks.iter().map(|b| b.post_id).collect();
+        let posts = self.post_repository.find_by_ids(&post_ids).await?;
+        let mut posts: HashMap<Uuid, Post> = self
+            .visibility_service
+            .filter_visible(Some(user_id), posts)
+            .await?
+            .into_iter()
+            .map(|post| (post.id, post))
+            .collect();
+
+        let items = bookmarks
+            .into_iter()
+            .filter_map(|bookmark| {
+                let post = posts.remove(&bookmark.post_id)?;
+                Some((bookmark, post))
+            })
+            .collect();
+
+        Ok(BookmarkPage { items, next_cursor })
+    }
+
+    /// Create a named collection
+    pub async fn create_collection(
+        &self,
+        user_id: Uuid,
+        name: String,
+    ) -> Result<BookmarkCollection> {
+        let collection = BookmarkCollection::new(user_id, name)?;
+        self.bookmark_repository
+            .create_collection(&collection)
+            .await
+    }
+
+    /// List a user's collections
+    pub async fn get_collections(&self, user_id: Uuid) -> Result<Vec<BookmarkCollection>> {
+        self.bookmark_repository
+            .find_collections_by_user(user_id)
+            .await
+    }
+
+    /// Delete a collection; its bookmarks stay saved without a collection
+    pub async fn delete_collection(&self, user_id: Uuid, collection_id: Uuid) -> Result<()> {
+        self.get_owned_collection(user_id, collection_id).await?;
+        self.bookmark_repository
+            .delete_collection(collection_id)
+            .await
+    }
+
+    async fn get_owned_collection(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<BookmarkCollection> {
+        let collection = self
+            .bookmark_repository
+            .find_collection_by_id(collection_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Collection not found".to_string()))?;
+
+        // Collections are private, so someone else's collection is reported as missing
+        if collection.user_id != user_id {
+            return Err(AppError::NotFound("Collection not found".to_string()));
+        }
+
+        Ok(collection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::domain::entities::PostVisibility;
+    use crate::domain::repositories::UserRepository;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::sync::Mutex;
+
+    struct MockBookmarkRepository {
+        bookmarks: Mutex<Vec<Bookmark>>,
+        collections: Mutex<HashMap<Uuid, BookmarkCollection>>,
+    }
+
+    impl MockBookmarkRepository {
+        fn new() -> Self {
+            Self {
+                bookmarks: Mutex::new(Vec::new()),
+                collections: Mutex::new(HashMap::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BookmarkRepository for MockBookmarkRepository {
+        async fn upsert(&self, bookmark: &Bookmark) -> Result<Bookmark> {
+            let mut bookmarks = self.bookmarks.lock().unwrap();
+            if let Some(existing) = bookmarks
+                .iter_mut()
+                .find(|b| b.user_id == bookmark.user_id && b.post_id == bookmark.post_id)
+            {
+                existing.collection_id = bookmark.collection_id;
+                return Ok(existing.clone());
+            }
+            bookmarks.push(bookmark.clone());
+            Ok(bookmark.clone())
+        }
+
+        async fn remove(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+            self.bookmarks
+                .lock()
+                .unwrap()
+                .retain(|b| !(b.user_id == user_id && b.post_id == post_id));
+            Ok(())
+        }
+
+        async fn find_by_user(
+            &self,
+            user_id: Uuid,
+            collection_id: Option<Uuid>,
+            after: Option<Cursor>,
+            limit: i64,
+        ) -> Result<Vec<Bookmark>> {
+            let mut bookmarks: Vec<Bookmark> = self
+                .bookmarks
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|b| b.user_id == user_id)
+                .filter(|b| collection_id.is_none() || b.collection_id == collection_id)
+                .filter(|b| after.is_none_or(|c| (b.created_at, b.id) < (c.created_at, c.id)))
+                .cloned()
+                .collect();
+            bookmarks.sort_by_key(|b| std::cmp::Reverse((b.created_at, b.id)));
+            bookmarks.truncate(limit as usize);
+            Ok(bookmarks)
+        }
+
+        async fn is_bookmarked(&self, user_id: Uuid, post_id: Uuid) -> Result<bool> {
+            Ok(self
+                .bookmarks
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|b| b.user_id == user_id && b.post_id == post_id))
+        }
+
+        async fn create_collection(
+            &self,
+            collection: &BookmarkCollection,
+        ) -> Result<BookmarkCollection> {
+            self.collections
+                .lock()
+                .unwrap()
+                .insert(collection.id, collection.clone());
+            Ok(collection.clone())
+        }
+
+        async fn find_collection_by_id(&self, id: Uuid) -> Result<Option<BookmarkCollection>> {
+            Ok(self.collections.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_collections_by_user(&self, user_id: Uuid) -> Result<Vec<BookmarkCollection>> {
+            Ok(self
+                .collections
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|c| c.user_id == user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete_collection(&self, id: Uuid) -> Result<()> {
+            self.collections.lock().unwrap().remove(&id);
+            for bookmark in self.bookmarks.lock().unwrap().iter_mut() {
+                if bookmark.collection_id == Some(id) {
+                    bookmark.collection_id = None;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn setup() -> (
+        BookmarkService,
+        Arc<MockBookmarkRepository>,
+        Arc<MockPostRepository>,
+        Arc<TestUserRepository>,
+    ) {
+        let bookmarks = Arc::new(MockBookmarkRepository::new());
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let visibility = Arc::new(PostVisibilityService::new(posts.clone(), users.clone()));
+        let service = BookmarkService::new(bookmarks.clone(), posts.clone(), visibility);
+        (service, bookmarks, posts, users)
+    }
+
+    #[tokio::test]
+    async fn test_bookmark_pagination_with_cursor() {
+        let (service, _, posts, _) = setup();
+        let user = create_test_user("reader", "reader@example.com");
+        let author = Uuid::new_v4();
+
+        for _ in 0..5 {
+            let post = create_post(author, PostVisibility::Public);
+            posts.add_post(post.clone());
+            service.add_bookmark(user.id, post.id, None).await.unwrap();
+        }
+
+        let first = service.get_bookmarks(user.id, None, None, 3).await.unwrap();
+        assert_eq!(first.items.len(), 3);
+        assert!(first.next_cursor.is_some());
+
+        let second = service
+            .get_bookmarks(user.id, None, first.next_cursor, 3)
+            .await
+            .unwrap();
+        assert_eq!(second.items.len(), 2);
+        assert!(second.next_cursor.is_none());
+
+        let first_ids: Vec<Uuid> = first.items.iter().map(|(b, _)| b.id).collect();
+        assert!(second.items.iter().all(|(b, _)| !first_ids.contains(&b.id)));
+    }
+
+    #[tokio::test]
+    async fn test_deleted_and_inaccessible_posts_are_skipped() {
+        let (service, _, posts, _) = setup();
+        let user = create_test_user("reader", "reader@example.com");
+        let author = Uuid::new_v4();
+
+        let kept = create_post(author, PostVisibility::Public);
+        let deleted = create_post(author, PostVisibility::Public);
+        let hidden = create_post(author, PostVisibility::Public);
+        for post in [&kept, &deleted, &hidden] {
+            posts.add_post(post.clone());
+            service.add_bookmark(user.id, post.id, None).await.unwrap();
+        }
+
+        posts.delete(deleted.id).await.unwrap();
+        let mut now_private = hidden.clone();
+        now_private.visibility = PostVisibility::Private;
+        posts.add_post(now_private);
+
+        let page = service
+            .get_bookmarks(user.id, None, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].1.id, kept.id);
+    }
+
+    #[tokio::test]
+    async fn test_cannot_bookmark_followers_only_post_without_following() {
+        let (service, _, posts, users) = setup();
+        let reader = create_test_user("reader", "reader@example.com");
+        let author = create_test_user("author", "author@example.com");
+        let post = create_post(author.id, PostVisibility::Followers);
+        posts.add_post(post.clone());
+
+        let result = service.add_bookmark(reader.id, post.id, None).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        users.follow(reader.id, author.id).await.unwrap();
+        assert!(service.add_bookmark(reader.id, post.id, None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_collections_are_private() {
+        let (service, bookmarks, posts, _) = setup();
+        let owner = create_test_user("owner", "owner@example.com");
+        let other = create_test_user("other", "other@example.com");
+        let post = create_post(Uuid::new_v4(), PostVisibility::Public);
+        posts.add_post(post.clone());
+
+        let collection = service
+            .create_collection(owner.id, "Recipes".to_string())
+            .await
+            .unwrap();
+
+        let result = service
+            .add_bookmark(other.id, post.id, Some(collection.id))
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = service
+            .get_bookmarks(other.id, Some(collection.id), None, 10)
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        service
+            .add_bookmark(owner.id, post.id, Some(collection.id))
+            .await
+            .unwrap();
+        service
+            .delete_collection(owner.id, collection.id)
+            .await
+            .unwrap();
+        assert!(bookmarks.is_bookmarked(owner.id, post.id).await.unwrap());
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::new(Utc::now(), Uuid::new_v4());
+        let decoded = Cursor::decode(&cursor.encode()).unwrap();
+        assert_eq!(decoded.id, cursor.id);
+        assert_eq!(
+            decoded.created_at.timestamp_micros(),
+            cursor.created_at.timestamp_micros()
+        );
+        assert!(Cursor::decode("not-a-cursor").is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! Screening user-written text against the content filter before it is saved,
+//! and queueing flagged content for moderators to review.
+use super::ModerationService;
+use crate::domain::entities::ReportTargetType;
+use crate::domain::errors::Result;
+use crate::infrastructure::content_filter::{ContentFilter, FilterField, ScreenedText};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Content filter service screening user-written text before it is saved
+pub struct ContentFilterService {
+    filter: Arc<ContentFilter>,
+    moderation_service: Arc<ModerationService>,
+}
+
+impl ContentFilterService {
+    pub fn new(filter: Arc<ContentFilter>, moderation_service: Arc<ModerationService>) -> Self {
+        Self {
+            filter,
+            moderation_service,
+        }
+    }
+
+    /// Screen text for a field, returning it with masked words hidden
+    pub fn screen(&self, field: FilterField, text: &str) -> Result<ScreenedText> {
+        self.filter.screen(field, text)
+    }
+
+    /// Screen optional text, such as a bio that may be left out of an update
+    pub fn screen_optional(
+        &self,
+        field: FilterField,
+        text: Option<String>,
+    ) -> Result<(Option<String>, Vec<String>)> {
+        match text {
+            Some(text) => {
+                let screened = self.screen(field, &text)?;
+                Ok((Some(screened.text), screened.flagged_rules))
+            }
+            None => Ok((None, Vec::new())),
+        }
+    }
+
+    /// Queue saved content for review if any flag rules matched. Best effort,
+    /// since the content has already been saved.
+    pub async fn flag(
+        &self,
+        flagged_rules: &[String],
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        target_user_id: Uuid,
+    ) {
+        if flagged_rules.is_empty() {
+            return;
+        }
+
+        if let Err(e) = self
+            .moderation_service
+            .flag_for_review(target_type, target_id, target_user_id, flagged_rules)
+            .await
+        {
+            tracing::warn!(
+                "Failed to flag {} {} for review: {}",
+                target_type,
+                target_id,
+                e
+            );
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Loading the authors, likes and polls that a page of posts needs before it
+//! can be shown to a viewer.
+use super::{PollService, PollView};
+use crate::domain::entities::{Post, PostContentType, User};
+use crate::domain::errors::Result;
+use crate::domain::repositories::{PostRepository, UserRepository};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Post hydration service loading what a page of posts needs to be shown,
+/// with a fixed number of queries however long the page is
+pub struct PostHydrationService {
+    post_repository: Arc<dyn PostRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    poll_service: Arc<PollService>,
+}
+
+/// A post together with everything needed to show it to one viewer
+#[derive(Debug)]
+pub struct HydratedPost {
+    pub post: Post,
+    pub author: User,
+    pub is_liked: bool,
+    pub poll: Option<PollView>,
+}
+
+impl PostHydrationService {
+    pub fn new(
+        post_repository: Arc<dyn PostRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        poll_service: Arc<PollService>,
+    ) -> Self {
+        Self {
+            post_repository,
+            user_repository,
+            poll_service,
+        }
+    }
+
+    /// Load the authors, the viewer's likes and the polls for a page of posts.
+    ///
+    /// Takes at most four queries whatever the page size. The order of the
+    /// posts is kept and posts whose author no longer exists are dropped.
+    pub async fn hydrate(
+        &self,
+        viewer_id: Option<Uuid>,
+        posts: Vec<Post>,
+    ) -> Result<Vec<HydratedPost>> {
+        if posts.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut author_ids: Vec<Uuid> = posts.iter().map(|p| p.user_id).collect();
+        author_ids.sort_unstable();
+        author_ids.dedup();
+        let authors: HashMap<Uuid, User> = self
+            .user_repository
+            .find_by_ids(&author_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect();
+
+        let liked = match viewer_id {
+            Some(viewer_id) => {
+                let post_ids: Vec<Uuid> = posts.iter().map(|p| p.id).collect();
+                self.post_repository
+                    .liked_post_ids(viewer_id, &post_ids)
+                    .await?
+            }
+            None => HashSet::new(),
+        };
+
+        let poll_post_ids: Vec<Uuid> = posts
+            .iter()
+            .filter(|p| p.content_type == PostContentType::Poll)
+            .map(|p| p.id)
+            .collect();
+        let mut polls = self
+            .poll_service
+            .find_poll_views(viewer_id, &poll_post_ids)
+            .await?;
+
+        Ok(posts
+            .into_iter()
+            .filter_map(|post| {
+                let author = authors.get(&post.user_id)?.clone();
+                Some(HydratedPost {
+                    author,
+                    is_liked: liked.contains(&post.id),
+                    poll: polls.remove(&post.id),
+                    post,
+                })
+            })
+            .collect())
+    }
+}
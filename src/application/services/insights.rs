@@ -0,0 +1,509 @@
+//! Creator insights: engagement and follower growth per day, for one post or
+//! for all of a creator's posts.
+use crate::domain::entities::{
+    DailyEngagement, DailyFollowerStats, EngagementEvent, EngagementTotals, Post,
+};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{InsightsRepository, PostRepository, UserRepository};
+use chrono::{NaiveDate, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Insights service for creators' engagement rollups
+pub struct InsightsService {
+    insights_repository: Arc<dyn InsightsRepository>,
+    post_repository: Arc<dyn PostRepository>,
+    user_repository: Arc<dyn UserRepository>,
+}
+
+/// How one post performed over a range of days
+#[derive(Debug)]
+pub struct PostInsights {
+    pub post: Post,
+    pub totals: EngagementTotals,
+    /// One entry per day in the range, oldest first
+    pub daily: Vec<DailyEngagement>,
+}
+
+/// How all of a creator's posts performed over a range of days
+#[derive(Debug)]
+pub struct UserInsights {
+    pub follower_count: i32,
+    pub totals: EngagementTotals,
+    /// One entry per day in the range, oldest first
+    pub daily: Vec<DailyEngagement>,
+    /// One entry per day in the range, oldest first
+    pub follower_growth: Vec<DailyFollowerStats>,
+    pub top_posts: Vec<(Post, EngagementTotals)>,
+}
+
+/// Number of days insights cover unless asked otherwise, and at most
+pub const DEFAULT_INSIGHTS_DAYS: i64 = 28;
+
+pub const MAX_INSIGHTS_DAYS: i64 = 90;
+
+/// Number of top posts included in a creator's insights
+const INSIGHTS_TOP_POSTS: i64 = 5;
+
+impl InsightsService {
+    pub fn new(
+        insights_repository: Arc<dyn InsightsRepository>,
+        post_repository: Arc<dyn PostRepository>,
+        user_repository: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            insights_repository,
+            post_repository,
+            user_repository,
+        }
+    }
+
+    /// Add an engagement event to today's rollup. Insights are best effort,
+    /// so a failure is only logged and never fails the action itself.
+    pub async fn record_event(&self, event: EngagementEvent) {
+        if let Err(e) = self
+            .insights_repository
+            .record(&event, Utc::now().date_naive())
+            .await
+        {
+            tracing::warn!("Failed to record engagement event {:?}: {}", event, e);
+        }
+    }
+
+    /// Get a post's insights for the last `days` days. Only the author may
+    /// see them; anyone else is told the post does not exist.
+    pub async fn get_post_insights(
+        &self,
+        user_id: Uuid,
+        post_id: Uuid,
+        days: i64,
+    ) -> Result<PostInsights> {
+        let post = self
+            .post_repository
+            .find_by_id(post_id)
+            .await?
+            .filter(|post| post.user_id == user_id)
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+        let (from, to) = insights_range(days);
+        let rows = self
+            .insights_repository
+            .find_post_daily(post_id, from, to)
+            .await?;
+
+        Ok(PostInsights {
+            post,
+            totals: sum_totals(&rows),
+            daily: fill_engagement_days(from, to, rows),
+        })
+    }
+
+    /// Get a creator's insights across all their posts for the last `days` days
+    pub async fn get_user_insights(&self, user_id: Uuid, days: i64) -> Result<UserInsights> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let (from, to) = insights_range(days);
+        let rows = self
+            .insights_repository
+            .find_author_daily(user_id, from, to)
+            .await?;
+        let follower_rows = self
+            .insights_repository
+            .find_follower_daily(user_id, from, to)
+            .await?;
+
+        let ranked = self
+            .insights_repository
+            .find_top_posts(user_id, from, to, INSIGHTS_TOP_POSTS)
+            .await?;
+        let mut top_posts = Vec::with_capacity(ranked.len());
+        for (post_id, totals) in ranked {
+            // Posts deleted since are left out
+            if let Some(post) = self.post_repository.find_by_id(post_id).await? {
+                top_posts.push((post, totals));
+            }
+        }
+
+        Ok(UserInsights {
+            follower_count: user.follower_count,
+            totals: sum_totals(&rows),
+            daily: fill_engagement_days(from, to, rows),
+            follower_growth: fill_follower_days(from, to, follower_rows),
+            top_posts,
+        })
+    }
+}
+
+/// First and last day of an insights range ending today
+fn insights_range(days: i64) -> (NaiveDate, NaiveDate) {
+    let to = Utc::now().date_naive();
+    let from = to - chrono::Duration::days(days.clamp(1, MAX_INSIGHTS_DAYS) - 1);
+    (from, to)
+}
+
+fn sum_totals(rows: &[DailyEngagement]) -> EngagementTotals {
+    let mut totals = EngagementTotals::default();
+    for row in rows {
+        totals.add(&row.totals);
+    }
+    totals
+}
+
+/// Fill in zeroes for the days in `from..=to` without a row
+fn fill_engagement_days(
+    from: NaiveDate,
+    to: NaiveDate,
+    rows: Vec<DailyEngagement>,
+) -> Vec<DailyEngagement> {
+    let mut by_day: std::collections::HashMap<NaiveDate, EngagementTotals> =
+        rows.into_iter().map(|row| (row.day, row.totals)).collect();
+
+    from.iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| DailyEngagement {
+            day,
+            totals: by_day.remove(&day).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Fill in zeroes for the days in `from..=to` without a row
+fn fill_follower_days(
+    from: NaiveDate,
+    to: NaiveDate,
+    rows: Vec<DailyFollowerStats>,
+) -> Vec<DailyFollowerStats> {
+    let mut by_day: std::collections::HashMap<NaiveDate, DailyFollowerStats> =
+        rows.into_iter().map(|row| (row.day, row)).collect();
+
+    from.iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| {
+            by_day.remove(&day).unwrap_or(DailyFollowerStats {
+                day,
+                followers_gained: 0,
+                followers_lost: 0,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    struct MockInsightsRepository {
+        posts: Arc<MockPostRepository>,
+        /// (post_id, author_id, day, totals)
+        post_rows: Mutex<Vec<(Uuid, Uuid, NaiveDate, EngagementTotals)>>,
+        follower_rows: Mutex<Vec<(Uuid, DailyFollowerStats)>>,
+    }
+
+    impl MockInsightsRepository {
+        fn new(posts: Arc<MockPostRepository>) -> Self {
+            Self {
+                posts,
+                post_rows: Mutex::new(Vec::new()),
+                follower_rows: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Stand-in for the view rollup adding impressions and reach
+        fn add_post_totals(&self, post: &Post, day: NaiveDate, delta: EngagementTotals) {
+            let mut rows = self.post_rows.lock().unwrap();
+            match rows
+                .iter_mut()
+                .find(|(post_id, _, row_day, _)| *post_id == post.id && *row_day == day)
+            {
+                Some((_, _, _, totals)) => totals.add(&delta),
+                None => rows.push((post.id, post.user_id, day, delta)),
+            }
+        }
+
+        fn daily<F>(&self, from: NaiveDate, to: NaiveDate, matches: F) -> Vec<DailyEngagement>
+        where
+            F: Fn(Uuid, Uuid) -> bool,
+        {
+            let mut by_day: std::collections::BTreeMap<NaiveDate, EngagementTotals> =
+                std::collections::BTreeMap::new();
+            for (post_id, author_id, day, totals) in self.post_rows.lock().unwrap().iter() {
+                if matches(*post_id, *author_id) && *day >= from && *day <= to {
+                    by_day.entry(*day).or_default().add(totals);
+                }
+            }
+            by_day
+                .into_iter()
+                .map(|(day, totals)| DailyEngagement { day, totals })
+                .collect()
+        }
+
+        fn add_followers(&self, user_id: Uuid, day: NaiveDate, gained: i64, lost: i64) {
+            let mut rows = self.follower_rows.lock().unwrap();
+            match rows
+                .iter_mut()
+                .find(|(row_user, stats)| *row_user == user_id && stats.day == day)
+            {
+                Some((_, stats)) => {
+                    stats.followers_gained += gained;
+                    stats.followers_lost += lost;
+                }
+                None => rows.push((
+                    user_id,
+                    DailyFollowerStats {
+                        day,
+                        followers_gained: gained,
+                        followers_lost: lost,
+                    },
+                )),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl InsightsRepository for MockInsightsRepository {
+        async fn record(&self, event: &EngagementEvent, day: NaiveDate) -> Result<()> {
+            let (post_id, likes, comments) = match *event {
+                EngagementEvent::PostLiked(post_id) => (post_id, 1, 0),
+                EngagementEvent::PostUnliked(post_id) => (post_id, -1, 0),
+                EngagementEvent::PostCommented(post_id) => (post_id, 0, 1),
+                EngagementEvent::UserFollowed(user_id) => {
+                    self.add_followers(user_id, day, 1, 0);
+                    return Ok(());
+                }
+                EngagementEvent::UserUnfollowed(user_id) => {
+                    self.add_followers(user_id, day, 0, 1);
+                    return Ok(());
+                }
+            };
+
+            if let Some(post) = self.posts.find_by_id(post_id).await? {
+                self.add_post_totals(
+                    &post,
+                    day,
+                    EngagementTotals {
+                        likes,
+                        comments,
+                        ..Default::default()
+                    },
+                );
+            }
+            Ok(())
+        }
+
+        async fn find_post_daily(
+            &self,
+            post_id: Uuid,
+            from: NaiveDate,
+            to: NaiveDate,
+        ) -> Result<Vec<DailyEngagement>> {
+            Ok(self.daily(from, to, |row_post, _| row_post == post_id))
+        }
+
+        async fn find_author_daily(
+            &self,
+            author_id: Uuid,
+            from: NaiveDate,
+            to: NaiveDate,
+        ) -> Result<Vec<DailyEngagement>> {
+            Ok(self.daily(from, to, |_, row_author| row_author == author_id))
+        }
+
+        async fn find_top_posts(
+            &self,
+            author_id: Uuid,
+            from: NaiveDate,
+            to: NaiveDate,
+            limit: i64,
+        ) -> Result<Vec<(Uuid, EngagementTotals)>> {
+            let mut by_post: HashMap<Uuid, EngagementTotals> = HashMap::new();
+            for (post_id, row_author, day, totals) in self.post_rows.lock().unwrap().iter() {
+                if *row_author == author_id && *day >= from && *day <= to {
+                    by_post.entry(*post_id).or_default().add(totals);
+                }
+            }
+            let mut ranked: Vec<(Uuid, EngagementTotals)> = by_post.into_iter().collect();
+            ranked.sort_by_key(|(_, totals)| {
+                std::cmp::Reverse((totals.interactions(), totals.impressions))
+            });
+            ranked.truncate(limit as usize);
+            Ok(ranked)
+        }
+
+        async fn find_follower_daily(
+            &self,
+            user_id: Uuid,
+            from: NaiveDate,
+            to: NaiveDate,
+        ) -> Result<Vec<DailyFollowerStats>> {
+            let mut rows: Vec<DailyFollowerStats> = self
+                .follower_rows
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(row_user, stats)| {
+                    *row_user == user_id && stats.day >= from && stats.day <= to
+                })
+                .map(|(_, stats)| stats.clone())
+                .collect();
+            rows.sort_by_key(|stats| stats.day);
+            Ok(rows)
+        }
+    }
+
+    fn today() -> NaiveDate {
+        Utc::now().date_naive()
+    }
+
+    #[tokio::test]
+    async fn test_post_insights_cover_every_day_in_range() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let author = add_test_user(&users, "author");
+        let insights = Arc::new(MockInsightsRepository::new(posts.clone()));
+        let service = InsightsService::new(insights.clone(), posts.clone(), users);
+
+        let post = create_test_post(author.id, "Hello", false);
+        posts.add_post(post.clone());
+
+        let two_days_ago = today() - chrono::Duration::days(2);
+        insights.add_post_totals(
+            &post,
+            two_days_ago,
+            EngagementTotals {
+                impressions: 10,
+                follower_impressions: 4,
+                reach: 6,
+                follower_reach: 2,
+                ..Default::default()
+            },
+        );
+        service
+            .record_event(EngagementEvent::PostLiked(post.id))
+            .await;
+        service
+            .record_event(EngagementEvent::PostLiked(post.id))
+            .await;
+        service
+            .record_event(EngagementEvent::PostUnliked(post.id))
+            .await;
+        service
+            .record_event(EngagementEvent::PostCommented(post.id))
+            .await;
+
+        let insights = service
+            .get_post_insights(author.id, post.id, 7)
+            .await
+            .unwrap();
+
+        assert_eq!(insights.daily.len(), 7);
+        assert_eq!(
+            insights.daily.first().unwrap().day,
+            today() - chrono::Duration::days(6)
+        );
+        assert_eq!(insights.daily.last().unwrap().day, today());
+        assert_eq!(insights.daily[4].totals.impressions, 10);
+        assert_eq!(
+            insights.daily[5],
+            DailyEngagement {
+                day: today() - chrono::Duration::days(1),
+                totals: EngagementTotals::default(),
+            }
+        );
+        assert_eq!(insights.daily[6].totals.likes, 1);
+
+        assert_eq!(insights.totals.impressions, 10);
+        assert_eq!(insights.totals.non_follower_impressions(), 6);
+        assert_eq!(insights.totals.non_follower_reach(), 4);
+        assert_eq!(insights.totals.likes, 1);
+        assert_eq!(insights.totals.comments, 1);
+    }
+
+    #[tokio::test]
+    async fn test_post_insights_are_private_to_the_author() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let author = add_test_user(&users, "author");
+        let other = add_test_user(&users, "other");
+        let insights = Arc::new(MockInsightsRepository::new(posts.clone()));
+        let service = InsightsService::new(insights.clone(), posts.clone(), users);
+
+        let post = create_test_post(author.id, "Hello", false);
+        posts.add_post(post.clone());
+
+        let result = service.get_post_insights(other.id, post.id, 7).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = service
+            .get_post_insights(author.id, Uuid::new_v4(), 7)
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_user_insights_include_growth_and_top_posts() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let author = add_test_user(&users, "author");
+        let insights = Arc::new(MockInsightsRepository::new(posts.clone()));
+        let service = InsightsService::new(insights.clone(), posts.clone(), users);
+
+        let popular = create_test_post(author.id, "Popular", false);
+        let quiet = create_test_post(author.id, "Quiet", false);
+        let deleted = create_test_post(author.id, "Deleted", false);
+        posts.add_post(popular.clone());
+        posts.add_post(quiet.clone());
+        for post in [&popular, &quiet, &deleted] {
+            insights.add_post_totals(
+                post,
+                today(),
+                EngagementTotals {
+                    impressions: 5,
+                    ..Default::default()
+                },
+            );
+        }
+        insights.add_post_totals(
+            &deleted,
+            today(),
+            EngagementTotals {
+                likes: 50,
+                ..Default::default()
+            },
+        );
+        for _ in 0..3 {
+            service
+                .record_event(EngagementEvent::PostLiked(popular.id))
+                .await;
+        }
+        service
+            .record_event(EngagementEvent::UserFollowed(author.id))
+            .await;
+        service
+            .record_event(EngagementEvent::UserFollowed(author.id))
+            .await;
+        service
+            .record_event(EngagementEvent::UserUnfollowed(author.id))
+            .await;
+
+        let insights = service.get_user_insights(author.id, 0).await.unwrap();
+
+        // Ranges are at least one day long
+        assert_eq!(insights.daily.len(), 1);
+        assert_eq!(insights.totals.impressions, 15);
+        assert_eq!(insights.totals.likes, 53);
+
+        assert_eq!(insights.follower_growth.len(), 1);
+        assert_eq!(insights.follower_growth[0].net_change(), 1);
+
+        let top_ids: Vec<Uuid> = insights.top_posts.iter().map(|(p, _)| p.id).collect();
+        assert_eq!(top_ids, vec![popular.id, quiet.id]);
+        assert_eq!(insights.top_posts[0].1.likes, 3);
+    }
+}
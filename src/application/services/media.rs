@@ -0,0 +1,824 @@
+//! Upload sessions for post and story media: the file goes to object storage,
+//! and images and video posters are turned into renditions.
+use crate::domain::entities::{
+    media_type_for_extension, MediaAttachment, MediaRendition, MediaUpload,
+};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::MediaUploadRepository;
+use crate::infrastructure::media_processing::{
+    process_image, process_poster_frame, sniff_image_type, EncodedRendition, MediaProber,
+    PosterFrameExtractor, ProbeError,
+};
+use crate::infrastructure::storage::{MediaStorage, PresignedUpload};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Media service for upload sessions backed by object storage
+pub struct MediaService {
+    upload_repository: Arc<dyn MediaUploadRepository>,
+    storage: Arc<dyn MediaStorage>,
+    /// None when ffprobe is unavailable, in which case video and audio are refused
+    media_prober: Option<Arc<dyn MediaProber>>,
+    poster_frames: Option<Arc<dyn PosterFrameExtractor>>,
+}
+
+/// A new upload session and where the client should send the file
+#[derive(Debug)]
+pub struct UploadTarget {
+    pub upload: MediaUpload,
+    /// None when the file must be sent through the API
+    pub presigned: Option<PresignedUpload>,
+}
+
+/// Poster frames are taken this far into a video, or halfway through shorter ones
+const POSTER_FRAME_OFFSET_MS: u64 = 1000;
+
+impl MediaService {
+    pub fn new(
+        upload_repository: Arc<dyn MediaUploadRepository>,
+        storage: Arc<dyn MediaStorage>,
+        media_prober: Option<Arc<dyn MediaProber>>,
+        poster_frames: Option<Arc<dyn PosterFrameExtractor>>,
+    ) -> Self {
+        Self {
+            upload_repository,
+            storage,
+            media_prober,
+            poster_frames,
+        }
+    }
+
+    /// Start an upload session, checking the declared file against the attachment rules
+    pub async fn create_upload(
+        &self,
+        user_id: Uuid,
+        media_type: String,
+        size: i64,
+        width: Option<i32>,
+        height: Option<i32>,
+        duration: Option<i32>,
+    ) -> Result<UploadTarget> {
+        let upload = MediaUpload::new(user_id, media_type, size, width, height, duration)?;
+        if !upload.is_image() && self.media_prober.is_none() {
+            return Err(AppError::ServiceUnavailable);
+        }
+        let upload = self.upload_repository.create(&upload).await?;
+
+        let presigned = self
+            .storage
+            .presign_upload(&upload.storage_key, &upload.media_type, upload.expires_at)
+            .await?;
+
+        Ok(UploadTarget { upload, presigned })
+    }
+
+    /// Get one of the user's upload sessions
+    pub async fn get_upload(&self, user_id: Uuid, upload_id: Uuid) -> Result<MediaUpload> {
+        self.upload_repository
+            .find_by_id(upload_id)
+            .await?
+            .filter(|upload| upload.user_id == user_id)
+            .ok_or_else(|| AppError::NotFound("Media upload not found".to_string()))
+    }
+
+    /// Receive the file through the API and complete the upload
+    pub async fn upload_content(
+        &self,
+        user_id: Uuid,
+        upload_id: Uuid,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<MediaUpload> {
+        let mut upload = self.get_pending_upload(user_id, upload_id).await?;
+
+        if content_type != upload.media_type {
+            return Err(AppError::ValidationError(
+                "Content type does not match the upload".to_string(),
+            ));
+        }
+
+        if data.len() as i64 != upload.size {
+            return Err(AppError::ValidationError(
+                "Uploaded size does not match the declared size".to_string(),
+            ));
+        }
+
+        let data = self.process_content(&mut upload, data).await?;
+        self.storage
+            .put_object(&upload.storage_key, &upload.media_type, data)
+            .await?;
+
+        self.finish_upload(upload).await
+    }
+
+    /// Complete an upload the client sent straight to storage
+    pub async fn complete_upload(&self, user_id: Uuid, upload_id: Uuid) -> Result<MediaUpload> {
+        let mut upload = self.get_pending_upload(user_id, upload_id).await?;
+
+        match self.storage.object_size(&upload.storage_key).await? {
+            None => {
+                return Err(AppError::ValidationError(
+                    "Media file has not been uploaded".to_string(),
+                ))
+            }
+            Some(size) if size != upload.size => {
+                // Presigned uploads cannot enforce the size, so discard anything that does not match
+                self.storage.delete_object(&upload.storage_key).await?;
+                return Err(AppError::ValidationError(
+                    "Uploaded size does not match the declared size".to_string(),
+                ));
+            }
+            Some(_) => {}
+        }
+
+        // The file is checked after the fact; for images the stored object is replaced
+        let data = self
+            .storage
+            .get_object(&upload.storage_key)
+            .await?
+            .ok_or_else(|| {
+                AppError::ValidationError("Media file has not been uploaded".to_string())
+            })?;
+
+        let data = match self.process_content(&mut upload, data).await {
+            Ok(data) => data,
+            Err(e) => {
+                self.storage.delete_object(&upload.storage_key).await?;
+                return Err(e);
+            }
+        };
+        if upload.is_image() {
+            self.storage
+                .put_object(&upload.storage_key, &upload.media_type, data)
+                .await?;
+        }
+
+        self.finish_upload(upload).await
+    }
+
+    /// Turn completed uploads owned by the user into attachments, keeping their order
+    pub async fn resolve_attachments(
+        &self,
+        user_id: Uuid,
+        upload_ids: &[Uuid],
+    ) -> Result<Vec<MediaAttachment>> {
+        let mut attachments = Vec::with_capacity(upload_ids.len());
+        for upload_id in upload_ids {
+            attachments.push(self.resolve_attachment(user_id, *upload_id).await?);
+        }
+        Ok(attachments)
+    }
+
+    /// Turn a completed upload owned by the user into an attachment
+    pub async fn resolve_attachment(
+        &self,
+        user_id: Uuid,
+        upload_id: Uuid,
+    ) -> Result<MediaAttachment> {
+        self.get_upload(user_id, upload_id).await?.to_attachment()
+    }
+
+    /// Short-lived URL a stored file can be downloaded from directly, when the
+    /// storage backend supports it
+    pub async fn download_url(&self, key: &str) -> Result<Option<String>> {
+        self.storage.presign_download(key).await
+    }
+
+    /// Read a stored file along with its media type
+    pub async fn read_file(&self, key: &str) -> Result<(Vec<u8>, &'static str)> {
+        let not_found = || AppError::NotFound("Media file not found".to_string());
+
+        let media_type = key
+            .rsplit_once('.')
+            .and_then(|(_, extension)| media_type_for_extension(extension))
+            .ok_or_else(not_found)?;
+
+        let data = self.storage.get_object(key).await?.ok_or_else(not_found)?;
+        Ok((data, media_type))
+    }
+
+    /// Delete an upload along with its original and rendition files.
+    /// Deleting an upload that no longer exists is not an error.
+    pub async fn delete_upload(&self, upload_id: Uuid) -> Result<()> {
+        let upload = match self.upload_repository.find_by_id(upload_id).await? {
+            Some(upload) => upload,
+            None => return Ok(()),
+        };
+
+        for rendition in &upload.renditions {
+            let key = upload.rendition_key(rendition.kind, &rendition.media_type)?;
+            self.storage.delete_object(&key).await?;
+        }
+        self.storage.delete_object(&upload.storage_key).await?;
+
+        self.upload_repository.delete(upload.id).await
+    }
+
+    async fn get_pending_upload(&self, user_id: Uuid, upload_id: Uuid) -> Result<MediaUpload> {
+        let upload = self.get_upload(user_id, upload_id).await?;
+
+        if upload.is_completed() {
+            return Err(AppError::Conflict(
+                "Media upload is already completed".to_string(),
+            ));
+        }
+
+        if upload.is_expired(Utc::now()) {
+            return Err(AppError::ValidationError(
+                "Media upload has expired".to_string(),
+            ));
+        }
+
+        Ok(upload)
+    }
+
+    /// Check an uploaded file against its declared type and record what it really
+    /// contains. Returns the bytes to keep as the original, which for images no
+    /// longer carry EXIF/GPS or other metadata.
+    async fn process_content(&self, upload: &mut MediaUpload, data: Vec<u8>) -> Result<Vec<u8>> {
+        if !upload.is_image() {
+            return self.probe_content(upload, data).await;
+        }
+
+        // Checked before decoding so mismatched files are never decoded at all
+        if sniff_image_type(&data) != Some(upload.media_type.as_str()) {
+            return Err(AppError::ValidationError(
+                "File content does not match the declared media type".to_string(),
+            ));
+        }
+
+        let processed = tokio::task::spawn_blocking(move || process_image(&data))
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+        upload.size = processed.original.len() as i64;
+        upload.width = Some(processed.width as i32);
+        upload.height = Some(processed.height as i32);
+        upload.renditions = self.store_renditions(upload, processed.renditions).await?;
+        upload.blurhash = processed.blurhash;
+        Ok(processed.original)
+    }
+
+    /// Replace the client-declared duration and dimensions of a video or audio
+    /// file with those read from its container, and give videos a poster frame
+    async fn probe_content(&self, upload: &mut MediaUpload, data: Vec<u8>) -> Result<Vec<u8>> {
+        let prober = self
+            .media_prober
+            .as_ref()
+            .ok_or(AppError::ServiceUnavailable)?;
+        let probed = prober
+            .probe(&data, &upload.media_type)
+            .await
+            .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+        if probed.media_type != upload.media_type {
+            return Err(AppError::ValidationError(
+                "File content does not match the declared media type".to_string(),
+            ));
+        }
+
+        // Round to the nearest second, but never down to zero
+        let seconds = ((probed.duration_ms + 500) / 1000).max(1);
+        let duration = i32::try_from(seconds).unwrap_or(i32::MAX);
+        let width = probed.width.map(|width| width as i32);
+        let height = probed.height.map(|height| height as i32);
+        MediaAttachment::validate_media(
+            &upload.media_type,
+            upload.size,
+            width,
+            height,
+            Some(duration),
+        )?;
+
+        upload.width = width;
+        upload.height = height;
+        upload.duration = Some(duration);
+        upload.video_codec = probed.video_codec.map(str::to_string);
+        upload.audio_codec = probed.audio_codec.map(str::to_string);
+
+        if !upload.is_video() {
+            return Ok(data);
+        }
+        let Some(extractor) = self.poster_frames.clone() else {
+            return Ok(data);
+        };
+
+        let at_ms = (probed.duration_ms / 2).min(POSTER_FRAME_OFFSET_MS);
+        let frame = extractor
+            .extract_frame(&data, &upload.media_type, at_ms)
+            .await;
+
+        let frame = match frame {
+            Ok(frame) => frame,
+            // The container looked fine but its frames do not decode
+            Err(ProbeError::Invalid(message)) => return Err(AppError::ValidationError(message)),
+            Err(e) => {
+                tracing::warn!("Skipping poster frame for upload {}: {}", upload.id, e);
+                return Ok(data);
+            }
+        };
+
+        match tokio::task::spawn_blocking(move || process_poster_frame(&frame))
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+        {
+            Ok(poster) => {
+                upload.renditions = self.store_renditions(upload, poster.renditions).await?;
+                upload.blurhash = poster.blurhash;
+            }
+            Err(e) => {
+                tracing::warn!("Skipping poster frame for upload {}: {}", upload.id, e);
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Store encoded renditions next to the upload's original
+    async fn store_renditions(
+        &self,
+        upload: &MediaUpload,
+        encoded: Vec<EncodedRendition>,
+    ) -> Result<Vec<MediaRendition>> {
+        let mut renditions = Vec::with_capacity(encoded.len());
+        for rendition in encoded {
+            let key = upload.rendition_key(rendition.kind, rendition.media_type)?;
+            let size = rendition.data.len() as i64;
+            self.storage
+                .put_object(&key, rendition.media_type, rendition.data)
+                .await?;
+            renditions.push(MediaRendition {
+                kind: rendition.kind,
+                url: self.storage.public_url(&key),
+                media_type: rendition.media_type.to_string(),
+                width: rendition.width as i32,
+                height: rendition.height as i32,
+                size,
+            });
+        }
+        Ok(renditions)
+    }
+
+    async fn finish_upload(&self, mut upload: MediaUpload) -> Result<MediaUpload> {
+        let url = self.storage.public_url(&upload.storage_key);
+        upload.complete(url, Utc::now());
+        self.upload_repository
+            .complete(&upload)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Media upload is already completed".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::domain::entities::{MediaUploadStatus, RenditionKind};
+    use crate::infrastructure::media_processing::tests::{
+        mp3_report, mp4_report, sample_png, webm_report,
+    };
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Hands back a fixed frame, or fails as if the video could not be decoded
+    struct MockPosterFrames {
+        frame: Option<Vec<u8>>,
+        requested_at_ms: Mutex<Option<u64>>,
+    }
+
+    #[async_trait]
+    impl PosterFrameExtractor for MockPosterFrames {
+        async fn extract_frame(
+            &self,
+            _video: &[u8],
+            _media_type: &str,
+            at_ms: u64,
+        ) -> std::result::Result<Vec<u8>, ProbeError> {
+            *self.requested_at_ms.lock().unwrap() = Some(at_ms);
+            self.frame
+                .clone()
+                .ok_or_else(|| ProbeError::Invalid("Video frames could not be decoded".to_string()))
+        }
+    }
+
+    async fn create_av_upload(
+        service: &MediaService,
+        user_id: Uuid,
+        media_type: &str,
+        size: usize,
+    ) -> MediaUpload {
+        // Deliberately wrong declared metadata, which probing must replace
+        service
+            .create_upload(
+                user_id,
+                media_type.to_string(),
+                size as i64,
+                Some(10),
+                Some(10),
+                Some(1),
+            )
+            .await
+            .unwrap()
+            .upload
+    }
+
+    #[tokio::test]
+    async fn test_create_upload_applies_attachment_rules() {
+        let (service, _) = media_service(false);
+        let user_id = Uuid::new_v4();
+
+        let unsupported = service
+            .create_upload(user_id, "application/pdf".to_string(), 10, None, None, None)
+            .await;
+        assert!(matches!(unsupported, Err(AppError::ValidationError(_))));
+
+        let too_large = service
+            .create_upload(
+                user_id,
+                "image/png".to_string(),
+                crate::domain::entities::MAX_MEDIA_SIZE + 1,
+                None,
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(too_large, Err(AppError::ValidationError(_))));
+
+        let target = create_png_upload(&service, user_id, 4).await;
+        assert_eq!(target.upload.status, MediaUploadStatus::Pending);
+        assert!(target.presigned.is_none());
+        assert!(target.upload.storage_key.ends_with(".png"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_content_through_api() {
+        let (service, storage) = media_service(false);
+        let user_id = Uuid::new_v4();
+        let png = sample_png(40, 20);
+        let target = create_png_upload(&service, user_id, png.len() as i64).await;
+        let upload_id = target.upload.id;
+
+        let wrong_type = service
+            .upload_content(user_id, upload_id, "image/jpeg", png.clone())
+            .await;
+        assert!(matches!(wrong_type, Err(AppError::ValidationError(_))));
+
+        let mut too_long = png.clone();
+        too_long.push(0);
+        let wrong_size = service
+            .upload_content(user_id, upload_id, "image/png", too_long)
+            .await;
+        assert!(matches!(wrong_size, Err(AppError::ValidationError(_))));
+
+        let other_user = service
+            .upload_content(Uuid::new_v4(), upload_id, "image/png", png.clone())
+            .await;
+        assert!(matches!(other_user, Err(AppError::NotFound(_))));
+
+        let upload = service
+            .upload_content(user_id, upload_id, "image/png", png.clone())
+            .await
+            .unwrap();
+        assert!(upload.is_completed());
+        assert_eq!(
+            upload.url.as_deref(),
+            Some(format!("https://cdn.test/{}", upload.storage_key).as_str())
+        );
+        assert!(storage
+            .objects
+            .lock()
+            .unwrap()
+            .contains_key(&upload.storage_key));
+
+        let again = service
+            .upload_content(user_id, upload_id, "image/png", png.clone())
+            .await;
+        assert!(matches!(again, Err(AppError::Conflict(_))));
+
+        let (data, media_type) = service.read_file(&upload.storage_key).await.unwrap();
+        assert_eq!(data.len() as i64, upload.size);
+        assert_eq!(media_type, "image/png");
+    }
+
+    #[tokio::test]
+    async fn test_upload_image_is_stripped_and_resized() {
+        let (service, storage) = media_service(false);
+        let user_id = Uuid::new_v4();
+        let png = sample_png(400, 100);
+        let target = create_png_upload(&service, user_id, png.len() as i64).await;
+
+        let upload = service
+            .upload_content(user_id, target.upload.id, "image/png", png.clone())
+            .await
+            .unwrap();
+
+        // The declared dimensions are replaced by the real ones
+        assert_eq!((upload.width, upload.height), (Some(400), Some(100)));
+        assert!(upload.blurhash.is_some());
+
+        let objects = storage.objects.lock().unwrap();
+        let original = &objects[&upload.storage_key];
+        assert!(!original.windows(4).any(|w| w == b"tEXt"));
+        assert_eq!(original.len() as i64, upload.size);
+
+        let renditions: Vec<(RenditionKind, i32, i32)> = upload
+            .renditions
+            .iter()
+            .map(|r| (r.kind, r.width, r.height))
+            .collect();
+        assert_eq!(
+            renditions,
+            vec![
+                (RenditionKind::Thumbnail, 320, 80),
+                (RenditionKind::Thumbnail, 320, 80),
+                (RenditionKind::Feed, 400, 100),
+                (RenditionKind::Feed, 400, 100),
+                (RenditionKind::Full, 400, 100),
+                (RenditionKind::Full, 400, 100),
+            ]
+        );
+        for (rendition, (media_type, extension)) in upload.renditions.iter().zip(
+            [("image/jpeg", "jpg"), ("image/webp", "webp")]
+                .iter()
+                .cycle(),
+        ) {
+            let key = rendition.url.trim_start_matches("https://cdn.test/");
+            assert!(key.ends_with(&format!("_{}.{}", rendition.kind, extension)));
+            assert_eq!(objects[key].len() as i64, rendition.size);
+            assert_eq!(rendition.media_type, *media_type);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_content_not_matching_type() {
+        let (service, storage) = media_service(false);
+        let user_id = Uuid::new_v4();
+
+        let gif =
+            b"GIF89a\x01\0\x01\0\0\0\0\x2c\0\0\0\0\x01\0\x01\0\0\x02\x02\x44\x01\0\x3b".to_vec();
+        let target = create_png_upload(&service, user_id, gif.len() as i64).await;
+        let result = service
+            .upload_content(user_id, target.upload.id, "image/png", gif)
+            .await;
+        match result {
+            Err(AppError::ValidationError(message)) => {
+                assert_eq!(
+                    message,
+                    "File content does not match the declared media type"
+                )
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+
+        let garbage = vec![7; 16];
+        let target = create_png_upload(&service, user_id, 16).await;
+        let result = service
+            .upload_content(user_id, target.upload.id, "image/png", garbage)
+            .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        assert!(storage.objects.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_complete_direct_upload_checks_stored_size() {
+        let (service, storage) = media_service(true);
+        let user_id = Uuid::new_v4();
+        let png = sample_png(8, 8);
+        let target = create_png_upload(&service, user_id, png.len() as i64).await;
+        let key = target.upload.storage_key.clone();
+        assert!(target.presigned.is_some());
+
+        let missing = service.complete_upload(user_id, target.upload.id).await;
+        assert!(matches!(missing, Err(AppError::ValidationError(_))));
+
+        // A file of the wrong size is rejected and removed
+        storage
+            .objects
+            .lock()
+            .unwrap()
+            .insert(key.clone(), vec![0; 9]);
+        let wrong_size = service.complete_upload(user_id, target.upload.id).await;
+        assert!(matches!(wrong_size, Err(AppError::ValidationError(_))));
+        assert!(!storage.objects.lock().unwrap().contains_key(&key));
+
+        // So is a file of the right size that is not a valid image
+        storage
+            .objects
+            .lock()
+            .unwrap()
+            .insert(key.clone(), vec![0; png.len()]);
+        let invalid = service.complete_upload(user_id, target.upload.id).await;
+        assert!(matches!(invalid, Err(AppError::ValidationError(_))));
+        assert!(!storage.objects.lock().unwrap().contains_key(&key));
+
+        storage
+            .objects
+            .lock()
+            .unwrap()
+            .insert(key.clone(), png.clone());
+        let upload = service
+            .complete_upload(user_id, target.upload.id)
+            .await
+            .unwrap();
+        assert!(upload.is_completed());
+        assert_eq!(upload.renditions.len(), 6);
+        assert!(storage.objects.lock().unwrap()[&key].len() < png.len());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attachments_requires_completed_own_uploads() {
+        let (service, _) = media_service(false);
+        let user_id = Uuid::new_v4();
+        let (first_png, second_png) = (sample_png(4, 4), sample_png(2, 6));
+        let first = create_png_upload(&service, user_id, first_png.len() as i64)
+            .await
+            .upload;
+        let second = create_png_upload(&service, user_id, second_png.len() as i64)
+            .await
+            .upload;
+
+        let pending = service.resolve_attachments(user_id, &[first.id]).await;
+        assert!(matches!(pending, Err(AppError::ValidationError(_))));
+
+        service
+            .upload_content(user_id, first.id, "image/png", first_png)
+            .await
+            .unwrap();
+        service
+            .upload_content(user_id, second.id, "image/png", second_png)
+            .await
+            .unwrap();
+
+        let attachments = service
+            .resolve_attachments(user_id, &[second.id, first.id])
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = attachments.iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![second.id, first.id]);
+        assert_eq!(
+            (attachments[0].width, attachments[0].height),
+            (Some(2), Some(6))
+        );
+        assert_eq!(attachments[0].renditions.len(), 6);
+        assert!(attachments[0].blurhash.is_some());
+
+        let other_user = service
+            .resolve_attachments(Uuid::new_v4(), &[first.id])
+            .await;
+        assert!(matches!(other_user, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_upload_video_records_probed_metadata_and_poster() {
+        let frames = Arc::new(MockPosterFrames {
+            frame: Some(sample_png(360, 640)),
+            requested_at_ms: Mutex::new(None),
+        });
+        let (service, storage) = media_service_with_posters(true, Some(frames.clone()));
+        let user_id = Uuid::new_v4();
+        let mp4 = mp4_report(1080, 1920, 42_400);
+        let upload = create_av_upload(&service, user_id, "video/mp4", mp4.len()).await;
+        storage
+            .objects
+            .lock()
+            .unwrap()
+            .insert(upload.storage_key.clone(), mp4.clone());
+
+        let upload = service.complete_upload(user_id, upload.id).await.unwrap();
+        assert_eq!((upload.width, upload.height), (Some(1080), Some(1920)));
+        assert_eq!(upload.duration, Some(42));
+        assert_eq!(upload.video_codec.as_deref(), Some("h264"));
+        assert_eq!(upload.audio_codec.as_deref(), Some("aac"));
+        assert_eq!(*frames.requested_at_ms.lock().unwrap(), Some(1000));
+
+        // The video itself is kept as uploaded
+        let objects = storage.objects.lock().unwrap();
+        assert_eq!(objects[&upload.storage_key], mp4);
+        let kinds: Vec<RenditionKind> = upload.renditions.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RenditionKind::Thumbnail,
+                RenditionKind::Thumbnail,
+                RenditionKind::Feed,
+                RenditionKind::Feed,
+                RenditionKind::Poster,
+                RenditionKind::Poster
+            ]
+        );
+        assert!(upload.renditions[4].url.ends_with("_poster.jpg"));
+        assert!(upload.renditions[5].url.ends_with("_poster.webp"));
+        assert!(upload.blurhash.is_some());
+        assert!(upload.to_attachment().unwrap().is_playable_video());
+    }
+
+    #[tokio::test]
+    async fn test_upload_video_rejects_mismatched_or_undecodable_files() {
+        let frames = Arc::new(MockPosterFrames {
+            frame: None,
+            requested_at_ms: Mutex::new(None),
+        });
+        let (service, storage) = media_service_with_posters(false, Some(frames));
+        let user_id = Uuid::new_v4();
+
+        let webm = webm_report(3000);
+        let upload = create_av_upload(&service, user_id, "video/mp4", webm.len()).await;
+        let mismatched = service
+            .upload_content(user_id, upload.id, "video/mp4", webm)
+            .await;
+        assert!(matches!(mismatched, Err(AppError::ValidationError(_))));
+
+        let mp4 = mp4_report(640, 480, 3000);
+        let upload = create_av_upload(&service, user_id, "video/mp4", mp4.len()).await;
+        let undecodable = service
+            .upload_content(user_id, upload.id, "video/mp4", mp4)
+            .await;
+        match undecodable {
+            Err(AppError::ValidationError(message)) => {
+                assert_eq!(message, "Video frames could not be decoded")
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+        assert!(storage.objects.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upload_audio_records_probed_duration() {
+        let (service, _) = media_service(false);
+        let user_id = Uuid::new_v4();
+        let mp3 = mp3_report(5000);
+        let upload = create_av_upload(&service, user_id, "audio/mpeg", mp3.len()).await;
+
+        let upload = service
+            .upload_content(user_id, upload.id, "audio/mpeg", mp3)
+            .await
+            .unwrap();
+        assert_eq!(upload.duration, Some(5));
+        assert_eq!((upload.width, upload.height), (None, None));
+        assert_eq!(upload.audio_codec.as_deref(), Some("mp3"));
+        assert!(upload.video_codec.is_none());
+        assert!(upload.renditions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_video_and_audio_refused_without_prober() {
+        let service = MediaService::new(
+            Arc::new(MockMediaUploadRepository::new()),
+            Arc::new(MockMediaStorage::new(false)),
+            None,
+            None,
+        );
+        let user_id = Uuid::new_v4();
+
+        for media_type in ["video/mp4", "audio/mpeg"] {
+            let result = service
+                .create_upload(user_id, media_type.to_string(), 1000, None, None, Some(5))
+                .await;
+            assert!(matches!(result, Err(AppError::ServiceUnavailable)));
+        }
+        assert!(service
+            .create_upload(user_id, "image/png".to_string(), 1000, None, None, None)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_expired_upload_rejected() {
+        let (service, _) = media_service(false);
+        let user_id = Uuid::new_v4();
+        let mut upload =
+            MediaUpload::new(user_id, "audio/mpeg".to_string(), 3, None, None, Some(30)).unwrap();
+        upload.expires_at = Utc::now() - chrono::Duration::minutes(1);
+        service.upload_repository.create(&upload).await.unwrap();
+
+        let result = service
+            .upload_content(user_id, upload.id, "audio/mpeg", vec![0; 3])
+            .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_upload_removes_files() {
+        let (service, storage) = media_service(false);
+        let user_id = Uuid::new_v4();
+        let png = sample_png(400, 300);
+        let target = create_png_upload(&service, user_id, png.len() as i64).await;
+        let upload = service
+            .upload_content(user_id, target.upload.id, "image/png", png)
+            .await
+            .unwrap();
+        assert!(!upload.renditions.is_empty());
+
+        service.delete_upload(upload.id).await.unwrap();
+        assert!(storage.objects.lock().unwrap().is_empty());
+        assert!(matches!(
+            service.get_upload(user_id, upload.id).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        // Already gone
+        service.delete_upload(upload.id).await.unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+//! Resolving @mentions in posts, comments and messages, and notifying the
+//! mentioned users.
+use super::NotificationService;
+use crate::domain::entities::{
+    extract_mentions, Mention, MentionSource, MentionSpan, Post, User, MAX_MENTIONS_PER_TEXT,
+};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{MentionRepository, PostRepository, UserRepository};
+use crate::domain::value_objects::PageStart;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Mention service for resolving @mentions and notifying mentioned users
+pub struct MentionService {
+    user_repository: Arc<dyn UserRepository>,
+    post_repository: Arc<dyn PostRepository>,
+    mention_repository: Arc<dyn MentionRepository>,
+    notification_service: Arc<NotificationService>,
+}
+
+impl MentionService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        post_repository: Arc<dyn PostRepository>,
+        mention_repository: Arc<dyn MentionRepository>,
+        notification_service: Arc<NotificationService>,
+    ) -> Self {
+        Self {
+            user_repository,
+            post_repository,
+            mention_repository,
+            notification_service,
+        }
+    }
+
+    /// Resolve `@username` tokens in text to mention spans.
+    ///
+    /// Unknown usernames, self-mentions and users on either side of a block are
+    /// left as plain text.
+    pub async fn resolve_mentions(&self, author_id: Uuid, text: &str) -> Result<Vec<MentionSpan>> {
+        let mut resolved: std::collections::HashMap<String, Option<Uuid>> =
+            std::collections::HashMap::new();
+        let mut spans = Vec::new();
+
+        for candidate in extract_mentions(text) {
+            if !resolved.contains_key(&candidate.username) {
+                let distinct_users = resolved.values().filter(|id| id.is_some()).count();
+                let user_id = if distinct_users >= MAX_MENTIONS_PER_TEXT {
+                    None
+                } else {
+                    self.resolve_user(author_id, &candidate.username).await?
+                };
+                resolved.insert(candidate.username.clone(), user_id);
+            }
+
+            if let Some(Some(user_id)) = resolved.get(&candidate.username) {
+                spans.push(MentionSpan {
+                    user_id: *user_id,
+                    username: candidate.username,
+                    start: candidate.start,
+                    end: candidate.end,
+                });
+            }
+        }
+
+        Ok(spans)
+    }
+
+    async fn resolve_user(&self, author_id: Uuid, username: &str) -> Result<Option<Uuid>> {
+        let user = match self.user_repository.find_by_username(username).await? {
+            Some(user) => user,
+            None => return Ok(None),
+        };
+
+        if user.id == author_id
+            || self.user_repository.has_blocked(user.id, author_id).await?
+            || self.user_repository.has_blocked(author_id, user.id).await?
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(user.id))
+    }
+
+    /// Store mentions for a saved post, comment, or message and notify each mentioned user once
+    pub async fn record_mentions(
+        &self,
+        author: &User,
+        source: MentionSource,
+        source_id: Uuid,
+        post_id: Option<Uuid>,
+        spans: &[MentionSpan],
+    ) -> Result<()> {
+        let mut mentioned_user_ids: Vec<Uuid> = Vec::new();
+        for span in spans {
+            if !mentioned_user_ids.contains(&span.user_id) {
+                mentioned_user_ids.push(span.user_id);
+            }
+        }
+
+        if mentioned_user_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mentions: Vec<Mention> = mentioned_user_ids
+            .iter()
+            .map(|user_id| Mention::new(*user_id, author.id, source, source_id, post_id))
+            .collect();
+        self.mention_repository.create_many(&mentions).await?;
+
+        let author_name = author.get_display_name_or_username();
+        for user_id in mentioned_user_ids {
+            match self
+                .notification_service
+                .notify_mention(user_id, &author_name, source, source_id, post_id)
+                .await
+            {
+                Ok(()) => {}
+                // Mention notifications disabled in the user's preferences
+                Err(AppError::ValidationError(_)) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to send mention notification to {}: {}", user_id, e)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get posts that mention a user
+    pub async fn get_mentioning_posts(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        self.post_repository
+            .find_mentioning_user(user_id, page, limit)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::domain::entities::{NotificationPreferences, NotificationType};
+    use crate::domain::repositories::NotificationPreferencesRepository;
+    use crate::infrastructure::database::repositories::InMemoryNotificationPreferencesRepository;
+
+    #[tokio::test]
+    async fn test_resolve_mentions_skips_unknown_and_self() {
+        let users = Arc::new(TestUserRepository::new());
+        let service = mention_service(
+            users.clone(),
+            Arc::new(MockPostRepository::new()),
+            Arc::new(MockMentionRepository::new()),
+        );
+
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+
+        let spans = service
+            .resolve_mentions(author.id, "hi @Alice, @author and @nobody")
+            .await
+            .unwrap();
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].user_id, alice.id);
+        assert_eq!(spans[0].username, "alice");
+        assert_eq!((spans[0].start, spans[0].end), (3, 9));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_mentions_respects_blocks() {
+        let users = Arc::new(TestUserRepository::new());
+        let service = mention_service(
+            users.clone(),
+            Arc::new(MockPostRepository::new()),
+            Arc::new(MockMentionRepository::new()),
+        );
+
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+        let bob = add_test_user(&users, "bob");
+        users.block(alice.id, author.id);
+        users.block(author.id, bob.id);
+
+        let spans = service
+            .resolve_mentions(author.id, "@alice @bob")
+            .await
+            .unwrap();
+
+        assert!(spans.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_record_mentions_notifies_each_user_once() {
+        let users = Arc::new(TestUserRepository::new());
+        let mentions = Arc::new(MockMentionRepository::new());
+        let notifications = Arc::new(MockNotificationRepository::new());
+        let service = MentionService::new(
+            users.clone(),
+            Arc::new(MockPostRepository::new()),
+            mentions.clone(),
+            Arc::new(notification_service(users.clone(), notifications.clone())),
+        );
+
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+
+        let spans = service
+            .resolve_mentions(author.id, "@alice look, @alice")
+            .await
+            .unwrap();
+        assert_eq!(spans.len(), 2);
+
+        let post_id = Uuid::new_v4();
+        service
+            .record_mentions(&author, MentionSource::Post, post_id, Some(post_id), &spans)
+            .await
+            .unwrap();
+
+        assert_eq!(mentions.mentions.lock().unwrap().len(), 1);
+        let notifications = notifications.notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].user_id, alice.id);
+        assert_eq!(
+            notifications[0].notification_type,
+            NotificationType::Mention
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_mentions_respects_preferences() {
+        let users = Arc::new(TestUserRepository::new());
+        let mentions = Arc::new(MockMentionRepository::new());
+        let notifications = Arc::new(MockNotificationRepository::new());
+        let preferences = Arc::new(InMemoryNotificationPreferencesRepository::new());
+        let service = MentionService::new(
+            users.clone(),
+            Arc::new(MockPostRepository::new()),
+            mentions.clone(),
+            Arc::new(notification_service_with_preferences(
+                users.clone(),
+                notifications.clone(),
+                preferences.clone(),
+            )),
+        );
+
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+
+        let mut prefs = NotificationPreferences::new(alice.id);
+        prefs.mention_notifications = false;
+        preferences.upsert(&prefs).await.unwrap();
+
+        let spans = service.resolve_mentions(author.id, "@alice").await.unwrap();
+        let comment_id = Uuid::new_v4();
+        service
+            .record_mentions(
+                &author,
+                MentionSource::Comment,
+                comment_id,
+                Some(Uuid::new_v4()),
+                &spans,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(mentions.mentions.lock().unwrap().len(), 1);
+        assert!(notifications.notifications.lock().unwrap().is_empty());
+    }
+}
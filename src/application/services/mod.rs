@@ -0,0 +1,3070 @@
+mod bookmark;
+mod content_filter;
+mod hydration;
+mod insights;
+mod media;
+mod mention;
+mod moderation;
+mod pinned_post;
+mod poll;
+mod post_view;
+mod scheduler;
+mod search;
+mod story;
+mod suggestion;
+mod timeline;
+mod visibility;
+
+pub use bookmark::{BookmarkPage, BookmarkService};
+pub use content_filter::ContentFilterService;
+pub use hydration::{HydratedPost, PostHydrationService};
+pub use insights::{
+    InsightsService, PostInsights, UserInsights, DEFAULT_INSIGHTS_DAYS, MAX_INSIGHTS_DAYS,
+};
+pub use media::{MediaService, UploadTarget};
+pub use mention::MentionService;
+pub use moderation::{ModerationCaseDetail, ModerationService};
+pub use pinned_post::PinnedPostService;
+pub use poll::{PollService, PollView};
+pub use post_view::PostViewService;
+pub use scheduler::PostSchedulerService;
+pub use search::{PostSearchPage, SearchResults, SearchService};
+pub use story::{StoryFeedItem, StoryGroup, StoryReply, StoryService, StoryViewerPage};
+pub use suggestion::SuggestionService;
+pub use timeline::{TimelineService, FAN_OUT_MAX_FOLLOWERS};
+pub use visibility::PostVisibilityService;
+
+use crate::application::ranking::{
+    rank_posts, EngagementRanker, ExploreRanker, FeedRanker, PostFeatures, ViewerSignals,
+    AUTHOR_AFFINITY_WINDOW_DAYS, FEEDBACK_WINDOW_DAYS,
+};
+use crate::domain::entities::{
+    extract_hashtags, CreateNotificationRequest, DeviceToken, FeedFeedback, FeedFeedbackKind,
+    MentionSource, ModerationCase, Notification, NotificationPreferences, NotificationType, Post,
+    SensitiveMediaPreference, UpdateUserRequest, User,
+};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{
+    DeviceTokenRepository, FeedFeedbackRepository, ImpressionRepository,
+    NotificationPreferencesRepository, NotificationRepository, PostRepository, UserRepository,
+    WalletRepository,
+};
+use crate::domain::value_objects::{Cursor, PageStart};
+use crate::infrastructure::cache::{CacheConfig, RedisCache};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg(test)]
+mod test_support;
+
+/// User management service for coordinating user-related operations
+pub struct UserManagementService {
+    user_repository: Arc<dyn UserRepository>,
+    wallet_repository: Arc<dyn WalletRepository>,
+}
+
+/// Feed generation service for creating and managing user feeds
+pub struct FeedGenerationService {
+    post_repository: Arc<dyn PostRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    visibility: PostVisibilityService,
+    cache: Option<RedisCache>,
+    timeline_service: Option<Arc<TimelineService>>,
+    ranker: Arc<dyn FeedRanker>,
+    impression_repository: Option<Arc<dyn ImpressionRepository>>,
+    feedback_repository: Option<Arc<dyn FeedFeedbackRepository>>,
+}
+
+/// A newly published post together with the followers who should hear about it
+#[derive(Debug)]
+pub struct PublishedPost {
+    pub post: Post,
+    pub follower_ids: Vec<Uuid>,
+}
+
+/// Maximum number of due posts published, or expired stories removed, per scheduler run
+const SCHEDULER_BATCH_SIZE: i64 = 100;
+
+/// Page size used when collecting an author's followers for fan-out
+const FOLLOWER_PAGE_SIZE: i64 = 500;
+
+/// A page of a feed. Chronological feeds continue from `next_cursor`;
+/// ranked feeds have none and are paged by offset.
+#[derive(Debug, Default)]
+pub struct FeedPage {
+    pub posts: Vec<Post>,
+    pub next_cursor: Option<Cursor>,
+    pub has_more: bool,
+    /// Position of the first post the viewer had already seen, where the
+    /// feed shows that they are all caught up
+    pub caught_up_index: Option<usize>,
+    /// Posts folded into a post on the page by the same author, by the post shown
+    pub collapsed: HashMap<Uuid, Vec<Uuid>>,
+    /// Position of the first explore post filling out a feed too short on
+    /// its own, where the feed shows suggested posts
+    pub suggested_index: Option<usize>,
+}
+
+/// A page of a user's followers or followings
+#[derive(Debug)]
+pub struct FollowPage {
+    pub users: Vec<User>,
+    pub next_cursor: Option<Cursor>,
+}
+
+/// A page of a user's notifications
+#[derive(Debug)]
+pub struct NotificationPage {
+    pub notifications: Vec<Notification>,
+    pub next_cursor: Option<Cursor>,
+}
+
+/// Feed sorting strategy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedSortStrategy {
+    Chronological,
+    Algorithmic,
+}
+
+impl std::str::FromStr for FeedSortStrategy {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "chronological" => Ok(FeedSortStrategy::Chronological),
+            "algorithmic" => Ok(FeedSortStrategy::Algorithmic),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid feed strategy: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Content types a feed can be filtered to
+pub const FEED_CONTENT_TYPES: [&str; 5] = ["text", "image", "video", "mixed", "poll"];
+
+/// Feed filter options
+#[derive(Debug, Clone)]
+pub struct FeedFilters {
+    pub reels_only: bool,
+    pub exclude_reels: bool,
+    pub content_types: Option<Vec<String>>,
+    pub min_engagement: Option<i32>,
+}
+
+impl Default for FeedFilters {
+    fn default() -> Self {
+        Self {
+            reels_only: false,
+            exclude_reels: false,
+            content_types: None,
+            min_engagement: None,
+        }
+    }
+}
+
+impl FeedFilters {
+    /// Reject filters that contradict each other or name unknown content types
+    pub fn validate(&self) -> Result<()> {
+        if self.reels_only && self.exclude_reels {
+            return Err(AppError::ValidationError(
+                "reels_only and exclude_reels cannot both be set".to_string(),
+            ));
+        }
+
+        if let Some(ref content_types) = self.content_types {
+            if let Some(unknown) = content_types
+                .iter()
+                .find(|t| !FEED_CONTENT_TYPES.contains(&t.as_str()))
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Invalid content type: {}",
+                    unknown
+                )));
+            }
+        }
+
+        if self.min_engagement.is_some_and(|min| min < 0) {
+            return Err(AppError::ValidationError(
+                "min_engagement cannot be negative".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Cached feed item for efficient storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFeedItem {
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub engagement_score: f64,
+    pub is_reel: bool,
+    pub content_type: String,
+}
+
+/// Cached feed page, keeping where the next page starts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFeedPage {
+    pub items: Vec<CachedFeedItem>,
+    pub next_cursor: Option<Cursor>,
+    pub has_more: bool,
+    #[serde(default)]
+    pub collapsed: HashMap<Uuid, Vec<Uuid>>,
+}
+
+impl From<&Post> for CachedFeedItem {
+    fn from(post: &Post) -> Self {
+        let content_type = match post.content_type {
+            crate::domain::entities::PostContentType::Text => "text",
+            crate::domain::entities::PostContentType::Image => "image",
+            crate::domain::entities::PostContentType::Video => "video",
+            crate::domain::entities::PostContentType::Mixed => "mixed",
+            crate::domain::entities::PostContentType::Poll => "poll",
+        };
+
+        Self {
+            post_id: post.id,
+            user_id: post.user_id,
+            created_at: post.created_at,
+            engagement_score: calculate_engagement_score(post),
+            is_reel: post.is_reel,
+            content_type: content_type.to_string(),
+        }
+    }
+}
+
+/// Engagement score under the original formula, used where a ranking is
+/// shared by every viewer
+fn calculate_engagement_score(post: &Post) -> f64 {
+    EngagementRanker.score(&PostFeatures::of_post(post, Utc::now()))
+}
+
+/// Posts by the same author published within this many hours of a post
+/// already in a ranked feed are folded into it
+const COLLAPSE_WINDOW_HOURS: i64 = 3;
+
+/// How far back explore looks for posts
+const EXPLORE_WINDOW_DAYS: i64 = 7;
+
+/// Most explore candidates ranked per request
+const EXPLORE_CANDIDATES: i64 = 300;
+
+/// Most recent likes read for the viewer's hashtag affinity
+const HASHTAG_AFFINITY_LIKES: i64 = 200;
+
+/// Most recent feedback read when ranking a feed
+const MAX_FEEDBACK_SIGNALS: i64 = 500;
+
+/// Fold each post into a higher ranked post by the same author published
+/// within the collapse window. Returns the posts left, in order, and the
+/// posts folded into each of them.
+fn collapse_author_bursts(posts: Vec<Post>) -> (Vec<Post>, HashMap<Uuid, Vec<Uuid>>) {
+    let window = chrono::Duration::hours(COLLAPSE_WINDOW_HOURS);
+    let mut kept: Vec<Post> = Vec::with_capacity(posts.len());
+    let mut collapsed: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+
+    for post in posts {
+        let burst = kept.iter().find(|shown| {
+            shown.user_id == post.user_id && (shown.created_at - post.created_at).abs() <= window
+        });
+        match burst {
+            Some(shown) => collapsed.entry(shown.id).or_default().push(post.id),
+            None => kept.push(post),
+        }
+    }
+
+    (kept, collapsed)
+}
+
+/// Put posts in the order of `ids`, dropping any that are not listed
+fn order_by_ids(ids: &[Uuid], posts: Vec<Post>) -> Vec<Post> {
+    let mut by_id: HashMap<Uuid, Post> = posts.into_iter().map(|post| (post.id, post)).collect();
+    ids.iter().filter_map(|id| by_id.remove(id)).collect()
+}
+
+/// Collect the IDs of everyone following a user
+async fn collect_follower_ids(
+    user_repository: &dyn UserRepository,
+    user_id: Uuid,
+) -> Result<Vec<Uuid>> {
+    let mut follower_ids = Vec::new();
+    let mut page = PageStart::FIRST;
+
+    loop {
+        let followers = user_repository
+            .get_followers(user_id, page, FOLLOWER_PAGE_SIZE)
+            .await?;
+        let last = followers
+            .last()
+            .map(|(user, followed_at)| Cursor::new(*followed_at, user.id));
+        let page_len = followers.len() as i64;
+        follower_ids.extend(followers.into_iter().map(|(user, _)| user.id));
+
+        match last {
+            Some(cursor) if page_len == FOLLOWER_PAGE_SIZE => page = PageStart::After(cursor),
+            _ => break,
+        }
+    }
+
+    Ok(follower_ids)
+}
+
+/// Build a follow list page from rows fetched with one more than `limit`
+fn follow_page(mut rows: Vec<(User, DateTime<Utc>)>, limit: i64) -> FollowPage {
+    let next_cursor = Cursor::for_next_page(&mut rows, limit, |(user, followed_at)| {
+        Cursor::new(*followed_at, user.id)
+    });
+
+    FollowPage {
+        users: rows.into_iter().map(|(user, _)| user).collect(),
+        next_cursor,
+    }
+}
+
+impl FeedGenerationService {
+    pub fn new(
+        post_repository: Arc<dyn PostRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        cache: Option<RedisCache>,
+        timeline_service: Option<Arc<TimelineService>>,
+    ) -> Self {
+        Self {
+            visibility: PostVisibilityService::new(
+                post_repository.clone(),
+                user_repository.clone(),
+            ),
+            post_repository,
+            user_repository,
+            cache,
+            timeline_service,
+            ranker: Arc::new(EngagementRanker),
+            impression_repository: None,
+            feedback_repository: None,
+        }
+    }
+
+    /// Rank algorithmic feeds with `ranker` instead of the engagement formula
+    pub fn with_ranker(mut self, ranker: Arc<dyn FeedRanker>) -> Self {
+        self.ranker = ranker;
+        self
+    }
+
+    /// Track the posts each user is shown so ranked feeds put posts they have
+    /// already seen last, and pages mark where the user is all caught up
+    pub fn with_impressions(
+        mut self,
+        impression_repository: Arc<dyn ImpressionRepository>,
+    ) -> Self {
+        self.impression_repository = Some(impression_repository);
+        self
+    }
+
+    /// Store the feedback users give on posts so hidden posts are dropped
+    /// from their feeds and similar authors and hashtags ranked lower
+    pub fn with_feedback(mut self, feedback_repository: Arc<dyn FeedFeedbackRepository>) -> Self {
+        self.feedback_repository = Some(feedback_repository);
+        self
+    }
+
+    /// Record the viewer's feedback on a post in their feed. The post must be
+    /// one they may see and not their own.
+    pub async fn give_feedback(
+        &self,
+        user_id: Uuid,
+        post_id: Uuid,
+        kind: FeedFeedbackKind,
+    ) -> Result<()> {
+        let feedback_repository = self.feedback_repository.as_ref().ok_or_else(|| {
+            AppError::ConfigurationError("Feed feedback is not configured".to_string())
+        })?;
+        let post = self
+            .visibility
+            .find_visible_post(Some(user_id), post_id)
+            .await?;
+        if post.user_id == user_id {
+            return Err(AppError::ValidationError(
+                "Cannot give feedback on your own post".to_string(),
+            ));
+        }
+
+        feedback_repository
+            .upsert(&FeedFeedback::new(user_id, &post, kind))
+            .await?;
+        let _ = self.invalidate_user_feed_cache(user_id).await;
+        Ok(())
+    }
+
+    /// Take back every kind of feedback the viewer gave on a post
+    pub async fn undo_feedback(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+        let feedback_repository = self.feedback_repository.as_ref().ok_or_else(|| {
+            AppError::ConfigurationError("Feed feedback is not configured".to_string())
+        })?;
+        feedback_repository.remove(user_id, post_id).await?;
+        let _ = self.invalidate_user_feed_cache(user_id).await;
+        Ok(())
+    }
+
+    /// Generate feed for a user with specified strategy and filters
+    pub async fn generate_feed(
+        &self,
+        user_id: Uuid,
+        strategy: FeedSortStrategy,
+        filters: FeedFilters,
+        limit: i64,
+        page: PageStart,
+    ) -> Result<FeedPage> {
+        // Validate pagination parameters
+        let limit = limit.min(100).max(1);
+        if strategy == FeedSortStrategy::Algorithmic && page.cursor().is_some() {
+            return Err(AppError::ValidationError(
+                "Ranked feeds are paged by offset, not cursor".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let session_start = self.feed_session(user_id, page, now).await;
+
+        // Try to get from cache first if caching is enabled
+        if let Some(ref cache) = self.cache {
+            let cache_key =
+                self.generate_cache_key(user_id, &strategy, &filters, limit, page, session_start);
+
+            if let Ok(Some(cached)) = cache.get::<CachedFeedPage>(&cache_key) {
+                // Convert cached items back to full posts, re-checking visibility
+                // in case a post was hidden or a block added since caching
+                let posts = self.hydrate_cached_feed(cached.items).await?;
+                let posts = self.visibility.filter_visible(Some(user_id), posts).await?;
+                let posts = self.without_unwanted(user_id, posts).await?;
+                let feed = FeedPage {
+                    posts: self
+                        .apply_sensitive_media_preference(user_id, posts)
+                        .await?,
+                    next_cursor: cached.next_cursor,
+                    has_more: cached.has_more,
+                    collapsed: cached.collapsed,
+                    ..Default::default()
+                };
+                let feed = self
+                    .fill_cold_start(user_id, &filters, limit, page, feed)
+                    .await;
+                return Ok(self
+                    .mark_impressions(user_id, feed, session_start, now)
+                    .await);
+            }
+        }
+
+        // Cache miss or no cache - generate feed from database
+        let feed = match strategy {
+            FeedSortStrategy::Chronological => {
+                self.generate_chronological_feed(user_id, &filters, limit, page)
+                    .await?
+            }
+            FeedSortStrategy::Algorithmic => {
+                self.generate_algorithmic_feed(
+                    user_id,
+                    &filters,
+                    limit,
+                    page.offset(),
+                    session_start,
+                )
+                .await?
+            }
+        };
+        let posts = self
+            .visibility
+            .filter_visible(Some(user_id), feed.posts)
+            .await?;
+
+        // Cache the results if caching is enabled
+        if let Some(ref cache) = self.cache {
+            let cache_key =
+                self.generate_cache_key(user_id, &strategy, &filters, limit, page, session_start);
+            let cached = CachedFeedPage {
+                items: posts.iter().map(CachedFeedItem::from).collect(),
+                next_cursor: feed.next_cursor,
+                has_more: feed.has_more,
+                collapsed: feed.collapsed.clone(),
+            };
+
+            // Cache for 5 minutes (feed data changes frequently)
+            if let Err(e) = cache.set(&cache_key, &cached, CacheConfig::FEED_TTL) {
+                tracing::warn!("Failed to cache feed for user {}: {}", user_id, e);
+            }
+        }
+
+        // Applied after caching so a changed setting or mute takes effect right away
+        let posts = self.without_unwanted(user_id, posts).await?;
+        let feed = FeedPage {
+            posts: self
+                .apply_sensitive_media_preference(user_id, posts)
+                .await?,
+            ..feed
+        };
+        let feed = self
+            .fill_cold_start(user_id, &filters, limit, page, feed)
+            .await;
+        Ok(self
+            .mark_impressions(user_id, feed, session_start, now)
+            .await)
+    }
+
+    /// Rank public posts from accounts the viewer does not follow, for users
+    /// discovering new accounts. Trending posts are boosted by hashtags the
+    /// viewer recently liked and by likes from accounts they follow.
+    pub async fn generate_explore_feed(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<FeedPage> {
+        let limit = limit.clamp(1, 100);
+        let offset = offset.max(0) as usize;
+
+        let posts = self.rank_explore(user_id, &FeedFilters::default()).await?;
+        let end = (offset + limit as usize).min(posts.len());
+        if offset >= posts.len() {
+            return Ok(FeedPage::default());
+        }
+
+        Ok(FeedPage {
+            has_more: end < posts.len(),
+            posts: self
+                .apply_sensitive_media_preference(user_id, posts[offset..end].to_vec())
+                .await?,
+            ..Default::default()
+        })
+    }
+
+    /// Every explore candidate the viewer may see, ranked
+    async fn rank_explore(&self, user_id: Uuid, filters: &FeedFilters) -> Result<Vec<Post>> {
+        let now = Utc::now();
+        let candidates = self
+            .post_repository
+            .find_explore_candidates(
+                user_id,
+                now - chrono::Duration::days(EXPLORE_WINDOW_DAYS),
+                EXPLORE_CANDIDATES,
+            )
+            .await?;
+
+        let followed_likes: HashMap<Uuid, i64> = candidates
+            .iter()
+            .map(|(post, likes)| (post.id, *likes))
+            .collect();
+        let posts = candidates.into_iter().map(|(post, _)| post).collect();
+        let posts = self.apply_filters(posts, filters);
+        let posts = self.visibility.filter_visible(Some(user_id), posts).await?;
+        let muted = self.user_repository.muted_user_ids(user_id).await?;
+        let mut signals = self.feedback_signals(user_id).await?;
+        let posts: Vec<Post> = posts
+            .into_iter()
+            .filter(|post| {
+                !muted.contains(&post.user_id) && !signals.hidden_post_ids.contains(&post.id)
+            })
+            .collect();
+
+        let mut hashtag_affinity: HashMap<String, i64> = HashMap::new();
+        let liked = self
+            .post_repository
+            .find_liked_by_user(
+                user_id,
+                now - chrono::Duration::days(AUTHOR_AFFINITY_WINDOW_DAYS),
+                HASHTAG_AFFINITY_LIKES,
+            )
+            .await?;
+        for text in liked.iter().filter_map(|post| post.text_content.as_deref()) {
+            for tag in extract_hashtags(text) {
+                *hashtag_affinity.entry(tag).or_default() += 1;
+            }
+        }
+
+        signals.hashtag_affinity = hashtag_affinity;
+        signals.followed_likes = followed_likes;
+        Ok(rank_posts(&ExploreRanker, user_id, posts, &signals, now))
+    }
+
+    /// Fill out the first page of a feed too short on its own, as for a new
+    /// user following few accounts, with explore posts. Best effort; the
+    /// feed is returned as it was if explore fails.
+    async fn fill_cold_start(
+        &self,
+        user_id: Uuid,
+        filters: &FeedFilters,
+        limit: i64,
+        page: PageStart,
+        mut feed: FeedPage,
+    ) -> FeedPage {
+        let missing = limit as usize;
+        if page != PageStart::FIRST || feed.has_more || feed.posts.len() >= missing {
+            return feed;
+        }
+
+        let explore = match self.rank_explore(user_id, filters).await {
+            Ok(posts) => posts,
+            Err(e) => {
+                tracing::warn!("Failed to build explore posts for user {}: {}", user_id, e);
+                return feed;
+            }
+        };
+        let shown: HashSet<Uuid> = feed.posts.iter().map(|post| post.id).collect();
+        let suggested: Vec<Post> = explore
+            .into_iter()
+            .filter(|post| !shown.contains(&post.id))
+            .take(missing - feed.posts.len())
+            .collect();
+        let suggested = match self
+            .apply_sensitive_media_preference(user_id, suggested)
+            .await
+        {
+            Ok(posts) => posts,
+            Err(e) => {
+                tracing::warn!("Failed to build explore posts for user {}: {}", user_id, e);
+                return feed;
+            }
+        };
+
+        if !suggested.is_empty() {
+            feed.suggested_index = Some(feed.posts.len());
+            feed.posts.extend(suggested);
+        }
+        feed
+    }
+
+    /// Drop posts by accounts the viewer has muted and posts they hid
+    async fn without_unwanted(&self, user_id: Uuid, mut posts: Vec<Post>) -> Result<Vec<Post>> {
+        let muted = self.user_repository.muted_user_ids(user_id).await?;
+        let hidden = self.feedback_signals(user_id).await?.hidden_post_ids;
+        if !muted.is_empty() || !hidden.is_empty() {
+            posts.retain(|post| !muted.contains(&post.user_id) && !hidden.contains(&post.id));
+        }
+        Ok(posts)
+    }
+
+    /// The viewer's recent feedback on posts, for dropping hidden posts and
+    /// ranking posts like the ones they wanted fewer of lower
+    async fn feedback_signals(&self, user_id: Uuid) -> Result<ViewerSignals> {
+        let mut signals = ViewerSignals::default();
+        if let Some(ref feedback_repository) = self.feedback_repository {
+            let feedback = feedback_repository
+                .find_by_user(
+                    user_id,
+                    Utc::now() - chrono::Duration::days(FEEDBACK_WINDOW_DAYS),
+                    MAX_FEEDBACK_SIGNALS,
+                )
+                .await?;
+            signals.add_feedback(&feedback);
+        }
+        Ok(signals)
+    }
+
+    /// When the viewer's feed session started. A feed's first page starts a
+    /// new session, as does any page once the last session has expired.
+    /// None when impressions are not tracked or cannot be read.
+    async fn feed_session(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let impressions = self.impression_repository.as_ref()?;
+
+        if page != PageStart::FIRST {
+            match impressions.session_started_at(user_id).await {
+                Ok(Some(started_at)) => return Some(started_at),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to read feed session for user {}: {}", user_id, e);
+                    return None;
+                }
+            }
+        }
+
+        match impressions.start_session(user_id, now).await {
+            Ok(()) => Some(now),
+            Err(e) => {
+                tracing::warn!("Failed to start feed session for user {}: {}", user_id, e);
+                None
+            }
+        }
+    }
+
+    /// Mark where the posts the viewer saw before this session begin and
+    /// record the page's posts as seen. Impressions are best effort; a
+    /// failure only loses the caught-up marker or the demotion next time.
+    async fn mark_impressions(
+        &self,
+        user_id: Uuid,
+        mut feed: FeedPage,
+        session_start: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> FeedPage {
+        let (Some(impressions), Some(session_start)) =
+            (self.impression_repository.as_ref(), session_start)
+        else {
+            return feed;
+        };
+        let post_ids: Vec<Uuid> = feed.posts.iter().map(|post| post.id).collect();
+
+        match impressions
+            .seen_before(user_id, &post_ids, session_start)
+            .await
+        {
+            Ok(seen) => {
+                feed.caught_up_index = feed.posts.iter().position(|post| seen.contains(&post.id))
+            }
+            Err(e) => tracing::warn!("Failed to read impressions for user {}: {}", user_id, e),
+        }
+
+        if let Err(e) = impressions.record(user_id, &post_ids, now).await {
+            tracing::warn!("Failed to record impressions for user {}: {}", user_id, e);
+        }
+
+        feed
+    }
+
+    /// Leave sensitive posts out for users who hide them. Users who blur them
+    /// get the posts with `is_sensitive` set for the client to blur; a user's
+    /// own posts are always kept.
+    async fn apply_sensitive_media_preference(
+        &self,
+        user_id: Uuid,
+        mut posts: Vec<Post>,
+    ) -> Result<Vec<Post>> {
+        let preference = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .map(|user| user.sensitive_media)
+            .unwrap_or_default();
+
+        if preference == SensitiveMediaPreference::Hide {
+            posts.retain(|post| !post.is_sensitive || post.user_id == user_id);
+        }
+
+        Ok(posts)
+    }
+
+    /// Generate chronological feed (newest first). The next page starts
+    /// after the last post read, so posts dropped by filters are not read again.
+    async fn generate_chronological_feed(
+        &self,
+        user_id: Uuid,
+        filters: &FeedFilters,
+        limit: i64,
+        page: PageStart,
+    ) -> Result<FeedPage> {
+        let mut posts = if filters.reels_only {
+            // Get reels only from followed users
+            self.post_repository
+                .find_reels(Some(user_id), page, limit + 1)
+                .await?
+        } else {
+            // Get all posts from followed users
+            self.find_home_posts(user_id, limit + 1, page).await?
+        };
+        let next_cursor = Cursor::for_next_page(&mut posts, limit, |post| {
+            Cursor::new(post.created_at, post.id)
+        });
+
+        // Apply additional filters
+        posts = self.apply_filters(posts, filters);
+
+        // Sort chronologically (newest first) - should already be sorted by database
+        posts.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+
+        Ok(FeedPage {
+            posts,
+            next_cursor,
+            has_more: next_cursor.is_some(),
+            ..Default::default()
+        })
+    }
+
+    /// Generate algorithmic feed with the configured ranker. Posts the viewer
+    /// saw before this session go after every unseen post, and bursts of
+    /// posts by one author are collapsed into the highest ranked of them.
+    async fn generate_algorithmic_feed(
+        &self,
+        user_id: Uuid,
+        filters: &FeedFilters,
+        limit: i64,
+        offset: i64,
+        session_start: Option<DateTime<Utc>>,
+    ) -> Result<FeedPage> {
+        // Get more posts than requested to allow for better algorithmic sorting
+        let fetch_limit = (limit * 3).min(300);
+
+        let mut posts = if filters.reels_only {
+            self.post_repository
+                .find_reels(Some(user_id), PageStart::FIRST, fetch_limit)
+                .await?
+        } else {
+            self.find_home_posts(user_id, fetch_limit, PageStart::FIRST)
+                .await?
+        };
+
+        // Apply filters before ranking
+        posts = self.apply_filters(posts, filters);
+
+        let now = Utc::now();
+        let signals = self
+            .viewer_signals(user_id, &posts, session_start, now)
+            .await?;
+        // Dropped before paging so hidden posts do not leave pages short
+        posts.retain(|post| !signals.hidden_post_ids.contains(&post.id));
+        let ranked = rank_posts(self.ranker.as_ref(), user_id, posts, &signals, now);
+
+        let (mut posts, seen): (Vec<Post>, Vec<Post>) = ranked
+            .into_iter()
+            .partition(|post| !signals.seen_post_ids.contains(&post.id));
+        posts.extend(seen);
+        let (posts, mut collapsed) = collapse_author_bursts(posts);
+
+        // Apply pagination after sorting
+        let start = offset as usize;
+        let end = (start + limit as usize).min(posts.len());
+
+        if start >= posts.len() {
+            return Ok(FeedPage::default());
+        }
+
+        let page = posts[start..end].to_vec();
+        collapsed.retain(|post_id, _| page.iter().any(|post| post.id == *post_id));
+        Ok(FeedPage {
+            has_more: end < posts.len(),
+            posts: page,
+            collapsed,
+            ..Default::default()
+        })
+    }
+
+    /// What the ranker needs to know about the viewer's relationship to the
+    /// candidate posts
+    async fn viewer_signals(
+        &self,
+        user_id: Uuid,
+        posts: &[Post],
+        session_start: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<ViewerSignals> {
+        let author_ids: Vec<Uuid> = posts
+            .iter()
+            .map(|post| post.user_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let author_affinity = self
+            .post_repository
+            .count_interactions_with_authors(
+                user_id,
+                &author_ids,
+                now - chrono::Duration::days(AUTHOR_AFFINITY_WINDOW_DAYS),
+            )
+            .await?;
+
+        let mut seen_post_ids = HashSet::new();
+        if let (Some(impressions), Some(session_start)) =
+            (self.impression_repository.as_ref(), session_start)
+        {
+            let post_ids: Vec<Uuid> = posts.iter().map(|post| post.id).collect();
+            match impressions
+                .seen_before(user_id, &post_ids, session_start)
+                .await
+            {
+                Ok(seen) => seen_post_ids = seen,
+                Err(e) => {
+                    tracing::warn!("Failed to read impressions for user {}: {}", user_id, e)
+                }
+            }
+        }
+
+        Ok(ViewerSignals {
+            author_affinity,
+            seen_post_ids,
+            ..self.feedback_signals(user_id).await?
+        })
+    }
+
+    /// Get posts from followed users, newest first, from the home timeline
+    /// when timelines are enabled. A timeline that cannot be read falls back
+    /// to querying the database.
+    async fn find_home_posts(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        page: PageStart,
+    ) -> Result<Vec<Post>> {
+        if let Some(ref timelines) = self.timeline_service {
+            match timelines.find_home_feed(user_id, limit, page).await {
+                Ok(posts) => return Ok(posts),
+                Err(e) => {
+                    tracing::warn!("Failed to read home timeline for user {}: {}", user_id, e)
+                }
+            }
+        }
+
+        self.post_repository.find_feed(user_id, page, limit).await
+    }
+
+    /// Write a newly published post into its followers' home timelines in the background
+    pub fn fan_out_to_timelines(&self, post: &Post) {
+        if let Some(ref timelines) = self.timeline_service {
+            timelines.spawn_fan_out(post.clone());
+        }
+    }
+
+    /// Remove a deleted post from home timelines in the background
+    pub fn remove_from_timelines(&self, post: &Post) {
+        if let Some(ref timelines) = self.timeline_service {
+            timelines.spawn_remove_post(post.clone());
+        }
+    }
+
+    /// Add a newly followed author's recent posts to the follower's home
+    /// timeline. Timelines are rebuilt once they expire, so a failure is only
+    /// logged and never fails the follow itself.
+    pub async fn follow_in_timeline(&self, follower_id: Uuid, author_id: Uuid) {
+        if let Some(ref timelines) = self.timeline_service {
+            if let Err(e) = timelines.add_author(follower_id, author_id).await {
+                tracing::warn!(
+                    "Failed to add user {} to the timeline of user {}: {}",
+                    author_id,
+                    follower_id,
+                    e
+                );
+            }
+        }
+        let _ = self.invalidate_user_feed_cache(follower_id).await;
+    }
+
+    /// Remove an unfollowed author's posts from the follower's home timeline.
+    /// A failure is only logged and never fails the unfollow itself.
+    pub async fn unfollow_in_timeline(&self, follower_id: Uuid, author_id: Uuid) {
+        if let Some(ref timelines) = self.timeline_service {
+            if let Err(e) = timelines.remove_author(follower_id, author_id).await {
+                tracing::warn!(
+                    "Failed to remove user {} from the timeline of user {}: {}",
+                    author_id,
+                    follower_id,
+                    e
+                );
+            }
+        }
+        let _ = self.invalidate_user_feed_cache(follower_id).await;
+    }
+
+    /// Apply content filters to posts
+    fn apply_filters(&self, mut posts: Vec<Post>, filters: &FeedFilters) -> Vec<Post> {
+        posts.retain(|post| {
+            // Filter by reel preference
+            if filters.reels_only && !post.is_reel {
+                return false;
+            }
+            if filters.exclude_reels && post.is_reel {
+                return false;
+            }
+
+            // Filter by content types
+            if let Some(ref content_types) = filters.content_types {
+                let post_content_type = match post.content_type {
+                    crate::domain::entities::PostContentType::Text => "text",
+                    crate::domain::entities::PostContentType::Image => "image",
+                    crate::domain::entities::PostContentType::Video => "video",
+                    crate::domain::entities::PostContentType::Mixed => "mixed",
+                    crate::domain::entities::PostContentType::Poll => "poll",
+                };
+
+                if !content_types.contains(&post_content_type.to_string()) {
+                    return false;
+                }
+            }
+
+            // Filter by minimum engagement
+            if let Some(min_engagement) = filters.min_engagement {
+                let total_engagement = post.like_count + post.comment_count + post.reshare_count;
+                if total_engagement < min_engagement {
+                    return false;
+                }
+            }
+
+            true
+        });
+
+        posts
+    }
+
+    /// Generate cache key for feed
+    fn generate_cache_key(
+        &self,
+        user_id: Uuid,
+        strategy: &FeedSortStrategy,
+        filters: &FeedFilters,
+        limit: i64,
+        page: PageStart,
+        session_start: Option<DateTime<Utc>>,
+    ) -> String {
+        let strategy_str = match strategy {
+            FeedSortStrategy::Chronological => "chrono",
+            FeedSortStrategy::Algorithmic => "algo",
+        };
+
+        let mut key_parts = vec![
+            format!("feed:{}:{}", user_id, strategy_str),
+            format!("limit:{}", limit),
+            match page {
+                PageStart::After(cursor) => format!("after:{}", cursor.encode()),
+                PageStart::Offset(offset) => format!("offset:{}", offset),
+            },
+        ];
+
+        // Ranked pages depend on what was seen before the session
+        if let (FeedSortStrategy::Algorithmic, Some(session_start)) = (strategy, session_start) {
+            key_parts.push(format!("session:{}", session_start.timestamp_millis()));
+        }
+        if filters.reels_only {
+            key_parts.push("reels_only".to_string());
+        }
+        if filters.exclude_reels {
+            key_parts.push("no_reels".to_string());
+        }
+        if let Some(ref content_types) = filters.content_types {
+            key_parts.push(format!("types:{}", content_types.join(",")));
+        }
+        if let Some(min_engagement) = filters.min_engagement {
+            key_parts.push(format!("min_eng:{}", min_engagement));
+        }
+
+        key_parts.join(":")
+    }
+
+    /// Convert cached feed items back to full posts
+    async fn hydrate_cached_feed(&self, cached_items: Vec<CachedFeedItem>) -> Result<Vec<Post>> {
+        let ids: Vec<Uuid> = cached_items.iter().map(|item| item.post_id).collect();
+        Ok(order_by_ids(
+            &ids,
+            self.post_repository.find_by_ids(&ids).await?,
+        ))
+    }
+
+    /// Get reels feed specifically
+    pub async fn get_reels_feed(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<FeedPage> {
+        let filters = FeedFilters {
+            reels_only: true,
+            ..Default::default()
+        };
+
+        // Use algorithmic sorting for reels to promote engaging content
+        self.generate_feed(
+            user_id,
+            FeedSortStrategy::Algorithmic,
+            filters,
+            limit,
+            PageStart::Offset(offset),
+        )
+        .await
+    }
+
+    /// Get trending posts (public posts with high engagement) as seen by the
+    /// viewer. The ranking is shared by everyone; blocks and the viewer's
+    /// sensitive media setting are applied on top of it.
+    pub async fn get_trending_posts(
+        &self,
+        viewer_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<FeedPage> {
+        let (posts, has_more) = self.get_ranked_trending_posts(limit, offset).await?;
+
+        let posts = match viewer_id {
+            Some(viewer_id) => {
+                let posts = self
+                    .visibility
+                    .filter_visible(Some(viewer_id), posts)
+                    .await?;
+                let posts = self.without_unwanted(viewer_id, posts).await?;
+                self.apply_sensitive_media_preference(viewer_id, posts)
+                    .await?
+            }
+            None => posts,
+        };
+
+        Ok(FeedPage {
+            posts,
+            has_more,
+            ..Default::default()
+        })
+    }
+
+    /// Trending posts any anonymous viewer may see, cached for everyone
+    async fn get_ranked_trending_posts(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, bool)> {
+        // Try cache first
+        if let Some(ref cache) = self.cache {
+            let cache_key = format!("trending:posts:{}:{}", limit, offset);
+
+            if let Ok(Some(cached)) = cache.get::<CachedFeedPage>(&cache_key) {
+                let posts = self.hydrate_cached_feed(cached.items).await?;
+                let posts = self.visibility.filter_visible(None, posts).await?;
+                return Ok((posts, cached.has_more));
+            }
+        }
+
+        // Get public posts with high engagement
+        let mut posts = self.post_repository.find_public(limit * 3, 0).await?;
+
+        // Filter for high engagement (at least 5 total interactions)
+        posts.retain(|post| {
+            let total_engagement = post.like_count + post.comment_count + post.reshare_count;
+            total_engagement >= 5
+        });
+
+        // Sort by engagement score
+        posts.sort_by(|a, b| {
+            let score_a = calculate_engagement_score(a);
+            let score_b = calculate_engagement_score(b);
+            score_b
+                .partial_cmp(&score_a)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // Apply pagination
+        let start = offset as usize;
+        let end = (start + limit as usize).min(posts.len());
+        let has_more = end < posts.len();
+
+        let result = if start >= posts.len() {
+            vec![]
+        } else {
+            posts[start..end].to_vec()
+        };
+        let result = self.visibility.filter_visible(None, result).await?;
+
+        // Cache trending posts for 10 minutes
+        if let Some(ref cache) = self.cache {
+            let cache_key = format!("trending:posts:{}:{}", limit, offset);
+            let cached = CachedFeedPage {
+                items: result.iter().map(CachedFeedItem::from).collect(),
+                next_cursor: None,
+                has_more,
+                collapsed: HashMap::new(),
+            };
+
+            if let Err(e) = cache.set(&cache_key, &cached, 10 * 60) {
+                tracing::warn!("Failed to cache trending posts: {}", e);
+            }
+        }
+
+        Ok((result, has_more))
+    }
+
+    /// Invalidate feed cache for a user
+    pub async fn invalidate_user_feed_cache(&self, user_id: Uuid) -> Result<()> {
+        if let Some(ref cache) = self.cache {
+            // Every cached page of the user's feeds, whatever the strategy,
+            // cursor, session or filters it was cached under
+            let pattern = format!("feed:{}:*", user_id);
+            if let Err(e) = cache.delete_matching(&pattern) {
+                tracing::warn!(
+                    "Failed to invalidate feed cache for user {}: {}",
+                    user_id,
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Invalidate trending posts cache
+    pub async fn invalidate_trending_cache(&self) -> Result<()> {
+        if let Some(ref cache) = self.cache {
+            if let Err(e) = cache.delete_matching("trending:posts:*") {
+                tracing::warn!("Failed to invalidate trending cache: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get feed statistics for analytics
+    pub async fn get_feed_stats(&self, user_id: Uuid) -> Result<FeedStats> {
+        // Get user's following count
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        // Get sample of recent posts from feed
+        let recent_posts = self
+            .post_repository
+            .find_feed(user_id, PageStart::FIRST, 50)
+            .await?;
+
+        let total_posts = recent_posts.len() as i32;
+        let reel_count = recent_posts.iter().filter(|p| p.is_reel).count() as i32;
+        let avg_engagement = if total_posts > 0 {
+            recent_posts
+                .iter()
+                .map(|p| p.like_count + p.comment_count + p.reshare_count)
+                .sum::<i32>() as f64
+                / total_posts as f64
+        } else {
+            0.0
+        };
+
+        Ok(FeedStats {
+            following_count: user.following_count,
+            recent_posts_count: total_posts,
+            reel_percentage: if total_posts > 0 {
+                (reel_count as f64 / total_posts as f64) * 100.0
+            } else {
+                0.0
+            },
+            avg_engagement,
+        })
+    }
+}
+
+/// Feed statistics for analytics
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedStats {
+    pub following_count: i32,
+    pub recent_posts_count: i32,
+    pub reel_percentage: f64,
+    pub avg_engagement: f64,
+}
+
+/// Notification service for managing notifications and push notifications
+pub struct NotificationService {
+    notification_repository: Arc<dyn NotificationRepository>,
+    device_token_repository: Arc<dyn DeviceTokenRepository>,
+    preferences_repository: Arc<dyn NotificationPreferencesRepository>,
+    user_repository: Arc<dyn UserRepository>,
+}
+
+/// Push notification payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushNotificationPayload {
+    pub title: String,
+    pub body: String,
+    pub data: serde_json::Value,
+    pub badge_count: Option<i32>,
+}
+
+/// Notification statistics
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationStats {
+    pub total_notifications: i64,
+    pub unread_count: i64,
+    pub recent_count: i64, // Last 24 hours
+    pub by_type: std::collections::HashMap<String, i64>,
+}
+
+impl NotificationService {
+    pub fn new(
+        notification_repository: Arc<dyn NotificationRepository>,
+        device_token_repository: Arc<dyn DeviceTokenRepository>,
+        preferences_repository: Arc<dyn NotificationPreferencesRepository>,
+        user_repository: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            notification_repository,
+            device_token_repository,
+            preferences_repository,
+            user_repository,
+        }
+    }
+
+    /// Create and send a notification
+    pub async fn create_notification(
+        &self,
+        request: CreateNotificationRequest,
+    ) -> Result<Notification> {
+        // Check if user exists
+        let _user = self
+            .user_repository
+            .find_by_id(request.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        // Check user preferences
+        let preferences = self
+            .get_user_preferences(request.user_id)
+            .await?
+            .unwrap_or_else(|| NotificationPreferences::new(request.user_id));
+
+        // Check if this type of notification is enabled
+        if !preferences.is_notification_enabled(&request.notification_type) {
+            return Err(AppError::ValidationError(
+                "Notification type is disabled for this user".to_string(),
+            ));
+        }
+
+        // Create the notification
+        let notification = Notification::new(request)?;
+        let created_notification = self.notification_repository.create(&notification).await?;
+
+        // Send push notification if enabled
+        if preferences.push_notifications_enabled {
+            if let Err(e) = self.send_push_notification(&created_notification).await {
+                tracing::warn!(
+                    "Failed to send push notification for notification {}: {}",
+                    created_notification.id,
+                    e
+                );
+                // Don't fail the entire operation if push notification fails
+            }
+        }
+
+        Ok(created_notification)
+    }
+
+    /// Send push notification to user's devices
+    async fn send_push_notification(&self, notification: &Notification) -> Result<()> {
+        let device_tokens = self
+            .device_token_repository
+            .find_active_by_user_id(notification.user_id)
+            .await?;
+
+        if device_tokens.is_empty() {
+            tracing::debug!(
+                "No active device tokens found for user {}",
+                notification.user_id
+            );
+            return Ok(());
+        }
+
+        let unread_count = self
+            .notification_repository
+            .get_unread_count(notification.user_id)
+            .await?;
+
+        let payload = PushNotificationPayload {
+            title: notification.title.clone(),
+            body: notification.body.clone(),
+            data: notification.data.clone(),
+            badge_count: Some(unread_count as i32),
+        };
+
+        // Send to each device
+        for device_token in device_tokens {
+            if let Err(e) = self.send_to_device(&device_token, &payload).await {
+                tracing::warn!(
+                    "Failed to send push notification to device {}: {}",
+                    device_token.id,
+                    e
+                );
+                // Consider deactivating invalid tokens
+                if e.to_string().contains("invalid token") {
+                    let _ = self
+                        .device_token_repository
+                        .deactivate(device_token.id)
+                        .await;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send push notification to a specific device
+    async fn send_to_device(
+        &self,
+        device_token: &DeviceToken,
+        payload: &PushNotificationPayload,
+    ) -> Result<()> {
+        // This is a placeholder for actual push notification implementation
+        // In a real implementation, you would integrate with:
+        // - Firebase Cloud Messaging (FCM) for Android
+        // - Apple Push Notification Service (APNs) for iOS
+        // - Web Push for web browsers
+
+        tracing::info!(
+            "Sending push notification to {} device {}: {}",
+            device_token.platform,
+            device_token.id,
+            payload.title
+        );
+
+        // Simulate push notification sending
+        // In production, replace this with actual push service calls
+        match device_token.platform {
+            crate::domain::entities::DevicePlatform::Android => {
+                // Send via FCM
+                self.send_fcm_notification(device_token, payload).await
+            }
+            crate::domain::entities::DevicePlatform::Ios => {
+                // Send via APNs
+                self.send_apns_notification(device_token, payload).await
+            }
+            crate::domain::entities::DevicePlatform::Web => {
+                // Send via Web Push
+                self.send_web_push_notification(device_token, payload).await
+            }
+        }
+    }
+
+    /// Send FCM notification (Android)
+    async fn send_fcm_notification(
+        &self,
+        device_token: &DeviceToken,
+        payload: &PushNotificationPayload,
+    ) -> Result<()> {
+        // Placeholder for FCM implementation
+        tracing::debug!(
+            "FCM notification sent to token {}: {}",
+            device_token.token,
+            payload.title
+        );
+        Ok(())
+    }
+
+    /// Send APNs notification (iOS)
+    async fn send_apns_notification(
+        &self,
+        device_token: &DeviceToken,
+        payload: &PushNotificationPayload,
+    ) -> Result<()> {
+        // Placeholder for APNs implementation
+        tracing::debug!(
+            "APNs notification sent to token {}: {}",
+            device_token.token,
+            payload.title
+        );
+        Ok(())
+    }
+
+    /// Send Web Push notification
+    async fn send_web_push_notification(
+        &self,
+        device_token: &DeviceToken,
+        payload: &PushNotificationPayload,
+    ) -> Result<()> {
+        // Placeholder for Web Push implementation
+        tracing::debug!(
+            "Web Push notification sent to token {}: {}",
+            device_token.token,
+            payload.title
+        );
+        Ok(())
+    }
+
+    /// Get a page of notifications for a user, newest first
+    pub async fn get_user_notifications(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        page: PageStart,
+    ) -> Result<NotificationPage> {
+        // Validate that user exists
+        let _user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let mut notifications = self
+            .notification_repository
+            .find_by_user_id(user_id, page, limit + 1)
+            .await?;
+        let next_cursor = Cursor::for_next_page(&mut notifications, limit, |notification| {
+            Cursor::new(notification.created_at, notification.id)
+        });
+
+        Ok(NotificationPage {
+            notifications,
+            next_cursor,
+        })
+    }
+
+    /// Get unread notifications for a user
+    pub async fn get_unread_notifications(&self, user_id: Uuid) -> Result<Vec<Notification>> {
+        // Validate that user exists
+        let _user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        self.notification_repository
+            .find_unread_by_user_id(user_id)
+            .await
+    }
+
+    /// Mark notification as read
+    pub async fn mark_notification_as_read(&self, notification_id: Uuid) -> Result<()> {
+        // Check if notification exists
+        let _notification = self
+            .notification_repository
+            .find_by_id(notification_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Notification not found".to_string()))?;
+
+        self.notification_repository
+            .mark_as_read(notification_id)
+            .await
+    }
+
+    /// Mark all notifications as read for a user
+    pub async fn mark_all_notifications_as_read(&self, user_id: Uuid) -> Result<()> {
+        // Validate that user exists
+        let _user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        self.notification_repository.mark_all_as_read(user_id).await
+    }
+
+    /// Get notification statistics for a user
+    pub async fn get_notification_stats(&self, user_id: Uuid) -> Result<NotificationStats> {
+        // Validate that user exists
+        let _user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let unread_count = self
+            .notification_repository
+            .get_unread_count(user_id)
+            .await?;
+
+        // Get recent notifications (last 100) to calculate stats
+        let recent_notifications = self
+            .notification_repository
+            .find_by_user_id(user_id, PageStart::FIRST, 100)
+            .await?;
+
+        let total_notifications = recent_notifications.len() as i64;
+        let recent_count = recent_notifications
+            .iter()
+            .filter(|n| n.is_recent())
+            .count() as i64;
+
+        // Count by type
+        let mut by_type = std::collections::HashMap::new();
+        for notification in &recent_notifications {
+            let type_str = notification.notification_type.to_string();
+            *by_type.entry(type_str).or_insert(0) += 1;
+        }
+
+        Ok(NotificationStats {
+            total_notifications,
+            unread_count,
+            recent_count,
+            by_type,
+        })
+    }
+
+    /// Register device token for push notifications
+    pub async fn register_device_token(&self, device_token: &DeviceToken) -> Result<DeviceToken> {
+        // Validate that user exists
+        let _user = self
+            .user_repository
+            .find_by_id(device_token.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        self.device_token_repository.upsert(device_token).await
+    }
+
+    /// Unregister device token
+    pub async fn unregister_device_token(&self, token_id: Uuid) -> Result<()> {
+        self.device_token_repository.deactivate(token_id).await
+    }
+
+    /// Get user notification preferences
+    pub async fn get_user_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>> {
+        self.preferences_repository.find_by_user_id(user_id).await
+    }
+
+    /// Update user notification preferences
+    pub async fn update_user_preferences(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Result<NotificationPreferences> {
+        // Validate that user exists
+        let _user = self
+            .user_repository
+            .find_by_id(preferences.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        self.preferences_repository.upsert(preferences).await
+    }
+
+    /// Create notification for message received
+    pub async fn notify_message_received(
+        &self,
+        recipient_id: Uuid,
+        sender_name: &str,
+        message_preview: &str,
+        conversation_id: Uuid,
+    ) -> Result<()> {
+        let request = CreateNotificationRequest {
+            user_id: recipient_id,
+            notification_type: NotificationType::Message,
+            title: format!("New message from {}", sender_name),
+            body: message_preview.to_string(),
+            data: Some(serde_json::json!({
+                "conversation_id": conversation_id,
+                "sender_name": sender_name
+            })),
+        };
+
+        self.create_notification(request).await?;
+        Ok(())
+    }
+
+    /// Create notification for post liked
+    pub async fn notify_post_liked(
+        &self,
+        post_author_id: Uuid,
+        liker_name: &str,
+        post_id: Uuid,
+    ) -> Result<()> {
+        let request = CreateNotificationRequest {
+            user_id: post_author_id,
+            notification_type: NotificationType::Like,
+            title: format!("{} liked your post", liker_name),
+            body: "Your post received a new like".to_string(),
+            data: Some(serde_json::json!({
+                "post_id": post_id,
+                "liker_name": liker_name
+            })),
+        };
+
+        self.create_notification(request).await?;
+        Ok(())
+    }
+
+    /// Create notification for new follower
+    pub async fn notify_new_follower(
+        &self,
+        followed_user_id: Uuid,
+        follower_name: &str,
+        follower_id: Uuid,
+    ) -> Result<()> {
+        let request = CreateNotificationRequest {
+            user_id: followed_user_id,
+            notification_type: NotificationType::Follow,
+            title: format!("{} started following you", follower_name),
+            body: "You have a new follower".to_string(),
+            data: Some(serde_json::json!({
+                "follower_id": follower_id,
+                "follower_name": follower_name
+            })),
+        };
+
+        self.create_notification(request).await?;
+        Ok(())
+    }
+
+    /// Create notification for payment received
+    pub async fn notify_payment_received(
+        &self,
+        recipient_id: Uuid,
+        sender_name: &str,
+        amount: &str,
+        transaction_id: Uuid,
+    ) -> Result<()> {
+        let request = CreateNotificationRequest {
+            user_id: recipient_id,
+            notification_type: NotificationType::PaymentReceived,
+            title: format!("Payment received from {}", sender_name),
+            body: format!("You received {} from {}", amount, sender_name),
+            data: Some(serde_json::json!({
+                "transaction_id": transaction_id,
+                "sender_name": sender_name,
+                "amount": amount
+            })),
+        };
+
+        self.create_notification(request).await?;
+        Ok(())
+    }
+
+    /// Create notification for being mentioned in a post, comment, or message
+    pub async fn notify_mention(
+        &self,
+        mentioned_user_id: Uuid,
+        author_name: &str,
+        source: MentionSource,
+        source_id: Uuid,
+        post_id: Option<Uuid>,
+    ) -> Result<()> {
+        let request = CreateNotificationRequest {
+            user_id: mentioned_user_id,
+            notification_type: NotificationType::Mention,
+            title: format!("{} mentioned you", author_name),
+            body: format!("You were mentioned in a {}", source),
+            data: Some(serde_json::json!({
+                "source_type": source.to_string(),
+                "source_id": source_id,
+                "post_id": post_id,
+                "author_name": author_name
+            })),
+        };
+
+        self.create_notification(request).await?;
+        Ok(())
+    }
+
+    /// Clean up old notifications
+    /// Tell a poll's author that voting has ended
+    pub async fn notify_poll_closed(
+        &self,
+        author_id: Uuid,
+        post_id: Uuid,
+        total_votes: i32,
+    ) -> Result<()> {
+        let request = CreateNotificationRequest {
+            user_id: author_id,
+            notification_type: NotificationType::PollClosed,
+            title: "Your poll has closed".to_string(),
+            body: format!(
+                "Your poll received {} vote{}",
+                total_votes,
+                if total_votes == 1 { "" } else { "s" }
+            ),
+            data: Some(serde_json::json!({
+                "post_id": post_id,
+                "total_votes": total_votes
+            })),
+        };
+
+        self.create_notification(request).await?;
+        Ok(())
+    }
+
+    /// Tell a user about a moderation decision affecting them
+    pub async fn notify_moderation(
+        &self,
+        user_id: Uuid,
+        case: &ModerationCase,
+        title: &str,
+        body: String,
+    ) -> Result<()> {
+        let request = CreateNotificationRequest {
+            user_id,
+            notification_type: NotificationType::System,
+            title: title.to_string(),
+            body,
+            data: Some(serde_json::json!({
+                "case_id": case.id,
+                "target_type": case.target_type.to_string(),
+                "target_id": case.target_id
+            })),
+        };
+
+        self.create_notification(request).await?;
+        Ok(())
+    }
+
+    pub async fn cleanup_old_notifications(&self, days: i32) -> Result<i64> {
+        self.notification_repository
+            .delete_old_notifications(days)
+            .await
+    }
+
+    /// Clean up inactive device tokens
+    pub async fn cleanup_inactive_tokens(&self, days: i32) -> Result<i64> {
+        self.device_token_repository
+            .delete_inactive_tokens(days)
+            .await
+    }
+}
+
+impl UserManagementService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        wallet_repository: Arc<dyn WalletRepository>,
+    ) -> Self {
+        Self {
+            user_repository,
+            wallet_repository,
+        }
+    }
+
+    /// Update user profile with coordination and validation
+    pub async fn update_profile(
+        &self,
+        user_id: Uuid,
+        update_request: UpdateUserRequest,
+    ) -> Result<User> {
+        // Find the user first
+        let mut user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        // Apply the update to the user entity (this handles validation)
+        user.update(update_request)?;
+
+        // Persist the updated user
+        let updated_user = self.user_repository.update(&user).await?;
+
+        Ok(updated_user)
+    }
+
+    /// Follow another user with proper coordination
+    pub async fn follow_user(&self, follower_id: Uuid, following_id: Uuid) -> Result<()> {
+        // Validate that both users exist
+        let _follower = self
+            .user_repository
+            .find_by_id(follower_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Follower user not found".to_string()))?;
+
+        let _following = self
+            .user_repository
+            .find_by_id(following_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User to follow not found".to_string()))?;
+
+        // Prevent self-following
+        if follower_id == following_id {
+            return Err(AppError::ValidationError(
+                "Users cannot follow themselves".to_string(),
+            ));
+        }
+
+        // Check if already following
+        let is_already_following = self
+            .user_repository
+            .is_following(follower_id, following_id)
+            .await?;
+
+        if is_already_following {
+            return Err(AppError::ValidationError(
+                "User is already following this user".to_string(),
+            ));
+        }
+
+        // Perform the follow operation (this updates counts atomically)
+        self.user_repository
+            .follow(follower_id, following_id)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Unfollow another user with proper coordination
+    pub async fn unfollow_user(&self, follower_id: Uuid, following_id: Uuid) -> Result<()> {
+        // Validate that both users exist
+        let _follower = self
+            .user_repository
+            .find_by_id(follower_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Follower user not found".to_string()))?;
+
+        let _following = self
+            .user_repository
+            .find_by_id(following_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User to unfollow not found".to_string()))?;
+
+        // Check if currently following
+        let is_following = self
+            .user_repository
+            .is_following(follower_id, following_id)
+            .await?;
+
+        if !is_following {
+            return Err(AppError::ValidationError(
+                "User is not following this user".to_string(),
+            ));
+        }
+
+        // Perform the unfollow operation (this updates counts atomically)
+        self.user_repository
+            .unfollow(follower_id, following_id)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Mute another user, keeping their posts out of the muter's feeds
+    /// without unfollowing them. Muting is idempotent.
+    pub async fn mute_user(&self, user_id: Uuid, muted_user_id: Uuid) -> Result<()> {
+        if user_id == muted_user_id {
+            return Err(AppError::ValidationError(
+                "Users cannot mute themselves".to_string(),
+            ));
+        }
+
+        self.user_repository
+            .find_by_id(muted_user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User to mute not found".to_string()))?;
+
+        self.user_repository.mute(user_id, muted_user_id).await
+    }
+
+    /// Unmute a user. Unmuting a user who is not muted does nothing.
+    pub async fn unmute_user(&self, user_id: Uuid, muted_user_id: Uuid) -> Result<()> {
+        self.user_repository.unmute(user_id, muted_user_id).await
+    }
+
+    /// Get user profile by ID
+    pub async fn get_user_profile(&self, user_id: Uuid) -> Result<User> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    /// Get a page of a user's followers, most recent follow first
+    pub async fn get_user_followers(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        page: PageStart,
+    ) -> Result<FollowPage> {
+        // Validate that user exists
+        let _user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let followers = self
+            .user_repository
+            .get_followers(user_id, page, limit + 1)
+            .await?;
+        Ok(follow_page(followers, limit))
+    }
+
+    /// Get a page of the users a user is following, most recent follow first
+    pub async fn get_user_following(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        page: PageStart,
+    ) -> Result<FollowPage> {
+        // Validate that user exists
+        let _user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let following = self
+            .user_repository
+            .get_following(user_id, page, limit + 1)
+            .await?;
+        Ok(follow_page(following, limit))
+    }
+
+    /// Check if one user follows another
+    pub async fn is_following(&self, follower_id: Uuid, following_id: Uuid) -> Result<bool> {
+        self.user_repository
+            .is_following(follower_id, following_id)
+            .await
+    }
+
+    /// Search for users by query
+    pub async fn search_users(&self, query: &str, limit: i64, offset: i64) -> Result<Vec<User>> {
+        if query.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Search query cannot be empty".to_string(),
+            ));
+        }
+
+        if query.len() < 2 {
+            return Err(AppError::ValidationError(
+                "Search query must be at least 2 characters".to_string(),
+            ));
+        }
+
+        self.user_repository.search(query, limit, offset).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::*;
+    use super::*;
+    use crate::domain::entities::UpdateUserRequest;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_update_profile_success() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let user = create_test_user("testuser", "test@example.com");
+        user_repo.add_user(user.clone());
+
+        let update_request = UpdateUserRequest {
+            display_name: Some("Updated Name".to_string()),
+            bio: Some("Updated bio".to_string()),
+            avatar_url: Some("https://example.com/avatar.jpg".to_string()),
+            sensitive_media: None,
+        };
+
+        let result = service.update_profile(user.id, update_request).await;
+        assert!(result.is_ok());
+
+        let updated_user = result.unwrap();
+        assert_eq!(
+            updated_user.display_name.as_ref().unwrap().value(),
+            "Updated Name"
+        );
+        assert_eq!(updated_user.bio.as_ref().unwrap().value(), "Updated bio");
+        assert_eq!(
+            updated_user.avatar_url.as_ref().unwrap(),
+            "https://example.com/avatar.jpg"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_user_not_found() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo, wallet_repo);
+
+        let update_request = UpdateUserRequest {
+            display_name: Some("Updated Name".to_string()),
+            bio: None,
+            avatar_url: None,
+            sensitive_media: None,
+        };
+
+        let result = service.update_profile(Uuid::new_v4(), update_request).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_mute_user() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let user = add_test_user(&user_repo, "muter");
+        let other = add_test_user(&user_repo, "muted");
+
+        service.mute_user(user.id, other.id).await.unwrap();
+        let muted = user_repo.muted_user_ids(user.id).await.unwrap();
+        assert!(muted.contains(&other.id));
+
+        service.unmute_user(user.id, other.id).await.unwrap();
+        assert!(user_repo.muted_user_ids(user.id).await.unwrap().is_empty());
+
+        let result = service.mute_user(user.id, user.id).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        let result = service.mute_user(user.id, Uuid::new_v4()).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_follow_user_success() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let follower = add_test_user(&user_repo, "follower");
+        let following = add_test_user(&user_repo, "following");
+
+        let result = service.follow_user(follower.id, following.id).await;
+        assert!(result.is_ok());
+
+        let is_following = service
+            .is_following(follower.id, following.id)
+            .await
+            .unwrap();
+        assert!(is_following);
+    }
+
+    #[tokio::test]
+    async fn test_follow_user_self_follow() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let user = create_test_user("testuser", "test@example.com");
+        user_repo.add_user(user.clone());
+
+        let result = service.follow_user(user.id, user.id).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::ValidationError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_follow_user_already_following() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let follower = add_test_user(&user_repo, "follower");
+        let following = add_test_user(&user_repo, "following");
+
+        // First follow should succeed
+        service
+            .follow_user(follower.id, following.id)
+            .await
+            .unwrap();
+
+        // Second follow should fail
+        let result = service.follow_user(follower.id, following.id).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::ValidationError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_unfollow_user_success() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let follower = add_test_user(&user_repo, "follower");
+        let following = add_test_user(&user_repo, "following");
+
+        // First follow
+        service
+            .follow_user(follower.id, following.id)
+            .await
+            .unwrap();
+
+        // Then unfollow
+        let result = service.unfollow_user(follower.id, following.id).await;
+        assert!(result.is_ok());
+
+        let is_following = service
+            .is_following(follower.id, following.id)
+            .await
+            .unwrap();
+        assert!(!is_following);
+    }
+
+    #[tokio::test]
+    async fn test_unfollow_user_not_following() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let follower = add_test_user(&user_repo, "follower");
+        let following = add_test_user(&user_repo, "following");
+
+        let result = service.unfollow_user(follower.id, following.id).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::ValidationError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_search_users_success() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let user1 = create_test_user("testuser1", "test1@example.com");
+        let user2 = create_test_user("testuser2", "test2@example.com");
+        let user3 = create_test_user("otheruser", "other@example.com");
+
+        user_repo.add_user(user1);
+        user_repo.add_user(user2);
+        user_repo.add_user(user3);
+
+        let result = service.search_users("test", 10, 0).await;
+        assert!(result.is_ok());
+
+        let users = result.unwrap();
+        assert_eq!(users.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_users_empty_query() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo, wallet_repo);
+
+        let result = service.search_users("", 10, 0).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::ValidationError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_search_users_short_query() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo, wallet_repo);
+
+        let result = service.search_users("a", 10, 0).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::ValidationError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_user_profile_success() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo.clone(), wallet_repo);
+
+        let user = create_test_user("testuser", "test@example.com");
+        user_repo.add_user(user.clone());
+
+        let result = service.get_user_profile(user.id).await;
+        assert!(result.is_ok());
+
+        let retrieved_user = result.unwrap();
+        assert_eq!(retrieved_user.id, user.id);
+        assert_eq!(retrieved_user.username.value(), "testuser");
+    }
+
+    #[tokio::test]
+    async fn test_get_user_profile_not_found() {
+        let user_repo = Arc::new(TestUserRepository::new());
+        let wallet_repo = Arc::new(MockWalletRepository);
+        let service = UserManagementService::new(user_repo, wallet_repo);
+
+        let result = service.get_user_profile(Uuid::new_v4()).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::NotFound(_)));
+    }
+}
+#[cfg(test)]
+mod feed_generation_tests {
+    use super::test_support::*;
+    use super::*;
+    use crate::domain::entities::TimelineEntry;
+    use crate::domain::repositories::{MockUserRepository, PostRepository, TimelineRepository};
+    use async_trait::async_trait;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_chronological_feed_generation() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo, None, None);
+
+        let user_id = Uuid::new_v4();
+        let post1 = create_test_post(user_id, "First post", false);
+        let post2 = create_test_post(user_id, "Second post", false);
+
+        post_repo.add_post(post1.clone());
+        post_repo.add_post(post2.clone());
+        post_repo.set_user_feed(user_id, vec![post1.id, post2.id]);
+
+        let filters = FeedFilters::default();
+        let result = service
+            .generate_feed(
+                user_id,
+                FeedSortStrategy::Chronological,
+                filters,
+                10,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap()
+            .posts;
+
+        assert_eq!(result.len(), 2);
+        // Should be sorted by created_at descending (newest first)
+        assert!(result[0].created_at >= result[1].created_at);
+    }
+
+    #[tokio::test]
+    async fn test_reels_only_filter() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo, None, None);
+
+        let user_id = Uuid::new_v4();
+        let regular_post = create_test_post(user_id, "Regular post", false);
+        let reel_post = create_test_post(user_id, "Reel post", true);
+
+        post_repo.add_post(regular_post.clone());
+        post_repo.add_post(reel_post.clone());
+        post_repo.set_user_feed(user_id, vec![regular_post.id, reel_post.id]);
+
+        let filters = FeedFilters {
+            reels_only: true,
+            ..Default::default()
+        };
+
+        let result = service
+            .generate_feed(
+                user_id,
+                FeedSortStrategy::Chronological,
+                filters,
+                10,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap()
+            .posts;
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].is_reel);
+    }
+
+    #[tokio::test]
+    async fn test_algorithmic_feed_sorting() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo, None, None);
+
+        let user_id = Uuid::new_v4();
+
+        // Create posts with different engagement levels, by different authors
+        // so they are not collapsed into one
+        let mut low_engagement_post = create_test_post(user_id, "Low engagement", false);
+        low_engagement_post.like_count = 1;
+
+        let mut high_engagement_post = create_test_post(Uuid::new_v4(), "High engagement", false);
+        high_engagement_post.like_count = 50;
+        high_engagement_post.comment_count = 10;
+
+        post_repo.add_post(low_engagement_post.clone());
+        post_repo.add_post(high_engagement_post.clone());
+        post_repo.set_user_feed(
+            user_id,
+            vec![low_engagement_post.id, high_engagement_post.id],
+        );
+
+        let filters = FeedFilters::default();
+        let result = service
+            .generate_feed(
+                user_id,
+                FeedSortStrategy::Algorithmic,
+                filters,
+                10,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap()
+            .posts;
+
+        assert_eq!(result.len(), 2);
+        // High engagement post should come first in algorithmic feed
+        assert_eq!(result[0].id, high_engagement_post.id);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_ranker_favours_authors_the_viewer_engages_with() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let viewer_id = Uuid::new_v4();
+        let friend_id = Uuid::new_v4();
+
+        let mut stranger_post = create_test_post(Uuid::new_v4(), "Stranger", false);
+        stranger_post.like_count = 3;
+        let mut friend_post = create_test_post(friend_id, "Friend", false);
+        friend_post.like_count = 2;
+        post_repo.add_post(stranger_post.clone());
+        post_repo.add_post(friend_post.clone());
+        post_repo.set_user_feed(viewer_id, vec![stranger_post.id, friend_post.id]);
+
+        // The viewer has liked a few of the friend's older posts
+        for _ in 0..3 {
+            let older = create_test_post(friend_id, "Older", false);
+            post_repo.add_post(older.clone());
+            post_repo.like_post(viewer_id, older.id).await.unwrap();
+        }
+
+        let ranked_ids = |service: FeedGenerationService| async move {
+            service
+                .generate_feed(
+                    viewer_id,
+                    FeedSortStrategy::Algorithmic,
+                    FeedFilters::default(),
+                    10,
+                    PageStart::FIRST,
+                )
+                .await
+                .unwrap()
+                .posts
+                .iter()
+                .map(|post| post.id)
+                .collect::<Vec<_>>()
+        };
+
+        let default_service =
+            FeedGenerationService::new(post_repo.clone(), user_repo.clone(), None, None);
+        assert_eq!(
+            ranked_ids(default_service).await,
+            vec![stranger_post.id, friend_post.id]
+        );
+
+        let weighted_service = FeedGenerationService::new(post_repo, user_repo, None, None)
+            .with_ranker(Arc::new(
+                crate::application::ranking::WeightedRanker::default(),
+            ));
+        assert_eq!(
+            ranked_ids(weighted_service).await,
+            vec![friend_post.id, stranger_post.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chronological_feed_pages_with_cursor() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo, None, None);
+
+        let user_id = Uuid::new_v4();
+        let author_id = Uuid::new_v4();
+        let newest = post_at(author_id, "Newest", 0);
+        let middle = post_at(author_id, "Middle", 10);
+        let oldest = post_at(author_id, "Oldest", 20);
+        for post in [&newest, &middle, &oldest] {
+            post_repo.add_post(post.clone());
+        }
+        post_repo.set_user_feed(user_id, vec![oldest.id, middle.id, newest.id]);
+
+        let first = service
+            .generate_feed(
+                user_id,
+                FeedSortStrategy::Chronological,
+                FeedFilters::default(),
+                2,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = first.posts.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![newest.id, middle.id]);
+        assert!(first.has_more);
+        let cursor = first.next_cursor.unwrap();
+        assert_eq!(cursor, Cursor::new(middle.created_at, middle.id));
+
+        // A post published between requests does not shift the next page
+        let latest = post_at(author_id, "Latest", 0);
+        post_repo.add_post(latest.clone());
+        post_repo.set_user_feed(user_id, vec![oldest.id, middle.id, newest.id, latest.id]);
+
+        let second = service
+            .generate_feed(
+                user_id,
+                FeedSortStrategy::Chronological,
+                FeedFilters::default(),
+                2,
+                PageStart::After(cursor),
+            )
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = second.posts.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![oldest.id]);
+        assert!(!second.has_more);
+        assert!(second.next_cursor.is_none());
+
+        // Ranked feeds have no stable order to continue from
+        let ranked = service
+            .generate_feed(
+                user_id,
+                FeedSortStrategy::Algorithmic,
+                FeedFilters::default(),
+                2,
+                PageStart::After(cursor),
+            )
+            .await;
+        assert!(matches!(ranked, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_explore_ranks_posts_from_unfollowed_accounts() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(TestUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo.clone(), None, None);
+        let viewer_id = Uuid::new_v4();
+        let muted_id = Uuid::new_v4();
+
+        let followed_post = create_test_post(Uuid::new_v4(), "Followed", false);
+        let mut trending = create_test_post(Uuid::new_v4(), "Trending", false);
+        trending.like_count = 30;
+        let mut friends_liked = create_test_post(Uuid::new_v4(), "Friends liked this", false);
+        friends_liked.like_count = 5;
+        let mut hashtagged = create_test_post(Uuid::new_v4(), "Morning #Running", false);
+        hashtagged.like_count = 5;
+        let mut muted = create_test_post(muted_id, "Muted", false);
+        muted.like_count = 100;
+        for post in [
+            &followed_post,
+            &trending,
+            &friends_liked,
+            &hashtagged,
+            &muted,
+        ] {
+            post_repo.add_post(post.clone());
+        }
+        post_repo.set_user_feed(viewer_id, vec![followed_post.id]);
+        post_repo.set_followed_likes(friends_liked.id, 6);
+        user_repo.mute(viewer_id, muted_id).await.unwrap();
+
+        // The viewer keeps liking running posts from accounts they follow
+        for _ in 0..10 {
+            let liked = create_test_post(followed_post.user_id, "#running again", false);
+            post_repo.add_post(liked.clone());
+            post_repo.like_post(viewer_id, liked.id).await.unwrap();
+        }
+
+        let explore = service
+            .generate_explore_feed(viewer_id, 10, 0)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = explore.posts.iter().map(|post| post.id).collect();
+
+        assert_eq!(ids, vec![friends_liked.id, hashtagged.id, trending.id]);
+        assert!(!explore.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_short_feed_is_filled_with_explore_posts() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo, None, None);
+        let viewer_id = Uuid::new_v4();
+
+        let followed_post = create_test_post(Uuid::new_v4(), "Followed", false);
+        let suggested_post = create_test_post(Uuid::new_v4(), "Suggested", false);
+        post_repo.add_post(followed_post.clone());
+        post_repo.add_post(suggested_post.clone());
+        post_repo.set_user_feed(viewer_id, vec![followed_post.id]);
+
+        let feed = service
+            .generate_feed(
+                viewer_id,
+                FeedSortStrategy::Chronological,
+                FeedFilters::default(),
+                10,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(ids(&feed.posts), vec![followed_post.id, suggested_post.id]);
+        assert_eq!(feed.suggested_index, Some(1));
+
+        // A full feed is left alone
+        let full = service
+            .generate_feed(
+                viewer_id,
+                FeedSortStrategy::Chronological,
+                FeedFilters::default(),
+                1,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids(&full.posts), vec![followed_post.id]);
+        assert_eq!(full.suggested_index, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_reels_feed() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo, None, None);
+
+        let user_id = Uuid::new_v4();
+        let regular_post = create_test_post(user_id, "Regular post", false);
+        let reel_post = create_test_post(user_id, "Reel post", true);
+
+        post_repo.add_post(regular_post.clone());
+        post_repo.add_post(reel_post.clone());
+        post_repo.set_user_feed(user_id, vec![regular_post.id, reel_post.id]);
+
+        let result = service.get_reels_feed(user_id, 10, 0).await.unwrap().posts;
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].is_reel);
+        assert_eq!(result[0].id, reel_post.id);
+    }
+
+    #[tokio::test]
+    async fn test_get_trending_posts() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo, None, None);
+
+        // Create posts with different engagement levels
+        let mut low_engagement_post = create_test_post(Uuid::new_v4(), "Low engagement", false);
+        low_engagement_post.like_count = 2;
+
+        let mut high_engagement_post = create_test_post(Uuid::new_v4(), "High engagement", false);
+        high_engagement_post.like_count = 20;
+        high_engagement_post.comment_count = 5;
+
+        post_repo.add_post(low_engagement_post.clone());
+        post_repo.add_post(high_engagement_post.clone());
+
+        let result = service.get_trending_posts(None, 10, 0).await.unwrap();
+
+        // Only high engagement post should be in trending (>= 5 total engagement)
+        assert_eq!(result.posts.len(), 1);
+        assert_eq!(result.posts[0].id, high_engagement_post.id);
+        assert!(!result.has_more);
+
+        let mut more_engagement_post = create_test_post(Uuid::new_v4(), "More engagement", false);
+        more_engagement_post.like_count = 40;
+        post_repo.add_post(more_engagement_post.clone());
+
+        let first = service.get_trending_posts(None, 1, 0).await.unwrap();
+        assert_eq!(first.posts[0].id, more_engagement_post.id);
+        assert!(first.has_more);
+        let second = service.get_trending_posts(None, 1, 1).await.unwrap();
+        assert_eq!(second.posts[0].id, high_engagement_post.id);
+        assert!(!second.has_more);
+    }
+
+    #[test]
+    fn test_feed_query_validation() {
+        assert_eq!(
+            "algorithmic".parse::<FeedSortStrategy>().unwrap(),
+            FeedSortStrategy::Algorithmic
+        );
+        assert!("popular".parse::<FeedSortStrategy>().is_err());
+
+        let filters = FeedFilters {
+            content_types: Some(vec!["image".to_string(), "video".to_string()]),
+            ..Default::default()
+        };
+        assert!(filters.validate().is_ok());
+
+        let filters = FeedFilters {
+            content_types: Some(vec!["gif".to_string()]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            filters.validate(),
+            Err(AppError::ValidationError(_))
+        ));
+
+        let filters = FeedFilters {
+            reels_only: true,
+            exclude_reels: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            filters.validate(),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_feed_honors_sensitive_media_preference() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(TestUserRepository::new());
+        let service = FeedGenerationService::new(post_repo.clone(), user_repo.clone(), None, None);
+
+        let mut viewer = create_test_user("viewer", "viewer@example.com");
+        let author = add_test_user(&user_repo, "author");
+
+        let plain = create_test_post(author.id, "Plain post", false);
+        let mut sensitive = create_test_post(author.id, "Spoilers", false);
+        sensitive
+            .set_sensitivity(false, Some("Season finale".to_string()))
+            .unwrap();
+        let mut own = create_test_post(viewer.id, "My own sensitive post", false);
+        own.set_sensitivity(true, None).unwrap();
+        for post in [&plain, &sensitive, &own] {
+            post_repo.add_post(post.clone());
+        }
+        post_repo.set_user_feed(viewer.id, vec![plain.id, sensitive.id, own.id]);
+
+        let feed_ids = |posts: Vec<Post>| {
+            let mut ids: Vec<Uuid> = posts.iter().map(|post| post.id).collect();
+            ids.sort();
+            ids
+        };
+
+        // Blurred posts stay in the feed for the client to blur
+        user_repo.add_user(viewer.clone());
+        let blurred = service
+            .generate_feed(
+                viewer.id,
+                FeedSortStrategy::Chronological,
+                FeedFilters::default(),
+                10,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap()
+            .posts;
+        assert_eq!(blurred.len(), 3);
+        assert!(blurred
+            .iter()
+            .any(|post| post.id == sensitive.id && post.is_sensitive));
+
+        viewer.sensitive_media = SensitiveMediaPreference::Hide;
+        user_repo.add_user(viewer.clone());
+        let hidden = service
+            .generate_feed(
+                viewer.id,
+                FeedSortStrategy::Chronological,
+                FeedFilters::default(),
+                10,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap()
+            .posts;
+        let mut expected = vec![plain.id, own.id];
+        expected.sort();
+        assert_eq!(feed_ids(hidden), expected);
+    }
+
+    #[tokio::test]
+    async fn test_unfollow_removes_author_from_timeline() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let timelines = Arc::new(MockTimelineRepository::new());
+        let timeline_service = Arc::new(TimelineService::new(
+            timelines.clone(),
+            post_repo.clone(),
+            user_repo.clone(),
+        ));
+        let service =
+            FeedGenerationService::new(post_repo.clone(), user_repo, None, Some(timeline_service));
+
+        let viewer_id = Uuid::new_v4();
+        let kept_author = Uuid::new_v4();
+        let unfollowed_author = Uuid::new_v4();
+        let kept = post_at(kept_author, "Kept", 10);
+        let dropped = post_at(unfollowed_author, "Dropped", 0);
+        for post in [&kept, &dropped] {
+            post_repo.add_post(post.clone());
+        }
+        timelines
+            .replace(
+                viewer_id,
+                &[TimelineEntry::from(&kept), TimelineEntry::from(&dropped)],
+                100,
+            )
+            .await
+            .unwrap();
+
+        service
+            .unfollow_in_timeline(viewer_id, unfollowed_author)
+            .await;
+
+        let feed = service
+            .generate_feed(
+                viewer_id,
+                FeedSortStrategy::Chronological,
+                FeedFilters::default(),
+                10,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap();
+        // The unfollowed author may still be suggested after the home posts
+        let home = &feed.posts[..feed.suggested_index.unwrap_or(feed.posts.len())];
+        let ids: Vec<Uuid> = home.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![kept.id]);
+    }
+
+    // In-memory ImpressionRepository keeping when each post was first shown
+    struct MockImpressionRepository {
+        shown: Mutex<HashMap<(Uuid, Uuid), DateTime<Utc>>>,
+        sessions: Mutex<HashMap<Uuid, DateTime<Utc>>>,
+    }
+
+    impl MockImpressionRepository {
+        fn new() -> Self {
+            Self {
+                shown: Mutex::new(HashMap::new()),
+                sessions: Mutex::new(HashMap::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ImpressionRepository for MockImpressionRepository {
+        async fn record(&self, user_id: Uuid, post_ids: &[Uuid], at: DateTime<Utc>) -> Result<()> {
+            let mut shown = self.shown.lock().unwrap();
+            for post_id in post_ids {
+                shown.entry((user_id, *post_id)).or_insert(at);
+            }
+            Ok(())
+        }
+
+        async fn seen_before(
+            &self,
+            user_id: Uuid,
+            post_ids: &[Uuid],
+            before: DateTime<Utc>,
+        ) -> Result<HashSet<Uuid>> {
+            let shown = self.shown.lock().unwrap();
+            Ok(post_ids
+                .iter()
+                .filter(|post_id| {
+                    shown
+                        .get(&(user_id, **post_id))
+                        .is_some_and(|at| *at < before)
+                })
+                .copied()
+                .collect())
+        }
+
+        async fn start_session(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<()> {
+            self.sessions.lock().unwrap().insert(user_id, at);
+            Ok(())
+        }
+
+        async fn session_started_at(&self, user_id: Uuid) -> Result<Option<DateTime<Utc>>> {
+            Ok(self.sessions.lock().unwrap().get(&user_id).copied())
+        }
+    }
+
+    /// A feed service tracking impressions, with one post per like count
+    /// given, each by a different author, in the viewer's feed
+    fn feed_with_impressions(
+        viewer_id: Uuid,
+        like_counts: &[i32],
+    ) -> (FeedGenerationService, Vec<Post>) {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let posts: Vec<Post> = like_counts
+            .iter()
+            .map(|like_count| {
+                let mut post = post_at(Uuid::new_v4(), "Post", 10);
+                post.like_count = *like_count;
+                post_repo.add_post(post.clone());
+                post
+            })
+            .collect();
+        post_repo.set_user_feed(viewer_id, posts.iter().map(|post| post.id).collect());
+
+        let service =
+            FeedGenerationService::new(post_repo, Arc::new(MockUserRepository::new()), None, None)
+                .with_impressions(Arc::new(MockImpressionRepository::new()));
+        (service, posts)
+    }
+
+    async fn ranked_page(
+        service: &FeedGenerationService,
+        viewer_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> FeedPage {
+        service
+            .generate_feed(
+                viewer_id,
+                FeedSortStrategy::Algorithmic,
+                FeedFilters::default(),
+                limit,
+                PageStart::Offset(offset),
+            )
+            .await
+            .unwrap()
+    }
+
+    fn ids(posts: &[Post]) -> Vec<Uuid> {
+        posts.iter().map(|post| post.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_refreshed_ranked_feed_puts_seen_posts_last() {
+        let viewer_id = Uuid::new_v4();
+        let (service, posts) = feed_with_impressions(viewer_id, &[30, 20, 10]);
+
+        let first = ranked_page(&service, viewer_id, 2, 0).await;
+        assert_eq!(ids(&first.posts), vec![posts[0].id, posts[1].id]);
+        assert_eq!(first.caught_up_index, None);
+
+        // Refreshing starts a new session in which the shown posts count as seen
+        let refreshed = ranked_page(&service, viewer_id, 3, 0).await;
+        assert_eq!(
+            ids(&refreshed.posts),
+            vec![posts[2].id, posts[0].id, posts[1].id]
+        );
+        assert_eq!(refreshed.caught_up_index, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_ranked_feed_pages_stay_stable_within_a_session() {
+        let viewer_id = Uuid::new_v4();
+        let (service, posts) = feed_with_impressions(viewer_id, &[30, 20, 10]);
+
+        let mut paged = Vec::new();
+        for offset in 0..3 {
+            let page = ranked_page(&service, viewer_id, 1, offset).await;
+            assert_eq!(page.caught_up_index, None);
+            paged.extend(ids(&page.posts));
+        }
+        assert_eq!(paged, ids(&posts));
+
+        // Everything has been seen once the next session starts
+        let refreshed = ranked_page(&service, viewer_id, 3, 0).await;
+        assert_eq!(ids(&refreshed.posts), ids(&posts));
+        assert_eq!(refreshed.caught_up_index, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_ranked_feed_collapses_bursts_from_one_author() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let service = FeedGenerationService::new(
+            post_repo.clone(),
+            Arc::new(MockUserRepository::new()),
+            None,
+            None,
+        );
+        let viewer_id = Uuid::new_v4();
+        let prolific = Uuid::new_v4();
+
+        let mut best = post_at(prolific, "Best", 10);
+        best.like_count = 10;
+        let mut burst = post_at(prolific, "Same burst", 30);
+        burst.like_count = 5;
+        let mut later = post_at(prolific, "Hours earlier", 5 * 60);
+        later.like_count = 1;
+        let mut other = post_at(Uuid::new_v4(), "Other author", 20);
+        other.like_count = 3;
+        for post in [&best, &burst, &later, &other] {
+            post_repo.add_post(post.clone());
+        }
+        post_repo.set_user_feed(viewer_id, vec![best.id, burst.id, later.id, other.id]);
+
+        let page = ranked_page(&service, viewer_id, 10, 0).await;
+        assert_eq!(ids(&page.posts), vec![best.id, other.id, later.id]);
+        assert_eq!(page.collapsed.get(&best.id), Some(&vec![burst.id]));
+        assert_eq!(page.collapsed.len(), 1);
+    }
+
+    // In-memory FeedFeedbackRepository
+    struct MockFeedFeedbackRepository {
+        feedback: Mutex<Vec<FeedFeedback>>,
+    }
+
+    #[async_trait]
+    impl FeedFeedbackRepository for MockFeedFeedbackRepository {
+        async fn upsert(&self, feedback: &FeedFeedback) -> Result<()> {
+            let mut stored = self.feedback.lock().unwrap();
+            stored.retain(|item| {
+                (item.user_id, item.post_id, item.kind)
+                    != (feedback.user_id, feedback.post_id, feedback.kind)
+            });
+            stored.push(feedback.clone());
+            Ok(())
+        }
+
+        async fn remove(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+            self.feedback
+                .lock()
+                .unwrap()
+                .retain(|item| (item.user_id, item.post_id) != (user_id, post_id));
+            Ok(())
+        }
+
+        async fn find_by_user(
+            &self,
+            user_id: Uuid,
+            since: DateTime<Utc>,
+            limit: i64,
+        ) -> Result<Vec<FeedFeedback>> {
+            Ok(self
+                .feedback
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .filter(|item| item.user_id == user_id && item.created_at >= since)
+                .take(limit.max(0) as usize)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_feedback_drops_hidden_posts_and_ranks_similar_posts_lower() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let service = FeedGenerationService::new(
+            post_repo.clone(),
+            Arc::new(MockUserRepository::new()),
+            None,
+            None,
+        )
+        .with_feedback(Arc::new(MockFeedFeedbackRepository {
+            feedback: Mutex::new(Vec::new()),
+        }));
+        let viewer_id = Uuid::new_v4();
+
+        let post = |text: &str, like_count: i32| {
+            let mut post = post_at(Uuid::new_v4(), text, 10);
+            post.like_count = like_count;
+            post_repo.add_post(post.clone());
+            post
+        };
+        let hidden = post("Hidden", 40);
+        let fewer = post("Too much of this author", 30);
+        let not_interested = post("Derby day #Football", 25);
+        let neutral = post("Neutral", 20);
+        let same_hashtag = post("More #football", 18);
+        let quiet = post("Quiet", 12);
+        let all = [
+            &hidden,
+            &fewer,
+            &not_interested,
+            &neutral,
+            &same_hashtag,
+            &quiet,
+        ];
+        post_repo.set_user_feed(viewer_id, all.iter().map(|post| post.id).collect());
+
+        let page = ranked_page(&service, viewer_id, 10, 0).await;
+        assert_eq!(
+            ids(&page.posts),
+            all.iter().map(|post| post.id).collect::<Vec<_>>()
+        );
+
+        for (post, kind) in [
+            (&hidden, FeedFeedbackKind::Hide),
+            (&fewer, FeedFeedbackKind::ShowFewerFromAuthor),
+            (&not_interested, FeedFeedbackKind::NotInterested),
+        ] {
+            service
+                .give_feedback(viewer_id, post.id, kind)
+                .await
+                .unwrap();
+        }
+
+        let page = ranked_page(&service, viewer_id, 10, 0).await;
+        assert_eq!(
+            ids(&page.posts),
+            vec![neutral.id, fewer.id, quiet.id, same_hashtag.id]
+        );
+
+        // Hidden posts stay out of chronological feeds too, until unhidden
+        let chronological = service
+            .generate_feed(
+                viewer_id,
+                FeedSortStrategy::Chronological,
+                FeedFilters::default(),
+                10,
+                PageStart::FIRST,
+            )
+            .await
+            .unwrap();
+        assert!(!ids(&chronological.posts).contains(&hidden.id));
+
+        service.undo_feedback(viewer_id, hidden.id).await.unwrap();
+        let page = ranked_page(&service, viewer_id, 10, 0).await;
+        assert_eq!(page.posts[0].id, hidden.id);
+
+        // Feedback on your own posts is rejected
+        let own = post_at(viewer_id, "Mine", 10);
+        post_repo.add_post(own.clone());
+        let result = service
+            .give_feedback(viewer_id, own.id, FeedFeedbackKind::Hide)
+            .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
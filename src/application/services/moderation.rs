@@ -0,0 +1,1008 @@
+//! User reports and the moderators' review queue: reported content is grouped
+//! into cases, hidden past a report threshold and resolved by moderator actions.
+use super::{FeedGenerationService, NotificationService, PostVisibilityService};
+use crate::domain::entities::{
+    ModerationAction, ModerationActionType, ModerationCase, Report, ReportReason, ReportTargetType,
+};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{
+    CommentRepository, ConversationRepository, MessageRepository, ModerationRepository,
+    PostRepository, UserRepository, WalletRepository,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Moderation service for user reports and the moderators' review queue
+pub struct ModerationService {
+    moderation_repository: Arc<dyn ModerationRepository>,
+    visibility_service: Arc<PostVisibilityService>,
+    post_repository: Arc<dyn PostRepository>,
+    feed_service: Arc<FeedGenerationService>,
+    comment_repository: Arc<dyn CommentRepository>,
+    conversation_repository: Arc<dyn ConversationRepository>,
+    message_repository: Arc<dyn MessageRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    wallet_repository: Arc<dyn WalletRepository>,
+    notification_service: Arc<NotificationService>,
+    /// Reports on an open case that hide the reported content
+    hide_threshold: i32,
+}
+
+/// A case with everything a moderator needs to decide on it
+#[derive(Debug)]
+pub struct ModerationCaseDetail {
+    pub case: ModerationCase,
+    pub reports: Vec<Report>,
+    /// Oldest first
+    pub actions: Vec<ModerationAction>,
+}
+
+impl ModerationService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        moderation_repository: Arc<dyn ModerationRepository>,
+        visibility_service: Arc<PostVisibilityService>,
+        post_repository: Arc<dyn PostRepository>,
+        feed_service: Arc<FeedGenerationService>,
+        comment_repository: Arc<dyn CommentRepository>,
+        conversation_repository: Arc<dyn ConversationRepository>,
+        message_repository: Arc<dyn MessageRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        wallet_repository: Arc<dyn WalletRepository>,
+        notification_service: Arc<NotificationService>,
+        hide_threshold: u32,
+    ) -> Self {
+        Self {
+            moderation_repository,
+            visibility_service,
+            post_repository,
+            feed_service,
+            comment_repository,
+            conversation_repository,
+            message_repository,
+            user_repository,
+            wallet_repository,
+            notification_service,
+            hide_threshold: hide_threshold.max(1) as i32,
+        }
+    }
+
+    /// Report a post, comment, message or user.
+    ///
+    /// Users can only report what they can see, and never themselves or
+    /// their own content. Returns whether the report was new. Content is
+    /// hidden once its open case reaches the hide threshold.
+    pub async fn file_report(
+        &self,
+        reporter_id: Uuid,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        reason: ReportReason,
+        details: Option<String>,
+    ) -> Result<bool> {
+        let report = Report::new(reporter_id, target_type, target_id, reason, details)?;
+        let target_user_id = self
+            .find_target_owner(reporter_id, target_type, target_id)
+            .await?;
+        if target_user_id == reporter_id {
+            return Err(AppError::ValidationError(
+                "You cannot report yourself or your own content".to_string(),
+            ));
+        }
+
+        let (case, is_new) = self
+            .moderation_repository
+            .file_report(&report, target_user_id)
+            .await?;
+
+        if is_new
+            && target_type.is_content()
+            && !case.is_hidden
+            && case.report_count >= self.hide_threshold
+        {
+            self.moderation_repository
+                .set_content_hidden(&case, true)
+                .await?;
+            self.notify(
+                &case,
+                "Your content has been hidden",
+                format!(
+                    "Your {} was hidden after several reports and is waiting for review",
+                    case.target_type
+                ),
+            )
+            .await;
+        }
+
+        Ok(is_new)
+    }
+
+    /// Get open cases for a moderator, most reported first
+    pub async fn get_queue(
+        &self,
+        moderator_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ModerationCase>> {
+        self.require_moderator(moderator_id).await?;
+        self.moderation_repository
+            .find_open_cases(limit.clamp(1, 100), offset.max(0))
+            .await
+    }
+
+    /// Get a case with its reports and the actions taken on it
+    pub async fn get_case(
+        &self,
+        moderator_id: Uuid,
+        case_id: Uuid,
+    ) -> Result<ModerationCaseDetail> {
+        self.require_moderator(moderator_id).await?;
+        let case = self.find_case(case_id).await?;
+        let reports = self.moderation_repository.find_reports(case_id).await?;
+        let actions = self.moderation_repository.find_actions(case_id).await?;
+
+        Ok(ModerationCaseDetail {
+            case,
+            reports,
+            actions,
+        })
+    }
+
+    /// Act on a case, record the action and resolve the case.
+    ///
+    /// The affected user is told about every action except a dismissal of
+    /// content that was never hidden.
+    pub async fn take_action(
+        &self,
+        moderator_id: Uuid,
+        case_id: Uuid,
+        action: ModerationActionType,
+        note: Option<String>,
+    ) -> Result<ModerationAction> {
+        self.require_moderator(moderator_id).await?;
+        let case = self.find_case(case_id).await?;
+        let action = ModerationAction::new(case_id, moderator_id, action, note)?;
+
+        match action.action {
+            ModerationActionType::Dismiss => {
+                if case.is_hidden {
+                    self.moderation_repository
+                        .set_content_hidden(&case, false)
+                        .await?;
+                }
+            }
+            ModerationActionType::RemoveContent => self.remove_content(&case).await?,
+            ModerationActionType::Warn => {}
+            ModerationActionType::SuspendUser { until } => {
+                self.moderation_repository
+                    .suspend_user(case.target_user_id, until)
+                    .await?;
+            }
+            ModerationActionType::LockWallet => {
+                let wallet = self
+                    .wallet_repository
+                    .find_by_user_id(case.target_user_id)
+                    .await?
+                    .ok_or_else(|| {
+                        AppError::ValidationError("User does not have a wallet".to_string())
+                    })?;
+                self.wallet_repository.lock_wallet(wallet.id).await?;
+            }
+        }
+
+        self.moderation_repository.record_action(&action).await?;
+
+        match action.action {
+            ModerationActionType::Dismiss if case.is_hidden => {
+                self.notify(
+                    &case,
+                    "Your content has been restored",
+                    format!(
+                        "Your {} was reviewed and is visible again",
+                        case.target_type
+                    ),
+                )
+                .await;
+            }
+            ModerationActionType::Dismiss => {}
+            ModerationActionType::RemoveContent => {
+                self.notify(
+                    &case,
+                    "Your content has been removed",
+                    format!(
+                        "Your {} was removed for breaking the community guidelines",
+                        case.target_type
+                    ),
+                )
+                .await;
+            }
+            ModerationActionType::Warn => {
+                self.notify(
+                    &case,
+                    "You have received a warning",
+                    "A moderator found that you broke the community guidelines".to_string(),
+                )
+                .await;
+            }
+            ModerationActionType::SuspendUser { until } => {
+                self.notify(
+                    &case,
+                    "Your account has been suspended",
+                    format!(
+                        "You can sign in again after {}",
+                        until.format("%Y-%m-%d %H:%M UTC")
+                    ),
+                )
+                .await;
+            }
+            ModerationActionType::LockWallet => {
+                self.notify(
+                    &case,
+                    "Your wallet has been locked",
+                    "Payments from your wallet are paused while a moderator reviews your account"
+                        .to_string(),
+                )
+                .await;
+            }
+        }
+
+        Ok(action)
+    }
+
+    async fn require_moderator(&self, user_id: Uuid) -> Result<()> {
+        if self.moderation_repository.is_moderator(user_id).await? {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden)
+        }
+    }
+
+    async fn find_case(&self, case_id: Uuid) -> Result<ModerationCase> {
+        self.moderation_repository
+            .find_case(case_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Moderation case not found".to_string()))
+    }
+
+    /// Find who a report target belongs to, as long as the reporter can see it
+    async fn find_target_owner(
+        &self,
+        reporter_id: Uuid,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+    ) -> Result<Uuid> {
+        match target_type {
+            ReportTargetType::Post => {
+                let post = self
+                    .visibility_service
+                    .find_visible_post(Some(reporter_id), target_id)
+                    .await?;
+                Ok(post.user_id)
+            }
+            ReportTargetType::Comment => {
+                let comment = self
+                    .comment_repository
+                    .find_by_id(target_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
+                let post = self.post_repository.find_by_id(comment.post_id).await?;
+                let visible = match post {
+                    Some(post) => {
+                        self.visibility_service
+                            .can_view(Some(reporter_id), &post)
+                            .await?
+                    }
+                    None => false,
+                };
+                if !visible {
+                    return Err(AppError::NotFound("Comment not found".to_string()));
+                }
+                Ok(comment.user_id)
+            }
+            ReportTargetType::Message => {
+                let message = self
+                    .message_repository
+                    .find_by_id(target_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Message not found".to_string()))?;
+                if !self
+                    .conversation_repository
+                    .is_participant(message.conversation_id, reporter_id)
+                    .await?
+                {
+                    return Err(AppError::NotFound("Message not found".to_string()));
+                }
+                message.sender_id.ok_or_else(|| {
+                    AppError::ValidationError("System messages cannot be reported".to_string())
+                })
+            }
+            ReportTargetType::User => {
+                let user = self
+                    .user_repository
+                    .find_by_id(target_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+                Ok(user.id)
+            }
+        }
+    }
+
+    async fn remove_content(&self, case: &ModerationCase) -> Result<()> {
+        match case.target_type {
+            ReportTargetType::Post => {
+                let post = self.post_repository.find_by_id(case.target_id).await?;
+                self.post_repository.delete(case.target_id).await?;
+                // Cleaned up as when authors delete their own posts
+                if let Some(post) = post {
+                    self.feed_service.remove_from_timelines(&post);
+                    self.feed_service.invalidate_trending_cache().await?;
+                }
+                Ok(())
+            }
+            ReportTargetType::Comment => {
+                if let Some(comment) = self.comment_repository.find_by_id(case.target_id).await? {
+                    self.comment_repository.delete(comment.id).await?;
+                    self.post_repository
+                        .decrement_comment_count(comment.post_id)
+                        .await?;
+                }
+                Ok(())
+            }
+            ReportTargetType::Message => self.message_repository.delete(case.target_id).await,
+            ReportTargetType::User => Err(AppError::ValidationError(
+                "Reported users cannot be removed; suspend them instead".to_string(),
+            )),
+        }
+    }
+
+    /// Put content the filter flagged in the review queue. The author is not
+    /// told, and the content stays visible until a moderator acts on it.
+    pub async fn flag_for_review(
+        &self,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        target_user_id: Uuid,
+        rules: &[String],
+    ) -> Result<ModerationCase> {
+        self.moderation_repository
+            .flag_for_review(target_type, target_id, target_user_id, rules)
+            .await
+    }
+
+    /// Notifications are best effort; the action itself has already happened
+    async fn notify(&self, case: &ModerationCase, title: &str, body: String) {
+        if let Err(e) = self
+            .notification_service
+            .notify_moderation(case.target_user_id, case, title, body)
+            .await
+        {
+            tracing::warn!(
+                "Failed to send moderation notice for case {}: {}",
+                case.id,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::application::services::ContentFilterService;
+    use crate::domain::entities::{
+        CreateMessageRequest, Message, MessageType, ModerationCaseStatus, NotificationType, Post,
+        User, MAX_SUSPENSION_DAYS,
+    };
+    use crate::domain::value_objects::PageStart;
+    use crate::infrastructure::content_filter::{ContentFilter, FilterField};
+    use chrono::{DateTime, Utc};
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockCommentRepository {
+        comments: Mutex<HashMap<Uuid, crate::domain::entities::Comment>>,
+    }
+
+    #[async_trait::async_trait]
+    impl CommentRepository for MockCommentRepository {
+        async fn create(
+            &self,
+            comment: &crate::domain::entities::Comment,
+        ) -> Result<crate::domain::entities::Comment> {
+            self.comments
+                .lock()
+                .unwrap()
+                .insert(comment.id, comment.clone());
+            Ok(comment.clone())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<crate::domain::entities::Comment>> {
+            Ok(self.comments.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_post(
+            &self,
+            _post_id: Uuid,
+            _page: PageStart,
+            _limit: i64,
+        ) -> Result<Vec<crate::domain::entities::Comment>> {
+            unimplemented!()
+        }
+
+        async fn delete(&self, id: Uuid) -> Result<()> {
+            self.comments.lock().unwrap().remove(&id);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockModerationRepository {
+        moderators: Mutex<HashSet<Uuid>>,
+        cases: Mutex<HashMap<Uuid, ModerationCase>>,
+        /// (case_id, report)
+        reports: Mutex<Vec<(Uuid, Report)>>,
+        actions: Mutex<Vec<ModerationAction>>,
+        suspensions: Mutex<HashMap<Uuid, DateTime<Utc>>>,
+    }
+
+    impl MockModerationRepository {
+        fn case_for(&self, target_id: Uuid) -> Option<ModerationCase> {
+            self.cases
+                .lock()
+                .unwrap()
+                .values()
+                .find(|case| case.target_id == target_id)
+                .cloned()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ModerationRepository for MockModerationRepository {
+        async fn is_moderator(&self, user_id: Uuid) -> Result<bool> {
+            Ok(self.moderators.lock().unwrap().contains(&user_id))
+        }
+
+        async fn file_report(
+            &self,
+            report: &Report,
+            target_user_id: Uuid,
+        ) -> Result<(ModerationCase, bool)> {
+            let mut cases = self.cases.lock().unwrap();
+            let case = match cases.values_mut().find(|case| {
+                case.target_type == report.target_type && case.target_id == report.target_id
+            }) {
+                Some(case) => case,
+                None => {
+                    let case = ModerationCase {
+                        id: Uuid::new_v4(),
+                        target_type: report.target_type,
+                        target_id: report.target_id,
+                        target_user_id,
+                        status: ModerationCaseStatus::Open,
+                        report_count: 0,
+                        is_hidden: false,
+                        flagged_rules: Vec::new(),
+                        created_at: report.created_at,
+                        updated_at: report.created_at,
+                        resolved_at: None,
+                    };
+                    cases.entry(case.id).or_insert(case)
+                }
+            };
+
+            let mut reports = self.reports.lock().unwrap();
+            let is_new = !reports.iter().any(|(_, existing)| {
+                existing.reporter_id == report.reporter_id
+                    && existing.target_type == report.target_type
+                    && existing.target_id == report.target_id
+            });
+            if is_new {
+                reports.push((case.id, report.clone()));
+                case.report_count += 1;
+                case.status = ModerationCaseStatus::Open;
+                case.resolved_at = None;
+            }
+
+            Ok((case.clone(), is_new))
+        }
+
+        async fn find_case(&self, case_id: Uuid) -> Result<Option<ModerationCase>> {
+            Ok(self.cases.lock().unwrap().get(&case_id).cloned())
+        }
+
+        async fn find_open_cases(&self, limit: i64, offset: i64) -> Result<Vec<ModerationCase>> {
+            let mut open: Vec<ModerationCase> = self
+                .cases
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|case| case.is_open())
+                .cloned()
+                .collect();
+            open.sort_by_key(|case| (std::cmp::Reverse(case.report_count), case.created_at));
+            Ok(open
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect())
+        }
+
+        async fn find_reports(&self, case_id: Uuid) -> Result<Vec<Report>> {
+            Ok(self
+                .reports
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(id, _)| *id == case_id)
+                .map(|(_, report)| report.clone())
+                .collect())
+        }
+
+        async fn find_actions(&self, case_id: Uuid) -> Result<Vec<ModerationAction>> {
+            Ok(self
+                .actions
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|action| action.case_id == case_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn set_content_hidden(&self, case: &ModerationCase, hidden: bool) -> Result<()> {
+            if let Some(case) = self.cases.lock().unwrap().get_mut(&case.id) {
+                case.is_hidden = hidden;
+            }
+            Ok(())
+        }
+
+        async fn flag_for_review(
+            &self,
+            target_type: ReportTargetType,
+            target_id: Uuid,
+            target_user_id: Uuid,
+            rules: &[String],
+        ) -> Result<ModerationCase> {
+            let now = Utc::now();
+            let mut cases = self.cases.lock().unwrap();
+            let case = match cases
+                .values_mut()
+                .find(|case| case.target_type == target_type && case.target_id == target_id)
+            {
+                Some(case) => case,
+                None => {
+                    let case = ModerationCase {
+                        id: Uuid::new_v4(),
+                        target_type,
+                        target_id,
+                        target_user_id,
+                        status: ModerationCaseStatus::Open,
+                        report_count: 0,
+                        is_hidden: false,
+                        flagged_rules: Vec::new(),
+                        created_at: now,
+                        updated_at: now,
+                        resolved_at: None,
+                    };
+                    cases.entry(case.id).or_insert(case)
+                }
+            };
+
+            for rule in rules {
+                if !case.flagged_rules.contains(rule) {
+                    case.flagged_rules.push(rule.clone());
+                }
+            }
+            case.status = ModerationCaseStatus::Open;
+            case.resolved_at = None;
+            Ok(case.clone())
+        }
+
+        async fn record_action(&self, action: &ModerationAction) -> Result<()> {
+            self.actions.lock().unwrap().push(action.clone());
+            if let Some(case) = self.cases.lock().unwrap().get_mut(&action.case_id) {
+                case.status = ModerationCaseStatus::Resolved;
+                case.report_count = 0;
+                case.resolved_at = Some(action.created_at);
+            }
+            Ok(())
+        }
+
+        async fn suspend_user(&self, user_id: Uuid, until: DateTime<Utc>) -> Result<()> {
+            self.suspensions.lock().unwrap().insert(user_id, until);
+            Ok(())
+        }
+    }
+
+    /// Moderation service that hides content after two reports
+    fn moderation_service(
+        users: Arc<TestUserRepository>,
+        posts: Arc<MockPostRepository>,
+        moderation: Arc<MockModerationRepository>,
+        conversations: Arc<MockConversationRepository>,
+        messages: Arc<MockMessageRepository>,
+        notifications: Arc<MockNotificationRepository>,
+    ) -> ModerationService {
+        ModerationService::new(
+            moderation,
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            posts.clone(),
+            Arc::new(FeedGenerationService::new(posts, users.clone(), None, None)),
+            Arc::new(MockCommentRepository::default()),
+            conversations,
+            messages,
+            users.clone(),
+            Arc::new(MockWalletRepository),
+            Arc::new(notification_service(users, notifications)),
+            2,
+        )
+    }
+
+    async fn report_post(
+        service: &ModerationService,
+        reporter: &User,
+        post: &Post,
+    ) -> Result<bool> {
+        service
+            .file_report(
+                reporter.id,
+                ReportTargetType::Post,
+                post.id,
+                ReportReason::Spam,
+                None,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_repeated_reports_are_not_counted_and_threshold_hides_content() {
+        let users = Arc::new(TestUserRepository::new());
+        let posts = Arc::new(MockPostRepository::new());
+        let moderation = Arc::new(MockModerationRepository::default());
+        let notifications = Arc::new(MockNotificationRepository::new());
+        let service = moderation_service(
+            users.clone(),
+            posts.clone(),
+            moderation.clone(),
+            Arc::default(),
+            Arc::default(),
+            notifications.clone(),
+        );
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+        let bob = add_test_user(&users, "bob");
+
+        let post = create_test_post(author.id, "Buy followers now", false);
+        posts.add_post(post.clone());
+
+        assert!(report_post(&service, &alice, &post).await.unwrap());
+        assert!(!report_post(&service, &alice, &post).await.unwrap());
+
+        let case = moderation.case_for(post.id).unwrap();
+        assert_eq!(case.report_count, 1);
+        assert!(!case.is_hidden);
+
+        assert!(report_post(&service, &bob, &post).await.unwrap());
+        let case = moderation.case_for(post.id).unwrap();
+        assert_eq!(case.report_count, 2);
+        assert!(case.is_hidden);
+
+        let notifications = notifications.notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].user_id, author.id);
+    }
+
+    #[tokio::test]
+    async fn test_reports_require_a_visible_target_that_is_not_your_own() {
+        let users = Arc::new(TestUserRepository::new());
+        let posts = Arc::new(MockPostRepository::new());
+        let moderation = Arc::new(MockModerationRepository::default());
+        let conversations = Arc::new(MockConversationRepository::default());
+        let messages = Arc::new(MockMessageRepository::default());
+        let service = moderation_service(
+            users.clone(),
+            posts.clone(),
+            moderation.clone(),
+            conversations.clone(),
+            messages.clone(),
+            Arc::new(MockNotificationRepository::new()),
+        );
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+        let bob = add_test_user(&users, "bob");
+
+        let post = create_test_post(author.id, "Mine", false);
+        posts.add_post(post.clone());
+
+        let own = report_post(&service, &author, &post).await;
+        assert!(matches!(own, Err(AppError::ValidationError(_))));
+
+        let mut draft = create_test_post(author.id, "Draft", false);
+        draft.save_as_draft();
+        posts.add_post(draft.clone());
+        let hidden = report_post(&service, &alice, &draft).await;
+        assert!(matches!(hidden, Err(AppError::NotFound(_))));
+
+        // Only participants can report a message
+        let conversation_id = Uuid::new_v4();
+        conversations
+            .create(
+                conversation_id,
+                vec![author.id, alice.id],
+                false,
+                None,
+                author.id,
+            )
+            .await
+            .unwrap();
+        let message = Message::new(CreateMessageRequest {
+            conversation_id,
+            sender_id: Some(author.id),
+            message_type: MessageType::Text,
+            content: Some("Send me your password".to_string()),
+            media_url: None,
+            payment_data: None,
+            reply_to_id: None,
+        })
+        .unwrap();
+        messages.create(&message).await.unwrap();
+
+        let outsider = service
+            .file_report(
+                bob.id,
+                ReportTargetType::Message,
+                message.id,
+                ReportReason::Scam,
+                None,
+            )
+            .await;
+        assert!(matches!(outsider, Err(AppError::NotFound(_))));
+
+        service
+            .file_report(
+                alice.id,
+                ReportTargetType::Message,
+                message.id,
+                ReportReason::Scam,
+                Some("  Phishing  ".to_string()),
+            )
+            .await
+            .unwrap();
+        let case = moderation.case_for(message.id).unwrap();
+        assert_eq!(case.target_user_id, author.id);
+        let reports = moderation.find_reports(case.id).await.unwrap();
+        assert_eq!(reports[0].details.as_deref(), Some("Phishing"));
+    }
+
+    #[tokio::test]
+    async fn test_only_moderators_can_work_the_queue() {
+        let users = Arc::new(TestUserRepository::new());
+        let posts = Arc::new(MockPostRepository::new());
+        let moderation = Arc::new(MockModerationRepository::default());
+        let service = moderation_service(
+            users.clone(),
+            posts.clone(),
+            moderation.clone(),
+            Arc::default(),
+            Arc::default(),
+            Arc::new(MockNotificationRepository::new()),
+        );
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+        let moderator = add_test_user(&users, "moderator");
+        moderation.moderators.lock().unwrap().insert(moderator.id);
+
+        let post = create_test_post(author.id, "Reported", false);
+        posts.add_post(post.clone());
+        report_post(&service, &alice, &post).await.unwrap();
+        let case = moderation.case_for(post.id).unwrap();
+
+        let queue = service.get_queue(alice.id, 20, 0).await;
+        assert!(matches!(queue, Err(AppError::Forbidden)));
+        let action = service
+            .take_action(alice.id, case.id, ModerationActionType::Warn, None)
+            .await;
+        assert!(matches!(action, Err(AppError::Forbidden)));
+
+        let queue = service.get_queue(moderator.id, 20, 0).await.unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].id, case.id);
+    }
+
+    #[tokio::test]
+    async fn test_dismiss_restores_hidden_content_and_resolves_case() {
+        let users = Arc::new(TestUserRepository::new());
+        let posts = Arc::new(MockPostRepository::new());
+        let moderation = Arc::new(MockModerationRepository::default());
+        let notifications = Arc::new(MockNotificationRepository::new());
+        let service = moderation_service(
+            users.clone(),
+            posts.clone(),
+            moderation.clone(),
+            Arc::default(),
+            Arc::default(),
+            notifications.clone(),
+        );
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+        let bob = add_test_user(&users, "bob");
+        let carol = add_test_user(&users, "carol");
+        let moderator = add_test_user(&users, "moderator");
+        moderation.moderators.lock().unwrap().insert(moderator.id);
+
+        let post = create_test_post(author.id, "Borderline", false);
+        posts.add_post(post.clone());
+        report_post(&service, &alice, &post).await.unwrap();
+        report_post(&service, &bob, &post).await.unwrap();
+        let case = moderation.case_for(post.id).unwrap();
+        assert!(case.is_hidden);
+
+        service
+            .take_action(
+                moderator.id,
+                case.id,
+                ModerationActionType::Dismiss,
+                Some("Satire".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let case = moderation.case_for(post.id).unwrap();
+        assert!(!case.is_hidden);
+        assert!(!case.is_open());
+        assert_eq!(case.report_count, 0);
+        assert!(service
+            .get_queue(moderator.id, 20, 0)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let detail = service.get_case(moderator.id, case.id).await.unwrap();
+        assert_eq!(detail.reports.len(), 2);
+        assert_eq!(detail.actions.len(), 1);
+        assert_eq!(detail.actions[0].note.as_deref(), Some("Satire"));
+
+        // Hidden, then restored
+        assert_eq!(notifications.notifications.lock().unwrap().len(), 2);
+
+        // A new reporter reopens the case
+        report_post(&service, &carol, &post).await.unwrap();
+        let case = moderation.case_for(post.id).unwrap();
+        assert!(case.is_open());
+        assert_eq!(case.report_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_remove_content_and_suspend_user() {
+        let users = Arc::new(TestUserRepository::new());
+        let posts = Arc::new(MockPostRepository::new());
+        let moderation = Arc::new(MockModerationRepository::default());
+        let notifications = Arc::new(MockNotificationRepository::new());
+        let service = moderation_service(
+            users.clone(),
+            posts.clone(),
+            moderation.clone(),
+            Arc::default(),
+            Arc::default(),
+            notifications.clone(),
+        );
+        let author = add_test_user(&users, "author");
+        let alice = add_test_user(&users, "alice");
+        let moderator = add_test_user(&users, "moderator");
+        moderation.moderators.lock().unwrap().insert(moderator.id);
+
+        let post = create_test_post(author.id, "Abuse", false);
+        posts.add_post(post.clone());
+        report_post(&service, &alice, &post).await.unwrap();
+        let case = moderation.case_for(post.id).unwrap();
+
+        service
+            .take_action(
+                moderator.id,
+                case.id,
+                ModerationActionType::RemoveContent,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(posts.find_by_id(post.id).await.unwrap().is_none());
+
+        let until = Utc::now() + chrono::Duration::days(7);
+        service
+            .take_action(
+                moderator.id,
+                case.id,
+                ModerationActionType::SuspendUser { until },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            moderation.suspensions.lock().unwrap().get(&author.id),
+            Some(&until)
+        );
+        assert_eq!(moderation.find_actions(case.id).await.unwrap().len(), 2);
+
+        let notifications = notifications.notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 2);
+        assert!(notifications
+            .iter()
+            .all(|notification| notification.user_id == author.id
+                && notification.notification_type == NotificationType::System));
+    }
+
+    #[tokio::test]
+    async fn test_filter_flags_reopen_a_case_without_notifying() {
+        use crate::infrastructure::content_filter::{FilterAction, FilterRule, FilterRuleSet};
+
+        let users = Arc::new(TestUserRepository::new());
+        let moderation = Arc::new(MockModerationRepository::default());
+        let notifications = Arc::new(MockNotificationRepository::new());
+        let service = moderation_service(
+            users.clone(),
+            Arc::new(MockPostRepository::new()),
+            moderation.clone(),
+            Arc::default(),
+            Arc::default(),
+            notifications.clone(),
+        );
+        let author_id = add_test_user(&users, "author").id;
+
+        let filter = ContentFilter::new(FilterRuleSet {
+            rules: vec![FilterRule {
+                name: "scams".to_string(),
+                action: FilterAction::Flag,
+                words: vec!["free crypto".to_string()],
+                domains: Vec::new(),
+                fields: Vec::new(),
+            }],
+        })
+        .unwrap();
+        let service = ContentFilterService::new(Arc::new(filter), Arc::new(service));
+
+        let screened = service
+            .screen(FilterField::Comment, "Free crypto for everyone")
+            .unwrap();
+        assert_eq!(screened.flagged_rules, vec!["scams".to_string()]);
+
+        let comment_id = Uuid::new_v4();
+        service
+            .flag(
+                &screened.flagged_rules,
+                ReportTargetType::Comment,
+                comment_id,
+                author_id,
+            )
+            .await;
+        service
+            .flag(
+                &screened.flagged_rules,
+                ReportTargetType::Comment,
+                comment_id,
+                author_id,
+            )
+            .await;
+
+        let case = moderation.case_for(comment_id).unwrap();
+        assert!(case.is_open());
+        assert!(!case.is_hidden);
+        assert_eq!(case.report_count, 0);
+        assert_eq!(case.flagged_rules, vec!["scams".to_string()]);
+        assert!(notifications.notifications.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_suspension_length_is_bounded() {
+        let now = Utc::now();
+        assert!(ModerationActionType::suspend_for(0, now).is_err());
+        assert!(ModerationActionType::suspend_for(MAX_SUSPENSION_DAYS + 1, now).is_err());
+        assert_eq!(
+            ModerationActionType::suspend_for(3, now).unwrap(),
+            ModerationActionType::SuspendUser {
+                until: now + chrono::Duration::days(3)
+            }
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Pinning posts to the top of their author's profile.
+use super::PostVisibilityService;
+use crate::domain::entities::{Post, MAX_PINNED_POSTS};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::PostRepository;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Pinned post service for the posts creators feature on their profile
+pub struct PinnedPostService {
+    post_repository: Arc<dyn PostRepository>,
+    visibility_service: Arc<PostVisibilityService>,
+}
+
+impl PinnedPostService {
+    pub fn new(
+        post_repository: Arc<dyn PostRepository>,
+        visibility_service: Arc<PostVisibilityService>,
+    ) -> Self {
+        Self {
+            post_repository,
+            visibility_service,
+        }
+    }
+
+    /// Pin one of the user's published posts. Pinning a post that is already
+    /// pinned leaves it where it is.
+    pub async fn pin_post(&self, user_id: Uuid, post_id: Uuid) -> Result<Post> {
+        let mut post = self.get_owned_post(user_id, post_id).await?;
+        if post.is_pinned() {
+            return Ok(post);
+        }
+
+        if !post.is_published() || post.is_hidden {
+            return Err(AppError::ValidationError(
+                "Only published posts can be pinned".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        if !self
+            .post_repository
+            .pin(post.id, user_id, MAX_PINNED_POSTS, now)
+            .await?
+        {
+            return Err(AppError::ValidationError(format!(
+                "You can pin at most {} posts",
+                MAX_PINNED_POSTS
+            )));
+        }
+
+        post.pinned_at = Some(now);
+        Ok(post)
+    }
+
+    /// Unpin one of the user's posts
+    pub async fn unpin_post(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+        self.get_owned_post(user_id, post_id).await?;
+        self.post_repository.unpin(post_id).await
+    }
+
+    /// Get the posts on a user's profile that the viewer may see, pinned posts first
+    pub async fn get_profile_posts(
+        &self,
+        viewer_id: Option<Uuid>,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Post>> {
+        let posts = self
+            .post_repository
+            .find_by_user_id(user_id, limit, offset)
+            .await?;
+
+        self.visibility_service
+            .filter_visible(viewer_id, posts)
+            .await
+    }
+
+    async fn get_owned_post(&self, user_id: Uuid, post_id: Uuid) -> Result<Post> {
+        self.post_repository
+            .find_by_id(post_id)
+            .await?
+            .filter(|post| post.user_id == user_id)
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::domain::entities::PostStatus;
+
+    fn setup() -> (PinnedPostService, Arc<MockPostRepository>) {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let visibility = Arc::new(PostVisibilityService::new(posts.clone(), users));
+        (PinnedPostService::new(posts.clone(), visibility), posts)
+    }
+
+    #[tokio::test]
+    async fn test_pinned_posts_come_first_up_to_the_limit() {
+        let (service, posts) = setup();
+        let author = Uuid::new_v4();
+        let created: Vec<Post> = (0..5)
+            .map(|i| post_at(author, "On my profile", i))
+            .collect();
+        for post in &created {
+            posts.add_post(post.clone());
+        }
+
+        // Pin the three oldest posts
+        for post in created.iter().rev().take(3) {
+            let pinned = service.pin_post(author, post.id).await.unwrap();
+            assert!(pinned.is_pinned());
+        }
+
+        let result = service.pin_post(author, created[0].id).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        // Pinning an already pinned post is not an error
+        assert!(service.pin_post(author, created[4].id).await.is_ok());
+
+        let profile = service
+            .get_profile_posts(None, author, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(profile.len(), 5);
+        assert!(profile[..3].iter().all(|p| p.is_pinned()));
+        assert_eq!(profile[3].id, created[0].id);
+        assert_eq!(profile[4].id, created[1].id);
+
+        service.unpin_post(author, created[4].id).await.unwrap();
+        assert!(service.pin_post(author, created[0].id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_only_the_author_can_pin_published_posts() {
+        let (service, posts) = setup();
+        let author = Uuid::new_v4();
+        let post = post_at(author, "On my profile", 0);
+        let mut draft = post_at(author, "On my profile", 1);
+        draft.status = PostStatus::Draft;
+        posts.add_post(post.clone());
+        posts.add_post(draft.clone());
+
+        let result = service.pin_post(Uuid::new_v4(), post.id).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = service.unpin_post(Uuid::new_v4(), post.id).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = service.pin_post(author, draft.id).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,492 @@
+//! Voting in polls and notifying voters once a poll closes.
+use super::{NotificationService, PostVisibilityService, SCHEDULER_BATCH_SIZE};
+use crate::domain::entities::{Poll, Post};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{PollRepository, PostRepository};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Poll service for voting and announcing closed polls
+pub struct PollService {
+    poll_repository: Arc<dyn PollRepository>,
+    post_repository: Arc<dyn PostRepository>,
+    visibility_service: Arc<PostVisibilityService>,
+    notification_service: Arc<NotificationService>,
+}
+
+/// A poll as seen by one user
+#[derive(Debug)]
+pub struct PollView {
+    pub poll: Poll,
+    pub user_vote: Option<i32>,
+}
+
+impl PollView {
+    /// Results stay hidden until the user has voted or the poll has closed
+    pub fn results_visible(&self) -> bool {
+        self.user_vote.is_some() || self.poll.is_closed()
+    }
+}
+
+impl PollService {
+    pub fn new(
+        poll_repository: Arc<dyn PollRepository>,
+        post_repository: Arc<dyn PostRepository>,
+        visibility_service: Arc<PostVisibilityService>,
+        notification_service: Arc<NotificationService>,
+    ) -> Self {
+        Self {
+            poll_repository,
+            post_repository,
+            visibility_service,
+            notification_service,
+        }
+    }
+
+    /// Save a poll post together with its poll, removing the post again if the poll fails
+    pub async fn create_poll_post(&self, post: &Post, poll: &Poll) -> Result<(Post, Poll)> {
+        let created_post = self.post_repository.create(post).await?;
+
+        match self.poll_repository.create(poll).await {
+            Ok(created_poll) => Ok((created_post, created_poll)),
+            Err(e) => {
+                self.post_repository.delete(created_post.id).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Get the poll on a post the user can see
+    pub async fn get_poll(&self, viewer_id: Uuid, post_id: Uuid) -> Result<PollView> {
+        self.visibility_service
+            .find_visible_post(Some(viewer_id), post_id)
+            .await?;
+
+        self.find_poll_view(Some(viewer_id), post_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Poll not found".to_string()))
+    }
+
+    /// Get a poll with the viewer's vote, without checking post visibility
+    pub async fn find_poll_view(
+        &self,
+        viewer_id: Option<Uuid>,
+        post_id: Uuid,
+    ) -> Result<Option<PollView>> {
+        let poll = match self.poll_repository.find_by_post_id(post_id).await? {
+            Some(poll) => poll,
+            None => return Ok(None),
+        };
+
+        let user_vote = match viewer_id {
+            Some(viewer_id) => {
+                self.poll_repository
+                    .find_user_vote(post_id, viewer_id)
+                    .await?
+            }
+            None => None,
+        };
+
+        Ok(Some(PollView { poll, user_vote }))
+    }
+
+    /// Get the polls of several posts with the viewer's votes, keyed by post ID,
+    /// without checking post visibility
+    pub async fn find_poll_views(
+        &self,
+        viewer_id: Option<Uuid>,
+        post_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, PollView>> {
+        if post_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let polls = self.poll_repository.find_by_post_ids(post_ids).await?;
+        let votes = match viewer_id {
+            Some(viewer_id) => {
+                self.poll_repository
+                    .find_user_votes(post_ids, viewer_id)
+                    .await?
+            }
+            None => HashMap::new(),
+        };
+
+        Ok(polls
+            .into_iter()
+            .map(|poll| {
+                let user_vote = votes.get(&poll.post_id).copied();
+                (poll.post_id, PollView { poll, user_vote })
+            })
+            .collect())
+    }
+
+    /// Cast a user's single vote in a poll
+    pub async fn vote(&self, user_id: Uuid, post_id: Uuid, option_index: i32) -> Result<PollView> {
+        self.visibility_service
+            .find_visible_post(Some(user_id), post_id)
+            .await?;
+
+        let poll = self
+            .poll_repository
+            .find_by_post_id(post_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Poll not found".to_string()))?;
+
+        if poll.is_closed() {
+            return Err(AppError::ValidationError("Poll is closed".to_string()));
+        }
+        poll.validate_option(option_index)?;
+
+        let poll = self
+            .poll_repository
+            .vote(post_id, user_id, option_index)
+            .await?;
+
+        Ok(PollView {
+            poll,
+            user_vote: Some(option_index),
+        })
+    }
+
+    /// Notify authors of polls that have closed since the last run
+    pub async fn notify_closed_polls(&self, now: DateTime<Utc>) -> Result<usize> {
+        let closed = self
+            .poll_repository
+            .find_closed_unnotified(now, SCHEDULER_BATCH_SIZE)
+            .await?;
+
+        let mut notified = 0;
+        for poll in closed {
+            // Another run may have claimed this poll already
+            if !self
+                .poll_repository
+                .mark_closed_notified(poll.post_id)
+                .await?
+            {
+                continue;
+            }
+
+            let post = match self.post_repository.find_by_id(poll.post_id).await? {
+                Some(post) => post,
+                None => continue,
+            };
+
+            match self
+                .notification_service
+                .notify_poll_closed(post.user_id, post.id, poll.total_votes())
+                .await
+            {
+                Ok(()) => notified += 1,
+                // Poll notifications disabled in the author's preferences
+                Err(AppError::ValidationError(_)) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to send poll closed notification for post {}: {}",
+                    post.id,
+                    e
+                ),
+            }
+        }
+
+        Ok(notified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::application::services::PostHydrationService;
+    use crate::domain::entities::{
+        CreatePollRequest, CreatePostRequest, NotificationType, PostVisibility,
+    };
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    struct MockPollRepository {
+        polls: Mutex<HashMap<Uuid, Poll>>,
+        votes: Mutex<HashMap<(Uuid, Uuid), i32>>,
+        notified: Mutex<Vec<Uuid>>,
+    }
+
+    impl MockPollRepository {
+        fn new() -> Self {
+            Self {
+                polls: Mutex::new(HashMap::new()),
+                votes: Mutex::new(HashMap::new()),
+                notified: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PollRepository for MockPollRepository {
+        async fn create(&self, poll: &Poll) -> Result<Poll> {
+            self.polls
+                .lock()
+                .unwrap()
+                .insert(poll.post_id, poll.clone());
+            Ok(poll.clone())
+        }
+
+        async fn find_by_post_id(&self, post_id: Uuid) -> Result<Option<Poll>> {
+            Ok(self.polls.lock().unwrap().get(&post_id).cloned())
+        }
+
+        async fn find_by_post_ids(&self, post_ids: &[Uuid]) -> Result<Vec<Poll>> {
+            let polls = self.polls.lock().unwrap();
+            Ok(post_ids
+                .iter()
+                .filter_map(|id| polls.get(id).cloned())
+                .collect())
+        }
+
+        async fn vote(&self, post_id: Uuid, user_id: Uuid, option_index: i32) -> Result<Poll> {
+            let mut votes = self.votes.lock().unwrap();
+            if votes.contains_key(&(post_id, user_id)) {
+                return Err(AppError::Conflict("Already voted in this poll".to_string()));
+            }
+            votes.insert((post_id, user_id), option_index);
+
+            let mut polls = self.polls.lock().unwrap();
+            let poll = polls.get_mut(&post_id).unwrap();
+            poll.vote_counts[option_index as usize] += 1;
+            Ok(poll.clone())
+        }
+
+        async fn find_user_vote(&self, post_id: Uuid, user_id: Uuid) -> Result<Option<i32>> {
+            Ok(self.votes.lock().unwrap().get(&(post_id, user_id)).copied())
+        }
+
+        async fn find_user_votes(
+            &self,
+            post_ids: &[Uuid],
+            user_id: Uuid,
+        ) -> Result<HashMap<Uuid, i32>> {
+            let votes = self.votes.lock().unwrap();
+            Ok(post_ids
+                .iter()
+                .filter_map(|id| votes.get(&(*id, user_id)).map(|vote| (*id, *vote)))
+                .collect())
+        }
+
+        async fn find_closed_unnotified(
+            &self,
+            now: DateTime<Utc>,
+            _limit: i64,
+        ) -> Result<Vec<Poll>> {
+            let notified = self.notified.lock().unwrap();
+            Ok(self
+                .polls
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|p| p.closes_at <= now && !notified.contains(&p.post_id))
+                .cloned()
+                .collect())
+        }
+
+        async fn mark_closed_notified(&self, post_id: Uuid) -> Result<bool> {
+            let mut notified = self.notified.lock().unwrap();
+            if notified.contains(&post_id) {
+                return Ok(false);
+            }
+            notified.push(post_id);
+            Ok(true)
+        }
+    }
+
+    async fn create_poll_post(service: &PollService, author_id: Uuid) -> (Post, Poll) {
+        let poll_request = CreatePollRequest {
+            options: vec!["Tabs".to_string(), "Spaces".to_string()],
+            closes_at: Utc::now() + chrono::Duration::days(1),
+        };
+        let post = Post::new(CreatePostRequest {
+            user_id: author_id,
+            text_content: Some("Tabs or spaces?".to_string()),
+            media_attachments: vec![],
+            is_reel: false,
+            visibility: PostVisibility::Public,
+            poll: Some(poll_request.clone()),
+        })
+        .unwrap();
+        let poll = Poll::new(post.id, poll_request).unwrap();
+
+        service.create_poll_post(&post, &poll).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_vote_once_and_reveal_results() {
+        let polls = Arc::new(MockPollRepository::new());
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let service = PollService::new(
+            polls.clone(),
+            posts.clone(),
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            Arc::new(notification_service(
+                users.clone(),
+                Arc::new(MockNotificationRepository::new()),
+            )),
+        );
+
+        let author = add_test_user(&users, "author");
+        let voter = add_test_user(&users, "voter");
+        let (post, _) = create_poll_post(&service, author.id).await;
+
+        let view = service.get_poll(voter.id, post.id).await.unwrap();
+        assert!(!view.results_visible());
+
+        let result = service.vote(voter.id, post.id, 5).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let view = service.vote(voter.id, post.id, 1).await.unwrap();
+        assert!(view.results_visible());
+        assert_eq!(view.user_vote, Some(1));
+        assert_eq!(view.poll.vote_counts, vec![0, 1]);
+
+        let result = service.vote(voter.id, post.id, 0).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+        assert_eq!(
+            polls
+                .find_by_post_id(post.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .total_votes(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vote_hidden_from_blocked_user() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let service = PollService::new(
+            Arc::new(MockPollRepository::new()),
+            posts.clone(),
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            Arc::new(notification_service(
+                users.clone(),
+                Arc::new(MockNotificationRepository::new()),
+            )),
+        );
+
+        let author = add_test_user(&users, "author");
+        let blocked = add_test_user(&users, "blocked");
+        users.block(author.id, blocked.id);
+        let (post, _) = create_poll_post(&service, author.id).await;
+
+        let result = service.vote(blocked.id, post.id, 0).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_closed_poll_notifies_author_once() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let notifications = Arc::new(MockNotificationRepository::new());
+        let service = PollService::new(
+            Arc::new(MockPollRepository::new()),
+            posts.clone(),
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            Arc::new(notification_service(users.clone(), notifications.clone())),
+        );
+
+        let author = add_test_user(&users, "author");
+        let voter = add_test_user(&users, "voter");
+        let (post, _) = create_poll_post(&service, author.id).await;
+        service.vote(voter.id, post.id, 0).await.unwrap();
+
+        let later = Utc::now() + chrono::Duration::days(2);
+        assert_eq!(service.notify_closed_polls(later).await.unwrap(), 1);
+        assert_eq!(service.notify_closed_polls(later).await.unwrap(), 0);
+
+        let notifications = notifications.notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].user_id, author.id);
+        assert_eq!(
+            notifications[0].notification_type,
+            NotificationType::PollClosed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hydrate_loads_authors_likes_and_polls() {
+        let polls = Arc::new(MockPollRepository::new());
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let poll_service = Arc::new(PollService::new(
+            polls,
+            posts.clone(),
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            Arc::new(notification_service(
+                users.clone(),
+                Arc::new(MockNotificationRepository::new()),
+            )),
+        ));
+        let hydration =
+            PostHydrationService::new(posts.clone(), users.clone(), poll_service.clone());
+
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let poll_request = CreatePollRequest {
+            options: vec!["Tabs".to_string(), "Spaces".to_string()],
+            closes_at: Utc::now() + chrono::Duration::days(1),
+        };
+        let poll_post = Post::new(CreatePostRequest {
+            user_id: author.id,
+            text_content: Some("Tabs or spaces?".to_string()),
+            media_attachments: vec![],
+            is_reel: false,
+            visibility: PostVisibility::Public,
+            poll: Some(poll_request.clone()),
+        })
+        .unwrap();
+        let poll = Poll::new(poll_post.id, poll_request).unwrap();
+        poll_service
+            .create_poll_post(&poll_post, &poll)
+            .await
+            .unwrap();
+        poll_service.vote(viewer.id, poll_post.id, 0).await.unwrap();
+
+        let text_post = |user_id| {
+            Post::new(CreatePostRequest {
+                user_id,
+                text_content: Some("Hello".to_string()),
+                media_attachments: vec![],
+                is_reel: false,
+                visibility: PostVisibility::Public,
+                poll: None,
+            })
+            .unwrap()
+        };
+        let liked = text_post(author.id);
+        let orphaned = text_post(Uuid::new_v4());
+        posts.like_post(viewer.id, liked.id).await.unwrap();
+
+        let hydrated = hydration
+            .hydrate(
+                Some(viewer.id),
+                vec![liked.clone(), orphaned, poll_post.clone()],
+            )
+            .await
+            .unwrap();
+
+        // The post without an author is dropped and the order is kept
+        assert_eq!(hydrated.len(), 2);
+        assert_eq!(hydrated[0].post.id, liked.id);
+        assert!(hydrated[0].is_liked);
+        assert!(hydrated[0].poll.is_none());
+        assert_eq!(hydrated[1].post.id, poll_post.id);
+        assert_eq!(hydrated[1].author.id, author.id);
+        assert!(!hydrated[1].is_liked);
+        assert_eq!(hydrated[1].poll.as_ref().unwrap().user_vote, Some(0));
+
+        let anonymous = hydration.hydrate(None, vec![poll_post]).await.unwrap();
+        assert_eq!(anonymous[0].poll.as_ref().unwrap().user_vote, None);
+    }
+}
@@ -0,0 +1,435 @@
+//! Recording video views, deduplicated per viewer, and rolling them up into
+//! view counts.
+use super::PostVisibilityService;
+use crate::domain::entities::PostViewEvent;
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{PendingViewRepository, PostViewRepository, UserRepository};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Post view service for ingesting video view events and rolling them up
+pub struct PostViewService {
+    visibility_service: Arc<PostVisibilityService>,
+    user_repository: Arc<dyn UserRepository>,
+    pending_repository: Arc<dyn PendingViewRepository>,
+    view_repository: Arc<dyn PostViewRepository>,
+}
+
+/// A viewer's repeat views of a post within this window count only once
+const VIEW_DEDUP_WINDOW_SECS: u64 = 30 * 60;
+
+impl PostViewService {
+    pub fn new(
+        visibility_service: Arc<PostVisibilityService>,
+        user_repository: Arc<dyn UserRepository>,
+        pending_repository: Arc<dyn PendingViewRepository>,
+        view_repository: Arc<dyn PostViewRepository>,
+    ) -> Self {
+        Self {
+            visibility_service,
+            user_repository,
+            pending_repository,
+            view_repository,
+        }
+    }
+
+    /// Record one viewing session of a video post the viewer may see.
+    ///
+    /// Returns whether the session counted as a new view. Only counted
+    /// sessions add watch time, completion and replays; authors watching
+    /// their own posts are never counted.
+    pub async fn record_view(
+        &self,
+        viewer_id: Uuid,
+        post_id: Uuid,
+        watch_time_ms: i64,
+        completion_percent: f64,
+        replays: i32,
+    ) -> Result<bool> {
+        let mut event = PostViewEvent::new(
+            post_id,
+            viewer_id,
+            watch_time_ms,
+            completion_percent,
+            replays,
+        )?;
+
+        let post = self
+            .visibility_service
+            .find_visible_post(Some(viewer_id), post_id)
+            .await?;
+
+        if !post.has_playable_video() {
+            return Err(AppError::ValidationError(
+                "Post has no video to view".to_string(),
+            ));
+        }
+
+        if post.user_id == viewer_id {
+            return Ok(false);
+        }
+
+        event.from_follower = self
+            .user_repository
+            .is_following(viewer_id, post.user_id)
+            .await?;
+
+        self.pending_repository
+            .record(&event, VIEW_DEDUP_WINDOW_SECS)
+            .await
+    }
+
+    /// Roll up pending view counters for up to `limit` posts into their
+    /// stored totals, counting them towards today's insights. Counters that
+    /// could not be written are put back for the next run.
+    pub async fn flush_pending_views(&self, now: DateTime<Utc>, limit: usize) -> Result<usize> {
+        let pending = self.pending_repository.take_pending(limit).await?;
+
+        for (index, delta) in pending.iter().enumerate() {
+            if let Err(e) = self.view_repository.apply(delta, now.date_naive()).await {
+                for unapplied in &pending[index..] {
+                    if let Err(restore_err) = self.pending_repository.restore(unapplied).await {
+                        tracing::error!(
+                            "Lost pending views for post {}: {}",
+                            unapplied.post_id,
+                            restore_err
+                        );
+                    }
+                }
+                return Err(e);
+            }
+        }
+
+        Ok(pending.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::calculate_engagement_score;
+    use crate::application::services::test_support::*;
+    use crate::domain::entities::PostViewStats;
+    use chrono::NaiveDate;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+
+    fn add_stats(stats_by_post: &mut HashMap<Uuid, PostViewStats>, delta: &PostViewStats) {
+        let stats = stats_by_post
+            .entry(delta.post_id)
+            .or_insert_with(|| PostViewStats {
+                post_id: delta.post_id,
+                ..Default::default()
+            });
+        stats.impression_count += delta.impression_count;
+        stats.view_count += delta.view_count;
+        stats.replay_count += delta.replay_count;
+        stats.completed_count += delta.completed_count;
+        stats.total_watch_ms += delta.total_watch_ms;
+        stats.completion_percent_sum += delta.completion_percent_sum;
+        stats.reach_count += delta.reach_count;
+        stats.follower_impression_count += delta.follower_impression_count;
+        stats.follower_reach_count += delta.follower_reach_count;
+    }
+
+    #[derive(Default)]
+    struct MockPendingViewRepository {
+        seen: Mutex<HashSet<(Uuid, Uuid)>>,
+        reached: Mutex<HashSet<(Uuid, Uuid, NaiveDate)>>,
+        pending: Mutex<HashMap<Uuid, PostViewStats>>,
+    }
+
+    impl MockPendingViewRepository {
+        fn pending_for(&self, post_id: Uuid) -> Option<PostViewStats> {
+            self.pending.lock().unwrap().get(&post_id).cloned()
+        }
+
+        fn add(&self, delta: &PostViewStats) {
+            let mut pending = self.pending.lock().unwrap();
+            add_stats(&mut pending, delta);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PendingViewRepository for MockPendingViewRepository {
+        async fn record(&self, event: &PostViewEvent, _dedup_window_secs: u64) -> Result<bool> {
+            let counted = self
+                .seen
+                .lock()
+                .unwrap()
+                .insert((event.post_id, event.viewer_id));
+            let reached = self.reached.lock().unwrap().insert((
+                event.post_id,
+                event.viewer_id,
+                event.occurred_at.date_naive(),
+            ));
+
+            self.add(&PostViewStats::for_event(event, counted, reached));
+
+            Ok(counted)
+        }
+
+        async fn take_pending(&self, limit: usize) -> Result<Vec<PostViewStats>> {
+            let mut pending = self.pending.lock().unwrap();
+            let post_ids: Vec<Uuid> = pending.keys().take(limit).copied().collect();
+            Ok(post_ids
+                .iter()
+                .filter_map(|post_id| pending.remove(post_id))
+                .collect())
+        }
+
+        async fn restore(&self, stats: &PostViewStats) -> Result<()> {
+            self.add(stats);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockPostViewRepository {
+        totals: Mutex<HashMap<Uuid, PostViewStats>>,
+        days: Mutex<Vec<NaiveDate>>,
+        fail: Mutex<bool>,
+    }
+
+    #[async_trait::async_trait]
+    impl PostViewRepository for MockPostViewRepository {
+        async fn apply(&self, delta: &PostViewStats, day: NaiveDate) -> Result<()> {
+            if *self.fail.lock().unwrap() {
+                return Err(AppError::DatabaseError("Connection lost".to_string()));
+            }
+
+            self.days.lock().unwrap().push(day);
+            let mut totals = self.totals.lock().unwrap();
+            add_stats(&mut totals, delta);
+            Ok(())
+        }
+
+        async fn find_by_post(&self, post_id: Uuid) -> Result<Option<PostViewStats>> {
+            Ok(self.totals.lock().unwrap().get(&post_id).cloned())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_view_deduplicates_views_but_keeps_impressions() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let pending = Arc::new(MockPendingViewRepository::default());
+        let service = PostViewService::new(
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            users.clone(),
+            pending.clone(),
+            Arc::new(MockPostViewRepository::default()),
+        );
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let reel = create_test_post(author.id, "Reel", true);
+        posts.add_post(reel.clone());
+
+        assert!(service
+            .record_view(viewer.id, reel.id, 30_000, 100.0, 1)
+            .await
+            .unwrap());
+        assert!(!service
+            .record_view(viewer.id, reel.id, 10_000, 40.0, 0)
+            .await
+            .unwrap());
+
+        let stats = pending.pending_for(reel.id).unwrap();
+        assert_eq!(stats.impression_count, 2);
+        assert_eq!(stats.view_count, 1);
+        assert_eq!(stats.replay_count, 1);
+        assert_eq!(stats.completed_count, 1);
+        assert_eq!(stats.avg_watch_ms(), 30_000);
+        assert_eq!(stats.avg_completion_percent(), 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_sessions_do_not_inflate_watch_metrics() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let pending = Arc::new(MockPendingViewRepository::default());
+        let service = PostViewService::new(
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            users.clone(),
+            pending.clone(),
+            Arc::new(MockPostViewRepository::default()),
+        );
+        let author = add_test_user(&users, "author");
+        let skipper = add_test_user(&users, "skipper");
+        let watcher = add_test_user(&users, "watcher");
+
+        let reel = create_test_post(author.id, "Reel", true);
+        posts.add_post(reel.clone());
+
+        service
+            .record_view(skipper.id, reel.id, 2_000, 10.0, 0)
+            .await
+            .unwrap();
+        for _ in 0..5 {
+            assert!(!service
+                .record_view(skipper.id, reel.id, 60_000, 100.0, 3)
+                .await
+                .unwrap());
+        }
+        service
+            .record_view(watcher.id, reel.id, 20_000, 50.0, 0)
+            .await
+            .unwrap();
+
+        let stats = pending.pending_for(reel.id).unwrap();
+        assert_eq!(stats.impression_count, 7);
+        assert_eq!(stats.view_count, 2);
+        assert_eq!(stats.replay_count, 0);
+        assert_eq!(stats.completed_count, 0);
+        assert_eq!(stats.total_watch_ms, 22_000);
+        assert_eq!(stats.avg_completion_percent(), 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_record_view_rejects_invalid_events() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let pending = Arc::new(MockPendingViewRepository::default());
+        let service = PostViewService::new(
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            users.clone(),
+            pending.clone(),
+            Arc::new(MockPostViewRepository::default()),
+        );
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let reel = create_test_post(author.id, "Reel", true);
+        let text_post = create_test_post(author.id, "Text", false);
+        posts.add_post(reel.clone());
+        posts.add_post(text_post.clone());
+
+        let result = service
+            .record_view(viewer.id, reel.id, 1_000, 120.0, 0)
+            .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = service
+            .record_view(viewer.id, text_post.id, 1_000, 50.0, 0)
+            .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = service
+            .record_view(viewer.id, Uuid::new_v4(), 1_000, 50.0, 0)
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        assert!(pending.pending.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_author_views_are_not_counted() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let pending = Arc::new(MockPendingViewRepository::default());
+        let service = PostViewService::new(
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            users.clone(),
+            pending.clone(),
+            Arc::new(MockPostViewRepository::default()),
+        );
+        let author = add_test_user(&users, "author");
+
+        let reel = create_test_post(author.id, "Reel", true);
+        posts.add_post(reel.clone());
+
+        assert!(!service
+            .record_view(author.id, reel.id, 30_000, 100.0, 0)
+            .await
+            .unwrap());
+        assert!(pending.pending_for(reel.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_flush_pending_views_rolls_up_and_restores_on_failure() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let pending = Arc::new(MockPendingViewRepository::default());
+        let views = Arc::new(MockPostViewRepository::default());
+        let service = PostViewService::new(
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            users.clone(),
+            pending.clone(),
+            views.clone(),
+        );
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let reel = create_test_post(author.id, "Reel", true);
+        posts.add_post(reel.clone());
+        service
+            .record_view(viewer.id, reel.id, 15_000, 50.0, 0)
+            .await
+            .unwrap();
+
+        *views.fail.lock().unwrap() = true;
+        assert!(service.flush_pending_views(Utc::now(), 10).await.is_err());
+        assert_eq!(pending.pending_for(reel.id).unwrap().view_count, 1);
+
+        *views.fail.lock().unwrap() = false;
+        let now = Utc::now();
+        assert_eq!(service.flush_pending_views(now, 10).await.unwrap(), 1);
+        assert_eq!(*views.days.lock().unwrap(), vec![now.date_naive()]);
+        assert!(pending.pending_for(reel.id).is_none());
+
+        let totals = views.find_by_post(reel.id).await.unwrap().unwrap();
+        assert_eq!(totals.impression_count, 1);
+        assert_eq!(totals.view_count, 1);
+        assert_eq!(totals.total_watch_ms, 15_000);
+    }
+
+    #[tokio::test]
+    async fn test_record_view_tracks_daily_reach_by_audience() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let pending = Arc::new(MockPendingViewRepository::default());
+        let service = PostViewService::new(
+            Arc::new(PostVisibilityService::new(posts.clone(), users.clone())),
+            users.clone(),
+            pending.clone(),
+            Arc::new(MockPostViewRepository::default()),
+        );
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let follower = add_test_user(&users, "follower");
+        users.follow(follower.id, author.id).await.unwrap();
+        let reel = create_test_post(author.id, "Reel", true);
+        posts.add_post(reel.clone());
+
+        for viewer_id in [follower.id, follower.id, viewer.id] {
+            service
+                .record_view(viewer_id, reel.id, 5_000, 20.0, 0)
+                .await
+                .unwrap();
+        }
+
+        let stats = pending.pending_for(reel.id).unwrap();
+        assert_eq!(stats.impression_count, 3);
+        assert_eq!(stats.follower_impression_count, 2);
+        assert_eq!(stats.reach_count, 2);
+        assert_eq!(stats.follower_reach_count, 1);
+    }
+
+    #[test]
+    fn test_watch_time_boosts_reel_ranking() {
+        let author_id = Uuid::new_v4();
+        let mut watched = create_test_post(author_id, "Watched", true);
+        let mut skipped = create_test_post(author_id, "Skipped", true);
+        for reel in [&mut watched, &mut skipped] {
+            reel.like_count = 10;
+            reel.view_count = 100;
+        }
+        watched.avg_completion_percent = 90.0;
+        skipped.avg_completion_percent = 10.0;
+
+        assert!(calculate_engagement_score(&watched) > calculate_engagement_score(&skipped));
+    }
+}
@@ -0,0 +1,316 @@
+//! Publishing scheduled posts once they fall due.
+use super::{
+    collect_follower_ids, FeedGenerationService, MentionService, PublishedPost,
+    FAN_OUT_MAX_FOLLOWERS, SCHEDULER_BATCH_SIZE,
+};
+use crate::domain::entities::{MentionSource, Post};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{PostRepository, UserRepository};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Post scheduler service for publishing drafts and scheduled posts
+pub struct PostSchedulerService {
+    post_repository: Arc<dyn PostRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    feed_service: Arc<FeedGenerationService>,
+    mention_service: Arc<MentionService>,
+}
+
+impl PostSchedulerService {
+    pub fn new(
+        post_repository: Arc<dyn PostRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        feed_service: Arc<FeedGenerationService>,
+        mention_service: Arc<MentionService>,
+    ) -> Self {
+        Self {
+            post_repository,
+            user_repository,
+            feed_service,
+            mention_service,
+        }
+    }
+
+    /// Publish every scheduled post whose publish time has passed
+    pub async fn publish_due_posts(&self, now: DateTime<Utc>) -> Result<Vec<PublishedPost>> {
+        let due = self
+            .post_repository
+            .find_due_scheduled(now, SCHEDULER_BATCH_SIZE)
+            .await?;
+
+        let mut published = Vec::new();
+        for post in due {
+            // Another scheduler run or a manual publish may have got there first
+            let post = match self.post_repository.publish_scheduled(post.id, now).await? {
+                Some(post) => post,
+                None => continue,
+            };
+
+            let follower_ids = self.after_publish(&post).await;
+            published.push(PublishedPost { post, follower_ids });
+        }
+
+        if !published.is_empty() {
+            self.feed_service.invalidate_trending_cache().await?;
+        }
+
+        Ok(published)
+    }
+
+    /// Publish a draft or scheduled post immediately
+    pub async fn publish_now(&self, user_id: Uuid, post_id: Uuid) -> Result<PublishedPost> {
+        let mut post = self
+            .post_repository
+            .find_by_id(post_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+        // Unpublished posts are invisible to everyone but their author
+        if post.user_id != user_id {
+            return Err(AppError::NotFound("Post not found".to_string()));
+        }
+
+        if post.is_published() {
+            return Err(AppError::Conflict("Post is already published".to_string()));
+        }
+
+        post.publish();
+        let post = self.post_repository.update(&post).await?;
+
+        let follower_ids = self.after_publish(&post).await;
+        self.feed_service.invalidate_trending_cache().await?;
+
+        Ok(PublishedPost { post, follower_ids })
+    }
+
+    /// Get a user's drafts and scheduled posts
+    pub async fn get_unpublished_posts(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Post>> {
+        self.post_repository
+            .find_unpublished_by_user(user_id, limit, offset)
+            .await
+    }
+
+    /// Fan the post out to home timelines, clear the author's cached feeds
+    /// and send the mention notifications held back while unpublished.
+    /// Followers' cached feeds are left to expire. The post is already
+    /// published by now, so failures are logged rather than returned and
+    /// never hold up the rest of a batch.
+    ///
+    /// Returns the followers to tell about the post.
+    async fn after_publish(&self, post: &Post) -> Vec<Uuid> {
+        self.feed_service.fan_out_to_timelines(post);
+        let _ = self
+            .feed_service
+            .invalidate_user_feed_cache(post.user_id)
+            .await;
+
+        let follower_ids = match self.followers_to_notify(post.user_id).await {
+            Ok(follower_ids) => follower_ids,
+            Err(e) => {
+                tracing::warn!("Failed to load followers for post {}: {}", post.id, e);
+                Vec::new()
+            }
+        };
+
+        if !post.mentions.is_empty() {
+            if let Err(e) = self.record_mentions(post).await {
+                tracing::warn!("Failed to record mentions for post {}: {}", post.id, e);
+            }
+        }
+
+        follower_ids
+    }
+
+    /// Followers of an author who are told about new posts. Authors too
+    /// popular to fan out to have none, as their followers would have to be
+    /// loaded in full for every post.
+    async fn followers_to_notify(&self, author_id: Uuid) -> Result<Vec<Uuid>> {
+        let follower_count = self.user_repository.follower_count(author_id).await?;
+        if follower_count.is_none_or(|count| count >= FAN_OUT_MAX_FOLLOWERS) {
+            return Ok(Vec::new());
+        }
+        collect_follower_ids(self.user_repository.as_ref(), author_id).await
+    }
+
+    async fn record_mentions(&self, post: &Post) -> Result<()> {
+        let author = match self.user_repository.find_by_id(post.user_id).await? {
+            Some(author) => author,
+            None => return Ok(()),
+        };
+        self.mention_service
+            .record_mentions(
+                &author,
+                MentionSource::Post,
+                post.id,
+                Some(post.id),
+                &post.mentions,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::domain::entities::{MentionSpan, PostStatus};
+
+    fn setup() -> (
+        PostSchedulerService,
+        Arc<MockPostRepository>,
+        Arc<TestUserRepository>,
+        Arc<MockMentionRepository>,
+    ) {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let mentions = Arc::new(MockMentionRepository::new());
+        let feed_service = Arc::new(FeedGenerationService::new(
+            posts.clone(),
+            users.clone(),
+            None,
+            None,
+        ));
+        let mention_service = Arc::new(mention_service(
+            users.clone(),
+            posts.clone(),
+            mentions.clone(),
+        ));
+        let service =
+            PostSchedulerService::new(posts.clone(), users.clone(), feed_service, mention_service);
+        (service, posts, users, mentions)
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_posts() {
+        let (service, posts, users, mentions) = setup();
+        let author = add_test_user(&users, "author");
+        let follower = add_test_user(&users, "follower");
+        users.follow(follower.id, author.id).await.unwrap();
+
+        let mut due = create_test_post(author.id, "Hello @follower", false);
+        due.mentions = vec![MentionSpan {
+            user_id: follower.id,
+            username: "follower".to_string(),
+            start: 6,
+            end: 15,
+        }];
+        due.schedule(Utc::now() + chrono::Duration::minutes(5))
+            .unwrap();
+        let mut later = create_test_post(author.id, "Not yet", false);
+        later
+            .schedule(Utc::now() + chrono::Duration::days(1))
+            .unwrap();
+        let mut draft = create_test_post(author.id, "Still drafting", false);
+        draft.save_as_draft();
+        posts.add_post(due.clone());
+        posts.add_post(later.clone());
+        posts.add_post(draft.clone());
+
+        let now = Utc::now() + chrono::Duration::hours(1);
+        let published = service.publish_due_posts(now).await.unwrap();
+
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].post.id, due.id);
+        assert!(published[0].post.is_published());
+        assert_eq!(published[0].follower_ids, vec![follower.id]);
+        // Mentions held back while scheduled are recorded on publish
+        assert_eq!(mentions.mentions.lock().unwrap().len(), 1);
+
+        let stored = posts.find_by_id(later.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, PostStatus::Scheduled);
+        let stored = posts.find_by_id(draft.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, PostStatus::Draft);
+
+        // Already published posts are not picked up again
+        assert!(service.publish_due_posts(now).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_mentions_do_not_stop_the_batch() {
+        let (service, posts, users, mentions) = setup();
+        let author = create_test_user("author", "author@example.com");
+        let mentioned = create_test_user("mentioned", "mentioned@example.com");
+        users.add_user(author.clone());
+        users.add_user(mentioned.clone());
+        *mentions.fail.lock().unwrap() = true;
+
+        let mut scheduled = Vec::new();
+        for text in ["Hi @mentioned", "Again @mentioned"] {
+            let mut post = create_test_post(author.id, text, false);
+            post.mentions = vec![MentionSpan {
+                user_id: mentioned.id,
+                username: "mentioned".to_string(),
+                start: 3,
+                end: 13,
+            }];
+            post.schedule(Utc::now() + chrono::Duration::minutes(5))
+                .unwrap();
+            posts.add_post(post.clone());
+            scheduled.push(post);
+        }
+
+        let now = Utc::now() + chrono::Duration::hours(1);
+        let published = service.publish_due_posts(now).await.unwrap();
+
+        assert_eq!(published.len(), 2);
+        for post in &scheduled {
+            let stored = posts.find_by_id(post.id).await.unwrap().unwrap();
+            assert!(stored.is_published());
+        }
+        assert!(mentions.mentions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_publish_now() {
+        let (service, posts, users, _) = setup();
+        let author = create_test_user("author", "author@example.com");
+        let other = create_test_user("other", "other@example.com");
+        users.add_user(author.clone());
+        users.add_user(other.clone());
+
+        let mut draft = create_test_post(author.id, "Draft", false);
+        draft.save_as_draft();
+        posts.add_post(draft.clone());
+
+        let result = service.publish_now(other.id, draft.id).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let published = service.publish_now(author.id, draft.id).await.unwrap();
+        assert!(published.post.is_published());
+        assert!(published.post.publish_at.is_none());
+
+        let result = service.publish_now(author.id, draft.id).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+        assert!(service
+            .get_unpublished_posts(author.id, 20, 0)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_popular_authors_publish_without_loading_followers() {
+        let (service, posts, users, _) = setup();
+        let author = add_test_user(&users, "author");
+        let follower = add_test_user(&users, "follower");
+        users.follow(follower.id, author.id).await.unwrap();
+        let mut popular = users.find_by_id(author.id).await.unwrap().unwrap();
+        popular.follower_count = FAN_OUT_MAX_FOLLOWERS;
+        users.add_user(popular);
+
+        let mut draft = create_test_post(author.id, "Draft", false);
+        draft.save_as_draft();
+        posts.add_post(draft.clone());
+
+        let published = service.publish_now(author.id, draft.id).await.unwrap();
+        assert!(published.post.is_published());
+        assert!(published.follower_ids.is_empty());
+    }
+}
@@ -0,0 +1,381 @@
+//! Full-text search over posts, users and hashtags.
+use super::PostVisibilityService;
+use crate::domain::entities::{
+    extract_hashtags, HashtagCount, PostSearchHit, PostSearchQuery, User,
+};
+use crate::domain::errors::Result;
+use crate::domain::repositories::{PostRepository, UserRepository};
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Search service finding posts, users and hashtags as seen by the viewer
+pub struct SearchService {
+    post_repository: Arc<dyn PostRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    visibility_service: Arc<PostVisibilityService>,
+}
+
+/// A page of post search results, most relevant first
+#[derive(Debug, Default)]
+pub struct PostSearchPage {
+    pub hits: Vec<PostSearchHit>,
+    pub has_more: bool,
+}
+
+/// Users, posts and hashtags matching one search
+#[derive(Debug, Default)]
+pub struct SearchResults {
+    pub users: Vec<User>,
+    pub posts: Vec<PostSearchHit>,
+    pub hashtags: Vec<HashtagCount>,
+}
+
+impl SearchService {
+    pub fn new(
+        post_repository: Arc<dyn PostRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        visibility_service: Arc<PostVisibilityService>,
+    ) -> Self {
+        Self {
+            post_repository,
+            user_repository,
+            visibility_service,
+        }
+    }
+
+    /// Search posts the viewer may see, most relevant first
+    pub async fn search_posts(
+        &self,
+        viewer_id: Option<Uuid>,
+        query: PostSearchQuery,
+        limit: i64,
+        offset: i64,
+    ) -> Result<PostSearchPage> {
+        let query = query.normalized()?;
+        let mut hits = self
+            .post_repository
+            .search(viewer_id, &query, limit + 1, offset)
+            .await?;
+        let has_more = hits.len() as i64 > limit;
+        hits.truncate(limit as usize);
+
+        let posts = hits.iter().map(|hit| hit.post.clone()).collect();
+        let visible: HashSet<Uuid> = self
+            .visibility_service
+            .filter_visible(viewer_id, posts)
+            .await?
+            .into_iter()
+            .map(|post| post.id)
+            .collect();
+        hits.retain(|hit| visible.contains(&hit.post.id));
+        Ok(PostSearchPage { hits, has_more })
+    }
+
+    /// Search users, posts and hashtags at once, up to `limit` of each.
+    /// Users are matched from two characters on, and hashtags only when the
+    /// query is a single tag (with or without its `#`).
+    pub async fn search(
+        &self,
+        viewer_id: Option<Uuid>,
+        text: &str,
+        limit: i64,
+    ) -> Result<SearchResults> {
+        let text = text.trim();
+        let posts = self
+            .search_posts(
+                viewer_id,
+                PostSearchQuery {
+                    text: text.to_string(),
+                    ..Default::default()
+                },
+                limit,
+                0,
+            )
+            .await?
+            .hits;
+
+        let username = text.trim_start_matches('@');
+        let users = if username.chars().count() >= 2 {
+            let blocked = match viewer_id {
+                Some(viewer_id) => self.user_repository.blocked_user_ids(viewer_id).await?,
+                None => HashSet::new(),
+            };
+            self.user_repository
+                .search(username, limit, 0)
+                .await?
+                .into_iter()
+                .filter(|user| !blocked.contains(&user.id))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let tag = text.trim_start_matches('#');
+        let hashtags = match extract_hashtags(&format!("#{}", tag)).as_slice() {
+            [parsed] if parsed.chars().count() == tag.chars().count() => {
+                self.post_repository.search_hashtags(parsed, limit).await?
+            }
+            _ => Vec::new(),
+        };
+
+        Ok(SearchResults {
+            users,
+            posts,
+            hashtags,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::domain::entities::PostVisibility;
+    use crate::domain::errors::AppError;
+    use chrono::Utc;
+
+    /// IDs of the matching posts the viewer may see, sorted for comparison
+    async fn search_ids(
+        service: &SearchService,
+        viewer_id: Option<Uuid>,
+        query: PostSearchQuery,
+    ) -> Vec<Uuid> {
+        let page = service.search_posts(viewer_id, query, 10, 0).await.unwrap();
+        let mut ids: Vec<Uuid> = page.hits.into_iter().map(|hit| hit.post.id).collect();
+        ids.sort();
+        ids
+    }
+
+    fn sorted(mut ids: Vec<Uuid>) -> Vec<Uuid> {
+        ids.sort();
+        ids
+    }
+
+    fn query(text: &str) -> PostSearchQuery {
+        PostSearchQuery {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_posts_ranks_matches_and_highlights_terms() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let visibility = Arc::new(PostVisibilityService::new(posts.clone(), users.clone()));
+        let service = SearchService::new(posts.clone(), users.clone(), visibility);
+        let author = add_test_user(&users, "runner");
+
+        let once = create_test_post(author.id, "Went for a run today", false);
+        let twice = create_test_post(author.id, "Run fast, run far #running", false);
+        let unrelated = create_test_post(author.id, "Baking bread", false);
+        for post in [&once, &twice, &unrelated] {
+            posts.add_post(post.clone());
+        }
+
+        let page = service
+            .search_posts(None, query("run"), 10, 0)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = page.hits.iter().map(|hit| hit.post.id).collect();
+        assert_eq!(ids, vec![twice.id, once.id]);
+        assert!(!page.has_more);
+        assert_eq!(page.hits[1].snippet.text, "Went for a run today");
+        assert_eq!(page.hits[1].snippet.highlights, vec![(11, 14)]);
+
+        let page = service
+            .search_posts(None, query("run"), 1, 0)
+            .await
+            .unwrap();
+        assert_eq!(page.hits.len(), 1);
+        assert!(page.has_more);
+
+        assert!(matches!(
+            service.search_posts(None, query("   "), 10, 0).await,
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_posts_applies_filters() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let visibility = Arc::new(PostVisibilityService::new(posts.clone(), users.clone()));
+        let service = SearchService::new(posts.clone(), users.clone(), visibility);
+        let author = add_test_user(&users, "runner");
+
+        let other = add_test_user(&users, "other");
+
+        let mut old = create_test_post(author.id, "Trail run", false);
+        old.created_at = Utc::now() - chrono::Duration::days(30);
+        let reel = create_test_post(author.id, "Trail run reel", true);
+        let tagged = create_test_post(author.id, "Trail run #Ultra", false);
+        let others = create_test_post(other.id, "Trail run with friends", false);
+        for post in [&old, &reel, &tagged, &others] {
+            posts.add_post(post.clone());
+        }
+
+        let by_author = search_ids(
+            &service,
+            None,
+            PostSearchQuery {
+                author_id: Some(other.id),
+                ..query("trail")
+            },
+        )
+        .await;
+        assert_eq!(by_author, vec![others.id]);
+
+        let recent = search_ids(
+            &service,
+            None,
+            PostSearchQuery {
+                since: Some(Utc::now() - chrono::Duration::days(7)),
+                ..query("trail")
+            },
+        )
+        .await;
+        assert_eq!(recent, sorted(vec![reel.id, tagged.id, others.id]));
+
+        let older = search_ids(
+            &service,
+            None,
+            PostSearchQuery {
+                until: Some(Utc::now() - chrono::Duration::days(7)),
+                ..query("trail")
+            },
+        )
+        .await;
+        assert_eq!(older, vec![old.id]);
+
+        let reels = search_ids(
+            &service,
+            None,
+            PostSearchQuery {
+                reels_only: true,
+                ..query("trail")
+            },
+        )
+        .await;
+        assert_eq!(reels, vec![reel.id]);
+
+        let videos = search_ids(
+            &service,
+            None,
+            PostSearchQuery {
+                content_type: Some(reel.content_type.clone()),
+                ..query("trail")
+            },
+        )
+        .await;
+        assert_eq!(videos, vec![reel.id]);
+
+        let hashtag = search_ids(
+            &service,
+            None,
+            PostSearchQuery {
+                hashtag: Some("#ultra".to_string()),
+                ..query("trail")
+            },
+        )
+        .await;
+        assert_eq!(hashtag, vec![tagged.id]);
+    }
+
+    #[tokio::test]
+    async fn test_search_posts_respects_visibility() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let visibility = Arc::new(PostVisibilityService::new(posts.clone(), users.clone()));
+        let service = SearchService::new(posts.clone(), users.clone(), visibility);
+        let author = add_test_user(&users, "runner");
+        let viewer = add_test_user(&users, "viewer");
+
+        let public = create_test_post(author.id, "Race day", false);
+        let mut followers = create_test_post(author.id, "Race day for friends", false);
+        followers.visibility = PostVisibility::Followers;
+        let mut private = create_test_post(author.id, "Race day notes", false);
+        private.visibility = PostVisibility::Private;
+        let mut draft = create_test_post(author.id, "Race day draft", false);
+        draft.save_as_draft();
+        for post in [&public, &followers, &private, &draft] {
+            posts.add_post(post.clone());
+        }
+
+        assert_eq!(
+            search_ids(&service, None, query("race")).await,
+            vec![public.id]
+        );
+        assert_eq!(
+            search_ids(&service, Some(viewer.id), query("race")).await,
+            vec![public.id]
+        );
+
+        users.follow(viewer.id, author.id).await.unwrap();
+        let before = users.relationship_lookups();
+        assert_eq!(
+            search_ids(&service, Some(viewer.id), query("race")).await,
+            sorted(vec![public.id, followers.id])
+        );
+        // Blocks and follows are looked up once for the whole page
+        assert_eq!(users.relationship_lookups() - before, 2);
+        assert_eq!(
+            search_ids(&service, Some(author.id), query("race")).await,
+            sorted(vec![public.id, followers.id, private.id])
+        );
+
+        users.block(author.id, viewer.id);
+        assert!(search_ids(&service, Some(viewer.id), query("race"))
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_returns_users_posts_and_hashtags() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let visibility = Arc::new(PostVisibilityService::new(posts.clone(), users.clone()));
+        let service = SearchService::new(posts.clone(), users.clone(), visibility);
+        let author = add_test_user(&users, "runner");
+        let viewer = add_test_user(&users, "viewer");
+
+        let blocked = create_test_user("runner_blocked", "blocked@example.com");
+        users.add_user(blocked.clone());
+        users.block(viewer.id, blocked.id);
+        let post = create_test_post(author.id, "Sunday #runner meetup", false);
+        let other = create_test_post(author.id, "#runners unite #runner", false);
+        posts.add_post(post.clone());
+        posts.add_post(other.clone());
+
+        let results = service.search(Some(viewer.id), "runner", 5).await.unwrap();
+
+        let user_ids: Vec<Uuid> = results.users.iter().map(|user| user.id).collect();
+        assert_eq!(user_ids, vec![author.id]);
+        let mut post_ids: Vec<Uuid> = results.posts.iter().map(|hit| hit.post.id).collect();
+        post_ids.sort();
+        let mut expected = vec![post.id, other.id];
+        expected.sort();
+        assert_eq!(post_ids, expected);
+        assert_eq!(
+            results.hashtags,
+            vec![
+                HashtagCount {
+                    tag: "runner".to_string(),
+                    post_count: 2
+                },
+                HashtagCount {
+                    tag: "runners".to_string(),
+                    post_count: 1
+                },
+            ]
+        );
+
+        // Phrases are not hashtags, and single characters match no users
+        let results = service.search(None, "r", 5).await.unwrap();
+        assert!(results.users.is_empty());
+        let results = service.search(None, "sunday meetup", 5).await.unwrap();
+        assert!(results.hashtags.is_empty());
+        assert_eq!(results.posts.len(), 1);
+    }
+}
@@ -0,0 +1,688 @@
+//! Stories: posting, the story tray, viewers, replies sent as direct messages,
+//! and removing stories once they expire.
+use super::{MediaService, SCHEDULER_BATCH_SIZE};
+use crate::domain::entities::{CreateMessageRequest, Message, MessageType, Story, StoryView, User};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{
+    ConversationRepository, MessageRepository, StoryRepository, UserRepository,
+};
+use crate::domain::value_objects::Cursor;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Story service for posting, viewing and replying to stories
+pub struct StoryService {
+    story_repository: Arc<dyn StoryRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    conversation_repository: Arc<dyn ConversationRepository>,
+    message_repository: Arc<dyn MessageRepository>,
+    media_service: Arc<MediaService>,
+}
+
+/// A story in a viewer's feed and whether they have seen it
+#[derive(Debug)]
+pub struct StoryFeedItem {
+    pub story: Story,
+    pub seen: bool,
+    /// How many users have seen the story; only given to its author
+    pub view_count: Option<i64>,
+}
+
+/// One author's unexpired stories, oldest first
+#[derive(Debug)]
+pub struct StoryGroup {
+    pub author_id: Uuid,
+    pub stories: Vec<StoryFeedItem>,
+}
+
+impl StoryGroup {
+    pub fn has_unseen(&self) -> bool {
+        self.stories.iter().any(|item| !item.seen)
+    }
+
+    fn latest_at(&self) -> Option<DateTime<Utc>> {
+        self.stories.last().map(|item| item.story.created_at)
+    }
+}
+
+/// A page of a story's viewers
+#[derive(Debug)]
+pub struct StoryViewerPage {
+    pub items: Vec<(StoryView, User)>,
+    pub next_cursor: Option<Cursor>,
+}
+
+/// A direct message sent in reply to a story
+#[derive(Debug)]
+pub struct StoryReply {
+    pub message: Message,
+    pub recipient_id: Uuid,
+}
+
+impl StoryService {
+    pub fn new(
+        story_repository: Arc<dyn StoryRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        conversation_repository: Arc<dyn ConversationRepository>,
+        message_repository: Arc<dyn MessageRepository>,
+        media_service: Arc<MediaService>,
+    ) -> Self {
+        Self {
+            story_repository,
+            user_repository,
+            conversation_repository,
+            message_repository,
+            media_service,
+        }
+    }
+
+    /// Post a story from one of the user's completed image or video uploads
+    pub async fn create_story(
+        &self,
+        user_id: Uuid,
+        media_id: Uuid,
+        caption: Option<String>,
+    ) -> Result<Story> {
+        let media = self
+            .media_service
+            .resolve_attachment(user_id, media_id)
+            .await?;
+        let story = Story::new(user_id, media, caption)?;
+        self.story_repository.create(&story).await
+    }
+
+    /// Get the viewer's story feed grouped by author.
+    ///
+    /// The viewer's own stories come first, then authors with stories the
+    /// viewer has not seen yet, then everyone else. Within each of those,
+    /// authors who posted most recently come first.
+    pub async fn get_feed(&self, viewer_id: Uuid) -> Result<Vec<StoryGroup>> {
+        let stories = self
+            .story_repository
+            .find_feed_stories(viewer_id, Utc::now())
+            .await?;
+
+        let story_ids: Vec<Uuid> = stories.iter().map(|story| story.id).collect();
+        let seen: std::collections::HashSet<Uuid> = self
+            .story_repository
+            .find_seen_story_ids(viewer_id, &story_ids)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut groups: Vec<StoryGroup> = Vec::new();
+        for story in stories {
+            // Authors never record views of their own stories
+            let is_own = story.user_id == viewer_id;
+            let view_count = if is_own {
+                Some(self.story_repository.count_views(story.id).await?)
+            } else {
+                None
+            };
+            let item = StoryFeedItem {
+                seen: is_own || seen.contains(&story.id),
+                story,
+                view_count,
+            };
+            match groups
+                .iter_mut()
+                .find(|group| group.author_id == item.story.user_id)
+            {
+                Some(group) => group.stories.push(item),
+                None => groups.push(StoryGroup {
+                    author_id: item.story.user_id,
+                    stories: vec![item],
+                }),
+            }
+        }
+
+        groups.sort_by_key(|group| {
+            (
+                group.author_id != viewer_id,
+                !group.has_unseen(),
+                std::cmp::Reverse(group.latest_at()),
+            )
+        });
+
+        Ok(groups)
+    }
+
+    /// Mark a story as seen by the viewer
+    pub async fn mark_seen(&self, viewer_id: Uuid, story_id: Uuid) -> Result<Story> {
+        let story = self.find_viewable_story(viewer_id, story_id).await?;
+
+        if story.user_id != viewer_id {
+            self.story_repository
+                .record_view(&StoryView {
+                    story_id: story.id,
+                    viewer_id,
+                    viewed_at: Utc::now(),
+                })
+                .await?;
+        }
+
+        Ok(story)
+    }
+
+    /// Get a page of the users who have seen one of the author's stories
+    pub async fn get_viewers(
+        &self,
+        author_id: Uuid,
+        story_id: Uuid,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<StoryViewerPage> {
+        let story = self.get_owned_story(author_id, story_id).await?;
+
+        let mut views = self
+            .story_repository
+            .find_views(story.id, after, limit + 1)
+            .await?;
+
+        let has_more = views.len() as i64 > limit;
+        views.truncate(limit.max(0) as usize);
+        let next_cursor = if has_more {
+            views
+                .last()
+                .map(|view| Cursor::new(view.viewed_at, view.viewer_id))
+        } else {
+            None
+        };
+
+        let mut items = Vec::with_capacity(views.len());
+        for view in views {
+            // Viewers whose account was deleted drop out of the list
+            if let Some(viewer) = self.user_repository.find_by_id(view.viewer_id).await? {
+                items.push((view, viewer));
+            }
+        }
+
+        Ok(StoryViewerPage { items, next_cursor })
+    }
+
+    /// Reply to a story with a direct message to its author, starting a
+    /// conversation between the two if they do not have one yet
+    pub async fn reply(
+        &self,
+        sender_id: Uuid,
+        story_id: Uuid,
+        content: String,
+    ) -> Result<StoryReply> {
+        let story = self.find_viewable_story(sender_id, story_id).await?;
+
+        if story.user_id == sender_id {
+            return Err(AppError::ValidationError(
+                "You cannot reply to your own story".to_string(),
+            ));
+        }
+
+        // Validate the message before creating a conversation for it
+        let mut message = Message::new(CreateMessageRequest {
+            conversation_id: Uuid::nil(),
+            sender_id: Some(sender_id),
+            message_type: MessageType::Text,
+            content: Some(content),
+            media_url: None,
+            payment_data: None,
+            reply_to_id: None,
+        })?;
+
+        message.conversation_id = match self
+            .conversation_repository
+            .find_direct_conversation(sender_id, story.user_id)
+            .await?
+        {
+            Some(conversation_id) => conversation_id,
+            None => {
+                self.conversation_repository
+                    .create(
+                        Uuid::new_v4(),
+                        vec![sender_id, story.user_id],
+                        false,
+                        None,
+                        sender_id,
+                    )
+                    .await?
+            }
+        };
+        message.story_id = Some(story.id);
+
+        let message = self.message_repository.create(&message).await?;
+
+        Ok(StoryReply {
+            message,
+            recipient_id: story.user_id,
+        })
+    }
+
+    /// Delete one of the user's stories before it expires
+    pub async fn delete_story(&self, user_id: Uuid, story_id: Uuid) -> Result<()> {
+        let story = self.get_owned_story(user_id, story_id).await?;
+        self.remove(&story).await
+    }
+
+    /// Remove stories that have expired, along with their media
+    pub async fn delete_expired_stories(&self, now: DateTime<Utc>) -> Result<usize> {
+        let expired = self
+            .story_repository
+            .find_expired(now, SCHEDULER_BATCH_SIZE)
+            .await?;
+
+        for story in &expired {
+            self.remove(story).await?;
+        }
+
+        Ok(expired.len())
+    }
+
+    /// Delete a story, then its upload. The story is gone even if its files
+    /// could not be removed, so a failure there is only logged.
+    async fn remove(&self, story: &Story) -> Result<()> {
+        self.story_repository.delete(story.id).await?;
+
+        if let Err(e) = self.media_service.delete_upload(story.media.id).await {
+            tracing::warn!("Failed to delete media for story {}: {}", story.id, e);
+        }
+
+        Ok(())
+    }
+
+    /// Fetch an unexpired story the viewer may see: their own, or one by an
+    /// author they follow with no block between them. Anything else is
+    /// reported as missing.
+    async fn find_viewable_story(&self, viewer_id: Uuid, story_id: Uuid) -> Result<Story> {
+        let not_found = || AppError::NotFound("Story not found".to_string());
+
+        let story = self
+            .story_repository
+            .find_by_id(story_id)
+            .await?
+            .filter(|story| !story.is_expired(Utc::now()))
+            .ok_or_else(not_found)?;
+
+        if story.user_id == viewer_id {
+            return Ok(story);
+        }
+
+        if self
+            .user_repository
+            .has_blocked(story.user_id, viewer_id)
+            .await?
+            || self
+                .user_repository
+                .has_blocked(viewer_id, story.user_id)
+                .await?
+            || !self
+                .user_repository
+                .is_following(viewer_id, story.user_id)
+                .await?
+        {
+            return Err(not_found());
+        }
+
+        Ok(story)
+    }
+
+    async fn get_owned_story(&self, user_id: Uuid, story_id: Uuid) -> Result<Story> {
+        self.story_repository
+            .find_by_id(story_id)
+            .await?
+            .filter(|story| story.user_id == user_id)
+            .ok_or_else(|| AppError::NotFound("Story not found".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::infrastructure::media_processing::tests::sample_png;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Stories and views in memory, using the user repository for the follow graph
+    struct MockStoryRepository {
+        stories: Mutex<HashMap<Uuid, Story>>,
+        views: Mutex<Vec<StoryView>>,
+        users: Arc<TestUserRepository>,
+    }
+
+    impl MockStoryRepository {
+        fn new(users: Arc<TestUserRepository>) -> Self {
+            Self {
+                stories: Mutex::new(HashMap::new()),
+                views: Mutex::new(Vec::new()),
+                users,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl StoryRepository for MockStoryRepository {
+        async fn create(&self, story: &Story) -> Result<Story> {
+            self.stories.lock().unwrap().insert(story.id, story.clone());
+            Ok(story.clone())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Story>> {
+            Ok(self.stories.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_feed_stories(
+            &self,
+            viewer_id: Uuid,
+            now: DateTime<Utc>,
+        ) -> Result<Vec<Story>> {
+            let active: Vec<Story> = self
+                .stories
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|story| !story.is_expired(now))
+                .cloned()
+                .collect();
+
+            let mut stories = Vec::new();
+            for story in active {
+                let author_id = story.user_id;
+                let follows =
+                    author_id == viewer_id || self.users.is_following(viewer_id, author_id).await?;
+                let blocked = self.users.has_blocked(author_id, viewer_id).await?
+                    || self.users.has_blocked(viewer_id, author_id).await?;
+                if follows && !blocked {
+                    stories.push(story);
+                }
+            }
+            stories.sort_by_key(|story| (story.created_at, story.id));
+            Ok(stories)
+        }
+
+        async fn delete(&self, id: Uuid) -> Result<()> {
+            self.stories.lock().unwrap().remove(&id);
+            self.views
+                .lock()
+                .unwrap()
+                .retain(|view| view.story_id != id);
+            Ok(())
+        }
+
+        async fn record_view(&self, view: &StoryView) -> Result<()> {
+            let mut views = self.views.lock().unwrap();
+            if !views
+                .iter()
+                .any(|v| v.story_id == view.story_id && v.viewer_id == view.viewer_id)
+            {
+                views.push(view.clone());
+            }
+            Ok(())
+        }
+
+        async fn find_seen_story_ids(
+            &self,
+            viewer_id: Uuid,
+            story_ids: &[Uuid],
+        ) -> Result<Vec<Uuid>> {
+            Ok(self
+                .views
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|view| view.viewer_id == viewer_id && story_ids.contains(&view.story_id))
+                .map(|view| view.story_id)
+                .collect())
+        }
+
+        async fn find_views(
+            &self,
+            story_id: Uuid,
+            after: Option<Cursor>,
+            limit: i64,
+        ) -> Result<Vec<StoryView>> {
+            let mut views: Vec<StoryView> = self
+                .views
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|view| view.story_id == story_id)
+                .filter(|view| {
+                    after.is_none_or(|c| (view.viewed_at, view.viewer_id) < (c.created_at, c.id))
+                })
+                .cloned()
+                .collect();
+            views.sort_by_key(|view| std::cmp::Reverse((view.viewed_at, view.viewer_id)));
+            views.truncate(limit as usize);
+            Ok(views)
+        }
+
+        async fn count_views(&self, story_id: Uuid) -> Result<i64> {
+            Ok(self
+                .views
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|view| view.story_id == story_id)
+                .count() as i64)
+        }
+
+        async fn find_expired(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Story>> {
+            let mut expired: Vec<Story> = self
+                .stories
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|story| story.is_expired(now))
+                .cloned()
+                .collect();
+            expired.sort_by_key(|story| story.expires_at);
+            expired.truncate(limit as usize);
+            Ok(expired)
+        }
+    }
+
+    async fn post_story(service: &StoryService, user_id: Uuid) -> Story {
+        let media_service = &service.media_service;
+        let png = sample_png(40, 20);
+        let target = create_png_upload(media_service, user_id, png.len() as i64).await;
+        media_service
+            .upload_content(user_id, target.upload.id, "image/png", png)
+            .await
+            .unwrap();
+
+        service
+            .create_story(user_id, target.upload.id, None)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_story_requires_own_upload() {
+        let users = Arc::new(TestUserRepository::new());
+        let author = add_test_user(&users, "author");
+        let follower = add_test_user(&users, "follower");
+        users.follow(follower.id, author.id).await.unwrap();
+        let service = StoryService::new(
+            Arc::new(MockStoryRepository::new(users.clone())),
+            users.clone(),
+            Arc::new(MockConversationRepository::default()),
+            Arc::new(MockMessageRepository::default()),
+            Arc::new(media_service(false).0),
+        );
+
+        let story = post_story(&service, author.id).await;
+        assert_eq!(story.user_id, author.id);
+        assert!(story.media.is_image());
+
+        let someone_elses = service
+            .create_story(follower.id, story.media.id, None)
+            .await;
+        assert!(matches!(someone_elses, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_feed_groups_by_author_unseen_first() {
+        let users = Arc::new(TestUserRepository::new());
+        let author = add_test_user(&users, "author");
+        let follower = add_test_user(&users, "follower");
+        users.follow(follower.id, author.id).await.unwrap();
+        let service = StoryService::new(
+            Arc::new(MockStoryRepository::new(users.clone())),
+            users.clone(),
+            Arc::new(MockConversationRepository::default()),
+            Arc::new(MockMessageRepository::default()),
+            Arc::new(media_service(false).0),
+        );
+
+        let other = add_test_user(&users, "other");
+        users.follow(follower.id, other.id).await.unwrap();
+
+        let first = post_story(&service, author.id).await;
+        let second = post_story(&service, author.id).await;
+        let others = post_story(&service, other.id).await;
+        let own = post_story(&service, follower.id).await;
+
+        // Having seen all of the author's stories moves them behind `other`
+        service.mark_seen(follower.id, first.id).await.unwrap();
+        service.mark_seen(follower.id, second.id).await.unwrap();
+
+        let feed = service.get_feed(follower.id).await.unwrap();
+        let authors: Vec<Uuid> = feed.iter().map(|group| group.author_id).collect();
+        assert_eq!(authors, vec![follower.id, other.id, author.id]);
+        assert_eq!(feed[0].stories[0].story.id, own.id);
+        assert_eq!(feed[0].stories[0].view_count, Some(0));
+        assert!(feed[2].stories[0].view_count.is_none());
+        assert!(feed[1].has_unseen());
+        assert_eq!(feed[1].stories[0].story.id, others.id);
+        assert!(!feed[2].has_unseen());
+        let author_stories: Vec<Uuid> = feed[2].stories.iter().map(|item| item.story.id).collect();
+        assert_eq!(author_stories, vec![first.id, second.id]);
+
+        // Blocking hides the author's stories entirely
+        users.block(other.id, follower.id);
+        let feed = service.get_feed(follower.id).await.unwrap();
+        assert!(feed.iter().all(|group| group.author_id != other.id));
+    }
+
+    #[tokio::test]
+    async fn test_only_followers_can_see_a_story() {
+        let users = Arc::new(TestUserRepository::new());
+        let author = add_test_user(&users, "author");
+        let follower = add_test_user(&users, "follower");
+        users.follow(follower.id, author.id).await.unwrap();
+        let service = StoryService::new(
+            Arc::new(MockStoryRepository::new(users.clone())),
+            users.clone(),
+            Arc::new(MockConversationRepository::default()),
+            Arc::new(MockMessageRepository::default()),
+            Arc::new(media_service(false).0),
+        );
+
+        let story = post_story(&service, author.id).await;
+        let stranger = add_test_user(&users, "stranger");
+
+        let result = service.mark_seen(stranger.id, story.id).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        // Authors do not show up as their own viewers
+        service.mark_seen(author.id, story.id).await.unwrap();
+        service.mark_seen(follower.id, story.id).await.unwrap();
+        service.mark_seen(follower.id, story.id).await.unwrap();
+
+        let page = service
+            .get_viewers(author.id, story.id, None, 20)
+            .await
+            .unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].1.id, follower.id);
+        assert!(page.next_cursor.is_none());
+
+        let not_author = service.get_viewers(follower.id, story.id, None, 20).await;
+        assert!(matches!(not_author, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reply_goes_to_direct_conversation() {
+        let users = Arc::new(TestUserRepository::new());
+        let author = add_test_user(&users, "author");
+        let follower = add_test_user(&users, "follower");
+        users.follow(follower.id, author.id).await.unwrap();
+        let conversations = Arc::new(MockConversationRepository::default());
+        let messages = Arc::new(MockMessageRepository::default());
+        let service = StoryService::new(
+            Arc::new(MockStoryRepository::new(users.clone())),
+            users.clone(),
+            conversations.clone(),
+            messages.clone(),
+            Arc::new(media_service(false).0),
+        );
+
+        let story = post_story(&service, author.id).await;
+
+        let reply = service
+            .reply(follower.id, story.id, "Nice!".to_string())
+            .await
+            .unwrap();
+        assert_eq!(reply.recipient_id, author.id);
+        assert_eq!(reply.message.story_id, Some(story.id));
+        assert_eq!(reply.message.sender_id, Some(follower.id));
+
+        // A second reply reuses the conversation
+        let again = service
+            .reply(follower.id, story.id, "Again".to_string())
+            .await
+            .unwrap();
+        assert_eq!(again.message.conversation_id, reply.message.conversation_id);
+        assert_eq!(conversations.conversations.lock().unwrap().len(), 1);
+        assert_eq!(messages.messages.lock().unwrap().len(), 2);
+
+        let empty = service.reply(follower.id, story.id, "  ".to_string()).await;
+        assert!(matches!(empty, Err(AppError::ValidationError(_))));
+
+        let own = service.reply(author.id, story.id, "Me".to_string()).await;
+        assert!(matches!(own, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_expired_stories_are_removed_with_their_media() {
+        let users = Arc::new(TestUserRepository::new());
+        let author = add_test_user(&users, "author");
+        let follower = add_test_user(&users, "follower");
+        users.follow(follower.id, author.id).await.unwrap();
+        let stories = Arc::new(MockStoryRepository::new(users.clone()));
+        let (media_service, storage) = media_service(false);
+        let service = StoryService::new(
+            stories.clone(),
+            users.clone(),
+            Arc::new(MockConversationRepository::default()),
+            Arc::new(MockMessageRepository::default()),
+            Arc::new(media_service),
+        );
+
+        let story = post_story(&service, author.id).await;
+        let kept = post_story(&service, author.id).await;
+        service.mark_seen(follower.id, story.id).await.unwrap();
+
+        stories
+            .stories
+            .lock()
+            .unwrap()
+            .get_mut(&story.id)
+            .unwrap()
+            .expires_at = Utc::now() - chrono::Duration::minutes(1);
+
+        let expired = service.mark_seen(follower.id, story.id).await;
+        assert!(matches!(expired, Err(AppError::NotFound(_))));
+
+        let removed = service.delete_expired_stories(Utc::now()).await.unwrap();
+        assert_eq!(removed, 1);
+        assert!(stories.stories.lock().unwrap().get(&story.id).is_none());
+        assert!(stories.views.lock().unwrap().is_empty());
+
+        let keys: Vec<String> = storage.objects.lock().unwrap().keys().cloned().collect();
+        assert!(!keys.is_empty());
+        assert!(keys
+            .iter()
+            .all(|key| key.contains(&kept.media.id.to_string())));
+    }
+}
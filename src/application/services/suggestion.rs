@@ -0,0 +1,510 @@
+//! Follow suggestions built from a user's network and conversations, cached
+//! per user and refreshed for users who have looked at them recently.
+use crate::domain::entities::{FollowSuggestion, User};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{
+    ConversationRepository, SuggestionCacheRepository, SuggestionRepository, UserRepository,
+};
+use crate::domain::value_objects::PageStart;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Follow suggestion service ranking the users a user may know
+pub struct SuggestionService {
+    user_repository: Arc<dyn UserRepository>,
+    conversation_repository: Arc<dyn ConversationRepository>,
+    suggestion_repository: Arc<dyn SuggestionRepository>,
+    suggestion_cache: Option<Arc<dyn SuggestionCacheRepository>>,
+}
+
+/// Followings and followers of the viewer read when computing suggestions
+const SUGGESTION_NETWORK_SIZE: i64 = 200;
+
+/// Accounts in the viewer's network whose followings are read for suggestions
+const SUGGESTION_SOURCES: usize = 50;
+
+/// Followings read per account in the viewer's network
+const SUGGESTION_SOURCE_FOLLOWING: i64 = 200;
+
+/// Most recent conversations read for suggestions
+const SUGGESTION_CONVERSATIONS: i64 = 100;
+
+/// Suggestions stored per user
+const MAX_SUGGESTIONS: usize = 100;
+
+/// Users who looked at their suggestions within this many days have them
+/// refreshed periodically
+const SUGGESTION_ACTIVE_DAYS: i64 = 7;
+
+/// Most users whose suggestions are refreshed per run
+const MAX_SUGGESTION_REFRESHES: usize = 1000;
+
+/// The suggestion being built for a candidate, started if new
+fn suggestion_for(
+    candidates: &mut HashMap<Uuid, FollowSuggestion>,
+    user_id: Uuid,
+) -> &mut FollowSuggestion {
+    candidates
+        .entry(user_id)
+        .or_insert_with(|| FollowSuggestion {
+            user_id,
+            ..Default::default()
+        })
+}
+
+impl SuggestionService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        conversation_repository: Arc<dyn ConversationRepository>,
+        suggestion_repository: Arc<dyn SuggestionRepository>,
+        suggestion_cache: Option<Arc<dyn SuggestionCacheRepository>>,
+    ) -> Self {
+        Self {
+            user_repository,
+            conversation_repository,
+            suggestion_repository,
+            suggestion_cache,
+        }
+    }
+
+    /// Get up to `limit` users the viewer may want to follow, best first.
+    /// Precomputed suggestions are used when stored; users followed, blocked
+    /// or dismissed since they were computed are left out.
+    pub async fn get_suggestions(
+        &self,
+        user_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<(User, FollowSuggestion)>> {
+        let now = Utc::now();
+        let mut stored = None;
+        if let Some(ref cache) = self.suggestion_cache {
+            if let Err(e) = cache.mark_active(user_id, now).await {
+                tracing::warn!("Failed to mark user {} active: {}", user_id, e);
+            }
+            match cache.find(user_id).await {
+                Ok(suggestions) => stored = suggestions,
+                Err(e) => tracing::warn!("Failed to read suggestions for user {}: {}", user_id, e),
+            }
+        }
+        let suggestions = match stored {
+            Some(suggestions) => suggestions,
+            None => self.refresh_suggestions(user_id).await?,
+        };
+
+        let mut excluded = self.suggestion_repository.dismissed_ids(user_id).await?;
+        excluded.extend(self.user_repository.blocked_user_ids(user_id).await?);
+        let candidate_ids: Vec<Uuid> = suggestions
+            .iter()
+            .map(|suggestion| suggestion.user_id)
+            .filter(|id| !excluded.contains(id))
+            .collect();
+        // Users followed since the suggestions were computed
+        excluded.extend(
+            self.user_repository
+                .followed_ids(user_id, &candidate_ids)
+                .await?,
+        );
+        let shown: Vec<FollowSuggestion> = suggestions
+            .into_iter()
+            .filter(|suggestion| !excluded.contains(&suggestion.user_id))
+            .take(limit)
+            .collect();
+
+        let ids: Vec<Uuid> = shown.iter().map(|suggestion| suggestion.user_id).collect();
+        let mut users: HashMap<Uuid, User> = self
+            .user_repository
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect();
+        Ok(shown
+            .into_iter()
+            .filter_map(|suggestion| {
+                users
+                    .remove(&suggestion.user_id)
+                    .map(|user| (user, suggestion))
+            })
+            .collect())
+    }
+
+    /// Stop suggesting a user to the viewer
+    pub async fn dismiss_suggestion(&self, user_id: Uuid, dismissed_user_id: Uuid) -> Result<()> {
+        if user_id == dismissed_user_id {
+            return Err(AppError::ValidationError(
+                "Users cannot dismiss themselves".to_string(),
+            ));
+        }
+        self.user_repository
+            .find_by_id(dismissed_user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        self.suggestion_repository
+            .dismiss(user_id, dismissed_user_id)
+            .await
+    }
+
+    /// Compute the viewer's suggestions and store them for later requests
+    pub async fn refresh_suggestions(&self, user_id: Uuid) -> Result<Vec<FollowSuggestion>> {
+        let suggestions = self.compute_suggestions(user_id).await?;
+        if let Some(ref cache) = self.suggestion_cache {
+            if let Err(e) = cache.save(user_id, &suggestions).await {
+                tracing::warn!("Failed to store suggestions for user {}: {}", user_id, e);
+            }
+        }
+        Ok(suggestions)
+    }
+
+    /// Recompute the suggestions of users who looked at theirs recently.
+    /// Returns how many users were refreshed; one user failing does not stop
+    /// the rest.
+    pub async fn refresh_active_users(&self, now: DateTime<Utc>) -> Result<usize> {
+        let Some(ref cache) = self.suggestion_cache else {
+            return Ok(0);
+        };
+        let active = cache
+            .active_since(
+                now - chrono::Duration::days(SUGGESTION_ACTIVE_DAYS),
+                MAX_SUGGESTION_REFRESHES,
+            )
+            .await?;
+
+        let mut refreshed = 0;
+        for user_id in active {
+            match self.refresh_suggestions(user_id).await {
+                Ok(_) => refreshed += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to refresh suggestions for user {}: {}", user_id, e)
+                }
+            }
+        }
+        Ok(refreshed)
+    }
+
+    /// Rank the users around the viewer's network: followed by the accounts
+    /// they follow or by their followers, following them back, in their
+    /// phone contacts, or in conversations with them
+    async fn compute_suggestions(&self, user_id: Uuid) -> Result<Vec<FollowSuggestion>> {
+        let following: HashSet<Uuid> = self
+            .user_repository
+            .get_following(user_id, PageStart::FIRST, SUGGESTION_NETWORK_SIZE)
+            .await?
+            .into_iter()
+            .map(|(user, _)| user.id)
+            .collect();
+        let followers: Vec<Uuid> = self
+            .user_repository
+            .get_followers(user_id, PageStart::FIRST, SUGGESTION_NETWORK_SIZE)
+            .await?
+            .into_iter()
+            .map(|(user, _)| user.id)
+            .collect();
+
+        let mut candidates: HashMap<Uuid, FollowSuggestion> = HashMap::new();
+
+        // Who the viewer's network follows, the accounts they follow first
+        let sources = following
+            .iter()
+            .map(|id| (*id, true))
+            .chain(
+                followers
+                    .iter()
+                    .filter(|id| !following.contains(id))
+                    .map(|id| (*id, false)),
+            )
+            .take(SUGGESTION_SOURCES);
+        for (source_id, followed) in sources {
+            let source_following = self
+                .user_repository
+                .get_following(source_id, PageStart::FIRST, SUGGESTION_SOURCE_FOLLOWING)
+                .await?;
+            for (user, _) in source_following {
+                let suggestion = suggestion_for(&mut candidates, user.id);
+                suggestion.network_followers += 1;
+                if followed {
+                    suggestion.mutual_follows += 1;
+                }
+            }
+        }
+
+        for follower_id in &followers {
+            suggestion_for(&mut candidates, *follower_id).follows_you = true;
+        }
+        for contact_id in self
+            .suggestion_repository
+            .matched_contact_ids(user_id)
+            .await?
+        {
+            suggestion_for(&mut candidates, contact_id).in_contacts = true;
+        }
+        let conversations = self
+            .conversation_repository
+            .find_by_user(user_id, PageStart::FIRST, SUGGESTION_CONVERSATIONS)
+            .await?;
+        for conversation in conversations {
+            for participant_id in conversation.participant_ids {
+                suggestion_for(&mut candidates, participant_id).shared_conversations += 1;
+            }
+        }
+
+        let mut excluded = self.suggestion_repository.dismissed_ids(user_id).await?;
+        excluded.extend(self.user_repository.blocked_user_ids(user_id).await?);
+        excluded.extend(following);
+        excluded.insert(user_id);
+
+        let mut suggestions: Vec<FollowSuggestion> = candidates
+            .into_values()
+            .filter(|suggestion| !excluded.contains(&suggestion.user_id))
+            .map(FollowSuggestion::scored)
+            .collect();
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.user_id.cmp(&a.user_id)));
+        suggestions.truncate(MAX_SUGGESTIONS);
+        Ok(suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockSuggestionRepository {
+        contacts: Mutex<Vec<(Uuid, Uuid)>>,
+        dismissed: Mutex<HashSet<(Uuid, Uuid)>>,
+    }
+
+    #[async_trait]
+    impl SuggestionRepository for MockSuggestionRepository {
+        async fn matched_contact_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>> {
+            Ok(self
+                .contacts
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(owner_id, _)| *owner_id == user_id)
+                .map(|(_, contact_id)| *contact_id)
+                .collect())
+        }
+
+        async fn dismiss(&self, user_id: Uuid, dismissed_user_id: Uuid) -> Result<()> {
+            self.dismissed
+                .lock()
+                .unwrap()
+                .insert((user_id, dismissed_user_id));
+            Ok(())
+        }
+
+        async fn dismissed_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>> {
+            Ok(self
+                .dismissed
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(owner_id, _)| *owner_id == user_id)
+                .map(|(_, dismissed_id)| *dismissed_id)
+                .collect())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockSuggestionCache {
+        suggestions: Mutex<HashMap<Uuid, Vec<FollowSuggestion>>>,
+        active: Mutex<HashMap<Uuid, DateTime<Utc>>>,
+    }
+
+    #[async_trait]
+    impl SuggestionCacheRepository for MockSuggestionCache {
+        async fn find(&self, user_id: Uuid) -> Result<Option<Vec<FollowSuggestion>>> {
+            Ok(self.suggestions.lock().unwrap().get(&user_id).cloned())
+        }
+
+        async fn save(&self, user_id: Uuid, suggestions: &[FollowSuggestion]) -> Result<()> {
+            self.suggestions
+                .lock()
+                .unwrap()
+                .insert(user_id, suggestions.to_vec());
+            Ok(())
+        }
+
+        async fn mark_active(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<()> {
+            self.active.lock().unwrap().insert(user_id, at);
+            Ok(())
+        }
+
+        async fn active_since(&self, since: DateTime<Utc>, limit: usize) -> Result<Vec<Uuid>> {
+            let mut active: Vec<(Uuid, DateTime<Utc>)> = self
+                .active
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, at)| **at >= since)
+                .map(|(id, at)| (*id, *at))
+                .collect();
+            active.sort_by_key(|(_, at)| std::cmp::Reverse(*at));
+            Ok(active.into_iter().take(limit).map(|(id, _)| id).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_suggestions_rank_mutual_follows_contacts_and_conversations() {
+        let users = Arc::new(TestUserRepository::new());
+        let conversations = Arc::new(MockConversationRepository::default());
+        let suggestions = Arc::new(MockSuggestionRepository::default());
+        let service = SuggestionService::new(
+            users.clone(),
+            conversations.clone(),
+            suggestions.clone(),
+            Some(Arc::new(MockSuggestionCache::default())),
+        );
+
+        let viewer = add_test_user(&users, "viewer").id;
+        let friend_a = add_test_user(&users, "friend_a").id;
+        let friend_b = add_test_user(&users, "friend_b").id;
+        let popular = add_test_user(&users, "popular").id;
+        let niche = add_test_user(&users, "niche").id;
+        let contact = add_test_user(&users, "contact").id;
+        let chat_partner = add_test_user(&users, "chat_partner").id;
+        let stranger = add_test_user(&users, "stranger").id;
+
+        users.follow(viewer, friend_a).await.unwrap();
+        users.follow(viewer, friend_b).await.unwrap();
+        users.follow(friend_a, popular).await.unwrap();
+        users.follow(friend_b, popular).await.unwrap();
+        users.follow(friend_a, niche).await.unwrap();
+        users.follow(friend_a, viewer).await.unwrap();
+        suggestions.contacts.lock().unwrap().push((viewer, contact));
+        conversations
+            .create(
+                Uuid::new_v4(),
+                vec![viewer, chat_partner],
+                false,
+                None,
+                viewer,
+            )
+            .await
+            .unwrap();
+
+        let suggestions = service.get_suggestions(viewer, 10).await.unwrap();
+        let ids: Vec<Uuid> = suggestions.iter().map(|(user, _)| user.id).collect();
+
+        assert!(!ids.contains(&viewer));
+        assert!(!ids.contains(&friend_a));
+        assert!(!ids.contains(&friend_b));
+        assert!(!ids.contains(&stranger));
+        assert_eq!(ids.len(), 4);
+        // Two mutual follows outrank one, contacts outrank a single mutual
+        // follow, and a shared conversation alone ranks last
+        let position = |id: Uuid| ids.iter().position(|candidate| *candidate == id).unwrap();
+        assert!(position(contact) < position(niche));
+        assert!(position(popular) < position(niche));
+        assert_eq!(position(chat_partner), 3);
+
+        let (_, popular_suggestion) = &suggestions[position(popular)];
+        assert_eq!(popular_suggestion.mutual_follows, 2);
+        let (_, contact_suggestion) = &suggestions[position(contact)];
+        assert!(contact_suggestion.in_contacts);
+        let (_, chat_suggestion) = &suggestions[position(chat_partner)];
+        assert_eq!(chat_suggestion.shared_conversations, 1);
+    }
+
+    #[tokio::test]
+    async fn test_suggestions_leave_out_followed_blocked_and_dismissed_users() {
+        let users = Arc::new(TestUserRepository::new());
+        let suggestions = Arc::new(MockSuggestionRepository::default());
+        let cache = Arc::new(MockSuggestionCache::default());
+        let service = SuggestionService::new(
+            users.clone(),
+            Arc::new(MockConversationRepository::default()),
+            suggestions.clone(),
+            Some(cache.clone()),
+        );
+
+        let viewer = add_test_user(&users, "viewer").id;
+        let blocked = add_test_user(&users, "blocked").id;
+        let blocker = add_test_user(&users, "blocker").id;
+        let dismissed = add_test_user(&users, "dismissed").id;
+        let followed_later = add_test_user(&users, "followed_later").id;
+        for contact_id in [blocked, blocker, dismissed, followed_later] {
+            suggestions
+                .contacts
+                .lock()
+                .unwrap()
+                .push((viewer, contact_id));
+        }
+        users.block(viewer, blocked);
+        users.block(blocker, viewer);
+
+        let suggestions = service.get_suggestions(viewer, 10).await.unwrap();
+        let ids: Vec<Uuid> = suggestions.iter().map(|(user, _)| user.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&dismissed));
+        assert!(ids.contains(&followed_later));
+
+        // Stored suggestions are filtered again when read
+        service.dismiss_suggestion(viewer, dismissed).await.unwrap();
+        users.follow(viewer, followed_later).await.unwrap();
+        assert!(cache.find(viewer).await.unwrap().is_some());
+        let before = users.relationship_lookups();
+        let suggestions = service.get_suggestions(viewer, 10).await.unwrap();
+        assert!(suggestions.is_empty());
+        // Follows are checked for all the stored suggestions at once
+        assert_eq!(users.relationship_lookups() - before, 1);
+
+        assert!(matches!(
+            service.dismiss_suggestion(viewer, viewer).await,
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            service.dismiss_suggestion(viewer, Uuid::new_v4()).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_recomputes_suggestions_of_active_users() {
+        let users = Arc::new(TestUserRepository::new());
+        let suggestions = Arc::new(MockSuggestionRepository::default());
+        let cache = Arc::new(MockSuggestionCache::default());
+        let service = SuggestionService::new(
+            users.clone(),
+            Arc::new(MockConversationRepository::default()),
+            suggestions.clone(),
+            Some(cache.clone()),
+        );
+
+        let viewer = add_test_user(&users, "viewer").id;
+        let idle = add_test_user(&users, "idle").id;
+        let contact = add_test_user(&users, "contact").id;
+
+        assert!(service
+            .get_suggestions(viewer, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        cache.active.lock().unwrap().insert(
+            idle,
+            Utc::now() - chrono::Duration::days(SUGGESTION_ACTIVE_DAYS + 1),
+        );
+
+        // The stored empty list is served until the next refresh
+        suggestions.contacts.lock().unwrap().push((viewer, contact));
+        assert!(service
+            .get_suggestions(viewer, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let refreshed = service.refresh_active_users(Utc::now()).await.unwrap();
+        assert_eq!(refreshed, 1);
+        assert!(cache.find(idle).await.unwrap().is_none());
+        let suggestions = service.get_suggestions(viewer, 10).await.unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].0.id, contact);
+    }
+}
@@ -0,0 +1,1596 @@
+//! Repositories and fixtures shared by the service tests. Everything is kept
+//! in memory; a lazily connected pool stands in where a Postgres repository
+//! is unavoidable and is never queried.
+use super::*;
+use crate::domain::entities::{
+    ConversationSummary, CreatePostRequest, CreateUserRequest, HashtagCount, MediaUpload, Mention,
+    Message, PostSearchHit, PostSearchQuery, PostStatus, PostVisibility, SearchSnippet,
+    TimelineEntry, SEARCH_HIGHLIGHT_END, SEARCH_HIGHLIGHT_START,
+};
+use crate::domain::repositories::{
+    ConversationRepository, MediaUploadRepository, MentionRepository, MessageRepository,
+    TimelineRepository, WalletRepository,
+};
+use crate::infrastructure::database::repositories::{
+    InMemoryNotificationPreferencesRepository, PostgresDeviceTokenRepository,
+};
+use crate::infrastructure::media_processing::{
+    parse_ffprobe_output, MediaProber, PosterFrameExtractor, ProbeError, ProbedMedia,
+};
+use crate::infrastructure::storage::{MediaStorage, PresignedUpload};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Wallet repository for services that need one but never touch wallets
+pub(super) struct MockWalletRepository;
+
+#[async_trait]
+impl WalletRepository for MockWalletRepository {
+    async fn create(
+        &self,
+        _wallet: &crate::domain::entities::Wallet,
+    ) -> Result<crate::domain::entities::Wallet> {
+        unimplemented!()
+    }
+
+    async fn find_by_id(&self, _id: Uuid) -> Result<Option<crate::domain::entities::Wallet>> {
+        unimplemented!()
+    }
+
+    async fn find_wallets_by_ids(
+        &self,
+        _ids: &[Uuid],
+    ) -> Result<Vec<crate::domain::entities::Wallet>> {
+        unimplemented!()
+    }
+
+    async fn find_by_user_id(
+        &self,
+        _user_id: Uuid,
+    ) -> Result<Option<crate::domain::entities::Wallet>> {
+        unimplemented!()
+    }
+
+    async fn update(
+        &self,
+        _wallet: &crate::domain::entities::Wallet,
+    ) -> Result<crate::domain::entities::Wallet> {
+        unimplemented!()
+    }
+
+    async fn update_balance(&self, _wallet_id: Uuid, _amount: Decimal) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn credit_balance(&self, _wallet_id: Uuid, _amount: Decimal) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn debit_balance(&self, _wallet_id: Uuid, _amount: Decimal) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn get_balance(&self, _wallet_id: Uuid) -> Result<Decimal> {
+        unimplemented!()
+    }
+
+    async fn has_sufficient_balance(&self, _wallet_id: Uuid, _amount: Decimal) -> Result<bool> {
+        unimplemented!()
+    }
+
+    async fn lock_wallet(&self, _wallet_id: Uuid) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn unlock_wallet(&self, _wallet_id: Uuid) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn create_transaction(
+        &self,
+        _transaction: &crate::domain::entities::Transaction,
+    ) -> Result<crate::domain::entities::Transaction> {
+        unimplemented!()
+    }
+
+    async fn find_transaction_by_id(
+        &self,
+        _id: Uuid,
+    ) -> Result<Option<crate::domain::entities::Transaction>> {
+        unimplemented!()
+    }
+
+    async fn find_transaction_by_reference(
+        &self,
+        _reference: &str,
+    ) -> Result<Option<crate::domain::entities::Transaction>> {
+        unimplemented!()
+    }
+
+    async fn update_transaction(
+        &self,
+        _transaction: &crate::domain::entities::Transaction,
+    ) -> Result<crate::domain::entities::Transaction> {
+        unimplemented!()
+    }
+
+    async fn get_transaction_history(
+        &self,
+        _wallet_id: Uuid,
+        _page: PageStart,
+        _limit: i64,
+    ) -> Result<Vec<crate::domain::entities::Transaction>> {
+        unimplemented!()
+    }
+
+    async fn get_pending_transactions(
+        &self,
+        _wallet_id: Uuid,
+    ) -> Result<Vec<crate::domain::entities::Transaction>> {
+        unimplemented!()
+    }
+
+    async fn process_transfer(
+        &self,
+        _sender_wallet_id: Uuid,
+        _receiver_wallet_id: Uuid,
+        _amount: Decimal,
+        _transaction: &crate::domain::entities::Transaction,
+    ) -> Result<crate::domain::entities::Transaction> {
+        unimplemented!()
+    }
+}
+
+/// Users, follows, blocks and mutes in memory
+pub(super) struct TestUserRepository {
+    users: Mutex<HashMap<Uuid, User>>,
+    follows: Mutex<HashMap<(Uuid, Uuid), bool>>,
+    blocks: Mutex<Vec<(Uuid, Uuid)>>,
+    mutes: Mutex<HashSet<(Uuid, Uuid)>>,
+    relationship_lookups: AtomicUsize,
+}
+
+impl TestUserRepository {
+    pub(super) fn new() -> Self {
+        Self {
+            users: Mutex::new(HashMap::new()),
+            follows: Mutex::new(HashMap::new()),
+            blocks: Mutex::new(Vec::new()),
+            mutes: Mutex::new(HashSet::new()),
+            relationship_lookups: AtomicUsize::new(0),
+        }
+    }
+
+    /// How many follow and block lookups have been made
+    pub(super) fn relationship_lookups(&self) -> usize {
+        self.relationship_lookups.load(Ordering::SeqCst)
+    }
+
+    fn count_lookup(&self) {
+        self.relationship_lookups.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(super) fn add_user(&self, user: User) {
+        self.users.lock().unwrap().insert(user.id, user);
+    }
+
+    pub(super) fn block(&self, blocker_id: Uuid, blocked_id: Uuid) {
+        self.blocks.lock().unwrap().push((blocker_id, blocked_id));
+    }
+}
+
+#[async_trait]
+impl UserRepository for TestUserRepository {
+    async fn create(&self, user: &User) -> Result<User> {
+        self.users.lock().unwrap().insert(user.id, user.clone());
+        Ok(user.clone())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>> {
+        Ok(self.users.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>> {
+        let users = self.users.lock().unwrap();
+        Ok(ids.iter().filter_map(|id| users.get(id).cloned()).collect())
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .find(|u| u.username.value() == username)
+            .cloned())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .find(|u| u.email.value() == email)
+            .cloned())
+    }
+
+    async fn find_by_phone_number(&self, phone_number: &str) -> Result<Option<User>> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .find(|u| u.phone_number.as_ref().map(|p| p.value()) == Some(phone_number))
+            .cloned())
+    }
+
+    async fn update(&self, user: &User) -> Result<User> {
+        self.users.lock().unwrap().insert(user.id, user.clone());
+        Ok(user.clone())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.users.lock().unwrap().remove(&id);
+        Ok(())
+    }
+
+    async fn username_exists(&self, username: &str) -> Result<bool> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .any(|u| u.username.value() == username))
+    }
+
+    async fn email_exists(&self, email: &str) -> Result<bool> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .any(|u| u.email.value() == email))
+    }
+
+    async fn search(&self, query: &str, _limit: i64, _offset: i64) -> Result<Vec<User>> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|u| {
+                u.username.value().contains(query)
+                    || u.display_name
+                        .as_ref()
+                        .map(|d| d.value().contains(query))
+                        .unwrap_or(false)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn get_followers(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>> {
+        let follows = self.follows.lock().unwrap();
+        let users = self.users.lock().unwrap();
+        // Follows are not timestamped here, so list followers by signup
+        let mut followers: Vec<(User, DateTime<Utc>)> = follows
+            .keys()
+            .filter(|(_, following_id)| *following_id == user_id)
+            .filter_map(|(follower_id, _)| users.get(follower_id).cloned())
+            .map(|user| {
+                let followed_at = user.created_at;
+                (user, followed_at)
+            })
+            .collect();
+        followers.sort_by(|a, b| b.1.cmp(&a.1).then(b.0.id.cmp(&a.0.id)));
+
+        Ok(followers
+            .into_iter()
+            .filter(|(user, followed_at)| match page.cursor() {
+                Some(cursor) => (*followed_at, user.id) < (cursor.created_at, cursor.id),
+                None => true,
+            })
+            .skip(page.offset() as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn get_following(
+        &self,
+        user_id: Uuid,
+        _page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>> {
+        let follows = self.follows.lock().unwrap();
+        let users = self.users.lock().unwrap();
+        Ok(follows
+            .keys()
+            .filter(|(follower_id, _)| *follower_id == user_id)
+            .filter_map(|(_, following_id)| users.get(following_id).cloned())
+            .map(|user| {
+                let followed_at = user.created_at;
+                (user, followed_at)
+            })
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn follower_count(&self, user_id: Uuid) -> Result<Option<i32>> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .map(|user| user.follower_count))
+    }
+
+    async fn is_following(&self, follower_id: Uuid, following_id: Uuid) -> Result<bool> {
+        self.count_lookup();
+        Ok(self
+            .follows
+            .lock()
+            .unwrap()
+            .get(&(follower_id, following_id))
+            .copied()
+            .unwrap_or(false))
+    }
+
+    async fn follow(&self, follower_id: Uuid, following_id: Uuid) -> Result<()> {
+        self.follows
+            .lock()
+            .unwrap()
+            .insert((follower_id, following_id), true);
+
+        // Update follower counts
+        if let Some(follower) = self.users.lock().unwrap().get_mut(&follower_id) {
+            follower.increment_following_count();
+        }
+        if let Some(following) = self.users.lock().unwrap().get_mut(&following_id) {
+            following.increment_follower_count();
+        }
+
+        Ok(())
+    }
+
+    async fn unfollow(&self, follower_id: Uuid, following_id: Uuid) -> Result<()> {
+        self.follows
+            .lock()
+            .unwrap()
+            .remove(&(follower_id, following_id));
+
+        // Update follower counts
+        if let Some(follower) = self.users.lock().unwrap().get_mut(&follower_id) {
+            follower.decrement_following_count();
+        }
+        if let Some(following) = self.users.lock().unwrap().get_mut(&following_id) {
+            following.decrement_follower_count();
+        }
+
+        Ok(())
+    }
+
+    async fn has_blocked(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<bool> {
+        self.count_lookup();
+        Ok(self
+            .blocks
+            .lock()
+            .unwrap()
+            .contains(&(blocker_id, blocked_id)))
+    }
+
+    async fn mute(&self, user_id: Uuid, muted_user_id: Uuid) -> Result<()> {
+        self.mutes.lock().unwrap().insert((user_id, muted_user_id));
+        Ok(())
+    }
+
+    async fn unmute(&self, user_id: Uuid, muted_user_id: Uuid) -> Result<()> {
+        self.mutes.lock().unwrap().remove(&(user_id, muted_user_id));
+        Ok(())
+    }
+
+    async fn muted_user_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>> {
+        Ok(self
+            .mutes
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(muter_id, _)| *muter_id == user_id)
+            .map(|(_, muted_id)| *muted_id)
+            .collect())
+    }
+
+    async fn blocked_user_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>> {
+        Ok(self
+            .blocks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(blocker_id, blocked_id)| {
+                if *blocker_id == user_id {
+                    Some(*blocked_id)
+                } else if *blocked_id == user_id {
+                    Some(*blocker_id)
+                } else {
+                    None
+                }
+            })
+            .collect())
+    }
+
+    async fn blocked_ids(&self, user_id: Uuid, user_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        self.count_lookup();
+        let blocks = self.blocks.lock().unwrap();
+        Ok(user_ids
+            .iter()
+            .filter(|id| blocks.contains(&(user_id, **id)) || blocks.contains(&(**id, user_id)))
+            .copied()
+            .collect())
+    }
+
+    async fn followed_ids(&self, follower_id: Uuid, user_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        self.count_lookup();
+        let follows = self.follows.lock().unwrap();
+        Ok(user_ids
+            .iter()
+            .filter(|id| follows.get(&(follower_id, **id)).copied().unwrap_or(false))
+            .copied()
+            .collect())
+    }
+}
+
+/// Create a user and add them to the repository
+pub(super) fn add_test_user(users: &TestUserRepository, username: &str) -> User {
+    let user = create_test_user(username, &format!("{}@example.com", username));
+    users.add_user(user.clone());
+    user
+}
+
+pub(super) fn create_test_user(username: &str, email: &str) -> User {
+    let request = CreateUserRequest {
+        username: username.to_string(),
+        email: email.to_string(),
+        phone_number: None,
+        password_hash: "test_hash".to_string(),
+        display_name: Some("Test User".to_string()),
+        bio: None,
+    };
+    User::new(request).unwrap()
+}
+
+/// Page through posts newest first, as the database does
+fn page_newest_first(mut posts: Vec<Post>, page: PageStart, limit: i64) -> Vec<Post> {
+    posts.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    posts
+        .into_iter()
+        .filter(|post| match page.cursor() {
+            Some(cursor) => (post.created_at, post.id) < (cursor.created_at, cursor.id),
+            None => true,
+        })
+        .skip(page.offset() as usize)
+        .take(limit.max(0) as usize)
+        .collect()
+}
+
+/// Posts, likes and feeds in memory
+pub(super) struct MockPostRepository {
+    posts: Mutex<HashMap<Uuid, Post>>,
+    user_feeds: Mutex<HashMap<Uuid, Vec<Uuid>>>, // user_id -> post_ids
+    likes: Mutex<HashSet<(Uuid, Uuid)>>,         // (user_id, post_id)
+    popular_authors: Mutex<HashSet<Uuid>>,
+    followed_likes: Mutex<HashMap<Uuid, i64>>, // post_id -> likes from followed accounts
+}
+
+impl MockPostRepository {
+    pub(super) fn new() -> Self {
+        Self {
+            posts: Mutex::new(HashMap::new()),
+            user_feeds: Mutex::new(HashMap::new()),
+            likes: Mutex::new(HashSet::new()),
+            popular_authors: Mutex::new(HashSet::new()),
+            followed_likes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a post as liked by this many accounts the viewer follows
+    pub(super) fn set_followed_likes(&self, post_id: Uuid, count: i64) {
+        self.followed_likes.lock().unwrap().insert(post_id, count);
+    }
+
+    /// Treat an author as having too many followers to fan out to
+    pub(super) fn mark_popular(&self, author_id: Uuid) {
+        self.popular_authors.lock().unwrap().insert(author_id);
+    }
+
+    pub(super) fn add_post(&self, post: Post) {
+        self.posts.lock().unwrap().insert(post.id, post);
+    }
+
+    pub(super) fn set_user_feed(&self, user_id: Uuid, post_ids: Vec<Uuid>) {
+        self.user_feeds.lock().unwrap().insert(user_id, post_ids);
+    }
+}
+
+#[async_trait]
+impl PostRepository for MockPostRepository {
+    async fn create(&self, post: &Post) -> Result<Post> {
+        self.posts.lock().unwrap().insert(post.id, post.clone());
+        Ok(post.clone())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Post>> {
+        Ok(self.posts.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Post>> {
+        let posts = self.posts.lock().unwrap();
+        Ok(ids.iter().filter_map(|id| posts.get(id).cloned()).collect())
+    }
+
+    async fn update(&self, post: &Post) -> Result<Post> {
+        self.posts.lock().unwrap().insert(post.id, post.clone());
+        Ok(post.clone())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.posts.lock().unwrap().remove(&id);
+        Ok(())
+    }
+
+    async fn find_feed(&self, user_id: Uuid, page: PageStart, limit: i64) -> Result<Vec<Post>> {
+        let feeds = self.user_feeds.lock().unwrap();
+        let posts = self.posts.lock().unwrap();
+
+        let user_posts: Vec<Post> = feeds
+            .get(&user_id)
+            .map(|post_ids| {
+                post_ids
+                    .iter()
+                    .filter_map(|id| posts.get(id).cloned())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(page_newest_first(user_posts, page, limit))
+    }
+
+    async fn find_feed_from_popular(
+        &self,
+        user_id: Uuid,
+        _min_followers: i32,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let popular = self.popular_authors.lock().unwrap().clone();
+        let page = after.map(PageStart::After).unwrap_or(PageStart::FIRST);
+        let mut posts = self.find_feed(user_id, page, i64::MAX).await?;
+        posts.retain(|post| popular.contains(&post.user_id));
+        posts.truncate(limit as usize);
+        Ok(posts)
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<Post>> {
+        let posts = self.posts.lock().unwrap();
+        let mut user_posts: Vec<Post> = posts
+            .values()
+            .filter(|p| p.user_id == user_id)
+            .cloned()
+            .collect();
+
+        // Pinned first (most recently pinned first), then newest first
+        user_posts.sort_by(|a, b| match (a.pinned_at, b.pinned_at) {
+            (Some(a_pin), Some(b_pin)) => b_pin.cmp(&a_pin),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => b.created_at.cmp(&a.created_at),
+        });
+
+        let start = offset as usize;
+        let end = (start + limit as usize).min(user_posts.len());
+
+        if start >= user_posts.len() {
+            Ok(vec![])
+        } else {
+            Ok(user_posts[start..end].to_vec())
+        }
+    }
+
+    async fn find_explore_candidates(
+        &self,
+        viewer_id: Uuid,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<(Post, i64)>> {
+        let posts = self.posts.lock().unwrap();
+        // Authors in the viewer's feed stand in for the accounts they follow
+        let followed: HashSet<Uuid> = self
+            .user_feeds
+            .lock()
+            .unwrap()
+            .get(&viewer_id)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| posts.get(id).map(|post| post.user_id))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let followed_likes = self.followed_likes.lock().unwrap();
+
+        let mut candidates: Vec<(Post, i64)> = posts
+            .values()
+            .filter(|post| {
+                post.visibility == PostVisibility::Public
+                    && post.status == PostStatus::Published
+                    && post.created_at >= since
+                    && post.user_id != viewer_id
+                    && !followed.contains(&post.user_id)
+            })
+            .map(|post| {
+                let likes = followed_likes.get(&post.id).copied().unwrap_or(0);
+                (post.clone(), likes)
+            })
+            .collect();
+        candidates.sort_by_key(|(post, likes)| {
+            (
+                std::cmp::Reverse(*likes),
+                std::cmp::Reverse(post.like_count + post.comment_count + post.reshare_count * 2),
+                std::cmp::Reverse(post.created_at),
+            )
+        });
+        candidates.truncate(limit.max(0) as usize);
+        Ok(candidates)
+    }
+
+    async fn find_liked_by_user(
+        &self,
+        user_id: Uuid,
+        _since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let posts = self.posts.lock().unwrap();
+        Ok(self
+            .likes
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(liker_id, _)| *liker_id == user_id)
+            .filter_map(|(_, post_id)| posts.get(post_id).cloned())
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn find_public(&self, limit: i64, offset: i64) -> Result<Vec<Post>> {
+        let posts = self.posts.lock().unwrap();
+        let mut public_posts: Vec<Post> = posts
+            .values()
+            .filter(|p| p.visibility == PostVisibility::Public)
+            .cloned()
+            .collect();
+
+        public_posts.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        let start = offset as usize;
+        let end = (start + limit as usize).min(public_posts.len());
+
+        if start >= public_posts.len() {
+            Ok(vec![])
+        } else {
+            Ok(public_posts[start..end].to_vec())
+        }
+    }
+
+    async fn find_reels(
+        &self,
+        user_id: Option<Uuid>,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let posts = self.posts.lock().unwrap();
+        let feeds = self.user_feeds.lock().unwrap();
+
+        let reels = if let Some(uid) = user_id {
+            // Get reels from user's feed
+            if let Some(post_ids) = feeds.get(&uid) {
+                post_ids
+                    .iter()
+                    .filter_map(|id| posts.get(id))
+                    .filter(|p| p.is_reel)
+                    .cloned()
+                    .collect()
+            } else {
+                vec![]
+            }
+        } else {
+            // Get all public reels
+            posts
+                .values()
+                .filter(|p| p.is_reel && p.visibility == PostVisibility::Public)
+                .cloned()
+                .collect()
+        };
+
+        Ok(page_newest_first(reels, page, limit))
+    }
+
+    /// Match posts containing every query word, ranked by how often
+    /// the words appear. Visibility is left to the caller.
+    async fn search(
+        &self,
+        _viewer_id: Option<Uuid>,
+        query: &PostSearchQuery,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PostSearchHit>> {
+        let terms: Vec<String> = query
+            .text
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+        let mut hits: Vec<PostSearchHit> = self
+            .posts
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|post| post.is_published())
+            .filter(|post| query.author_id.is_none_or(|id| post.user_id == id))
+            .filter(|post| query.since.is_none_or(|since| post.created_at >= since))
+            .filter(|post| query.until.is_none_or(|until| post.created_at < until))
+            .filter(|post| {
+                query
+                    .content_type
+                    .as_ref()
+                    .is_none_or(|content_type| post.content_type == *content_type)
+            })
+            .filter(|post| !query.reels_only || post.is_reel)
+            .filter_map(|post| {
+                let text = post.text_content.clone().unwrap_or_default();
+                if let Some(ref hashtag) = query.hashtag {
+                    if !extract_hashtags(&text).contains(hashtag) {
+                        return None;
+                    }
+                }
+                let words: Vec<&str> = text.split_whitespace().collect();
+                let is_match =
+                    |word: &str, term: &str| word.to_lowercase().trim_start_matches('#') == term;
+                if !terms
+                    .iter()
+                    .all(|term| words.iter().any(|word| is_match(word, term)))
+                {
+                    return None;
+                }
+                let rank = words
+                    .iter()
+                    .filter(|word| terms.iter().any(|term| is_match(word, term)))
+                    .count() as f32;
+                let headline: Vec<String> = words
+                    .iter()
+                    .map(|word| {
+                        if terms.iter().any(|term| is_match(word, term)) {
+                            format!("{}{}{}", SEARCH_HIGHLIGHT_START, word, SEARCH_HIGHLIGHT_END)
+                        } else {
+                            word.to_string()
+                        }
+                    })
+                    .collect();
+                Some(PostSearchHit {
+                    post: post.clone(),
+                    rank,
+                    snippet: SearchSnippet::from_headline(&headline.join(" ")),
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.rank
+                .total_cmp(&a.rank)
+                .then(b.post.created_at.cmp(&a.post.created_at))
+                .then(b.post.id.cmp(&a.post.id))
+        });
+        Ok(hits
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn search_hashtags(&self, prefix: &str, limit: i64) -> Result<Vec<HashtagCount>> {
+        let mut counts: HashMap<String, i64> = HashMap::new();
+        for post in self.posts.lock().unwrap().values() {
+            if post.visibility != PostVisibility::Public || !post.is_published() {
+                continue;
+            }
+            let text = post.text_content.as_deref().unwrap_or_default();
+            for tag in extract_hashtags(text) {
+                if tag.starts_with(prefix) {
+                    *counts.entry(tag).or_default() += 1;
+                }
+            }
+        }
+        let mut hashtags: Vec<HashtagCount> = counts
+            .into_iter()
+            .map(|(tag, post_count)| HashtagCount { tag, post_count })
+            .collect();
+        hashtags.sort_by(|a, b| b.post_count.cmp(&a.post_count).then(a.tag.cmp(&b.tag)));
+        hashtags.truncate(limit as usize);
+        Ok(hashtags)
+    }
+
+    async fn increment_like_count(&self, _post_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn decrement_like_count(&self, _post_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn increment_comment_count(&self, _post_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn decrement_comment_count(&self, _post_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn increment_reshare_count(&self, _post_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn decrement_reshare_count(&self, _post_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn has_user_liked(&self, user_id: Uuid, post_id: Uuid) -> Result<bool> {
+        Ok(self.likes.lock().unwrap().contains(&(user_id, post_id)))
+    }
+
+    async fn liked_post_ids(&self, user_id: Uuid, post_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        let likes = self.likes.lock().unwrap();
+        Ok(post_ids
+            .iter()
+            .filter(|post_id| likes.contains(&(user_id, **post_id)))
+            .copied()
+            .collect())
+    }
+
+    async fn count_interactions_with_authors(
+        &self,
+        user_id: Uuid,
+        author_ids: &[Uuid],
+        _since: DateTime<Utc>,
+    ) -> Result<HashMap<Uuid, i64>> {
+        let posts = self.posts.lock().unwrap();
+        let mut counts = HashMap::new();
+        for (liker_id, post_id) in self.likes.lock().unwrap().iter() {
+            if *liker_id != user_id {
+                continue;
+            }
+            if let Some(post) = posts.get(post_id) {
+                if author_ids.contains(&post.user_id) {
+                    *counts.entry(post.user_id).or_insert(0) += 1;
+                }
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn like_post(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+        self.likes.lock().unwrap().insert((user_id, post_id));
+        Ok(())
+    }
+
+    async fn unlike_post(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+        self.likes.lock().unwrap().remove(&(user_id, post_id));
+        Ok(())
+    }
+
+    async fn get_post_likes(&self, _post_id: Uuid, _limit: i64, _offset: i64) -> Result<Vec<User>> {
+        Ok(vec![])
+    }
+
+    async fn find_mentioning_user(
+        &self,
+        _user_id: Uuid,
+        _page: PageStart,
+        _limit: i64,
+    ) -> Result<Vec<Post>> {
+        Ok(vec![])
+    }
+
+    async fn find_unpublished_by_user(
+        &self,
+        user_id: Uuid,
+        _limit: i64,
+        _offset: i64,
+    ) -> Result<Vec<Post>> {
+        let posts = self.posts.lock().unwrap();
+        Ok(posts
+            .values()
+            .filter(|p| p.user_id == user_id && !p.is_published())
+            .cloned()
+            .collect())
+    }
+
+    async fn find_due_scheduled(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Post>> {
+        let posts = self.posts.lock().unwrap();
+        let mut due: Vec<Post> = posts
+            .values()
+            .filter(|p| {
+                p.status == PostStatus::Scheduled && p.publish_at.is_some_and(|at| at <= now)
+            })
+            .cloned()
+            .collect();
+
+        due.sort_by_key(|p| p.publish_at);
+        due.truncate(limit as usize);
+        Ok(due)
+    }
+
+    async fn publish_scheduled(&self, post_id: Uuid, _now: DateTime<Utc>) -> Result<Option<Post>> {
+        let mut posts = self.posts.lock().unwrap();
+        match posts.get_mut(&post_id) {
+            Some(post) if post.status == PostStatus::Scheduled => {
+                post.publish();
+                Ok(Some(post.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn pin(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        max_pinned: i64,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let mut posts = self.posts.lock().unwrap();
+        let pinned = posts
+            .values()
+            .filter(|p| p.user_id == user_id && p.pinned_at.is_some())
+            .count() as i64;
+        if pinned >= max_pinned {
+            return Ok(false);
+        }
+        match posts.get_mut(&post_id) {
+            Some(post) if post.user_id == user_id && post.pinned_at.is_none() => {
+                post.pinned_at = Some(now);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn unpin(&self, post_id: Uuid) -> Result<()> {
+        if let Some(post) = self.posts.lock().unwrap().get_mut(&post_id) {
+            post.pinned_at = None;
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn create_test_post(user_id: Uuid, content: &str, is_reel: bool) -> Post {
+    use crate::domain::entities::MediaAttachment;
+
+    let media_attachments = if is_reel {
+        // Reels need video content the server has probed
+        let mut video = MediaAttachment::new(
+            "https://example.com/video.mp4".to_string(),
+            "video/mp4".to_string(),
+            1024 * 1024, // 1MB
+            Some(1920),
+            Some(1080),
+            Some(30), // 30 seconds
+        )
+        .unwrap();
+        video.video_codec = Some("h264".to_string());
+        vec![video]
+    } else {
+        vec![]
+    };
+
+    let request = CreatePostRequest {
+        user_id,
+        text_content: Some(content.to_string()),
+        media_attachments,
+        is_reel,
+        visibility: PostVisibility::Public,
+        poll: None,
+    };
+    Post::new(request).unwrap()
+}
+
+pub(super) struct MockMentionRepository {
+    pub(super) mentions: Mutex<Vec<Mention>>,
+    pub(super) fail: Mutex<bool>,
+}
+
+impl MockMentionRepository {
+    pub(super) fn new() -> Self {
+        Self {
+            mentions: Mutex::new(Vec::new()),
+            fail: Mutex::new(false),
+        }
+    }
+}
+
+#[async_trait]
+impl MentionRepository for MockMentionRepository {
+    async fn create_many(&self, mentions: &[Mention]) -> Result<()> {
+        if *self.fail.lock().unwrap() {
+            return Err(AppError::DatabaseError("Mentions unavailable".to_string()));
+        }
+        self.mentions.lock().unwrap().extend_from_slice(mentions);
+        Ok(())
+    }
+}
+
+pub(super) struct MockNotificationRepository {
+    pub(super) notifications: Mutex<Vec<Notification>>,
+}
+
+impl MockNotificationRepository {
+    pub(super) fn new() -> Self {
+        Self {
+            notifications: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for MockNotificationRepository {
+    async fn create(&self, notification: &Notification) -> Result<Notification> {
+        self.notifications
+            .lock()
+            .unwrap()
+            .push(notification.clone());
+        Ok(notification.clone())
+    }
+
+    async fn find_by_id(&self, _id: Uuid) -> Result<Option<Notification>> {
+        Ok(None)
+    }
+
+    async fn update(&self, notification: &Notification) -> Result<Notification> {
+        Ok(notification.clone())
+    }
+
+    async fn delete(&self, _id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn find_by_user_id(
+        &self,
+        _user_id: Uuid,
+        _page: PageStart,
+        _limit: i64,
+    ) -> Result<Vec<Notification>> {
+        Ok(vec![])
+    }
+
+    async fn find_unread_by_user_id(&self, _user_id: Uuid) -> Result<Vec<Notification>> {
+        Ok(vec![])
+    }
+
+    async fn get_unread_count(&self, _user_id: Uuid) -> Result<i64> {
+        Ok(0)
+    }
+
+    async fn mark_as_read(&self, _notification_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn mark_all_as_read(&self, _user_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_old_notifications(&self, _days: i32) -> Result<i64> {
+        Ok(0)
+    }
+
+    async fn find_by_type(
+        &self,
+        _user_id: Uuid,
+        _notification_type: &str,
+        _limit: i64,
+        _offset: i64,
+    ) -> Result<Vec<Notification>> {
+        Ok(vec![])
+    }
+}
+
+/// Notification service that keeps notifications in the given repository
+pub(super) fn notification_service(
+    users: Arc<TestUserRepository>,
+    notifications: Arc<MockNotificationRepository>,
+) -> NotificationService {
+    notification_service_with_preferences(
+        users,
+        notifications,
+        Arc::new(InMemoryNotificationPreferencesRepository::new()),
+    )
+}
+
+pub(super) fn notification_service_with_preferences(
+    users: Arc<TestUserRepository>,
+    notifications: Arc<MockNotificationRepository>,
+    preferences: Arc<InMemoryNotificationPreferencesRepository>,
+) -> NotificationService {
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    NotificationService::new(
+        notifications,
+        Arc::new(PostgresDeviceTokenRepository::new(pool)),
+        preferences,
+        users,
+    )
+}
+
+/// Mention service over the given repositories with notifications kept in memory
+pub(super) fn mention_service(
+    users: Arc<TestUserRepository>,
+    posts: Arc<MockPostRepository>,
+    mentions: Arc<MockMentionRepository>,
+) -> MentionService {
+    let notification_service = Arc::new(notification_service(
+        users.clone(),
+        Arc::new(MockNotificationRepository::new()),
+    ));
+    MentionService::new(users, posts, mentions, notification_service)
+}
+
+#[derive(Default)]
+pub(super) struct MockConversationRepository {
+    pub(super) conversations: Mutex<Vec<(Uuid, Vec<Uuid>)>>,
+}
+
+#[async_trait]
+impl ConversationRepository for MockConversationRepository {
+    async fn create(
+        &self,
+        conversation_id: Uuid,
+        participant_ids: Vec<Uuid>,
+        _is_group: bool,
+        _group_name: Option<String>,
+        _created_by: Uuid,
+    ) -> Result<Uuid> {
+        self.conversations
+            .lock()
+            .unwrap()
+            .push((conversation_id, participant_ids));
+        Ok(conversation_id)
+    }
+
+    async fn find_by_id(
+        &self,
+        _id: Uuid,
+    ) -> Result<Option<(Uuid, Vec<Uuid>, bool, Option<String>, DateTime<Utc>)>> {
+        unimplemented!()
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        _page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<ConversationSummary>> {
+        let now = Utc::now();
+        Ok(self
+            .conversations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, participants)| participants.contains(&user_id))
+            .take(limit as usize)
+            .map(|(id, participants)| ConversationSummary {
+                id: *id,
+                participant_ids: participants.clone(),
+                is_group: participants.len() > 2,
+                group_name: None,
+                created_at: now,
+                last_activity_at: now,
+            })
+            .collect())
+    }
+
+    async fn is_participant(&self, conversation_id: Uuid, user_id: Uuid) -> Result<bool> {
+        Ok(self
+            .conversations
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(id, participants)| *id == conversation_id && participants.contains(&user_id)))
+    }
+
+    async fn add_participant(&self, _conversation_id: Uuid, _user_id: Uuid) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn remove_participant(&self, _conversation_id: Uuid, _user_id: Uuid) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn get_participants(&self, _conversation_id: Uuid) -> Result<Vec<Uuid>> {
+        unimplemented!()
+    }
+
+    async fn find_direct_conversation(
+        &self,
+        user1_id: Uuid,
+        user2_id: Uuid,
+    ) -> Result<Option<Uuid>> {
+        Ok(self
+            .conversations
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, participants)| {
+                participants.len() == 2
+                    && participants.contains(&user1_id)
+                    && participants.contains(&user2_id)
+            })
+            .map(|(id, _)| *id))
+    }
+}
+
+#[derive(Default)]
+pub(super) struct MockMessageRepository {
+    pub(super) messages: Mutex<Vec<Message>>,
+}
+
+#[async_trait]
+impl MessageRepository for MockMessageRepository {
+    async fn create(&self, message: &Message) -> Result<Message> {
+        self.messages.lock().unwrap().push(message.clone());
+        Ok(message.clone())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Message>> {
+        Ok(self
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|message| message.id == id)
+            .cloned())
+    }
+
+    async fn update(&self, _message: &Message) -> Result<Message> {
+        unimplemented!()
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.messages
+            .lock()
+            .unwrap()
+            .retain(|message| message.id != id);
+        Ok(())
+    }
+
+    async fn find_by_conversation(
+        &self,
+        _conversation_id: Uuid,
+        _limit: i64,
+        _before_id: Option<Uuid>,
+    ) -> Result<Vec<Message>> {
+        unimplemented!()
+    }
+
+    async fn find_latest_in_conversation(&self, _conversation_id: Uuid) -> Result<Option<Message>> {
+        unimplemented!()
+    }
+
+    async fn search_in_conversation(
+        &self,
+        _conversation_id: Uuid,
+        _query: &str,
+        _limit: i64,
+        _offset: i64,
+    ) -> Result<Vec<Message>> {
+        unimplemented!()
+    }
+
+    async fn mark_as_read(&self, _message_id: Uuid, _user_id: Uuid) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn get_message_reads(
+        &self,
+        _message_id: Uuid,
+    ) -> Result<Vec<crate::domain::entities::MessageRead>> {
+        unimplemented!()
+    }
+
+    async fn is_read_by_user(&self, _message_id: Uuid, _user_id: Uuid) -> Result<bool> {
+        unimplemented!()
+    }
+
+    async fn get_unread_count(&self, _conversation_id: Uuid, _user_id: Uuid) -> Result<i64> {
+        unimplemented!()
+    }
+
+    async fn get_all_unread_count(&self, _user_id: Uuid) -> Result<i64> {
+        unimplemented!()
+    }
+
+    async fn find_by_type(
+        &self,
+        _conversation_id: Uuid,
+        _message_type: &str,
+        _limit: i64,
+        _offset: i64,
+    ) -> Result<Vec<Message>> {
+        unimplemented!()
+    }
+}
+
+pub(super) struct MockMediaUploadRepository {
+    uploads: Mutex<HashMap<Uuid, MediaUpload>>,
+}
+
+impl MockMediaUploadRepository {
+    pub(super) fn new() -> Self {
+        Self {
+            uploads: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl MediaUploadRepository for MockMediaUploadRepository {
+    async fn create(&self, upload: &MediaUpload) -> Result<MediaUpload> {
+        self.uploads
+            .lock()
+            .unwrap()
+            .insert(upload.id, upload.clone());
+        Ok(upload.clone())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<MediaUpload>> {
+        Ok(self.uploads.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn complete(&self, upload: &MediaUpload) -> Result<Option<MediaUpload>> {
+        let mut uploads = self.uploads.lock().unwrap();
+        match uploads.get_mut(&upload.id) {
+            Some(stored) if !stored.is_completed() => {
+                *stored = upload.clone();
+                Ok(Some(upload.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.uploads.lock().unwrap().remove(&id);
+        Ok(())
+    }
+}
+
+/// In-memory storage that can optionally hand out presigned URLs
+pub(super) struct MockMediaStorage {
+    pub(super) objects: Mutex<HashMap<String, Vec<u8>>>,
+    presigned: bool,
+}
+
+impl MockMediaStorage {
+    pub(super) fn new(presigned: bool) -> Self {
+        Self {
+            objects: Mutex::new(HashMap::new()),
+            presigned,
+        }
+    }
+}
+
+#[async_trait]
+impl MediaStorage for MockMediaStorage {
+    async fn presign_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<PresignedUpload>> {
+        Ok(self.presigned.then(|| PresignedUpload {
+            url: format!("https://storage.test/{}", key),
+            method: "PUT".to_string(),
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            expires_at,
+        }))
+    }
+
+    async fn put_object(&self, key: &str, _content_type: &str, data: Vec<u8>) -> Result<()> {
+        self.objects.lock().unwrap().insert(key.to_string(), data);
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.objects.lock().unwrap().get(key).cloned())
+    }
+
+    async fn object_size(&self, key: &str) -> Result<Option<i64>> {
+        Ok(self
+            .objects
+            .lock()
+            .unwrap()
+            .get(key)
+            .map(|data| data.len() as i64))
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        self.objects.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn presign_download(&self, _key: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("https://cdn.test/{}", key)
+    }
+}
+
+/// Reads uploaded "files" as ffprobe's JSON report on them, so tests can
+/// describe any video or audio file
+struct ReportMediaProber;
+
+#[async_trait]
+impl MediaProber for ReportMediaProber {
+    async fn probe(
+        &self,
+        data: &[u8],
+        _media_type: &str,
+    ) -> std::result::Result<ProbedMedia, ProbeError> {
+        parse_ffprobe_output(data)
+    }
+}
+
+/// Media service over in-memory uploads and storage, probing video and audio
+/// with `ReportMediaProber`
+pub(super) fn media_service(presigned: bool) -> (MediaService, Arc<MockMediaStorage>) {
+    media_service_with_posters(presigned, None)
+}
+
+pub(super) fn media_service_with_posters(
+    presigned: bool,
+    poster_frames: Option<Arc<dyn PosterFrameExtractor>>,
+) -> (MediaService, Arc<MockMediaStorage>) {
+    let storage = Arc::new(MockMediaStorage::new(presigned));
+    (
+        MediaService::new(
+            Arc::new(MockMediaUploadRepository::new()),
+            storage.clone(),
+            Some(Arc::new(ReportMediaProber)),
+            poster_frames,
+        ),
+        storage,
+    )
+}
+
+pub(super) async fn create_png_upload(
+    service: &MediaService,
+    user_id: Uuid,
+    size: i64,
+) -> UploadTarget {
+    service
+        .create_upload(
+            user_id,
+            "image/png".to_string(),
+            size,
+            Some(100),
+            Some(100),
+            None,
+        )
+        .await
+        .unwrap()
+}
+
+/// A text post with the given visibility
+pub(super) fn create_post(user_id: Uuid, visibility: PostVisibility) -> Post {
+    let mut post = create_test_post(user_id, "Hello", false);
+    post.visibility = visibility;
+    post
+}
+
+/// A public text post created `minutes_ago`
+pub(super) fn post_at(user_id: Uuid, content: &str, minutes_ago: i64) -> Post {
+    let mut post = create_test_post(user_id, content, false);
+    post.created_at = Utc::now() - chrono::Duration::minutes(minutes_ago);
+    post
+}
+
+// In-memory TimelineRepository that, like Redis, only pushes into
+// timelines that exist and forgets timelines left empty
+pub(super) struct MockTimelineRepository {
+    timelines: Mutex<HashMap<Uuid, Vec<TimelineEntry>>>,
+}
+
+impl MockTimelineRepository {
+    pub(super) fn new() -> Self {
+        Self {
+            timelines: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(super) fn entries(&self, user_id: Uuid) -> Option<Vec<Uuid>> {
+        self.timelines
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .map(|entries| entries.iter().map(|entry| entry.post_id).collect())
+    }
+
+    fn store(&self, user_id: Uuid, mut entries: Vec<TimelineEntry>, max_items: usize) {
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.created_at));
+        entries.dedup_by_key(|entry| entry.post_id);
+        entries.truncate(max_items);
+        let mut timelines = self.timelines.lock().unwrap();
+        if entries.is_empty() {
+            timelines.remove(&user_id);
+        } else {
+            timelines.insert(user_id, entries);
+        }
+    }
+}
+
+#[async_trait]
+impl TimelineRepository for MockTimelineRepository {
+    async fn find(&self, user_id: Uuid, limit: usize) -> Result<Option<Vec<TimelineEntry>>> {
+        Ok(self
+            .timelines
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .map(|entries| entries.iter().take(limit).copied().collect()))
+    }
+
+    async fn replace(
+        &self,
+        user_id: Uuid,
+        entries: &[TimelineEntry],
+        max_items: usize,
+    ) -> Result<()> {
+        self.store(user_id, entries.to_vec(), max_items);
+        Ok(())
+    }
+
+    async fn push(
+        &self,
+        user_ids: &[Uuid],
+        entries: &[TimelineEntry],
+        max_items: usize,
+    ) -> Result<()> {
+        for user_id in user_ids {
+            let existing = self.timelines.lock().unwrap().get(user_id).cloned();
+            if let Some(mut existing) = existing {
+                existing.extend_from_slice(entries);
+                self.store(*user_id, existing, max_items);
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, user_ids: &[Uuid], entry: &TimelineEntry) -> Result<()> {
+        for user_id in user_ids {
+            let existing = self.timelines.lock().unwrap().get(user_id).cloned();
+            if let Some(mut existing) = existing {
+                existing.retain(|e| e.post_id != entry.post_id);
+                self.store(*user_id, existing, usize::MAX);
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove_author(&self, user_id: Uuid, author_id: Uuid) -> Result<()> {
+        let existing = self.timelines.lock().unwrap().get(&user_id).cloned();
+        if let Some(mut existing) = existing {
+            existing.retain(|e| e.author_id != author_id);
+            self.store(user_id, existing, usize::MAX);
+        }
+        Ok(())
+    }
+}
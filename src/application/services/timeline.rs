@@ -0,0 +1,389 @@
+//! Home timelines and the fan-out that fills them as posts are published,
+//! followed and deleted.
+use super::{collect_follower_ids, order_by_ids, FOLLOWER_PAGE_SIZE};
+use crate::domain::entities::{Post, PostVisibility, TimelineEntry};
+use crate::domain::errors::Result;
+use crate::domain::repositories::{PostRepository, TimelineRepository, UserRepository};
+use crate::domain::value_objects::PageStart;
+use crate::infrastructure::cache::CacheConfig;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Timeline service keeping push-based home timelines: published posts are
+/// written into their followers' timelines, except for authors too popular
+/// to fan out to, whose posts are merged in when a timeline is read
+pub struct TimelineService {
+    timeline_repository: Arc<dyn TimelineRepository>,
+    post_repository: Arc<dyn PostRepository>,
+    user_repository: Arc<dyn UserRepository>,
+}
+
+/// Authors with at least this many followers are not fanned out on write;
+/// their posts are pulled into home timelines when those are read
+pub const FAN_OUT_MAX_FOLLOWERS: i32 = 10_000;
+
+/// Number of an author's recent posts added to a new follower's timeline
+const FOLLOW_BACKFILL_POSTS: i64 = 20;
+
+impl TimelineService {
+    pub fn new(
+        timeline_repository: Arc<dyn TimelineRepository>,
+        post_repository: Arc<dyn PostRepository>,
+        user_repository: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            timeline_repository,
+            post_repository,
+            user_repository,
+        }
+    }
+
+    /// Whether an author's posts are written into their followers' timelines
+    async fn is_fanned_out(&self, author_id: Uuid) -> Result<bool> {
+        Ok(self
+            .user_repository
+            .follower_count(author_id)
+            .await?
+            .is_some_and(|follower_count| follower_count < FAN_OUT_MAX_FOLLOWERS))
+    }
+
+    /// Write a published post into its author's followers' timelines.
+    /// Private posts and posts by popular authors are left out.
+    pub async fn fan_out(&self, post: &Post) -> Result<()> {
+        if !post.is_published() || post.visibility == PostVisibility::Private {
+            return Ok(());
+        }
+        if !self.is_fanned_out(post.user_id).await? {
+            return Ok(());
+        }
+
+        let follower_ids =
+            collect_follower_ids(self.user_repository.as_ref(), post.user_id).await?;
+        let entry = TimelineEntry::from(post);
+        for follower_ids in follower_ids.chunks(FOLLOWER_PAGE_SIZE as usize) {
+            self.timeline_repository
+                .push(follower_ids, &[entry], CacheConfig::MAX_FEED_ITEMS)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Run `fan_out` in the background
+    pub fn spawn_fan_out(self: &Arc<Self>, post: Post) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.fan_out(&post).await {
+                tracing::error!("Failed to fan out post {}: {}", post.id, e);
+            }
+        });
+    }
+
+    /// Remove a deleted post from its author's followers' timelines. This
+    /// does not depend on the author's current follower count: they may have
+    /// crossed `FAN_OUT_MAX_FOLLOWERS` since the post was fanned out, and a
+    /// rebuilt timeline can hold any followed author's posts.
+    pub async fn remove_post(&self, post: &Post) -> Result<()> {
+        let follower_ids =
+            collect_follower_ids(self.user_repository.as_ref(), post.user_id).await?;
+        let entry = TimelineEntry::from(post);
+        for follower_ids in follower_ids.chunks(FOLLOWER_PAGE_SIZE as usize) {
+            self.timeline_repository
+                .remove(follower_ids, &entry)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Run `remove_post` in the background
+    pub fn spawn_remove_post(self: &Arc<Self>, post: Post) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.remove_post(&post).await {
+                tracing::error!("Failed to remove post {} from timelines: {}", post.id, e);
+            }
+        });
+    }
+
+    /// Add an author's recent posts to a new follower's timeline
+    pub async fn add_author(&self, follower_id: Uuid, author_id: Uuid) -> Result<()> {
+        if !self.is_fanned_out(author_id).await? {
+            return Ok(());
+        }
+
+        let entries: Vec<TimelineEntry> = self
+            .post_repository
+            .find_by_user_id(author_id, FOLLOW_BACKFILL_POSTS, 0)
+            .await?
+            .iter()
+            .filter(|post| post.visibility != PostVisibility::Private)
+            .map(TimelineEntry::from)
+            .collect();
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        self.timeline_repository
+            .push(&[follower_id], &entries, CacheConfig::MAX_FEED_ITEMS)
+            .await
+    }
+
+    /// Remove every post by an author from a former follower's timeline
+    pub async fn remove_author(&self, follower_id: Uuid, author_id: Uuid) -> Result<()> {
+        self.timeline_repository
+            .remove_author(follower_id, author_id)
+            .await
+    }
+
+    /// Get a page of a user's home feed, newest first. The stored timeline is
+    /// merged with the newest posts of followed popular authors; a missing
+    /// timeline is rebuilt from the database, and pages beyond what
+    /// timelines hold are read from the database directly.
+    pub async fn find_home_feed(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        page: PageStart,
+    ) -> Result<Vec<Post>> {
+        let offset = page.offset();
+        let wanted = (offset + limit) as usize;
+        if wanted > CacheConfig::MAX_FEED_ITEMS {
+            return self.post_repository.find_feed(user_id, page, limit).await;
+        }
+
+        // A cursor can point anywhere in the timeline, so read all of it
+        let read = match page {
+            PageStart::After(_) => CacheConfig::MAX_FEED_ITEMS,
+            PageStart::Offset(_) => wanted,
+        };
+        let pushed = match self.timeline_repository.find(user_id, read).await? {
+            Some(entries) => {
+                let ids: Vec<Uuid> = entries.iter().map(|entry| entry.post_id).collect();
+                order_by_ids(&ids, self.post_repository.find_by_ids(&ids).await?)
+            }
+            None => {
+                let posts = self
+                    .post_repository
+                    .find_feed(
+                        user_id,
+                        PageStart::FIRST,
+                        CacheConfig::MAX_FEED_ITEMS as i64,
+                    )
+                    .await?;
+                let entries: Vec<TimelineEntry> = posts.iter().map(TimelineEntry::from).collect();
+                self.timeline_repository
+                    .replace(user_id, &entries, CacheConfig::MAX_FEED_ITEMS)
+                    .await?;
+                posts
+            }
+        };
+        // Only a full timeline may have dropped older posts
+        let timeline_full = pushed.len() >= CacheConfig::MAX_FEED_ITEMS;
+
+        let pulled = self
+            .post_repository
+            .find_feed_from_popular(user_id, FAN_OUT_MAX_FOLLOWERS, page.cursor(), wanted as i64)
+            .await?;
+
+        let mut seen = HashSet::new();
+        let mut posts: Vec<Post> = pushed
+            .into_iter()
+            .chain(pulled)
+            .filter(|post| seen.insert(post.id))
+            .collect();
+        posts.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+
+        let posts: Vec<Post> = match page {
+            PageStart::After(cursor) => posts
+                .into_iter()
+                .filter(|post| (post.created_at, post.id) < (cursor.created_at, cursor.id))
+                .take(limit as usize)
+                .collect(),
+            PageStart::Offset(offset) => posts
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect(),
+        };
+
+        if timeline_full && (posts.len() as i64) < limit {
+            return self.post_repository.find_feed(user_id, page, limit).await;
+        }
+        Ok(posts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use crate::domain::repositories::MockUserRepository;
+    use crate::domain::value_objects::Cursor;
+
+    #[tokio::test]
+    async fn test_fan_out_writes_to_existing_follower_timelines() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(TestUserRepository::new());
+        let timelines = Arc::new(MockTimelineRepository::new());
+        let service = TimelineService::new(timelines.clone(), post_repo.clone(), user_repo.clone());
+
+        let author = create_test_user("author", "author@example.com");
+        let active = create_test_user("active", "active@example.com");
+        let inactive = create_test_user("inactive", "inactive@example.com");
+        for user in [&author, &active, &inactive] {
+            user_repo.add_user(user.clone());
+        }
+        user_repo.follow(active.id, author.id).await.unwrap();
+        user_repo.follow(inactive.id, author.id).await.unwrap();
+
+        let older = post_at(Uuid::new_v4(), "Older post", 60);
+        timelines
+            .replace(active.id, &[TimelineEntry::from(&older)], 100)
+            .await
+            .unwrap();
+
+        let post = post_at(author.id, "New post", 0);
+        service.fan_out(&post).await.unwrap();
+        assert_eq!(timelines.entries(active.id), Some(vec![post.id, older.id]));
+        // Timelines nobody reads are rebuilt when they are next read instead
+        assert_eq!(timelines.entries(inactive.id), None);
+
+        // Private posts are not fanned out
+        let mut private = post_at(author.id, "Private post", 0);
+        private.visibility = PostVisibility::Private;
+        service.fan_out(&private).await.unwrap();
+        assert_eq!(timelines.entries(active.id), Some(vec![post.id, older.id]));
+
+        // Neither are posts by authors too popular to fan out to
+        let mut popular = user_repo.find_by_id(author.id).await.unwrap().unwrap();
+        popular.follower_count = FAN_OUT_MAX_FOLLOWERS;
+        user_repo.add_user(popular);
+        service
+            .fan_out(&post_at(author.id, "Popular post", 0))
+            .await
+            .unwrap();
+        assert_eq!(timelines.entries(active.id), Some(vec![post.id, older.id]));
+    }
+
+    #[tokio::test]
+    async fn test_remove_post_after_author_becomes_popular() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(TestUserRepository::new());
+        let timelines = Arc::new(MockTimelineRepository::new());
+        let service = TimelineService::new(timelines.clone(), post_repo.clone(), user_repo.clone());
+
+        let author = add_test_user(&user_repo, "author");
+        let follower = add_test_user(&user_repo, "follower");
+        user_repo.follow(follower.id, author.id).await.unwrap();
+
+        let older = post_at(Uuid::new_v4(), "Older post", 60);
+        timelines
+            .replace(follower.id, &[TimelineEntry::from(&older)], 100)
+            .await
+            .unwrap();
+        let post = post_at(author.id, "New post", 0);
+        service.fan_out(&post).await.unwrap();
+        assert_eq!(
+            timelines.entries(follower.id),
+            Some(vec![post.id, older.id])
+        );
+
+        // The author crosses the fan-out threshold before deleting the post
+        let mut popular = user_repo.find_by_id(author.id).await.unwrap().unwrap();
+        popular.follower_count = FAN_OUT_MAX_FOLLOWERS;
+        user_repo.add_user(popular);
+
+        service.remove_post(&post).await.unwrap();
+        assert_eq!(timelines.entries(follower.id), Some(vec![older.id]));
+    }
+
+    #[tokio::test]
+    async fn test_home_feed_rebuilds_timeline_and_merges_popular_authors() {
+        let post_repo = Arc::new(MockPostRepository::new());
+        let user_repo = Arc::new(MockUserRepository::new());
+        let timelines = Arc::new(MockTimelineRepository::new());
+        let service = TimelineService::new(timelines.clone(), post_repo.clone(), user_repo);
+
+        let viewer_id = Uuid::new_v4();
+        let author_id = Uuid::new_v4();
+        let celebrity_id = Uuid::new_v4();
+        post_repo.mark_popular(celebrity_id);
+
+        let first = post_at(author_id, "First", 30);
+        let celebrity_post = post_at(celebrity_id, "Celebrity", 20);
+        for post in [&first, &celebrity_post] {
+            post_repo.add_post(post.clone());
+        }
+        post_repo.set_user_feed(viewer_id, vec![first.id, celebrity_post.id]);
+
+        // A missing timeline is rebuilt from the database
+        let feed = service
+            .find_home_feed(viewer_id, 10, PageStart::FIRST)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = feed.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![celebrity_post.id, first.id]);
+        assert!(timelines.entries(viewer_id).is_some());
+
+        // Newer celebrity posts are only pulled in at read time
+        let second = post_at(author_id, "Second", 10);
+        let newest_celebrity_post = post_at(celebrity_id, "Celebrity again", 0);
+        for post in [&second, &newest_celebrity_post] {
+            post_repo.add_post(post.clone());
+        }
+        post_repo.set_user_feed(
+            viewer_id,
+            vec![
+                first.id,
+                celebrity_post.id,
+                second.id,
+                newest_celebrity_post.id,
+            ],
+        );
+        timelines
+            .push(&[viewer_id], &[TimelineEntry::from(&second)], 100)
+            .await
+            .unwrap();
+
+        let feed = service
+            .find_home_feed(viewer_id, 10, PageStart::FIRST)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = feed.iter().map(|post| post.id).collect();
+        assert_eq!(
+            ids,
+            vec![
+                newest_celebrity_post.id,
+                second.id,
+                celebrity_post.id,
+                first.id
+            ]
+        );
+
+        let page = service
+            .find_home_feed(viewer_id, 2, PageStart::Offset(1))
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = page.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![second.id, celebrity_post.id]);
+
+        // Cursors continue after the post they point at, pulled posts included
+        let after_second = PageStart::After(Cursor::new(second.created_at, second.id));
+        let page = service
+            .find_home_feed(viewer_id, 10, after_second)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = page.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![celebrity_post.id, first.id]);
+
+        // Deleted posts drop out even while their entry is still stored
+        post_repo.delete(second.id).await.unwrap();
+        let feed = service
+            .find_home_feed(viewer_id, 10, PageStart::FIRST)
+            .await
+            .unwrap();
+        assert!(feed.iter().all(|post| post.id != second.id));
+    }
+}
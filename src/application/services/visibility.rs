@@ -0,0 +1,387 @@
+//! Who may see, like or comment on a post: visibility settings, blocks and
+//! follows.
+use crate::domain::entities::{Post, PostVisibility};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::{PostRepository, UserRepository};
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Post visibility policy deciding who may see, like or comment on a post
+pub struct PostVisibilityService {
+    post_repository: Arc<dyn PostRepository>,
+    user_repository: Arc<dyn UserRepository>,
+}
+
+impl PostVisibilityService {
+    pub fn new(
+        post_repository: Arc<dyn PostRepository>,
+        user_repository: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            post_repository,
+            user_repository,
+        }
+    }
+
+    /// Check whether a viewer may see a post.
+    ///
+    /// Authors always see their own posts. Everyone else only sees published
+    /// posts that moderation has not hidden, never across a block in either
+    /// direction, and only if the post's visibility admits them. Anonymous
+    /// viewers only see public posts.
+    pub async fn can_view(&self, viewer_id: Option<Uuid>, post: &Post) -> Result<bool> {
+        if viewer_id == Some(post.user_id) {
+            return Ok(true);
+        }
+
+        if !post.is_published() || post.is_hidden {
+            return Ok(false);
+        }
+
+        let viewer_id = match viewer_id {
+            Some(viewer_id) => viewer_id,
+            None => return Ok(post.visibility == PostVisibility::Public),
+        };
+
+        if self
+            .user_repository
+            .has_blocked(post.user_id, viewer_id)
+            .await?
+            || self
+                .user_repository
+                .has_blocked(viewer_id, post.user_id)
+                .await?
+        {
+            return Ok(false);
+        }
+
+        match post.visibility {
+            PostVisibility::Public => Ok(true),
+            PostVisibility::Followers => {
+                self.user_repository
+                    .is_following(viewer_id, post.user_id)
+                    .await
+            }
+            PostVisibility::Private => Ok(false),
+        }
+    }
+
+    /// Fetch a post the viewer may see. Hidden posts are reported as missing
+    /// so their existence is not leaked.
+    pub async fn find_visible_post(&self, viewer_id: Option<Uuid>, post_id: Uuid) -> Result<Post> {
+        let post = self
+            .post_repository
+            .find_by_id(post_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+        if !self.can_view(viewer_id, &post).await? {
+            return Err(AppError::NotFound("Post not found".to_string()));
+        }
+
+        Ok(post)
+    }
+
+    /// Drop the posts a viewer may not see, keeping the original order.
+    ///
+    /// Applies the same rules as `can_view`, but looks up blocks and follows
+    /// for all the authors at once instead of per post.
+    pub async fn filter_visible(
+        &self,
+        viewer_id: Option<Uuid>,
+        posts: Vec<Post>,
+    ) -> Result<Vec<Post>> {
+        let viewer_id = match viewer_id {
+            Some(viewer_id) => viewer_id,
+            None => {
+                return Ok(posts
+                    .into_iter()
+                    .filter(|post| {
+                        post.is_published()
+                            && !post.is_hidden
+                            && post.visibility == PostVisibility::Public
+                    })
+                    .collect())
+            }
+        };
+
+        let needs_lookup = |post: &Post| {
+            post.user_id != viewer_id
+                && post.is_published()
+                && !post.is_hidden
+                && post.visibility != PostVisibility::Private
+        };
+        let author_ids = |posts: &mut dyn Iterator<Item = &Post>| {
+            posts
+                .map(|post| post.user_id)
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+
+        let authors = author_ids(&mut posts.iter().filter(|post| needs_lookup(post)));
+        let blocked = self
+            .user_repository
+            .blocked_ids(viewer_id, &authors)
+            .await?;
+
+        let followers_only = author_ids(&mut posts.iter().filter(|post| {
+            needs_lookup(post)
+                && post.visibility == PostVisibility::Followers
+                && !blocked.contains(&post.user_id)
+        }));
+        let followed = self
+            .user_repository
+            .followed_ids(viewer_id, &followers_only)
+            .await?;
+
+        Ok(posts
+            .into_iter()
+            .filter(|post| {
+                if post.user_id == viewer_id {
+                    return true;
+                }
+                if !needs_lookup(post) || blocked.contains(&post.user_id) {
+                    return false;
+                }
+                match post.visibility {
+                    PostVisibility::Public => true,
+                    PostVisibility::Followers => followed.contains(&post.user_id),
+                    PostVisibility::Private => false,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_support::*;
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_author_sees_own_posts() {
+        let users = Arc::new(TestUserRepository::new());
+        let service =
+            PostVisibilityService::new(Arc::new(MockPostRepository::new()), users.clone());
+        let author = add_test_user(&users, "author");
+
+        let private = create_post(author.id, PostVisibility::Private);
+        let mut draft = create_post(author.id, PostVisibility::Public);
+        draft.save_as_draft();
+
+        assert!(service.can_view(Some(author.id), &private).await.unwrap());
+        assert!(service.can_view(Some(author.id), &draft).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_visibility_levels_for_stranger_and_follower() {
+        let users = Arc::new(TestUserRepository::new());
+        let service =
+            PostVisibilityService::new(Arc::new(MockPostRepository::new()), users.clone());
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let public = create_post(author.id, PostVisibility::Public);
+        let followers = create_post(author.id, PostVisibility::Followers);
+        let private = create_post(author.id, PostVisibility::Private);
+
+        let viewer_id = Some(viewer.id);
+        assert!(service.can_view(viewer_id, &public).await.unwrap());
+        assert!(!service.can_view(viewer_id, &followers).await.unwrap());
+        assert!(!service.can_view(viewer_id, &private).await.unwrap());
+
+        users.follow(viewer.id, author.id).await.unwrap();
+        assert!(service.can_view(viewer_id, &followers).await.unwrap());
+        assert!(!service.can_view(viewer_id, &private).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_viewer_sees_public_only() {
+        let users = Arc::new(TestUserRepository::new());
+        let service =
+            PostVisibilityService::new(Arc::new(MockPostRepository::new()), users.clone());
+        let author = add_test_user(&users, "author");
+
+        let public = create_post(author.id, PostVisibility::Public);
+        let followers = create_post(author.id, PostVisibility::Followers);
+        let private = create_post(author.id, PostVisibility::Private);
+
+        assert!(service.can_view(None, &public).await.unwrap());
+        assert!(!service.can_view(None, &followers).await.unwrap());
+        assert!(!service.can_view(None, &private).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_blocks_hide_posts_in_both_directions() {
+        let users = Arc::new(TestUserRepository::new());
+        let service =
+            PostVisibilityService::new(Arc::new(MockPostRepository::new()), users.clone());
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let post = create_post(author.id, PostVisibility::Public);
+        users.follow(viewer.id, author.id).await.unwrap();
+
+        users.block(author.id, viewer.id);
+        assert!(!service.can_view(Some(viewer.id), &post).await.unwrap());
+
+        let other = add_test_user(&users, "other");
+        users.block(other.id, author.id);
+        assert!(!service.can_view(Some(other.id), &post).await.unwrap());
+
+        // Anonymous viewers are unaffected by blocks
+        assert!(service.can_view(None, &post).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_unpublished_posts_hidden_from_others() {
+        let users = Arc::new(TestUserRepository::new());
+        let service =
+            PostVisibilityService::new(Arc::new(MockPostRepository::new()), users.clone());
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        users.follow(viewer.id, author.id).await.unwrap();
+
+        let mut draft = create_post(author.id, PostVisibility::Public);
+        draft.save_as_draft();
+        let mut scheduled = create_post(author.id, PostVisibility::Followers);
+        scheduled
+            .schedule(Utc::now() + chrono::Duration::hours(1))
+            .unwrap();
+
+        assert!(!service.can_view(Some(viewer.id), &draft).await.unwrap());
+        assert!(!service.can_view(Some(viewer.id), &scheduled).await.unwrap());
+        assert!(!service.can_view(None, &draft).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_find_visible_post_hides_existence() {
+        let posts = Arc::new(MockPostRepository::new());
+        let users = Arc::new(TestUserRepository::new());
+        let service = PostVisibilityService::new(posts.clone(), users.clone());
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let public = create_post(author.id, PostVisibility::Public);
+        let private = create_post(author.id, PostVisibility::Private);
+        posts.add_post(public.clone());
+        posts.add_post(private.clone());
+
+        let found = service
+            .find_visible_post(Some(viewer.id), public.id)
+            .await
+            .unwrap();
+        assert_eq!(found.id, public.id);
+
+        let hidden = service.find_visible_post(Some(viewer.id), private.id).await;
+        assert!(matches!(hidden, Err(AppError::NotFound(_))));
+
+        let missing = service
+            .find_visible_post(Some(viewer.id), Uuid::new_v4())
+            .await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_filter_visible_keeps_order() {
+        let users = Arc::new(TestUserRepository::new());
+        let service =
+            PostVisibilityService::new(Arc::new(MockPostRepository::new()), users.clone());
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let first = create_post(author.id, PostVisibility::Public);
+        let hidden = create_post(author.id, PostVisibility::Private);
+        let second = create_post(author.id, PostVisibility::Public);
+        let third = create_post(viewer.id, PostVisibility::Private);
+
+        let visible = service
+            .filter_visible(
+                Some(viewer.id),
+                vec![first.clone(), hidden, second.clone(), third.clone()],
+            )
+            .await
+            .unwrap();
+
+        let ids: Vec<Uuid> = visible.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![first.id, second.id, third.id]);
+    }
+
+    #[tokio::test]
+    async fn test_filter_visible_applies_blocks_and_follows() {
+        let users = Arc::new(TestUserRepository::new());
+        let service =
+            PostVisibilityService::new(Arc::new(MockPostRepository::new()), users.clone());
+        let author = add_test_user(&users, "author");
+        let viewer = add_test_user(&users, "viewer");
+
+        let blocker = add_test_user(&users, "blocker");
+        let followed = add_test_user(&users, "followed");
+        users.block(blocker.id, viewer.id);
+        users.follow(viewer.id, followed.id).await.unwrap();
+
+        let blocked_post = create_post(blocker.id, PostVisibility::Public);
+        let followed_post = create_post(followed.id, PostVisibility::Followers);
+        let stranger_post = create_post(author.id, PostVisibility::Followers);
+        let mut hidden_post = create_post(followed.id, PostVisibility::Public);
+        hidden_post.is_hidden = true;
+        let posts = vec![
+            blocked_post,
+            followed_post.clone(),
+            stranger_post,
+            hidden_post,
+        ];
+
+        let visible = service
+            .filter_visible(Some(viewer.id), posts.clone())
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = visible.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![followed_post.id]);
+
+        // The batch agrees with checking each post on its own
+        for post in &posts {
+            assert_eq!(
+                service.can_view(Some(viewer.id), post).await.unwrap(),
+                ids.contains(&post.id)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_filter_visible_looks_up_relationships_once() {
+        let users = Arc::new(TestUserRepository::new());
+        let service =
+            PostVisibilityService::new(Arc::new(MockPostRepository::new()), users.clone());
+        let viewer = add_test_user(&users, "viewer");
+
+        let mut posts = Vec::new();
+        for i in 0..20 {
+            let author = create_test_user(&format!("author{}", i), &format!("a{}@example.com", i));
+            users.add_user(author.clone());
+            if i % 2 == 0 {
+                users.follow(viewer.id, author.id).await.unwrap();
+            }
+            posts.push(create_post(author.id, PostVisibility::Public));
+            posts.push(create_post(author.id, PostVisibility::Followers));
+        }
+
+        let before = users.relationship_lookups();
+        let visible = service
+            .filter_visible(Some(viewer.id), posts)
+            .await
+            .unwrap();
+
+        assert_eq!(visible.len(), 30);
+        assert_eq!(users.relationship_lookups() - before, 2);
+
+        // Anonymous viewers need no lookups at all
+        let before = users.relationship_lookups();
+        service.filter_visible(None, visible).await.unwrap();
+        assert_eq!(users.relationship_lookups(), before);
+    }
+}
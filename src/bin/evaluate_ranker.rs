@@ -0,0 +1,60 @@
+//! Replay logged feed sessions through each ranker and report how well they
+//! put the posts people engaged with near the top.
+//!
+//! Usage: evaluate-ranker <sessions.jsonl> [weights.json] [k]
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader};
+use view_social_backend::application::ranking::{
+    evaluate, EngagementRanker, FeedRanker, LoggedSession, WeightedRanker,
+};
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let Some(sessions_path) = args.next() else {
+        bail!("Usage: evaluate-ranker <sessions.jsonl> [weights.json] [k]");
+    };
+    let weighted = match args.next() {
+        Some(path) => WeightedRanker::from_file(&path)?,
+        None => WeightedRanker::default(),
+    };
+    let k: usize = match args.next() {
+        Some(k) => k.parse().context("k must be a positive number")?,
+        None => 10,
+    };
+
+    let file = std::fs::File::open(&sessions_path)
+        .with_context(|| format!("Failed to open {}", sessions_path))?;
+    let mut sessions = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let session: LoggedSession = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid session on line {}", number + 1))?;
+        sessions.push(session);
+    }
+
+    let rankers: [&dyn FeedRanker; 2] = [&EngagementRanker, &weighted];
+    println!(
+        "{:<12} {:>9} {:>9} {:>13} {:>9}",
+        "ranker",
+        "sessions",
+        format!("ndcg@{}", k),
+        format!("precision@{}", k),
+        "mrr"
+    );
+    for ranker in rankers {
+        let evaluation = evaluate(ranker, &sessions, k);
+        println!(
+            "{:<12} {:>9} {:>9.4} {:>13.4} {:>9.4}",
+            evaluation.ranker,
+            evaluation.sessions,
+            evaluation.ndcg_at_k,
+            evaluation.precision_at_k,
+            evaluation.mean_reciprocal_rank
+        );
+    }
+
+    Ok(())
+}
@@ -7,6 +7,14 @@ pub struct Config {
     pub database_url: String,
     pub redis_url: String,
     pub jwt_secret: String,
+    /// Reports on a piece of content before it is hidden pending review
+    pub report_hide_threshold: u32,
+    /// JSON file with content filter rules; nothing is filtered when unset
+    pub content_filter_rules_path: Option<String>,
+    /// Ranker for algorithmic feeds: "engagement" or "weighted"
+    pub feed_ranker: String,
+    /// JSON file with the weighted ranker's weights; defaults are used when unset
+    pub feed_ranking_weights_path: Option<String>,
 }
 
 impl Config {
@@ -23,6 +31,16 @@ impl Config {
             redis_url: env::var("REDIS_URL")
                 .unwrap_or_else(|_| "redis://localhost:6379".to_string()),
             jwt_secret: env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string()),
+            report_hide_threshold: env::var("REPORT_HIDE_THRESHOLD")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()?,
+            content_filter_rules_path: env::var("CONTENT_FILTER_RULES_PATH")
+                .ok()
+                .filter(|path| !path.trim().is_empty()),
+            feed_ranker: env::var("FEED_RANKER").unwrap_or_else(|_| "engagement".to_string()),
+            feed_ranking_weights_path: env::var("FEED_RANKING_WEIGHTS_PATH")
+                .ok()
+                .filter(|path| !path.trim().is_empty()),
         })
     }
 }
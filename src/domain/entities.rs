@@ -1,6 +1,6 @@
 use crate::domain::errors::{AppError, Result};
 use crate::domain::value_objects::{Bio, DisplayName, Email, PhoneNumber, Username};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use proptest::proptest;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -20,10 +20,51 @@ pub struct User {
     pub phone_verified: bool,
     pub follower_count: i32,
     pub following_count: i32,
+    /// Set by a moderator; the user cannot sign in until it passes
+    pub suspended_until: Option<DateTime<Utc>>,
+    pub sensitive_media: SensitiveMediaPreference,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// How a user wants posts marked as sensitive to appear in their feeds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensitiveMediaPreference {
+    Show,
+    /// Shown behind a blur that the viewer taps through
+    #[default]
+    Blur,
+    /// Left out of feeds entirely
+    Hide,
+}
+
+impl std::fmt::Display for SensitiveMediaPreference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensitiveMediaPreference::Show => write!(f, "show"),
+            SensitiveMediaPreference::Blur => write!(f, "blur"),
+            SensitiveMediaPreference::Hide => write!(f, "hide"),
+        }
+    }
+}
+
+impl std::str::FromStr for SensitiveMediaPreference {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "show" => Ok(SensitiveMediaPreference::Show),
+            "blur" => Ok(SensitiveMediaPreference::Blur),
+            "hide" => Ok(SensitiveMediaPreference::Hide),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid sensitive media preference: {}",
+                s
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateUserRequest {
     pub username: String,
@@ -39,6 +80,8 @@ pub struct UpdateUserRequest {
     pub display_name: Option<String>,
     pub bio: Option<String>,
     pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub sensitive_media: Option<SensitiveMediaPreference>,
 }
 
 impl User {
@@ -80,11 +123,17 @@ impl User {
             phone_verified: false,
             follower_count: 0,
             following_count: 0,
+            suspended_until: None,
+            sensitive_media: SensitiveMediaPreference::default(),
             created_at: now,
             updated_at: now,
         })
     }
 
+    pub fn is_suspended(&self, now: DateTime<Utc>) -> bool {
+        self.suspended_until.is_some_and(|until| now < until)
+    }
+
     pub fn update(&mut self, request: UpdateUserRequest) -> Result<()> {
         if let Some(name) = request.display_name {
             self.display_name = Some(DisplayName::new(name)?);
@@ -112,6 +161,10 @@ impl User {
             }
         }
 
+        if let Some(sensitive_media) = request.sensitive_media {
+            self.sensitive_media = sensitive_media;
+        }
+
         self.updated_at = Utc::now();
         Ok(())
     }
@@ -178,6 +231,37 @@ pub enum PostContentType {
     Image,
     Video,
     Mixed,
+    Poll,
+}
+
+impl std::fmt::Display for PostContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostContentType::Text => write!(f, "text"),
+            PostContentType::Image => write!(f, "image"),
+            PostContentType::Video => write!(f, "video"),
+            PostContentType::Mixed => write!(f, "mixed"),
+            PostContentType::Poll => write!(f, "poll"),
+        }
+    }
+}
+
+impl std::str::FromStr for PostContentType {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(PostContentType::Text),
+            "image" => Ok(PostContentType::Image),
+            "video" => Ok(PostContentType::Video),
+            "mixed" => Ok(PostContentType::Mixed),
+            "poll" => Ok(PostContentType::Poll),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid content type: {}",
+                s
+            ))),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -187,6 +271,90 @@ pub enum PostVisibility {
     Private,
 }
 
+/// Publication state of a post. Only published posts are visible to anyone but the author.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostStatus {
+    Draft,
+    Scheduled,
+    Published,
+}
+
+impl std::fmt::Display for PostStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostStatus::Draft => write!(f, "draft"),
+            PostStatus::Scheduled => write!(f, "scheduled"),
+            PostStatus::Published => write!(f, "published"),
+        }
+    }
+}
+
+impl std::str::FromStr for PostStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "draft" => Ok(PostStatus::Draft),
+            "scheduled" => Ok(PostStatus::Scheduled),
+            "published" => Ok(PostStatus::Published),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid post status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// MIME types accepted for media attachments and uploads
+pub const SUPPORTED_MEDIA_TYPES: [&str; 10] = [
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "video/mp4",
+    "video/webm",
+    "video/quicktime",
+    "audio/mpeg",
+    "audio/wav",
+    "audio/ogg",
+];
+
+/// Maximum size of a single media file in bytes (100MB)
+pub const MAX_MEDIA_SIZE: i64 = 100 * 1024 * 1024;
+
+/// Size variant of a processed image. Videos get a poster frame in place of
+/// the full-size image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenditionKind {
+    Thumbnail,
+    Feed,
+    Full,
+    Poster,
+}
+
+impl std::fmt::Display for RenditionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenditionKind::Thumbnail => write!(f, "thumbnail"),
+            RenditionKind::Feed => write!(f, "feed"),
+            RenditionKind::Full => write!(f, "full"),
+            RenditionKind::Poster => write!(f, "poster"),
+        }
+    }
+}
+
+/// A resized, metadata-free copy of an uploaded image
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaRendition {
+    pub kind: RenditionKind,
+    pub url: String,
+    pub media_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaAttachment {
     pub id: Uuid,
@@ -196,8 +364,23 @@ pub struct MediaAttachment {
     pub width: Option<i32>,
     pub height: Option<i32>,
     pub duration: Option<i32>, // For videos, in seconds
+    #[serde(default)]
+    pub renditions: Vec<MediaRendition>,
+    #[serde(default)]
+    pub blurhash: Option<String>,
+    /// Set only when the server probed the file, never from client input
+    #[serde(default)]
+    pub video_codec: Option<String>,
+    #[serde(default)]
+    pub audio_codec: Option<String>,
+    /// Description read out by screen readers
+    #[serde(default)]
+    pub alt_text: Option<String>,
 }
 
+/// Longest alt text accepted for a media attachment
+pub const MAX_ALT_TEXT_LENGTH: usize = 1500;
+
 impl MediaAttachment {
     pub fn new(
         url: String,
@@ -220,6 +403,53 @@ impl MediaAttachment {
             ));
         }
 
+        Self::validate_media(&media_type, size, width, height, duration)?;
+
+        Ok(MediaAttachment {
+            id: Uuid::new_v4(),
+            url,
+            media_type,
+            size,
+            width,
+            height,
+            duration,
+            renditions: Vec::new(),
+            blurhash: None,
+            video_codec: None,
+            audio_codec: None,
+            alt_text: None,
+        })
+    }
+
+    /// Set or clear the attachment's alt text
+    pub fn set_alt_text(&mut self, alt_text: Option<String>) -> Result<()> {
+        let alt_text = alt_text
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+
+        if alt_text
+            .as_ref()
+            .is_some_and(|text| text.chars().count() > MAX_ALT_TEXT_LENGTH)
+        {
+            return Err(AppError::ValidationError(format!(
+                "Alt text cannot exceed {} characters",
+                MAX_ALT_TEXT_LENGTH
+            )));
+        }
+
+        self.alt_text = alt_text;
+        Ok(())
+    }
+
+    /// Validate a media type, size and dimensions against the attachment rules.
+    /// Upload sessions are checked with the same rules before any bytes are stored.
+    pub fn validate_media(
+        media_type: &str,
+        size: i64,
+        width: Option<i32>,
+        height: Option<i32>,
+        duration: Option<i32>,
+    ) -> Result<()> {
         // Validate media type
         if media_type.trim().is_empty() {
             return Err(AppError::ValidationError(
@@ -228,20 +458,7 @@ impl MediaAttachment {
         }
 
         // Validate supported media types
-        let supported_types = [
-            "image/jpeg",
-            "image/png",
-            "image/gif",
-            "image/webp",
-            "video/mp4",
-            "video/webm",
-            "video/quicktime",
-            "audio/mpeg",
-            "audio/wav",
-            "audio/ogg",
-        ];
-
-        if !supported_types.contains(&media_type.as_str()) {
+        if !SUPPORTED_MEDIA_TYPES.contains(&media_type) {
             return Err(AppError::ValidationError(format!(
                 "Unsupported media type: {}",
                 media_type
@@ -255,7 +472,7 @@ impl MediaAttachment {
             ));
         }
 
-        if size > 100 * 1024 * 1024 {
+        if size > MAX_MEDIA_SIZE {
             return Err(AppError::ValidationError(
                 "Media file cannot exceed 100MB".to_string(),
             ));
@@ -293,17 +510,154 @@ impl MediaAttachment {
             }
         }
 
-        Ok(MediaAttachment {
-            id: Uuid::new_v4(),
-            url,
+        Ok(())
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.media_type.starts_with("image/")
+    }
+
+    pub fn is_video(&self) -> bool {
+        self.media_type.starts_with("video/")
+    }
+
+    pub fn is_audio(&self) -> bool {
+        self.media_type.starts_with("audio/")
+    }
+
+    /// A video whose container, codec and duration were checked by the server
+    /// when it was uploaded
+    pub fn is_playable_video(&self) -> bool {
+        self.is_video() && self.video_codec.is_some() && self.duration.is_some()
+    }
+}
+
+/// How long a client has to upload the bytes for a new upload session
+pub const MEDIA_UPLOAD_EXPIRY_MINUTES: i64 = 30;
+
+/// File extension used for stored objects of a supported media type
+pub fn media_extension(media_type: &str) -> Option<&'static str> {
+    match media_type {
+        "image/jpeg" => Some("jpg"),
+        "image/png" => Some("png"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        "video/mp4" => Some("mp4"),
+        "video/webm" => Some("webm"),
+        "video/quicktime" => Some("mov"),
+        "audio/mpeg" => Some("mp3"),
+        "audio/wav" => Some("wav"),
+        "audio/ogg" => Some("ogg"),
+        _ => None,
+    }
+}
+
+/// Media type of a stored object, inferred from its file extension
+pub fn media_type_for_extension(extension: &str) -> Option<&'static str> {
+    SUPPORTED_MEDIA_TYPES
+        .iter()
+        .copied()
+        .find(|media_type| media_extension(media_type) == Some(extension))
+}
+
+/// State of an upload session. Only completed uploads can be attached to posts or messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaUploadStatus {
+    Pending,
+    Completed,
+}
+
+impl std::fmt::Display for MediaUploadStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaUploadStatus::Pending => write!(f, "pending"),
+            MediaUploadStatus::Completed => write!(f, "completed"),
+        }
+    }
+}
+
+impl std::str::FromStr for MediaUploadStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(MediaUploadStatus::Pending),
+            "completed" => Ok(MediaUploadStatus::Completed),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid media upload status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// An upload session for a media file stored in object storage.
+/// The declared type and size are validated up front and checked again when the upload completes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaUpload {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub storage_key: String,
+    pub media_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration: Option<i32>,
+    pub status: MediaUploadStatus,
+    pub url: Option<String>,
+    /// Set when the upload completes; empty for media that is not processed
+    pub renditions: Vec<MediaRendition>,
+    pub blurhash: Option<String>,
+    /// Probed from the file when a video or audio upload completes
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl MediaUpload {
+    pub fn new(
+        user_id: Uuid,
+        media_type: String,
+        size: i64,
+        width: Option<i32>,
+        height: Option<i32>,
+        duration: Option<i32>,
+    ) -> Result<Self> {
+        MediaAttachment::validate_media(&media_type, size, width, height, duration)?;
+
+        let id = Uuid::new_v4();
+        let extension = media_extension(&media_type).ok_or_else(|| {
+            AppError::ValidationError(format!("Unsupported media type: {}", media_type))
+        })?;
+        let now = Utc::now();
+
+        Ok(MediaUpload {
+            id,
+            user_id,
+            storage_key: format!("uploads/{}/{}.{}", user_id, id, extension),
             media_type,
             size,
             width,
             height,
             duration,
+            status: MediaUploadStatus::Pending,
+            url: None,
+            renditions: Vec::new(),
+            blurhash: None,
+            video_codec: None,
+            audio_codec: None,
+            expires_at: now + chrono::Duration::minutes(MEDIA_UPLOAD_EXPIRY_MINUTES),
+            created_at: now,
+            completed_at: None,
         })
     }
 
+    pub fn is_completed(&self) -> bool {
+        self.status == MediaUploadStatus::Completed
+    }
+
     pub fn is_image(&self) -> bool {
         self.media_type.starts_with("image/")
     }
@@ -315,6 +669,212 @@ impl MediaAttachment {
     pub fn is_audio(&self) -> bool {
         self.media_type.starts_with("audio/")
     }
+
+    /// Storage key of a rendition, stored next to the original
+    pub fn rendition_key(&self, kind: RenditionKind, media_type: &str) -> Result<String> {
+        let extension = media_extension(media_type).ok_or_else(|| {
+            AppError::ValidationError(format!("Unsupported media type: {}", media_type))
+        })?;
+        let stem = self
+            .storage_key
+            .rsplit_once('.')
+            .map_or(self.storage_key.as_str(), |(stem, _)| stem);
+        Ok(format!("{}_{}.{}", stem, kind, extension))
+    }
+
+    /// A pending upload can no longer receive bytes once it has expired
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        !self.is_completed() && now >= self.expires_at
+    }
+
+    /// Mark the upload as stored and reachable at the given URL
+    pub fn complete(&mut self, url: String, now: DateTime<Utc>) {
+        self.status = MediaUploadStatus::Completed;
+        self.url = Some(url);
+        self.completed_at = Some(now);
+    }
+
+    /// Build the attachment a post or message stores for this upload
+    pub fn to_attachment(&self) -> Result<MediaAttachment> {
+        let url = match (&self.url, self.is_completed()) {
+            (Some(url), true) => url.clone(),
+            _ => {
+                return Err(AppError::ValidationError(
+                    "Media upload has not been completed".to_string(),
+                ))
+            }
+        };
+
+        let mut attachment = MediaAttachment::new(
+            url,
+            self.media_type.clone(),
+            self.size,
+            self.width,
+            self.height,
+            self.duration,
+        )?;
+        attachment.id = self.id;
+        attachment.renditions = self.renditions.clone();
+        attachment.blurhash = self.blurhash.clone();
+        attachment.video_codec = self.video_codec.clone();
+        attachment.audio_codec = self.audio_codec.clone();
+        Ok(attachment)
+    }
+}
+
+/// Longest video a reel can contain
+pub const MAX_REEL_DURATION_SECONDS: i32 = 60;
+
+/// Maximum number of distinct users that can be mentioned in a single text
+pub const MAX_MENTIONS_PER_TEXT: usize = 10;
+
+/// Longest content warning an author can put on a post
+pub const MAX_CONTENT_WARNING_LENGTH: usize = 200;
+
+/// Posts a user can pin to the top of their profile at once
+pub const MAX_PINNED_POSTS: i64 = 3;
+
+/// An `@username` token found in user-generated text before it is resolved to a user.
+/// Offsets are character (not byte) positions and cover the leading `@`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionCandidate {
+    pub username: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Extract `@username` tokens from text.
+///
+/// A token must not be preceded by a word character (so `me@example.com` is
+/// ignored) and must satisfy the same length rules as `Username`.
+pub fn extract_mentions(text: &str) -> Vec<MentionCandidate> {
+    let chars: Vec<char> = text.chars().collect();
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut candidates = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '@' || (i > 0 && (is_word_char(chars[i - 1]) || chars[i - 1] == '@')) {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut end = i + 1;
+        while end < chars.len() && is_word_char(chars[end]) {
+            end += 1;
+        }
+
+        let username: String = chars[start + 1..end].iter().collect();
+        if (3..=30).contains(&username.len()) && !username.starts_with('_') {
+            candidates.push(MentionCandidate {
+                username: username.to_lowercase(),
+                start,
+                end,
+            });
+        }
+
+        i = end.max(i + 1);
+    }
+
+    candidates
+}
+
+/// Longest hashtag, in characters, that is recognized
+pub const MAX_HASHTAG_LENGTH: usize = 50;
+
+/// Extract `#hashtag` tokens from text, lowercased, in order of first use.
+///
+/// Like mentions, a tag must not be preceded by a word character (so
+/// `issue#12` is ignored). Tags are letters, digits and underscores and need
+/// at least one letter.
+pub fn extract_hashtags(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let is_tag_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut tags: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '#' || (i > 0 && (is_tag_char(chars[i - 1]) || chars[i - 1] == '#')) {
+            i += 1;
+            continue;
+        }
+
+        let mut end = i + 1;
+        while end < chars.len() && is_tag_char(chars[end]) {
+            end += 1;
+        }
+
+        let tag: String = chars[i + 1..end].iter().collect::<String>().to_lowercase();
+        if (1..=MAX_HASHTAG_LENGTH).contains(&tag.chars().count())
+            && tag.chars().any(char::is_alphabetic)
+            && !tags.contains(&tag)
+        {
+            tags.push(tag);
+        }
+
+        i = end.max(i + 1);
+    }
+
+    tags
+}
+
+/// A resolved mention stored alongside the text so clients can render links
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MentionSpan {
+    pub user_id: Uuid,
+    pub username: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MentionSource {
+    Post,
+    Comment,
+    Message,
+}
+
+impl std::fmt::Display for MentionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MentionSource::Post => write!(f, "post"),
+            MentionSource::Comment => write!(f, "comment"),
+            MentionSource::Message => write!(f, "message"),
+        }
+    }
+}
+
+/// A single user mentioned by a post, comment, or message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mention {
+    pub id: Uuid,
+    pub mentioned_user_id: Uuid,
+    pub author_id: Uuid,
+    pub source: MentionSource,
+    pub source_id: Uuid,
+    pub post_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Mention {
+    pub fn new(
+        mentioned_user_id: Uuid,
+        author_id: Uuid,
+        source: MentionSource,
+        source_id: Uuid,
+        post_id: Option<Uuid>,
+    ) -> Self {
+        Mention {
+            id: Uuid::new_v4(),
+            mentioned_user_id,
+            author_id,
+            source,
+            source_id,
+            post_id,
+            created_at: Utc::now(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -324,11 +884,26 @@ pub struct Post {
     pub content_type: PostContentType,
     pub text_content: Option<String>,
     pub media_attachments: Vec<MediaAttachment>,
+    pub mentions: Vec<MentionSpan>,
     pub is_reel: bool,
     pub visibility: PostVisibility,
+    pub status: PostStatus,
+    pub publish_at: Option<DateTime<Utc>>,
     pub like_count: i32,
     pub comment_count: i32,
     pub reshare_count: i32,
+    /// Deduplicated views, updated when view events are rolled up
+    pub view_count: i64,
+    /// Average share of the video watched per impression, from 0 to 100
+    pub avg_completion_percent: f64,
+    /// Hidden by moderation; only the author can still see the post
+    pub is_hidden: bool,
+    /// Set by the author; viewers may have sensitive media blurred or hidden
+    pub is_sensitive: bool,
+    /// Shown in front of sensitive media, such as "Spoilers" or "Medical"
+    pub content_warning: Option<String>,
+    /// When the author pinned the post to the top of their profile
+    pub pinned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -340,6 +915,7 @@ pub struct CreatePostRequest {
     pub media_attachments: Vec<MediaAttachment>,
     pub is_reel: bool,
     pub visibility: PostVisibility,
+    pub poll: Option<CreatePollRequest>,
 }
 
 impl Post {
@@ -379,125 +955,1267 @@ impl Post {
                 ));
             }
 
-            // Validate video duration for reels (under 60 seconds)
-            for media in &request.media_attachments {
-                if media.media_type.starts_with("video/") {
-                    if let Some(duration) = media.duration {
-                        if duration > 60 {
-                            return Err(AppError::ValidationError(
-                                "Reel videos must be under 60 seconds".to_string(),
-                            ));
-                        }
-                    }
+            // Only uploaded videos carry a duration and codec the server has checked
+            for media in request.media_attachments.iter().filter(|m| m.is_video()) {
+                if !media.is_playable_video() {
+                    return Err(AppError::ValidationError(
+                        "Reel videos must be uploaded through the media API".to_string(),
+                    ));
+                }
+                if media
+                    .duration
+                    .is_some_and(|d| d > MAX_REEL_DURATION_SECONDS)
+                {
+                    return Err(AppError::ValidationError(format!(
+                        "Reel videos must be under {} seconds",
+                        MAX_REEL_DURATION_SECONDS
+                    )));
                 }
             }
         }
 
-        // Validate media attachments count
-        if request.media_attachments.len() > 10 {
+        // Validate media attachments count
+        if request.media_attachments.len() > 10 {
+            return Err(AppError::ValidationError(
+                "Posts cannot have more than 10 media attachments".to_string(),
+            ));
+        }
+
+        // Validate poll constraints
+        if let Some(ref poll) = request.poll {
+            if request.is_reel {
+                return Err(AppError::ValidationError(
+                    "Reels cannot contain a poll".to_string(),
+                ));
+            }
+            if request.text_content.is_none() {
+                return Err(AppError::ValidationError(
+                    "Polls must have a question".to_string(),
+                ));
+            }
+            poll.validate()?;
+        }
+
+        // Determine content type
+        let content_type = if request.poll.is_some() {
+            PostContentType::Poll
+        } else if request.media_attachments.is_empty() {
+            PostContentType::Text
+        } else {
+            let has_image = request
+                .media_attachments
+                .iter()
+                .any(|m| m.media_type.starts_with("image/"));
+            let has_video = request
+                .media_attachments
+                .iter()
+                .any(|m| m.media_type.starts_with("video/"));
+
+            match (has_image, has_video) {
+                (true, true) => PostContentType::Mixed,
+                (true, false) => PostContentType::Image,
+                (false, true) => PostContentType::Video,
+                (false, false) => PostContentType::Text,
+            }
+        };
+
+        let now = Utc::now();
+
+        Ok(Post {
+            id: Uuid::new_v4(),
+            user_id: request.user_id,
+            content_type,
+            text_content: request.text_content,
+            media_attachments: request.media_attachments,
+            mentions: Vec::new(),
+            is_reel: request.is_reel,
+            visibility: request.visibility,
+            status: PostStatus::Published,
+            publish_at: None,
+            like_count: 0,
+            comment_count: 0,
+            reshare_count: 0,
+            view_count: 0,
+            avg_completion_percent: 0.0,
+            is_hidden: false,
+            is_sensitive: false,
+            content_warning: None,
+            pinned_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Mark the post as sensitive or not. A content warning always marks it
+    /// as sensitive.
+    pub fn set_sensitivity(
+        &mut self,
+        is_sensitive: bool,
+        content_warning: Option<String>,
+    ) -> Result<()> {
+        let content_warning = content_warning
+            .map(|warning| warning.trim().to_string())
+            .filter(|warning| !warning.is_empty());
+
+        if content_warning
+            .as_ref()
+            .is_some_and(|warning| warning.chars().count() > MAX_CONTENT_WARNING_LENGTH)
+        {
+            return Err(AppError::ValidationError(format!(
+                "Content warning cannot exceed {} characters",
+                MAX_CONTENT_WARNING_LENGTH
+            )));
+        }
+
+        self.is_sensitive = is_sensitive || content_warning.is_some();
+        self.content_warning = content_warning;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Keep the post as a draft that only the author can see
+    pub fn save_as_draft(&mut self) {
+        self.status = PostStatus::Draft;
+        self.publish_at = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Schedule the post to be published automatically at `publish_at`
+    pub fn schedule(&mut self, publish_at: DateTime<Utc>) -> Result<()> {
+        if publish_at <= Utc::now() {
+            return Err(AppError::ValidationError(
+                "Scheduled publish time must be in the future".to_string(),
+            ));
+        }
+
+        self.status = PostStatus::Scheduled;
+        self.publish_at = Some(publish_at);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Publish the post. `created_at` is moved to the publish time so that
+    /// feeds ordered by creation time show it as new.
+    pub fn publish(&mut self) {
+        let now = Utc::now();
+        self.status = PostStatus::Published;
+        self.publish_at = None;
+        self.created_at = now;
+        self.updated_at = now;
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.pinned_at.is_some()
+    }
+
+    pub fn is_published(&self) -> bool {
+        self.status == PostStatus::Published
+    }
+
+    pub fn increment_like_count(&mut self) {
+        self.like_count += 1;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn decrement_like_count(&mut self) {
+        if self.like_count > 0 {
+            self.like_count -= 1;
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn increment_comment_count(&mut self) {
+        self.comment_count += 1;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn decrement_comment_count(&mut self) {
+        if self.comment_count > 0 {
+            self.comment_count -= 1;
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn increment_reshare_count(&mut self) {
+        self.reshare_count += 1;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn decrement_reshare_count(&mut self) {
+        if self.reshare_count > 0 {
+            self.reshare_count -= 1;
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn is_video_content(&self) -> bool {
+        matches!(
+            self.content_type,
+            PostContentType::Video | PostContentType::Mixed
+        )
+    }
+
+    pub fn is_image_content(&self) -> bool {
+        matches!(
+            self.content_type,
+            PostContentType::Image | PostContentType::Mixed
+        )
+    }
+
+    pub fn has_media(&self) -> bool {
+        !self.media_attachments.is_empty()
+    }
+
+    pub fn get_total_engagement(&self) -> i32 {
+        self.like_count + self.comment_count + self.reshare_count
+    }
+
+    /// Whether the post has a server-probed video that can record views
+    pub fn has_playable_video(&self) -> bool {
+        self.media_attachments
+            .iter()
+            .any(MediaAttachment::is_playable_video)
+    }
+}
+
+/// A post as stored in a follower's home timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub post_id: Uuid,
+    pub author_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&Post> for TimelineEntry {
+    fn from(post: &Post) -> Self {
+        Self {
+            post_id: post.id,
+            author_id: post.user_id,
+            created_at: post.created_at,
+        }
+    }
+}
+
+/// What a user told the feed about a post they did not want to see
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedFeedbackKind {
+    /// Drop the post from the user's feeds
+    Hide,
+    /// Drop the post and show fewer posts like it
+    NotInterested,
+    /// Show fewer posts by the post's author
+    ShowFewerFromAuthor,
+}
+
+impl std::fmt::Display for FeedFeedbackKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeedFeedbackKind::Hide => write!(f, "hide"),
+            FeedFeedbackKind::NotInterested => write!(f, "not_interested"),
+            FeedFeedbackKind::ShowFewerFromAuthor => write!(f, "show_fewer_from_author"),
+        }
+    }
+}
+
+impl std::str::FromStr for FeedFeedbackKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "hide" => Ok(FeedFeedbackKind::Hide),
+            "not_interested" => Ok(FeedFeedbackKind::NotInterested),
+            "show_fewer_from_author" => Ok(FeedFeedbackKind::ShowFewerFromAuthor),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid feed feedback: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl FeedFeedbackKind {
+    /// Whether the post itself is dropped from the user's feeds
+    pub fn drops_post(&self) -> bool {
+        matches!(
+            self,
+            FeedFeedbackKind::Hide | FeedFeedbackKind::NotInterested
+        )
+    }
+}
+
+/// Negative feedback a user gave on a post. The post's author and hashtags
+/// are kept with it so the feedback still counts once the post is gone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedFeedback {
+    pub user_id: Uuid,
+    pub post_id: Uuid,
+    pub author_id: Uuid,
+    pub kind: FeedFeedbackKind,
+    pub hashtags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FeedFeedback {
+    pub fn new(user_id: Uuid, post: &Post, kind: FeedFeedbackKind) -> Self {
+        FeedFeedback {
+            user_id,
+            post_id: post.id,
+            author_id: post.user_id,
+            kind,
+            hashtags: post
+                .text_content
+                .as_deref()
+                .map(extract_hashtags)
+                .unwrap_or_default(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Watching at least this much of a video counts as completing it
+pub const VIEW_COMPLETION_THRESHOLD_PERCENT: f64 = 95.0;
+pub const MAX_VIEW_REPLAYS: i32 = 100;
+/// Upper bound on the watch time a single event can report
+pub const MAX_VIEW_WATCH_TIME_MS: i64 = 6 * 60 * 60 * 1000;
+
+/// One viewing session of a video post, reported by the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostViewEvent {
+    pub post_id: Uuid,
+    pub viewer_id: Uuid,
+    pub watch_time_ms: i64,
+    /// Furthest point reached, from 0 to 100
+    pub completion_percent: f64,
+    /// Times the video looped back to the start
+    pub replays: i32,
+    /// Whether the viewer follows the post's author
+    pub from_follower: bool,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl PostViewEvent {
+    pub fn new(
+        post_id: Uuid,
+        viewer_id: Uuid,
+        watch_time_ms: i64,
+        completion_percent: f64,
+        replays: i32,
+    ) -> Result<Self> {
+        if !(0..=MAX_VIEW_WATCH_TIME_MS).contains(&watch_time_ms) {
+            return Err(AppError::ValidationError(
+                "Watch time is out of range".to_string(),
+            ));
+        }
+
+        if !(0.0..=100.0).contains(&completion_percent) {
+            return Err(AppError::ValidationError(
+                "Completion percent must be between 0 and 100".to_string(),
+            ));
+        }
+
+        if !(0..=MAX_VIEW_REPLAYS).contains(&replays) {
+            return Err(AppError::ValidationError(format!(
+                "Replays must be between 0 and {}",
+                MAX_VIEW_REPLAYS
+            )));
+        }
+
+        Ok(PostViewEvent {
+            post_id,
+            viewer_id,
+            watch_time_ms,
+            completion_percent,
+            replays,
+            from_follower: false,
+            occurred_at: Utc::now(),
+        })
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completion_percent >= VIEW_COMPLETION_THRESHOLD_PERCENT || self.replays > 0
+    }
+}
+
+/// View counters for a post, either as totals or as a delta waiting to be rolled up
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostViewStats {
+    pub post_id: Uuid,
+    pub impression_count: i64,
+    pub view_count: i64,
+    pub replay_count: i64,
+    pub completed_count: i64,
+    pub total_watch_ms: i64,
+    pub completion_percent_sum: f64,
+    /// Distinct viewers per day
+    pub reach_count: i64,
+    pub follower_impression_count: i64,
+    pub follower_reach_count: i64,
+}
+
+impl PostViewStats {
+    /// Counters for one view event. Watch time, completion and replays are
+    /// only taken from events that count as a view, so repeating a session
+    /// within the dedup window cannot inflate them.
+    pub fn for_event(event: &PostViewEvent, counted: bool, reached: bool) -> Self {
+        let (replays, completed, watch_ms, completion_percent) = if counted {
+            (
+                event.replays as i64,
+                event.is_completed() as i64,
+                event.watch_time_ms,
+                event.completion_percent,
+            )
+        } else {
+            (0, 0, 0, 0.0)
+        };
+
+        PostViewStats {
+            post_id: event.post_id,
+            impression_count: 1,
+            view_count: counted as i64,
+            replay_count: replays,
+            completed_count: completed,
+            total_watch_ms: watch_ms,
+            completion_percent_sum: completion_percent,
+            reach_count: reached as i64,
+            follower_impression_count: event.from_follower as i64,
+            follower_reach_count: (reached && event.from_follower) as i64,
+        }
+    }
+
+    pub fn avg_watch_ms(&self) -> i64 {
+        if self.view_count == 0 {
+            0
+        } else {
+            self.total_watch_ms / self.view_count
+        }
+    }
+
+    pub fn avg_completion_percent(&self) -> f64 {
+        if self.view_count == 0 {
+            0.0
+        } else {
+            self.completion_percent_sum / self.view_count as f64
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.impression_count == 0 && self.view_count == 0
+    }
+}
+
+/// An interaction that feeds the daily insight rollups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngagementEvent {
+    PostLiked(Uuid),
+    PostUnliked(Uuid),
+    PostCommented(Uuid),
+    UserFollowed(Uuid),
+    UserUnfollowed(Uuid),
+}
+
+/// Engagement counters summed over some set of posts and days
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct EngagementTotals {
+    pub impressions: i64,
+    pub follower_impressions: i64,
+    /// Distinct viewers, counted once per day
+    pub reach: i64,
+    pub follower_reach: i64,
+    /// Likes minus unlikes
+    pub likes: i64,
+    pub comments: i64,
+}
+
+impl EngagementTotals {
+    pub fn non_follower_impressions(&self) -> i64 {
+        self.impressions - self.follower_impressions
+    }
+
+    pub fn non_follower_reach(&self) -> i64 {
+        self.reach - self.follower_reach
+    }
+
+    pub fn interactions(&self) -> i64 {
+        self.likes + self.comments
+    }
+
+    pub fn add(&mut self, other: &EngagementTotals) {
+        self.impressions += other.impressions;
+        self.follower_impressions += other.follower_impressions;
+        self.reach += other.reach;
+        self.follower_reach += other.follower_reach;
+        self.likes += other.likes;
+        self.comments += other.comments;
+    }
+}
+
+/// One day of rolled-up engagement for a post or for all of an author's posts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyEngagement {
+    pub day: NaiveDate,
+    pub totals: EngagementTotals,
+}
+
+/// One day of a user's follower changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyFollowerStats {
+    pub day: NaiveDate,
+    pub followers_gained: i64,
+    pub followers_lost: i64,
+}
+
+impl DailyFollowerStats {
+    pub fn net_change(&self) -> i64 {
+        self.followers_gained - self.followers_lost
+    }
+}
+
+pub const MIN_POLL_OPTIONS: usize = 2;
+pub const MAX_POLL_OPTIONS: usize = 4;
+pub const MAX_POLL_OPTION_LENGTH: usize = 80;
+pub const MAX_POLL_DURATION_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePollRequest {
+    pub options: Vec<String>,
+    pub closes_at: DateTime<Utc>,
+}
+
+impl CreatePollRequest {
+    pub fn validate(&self) -> Result<()> {
+        if self.options.len() < MIN_POLL_OPTIONS || self.options.len() > MAX_POLL_OPTIONS {
+            return Err(AppError::ValidationError(format!(
+                "Polls must have between {} and {} options",
+                MIN_POLL_OPTIONS, MAX_POLL_OPTIONS
+            )));
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for option in &self.options {
+            let option = option.trim();
+            if option.is_empty() {
+                return Err(AppError::ValidationError(
+                    "Poll options cannot be empty".to_string(),
+                ));
+            }
+            if option.chars().count() > MAX_POLL_OPTION_LENGTH {
+                return Err(AppError::ValidationError(format!(
+                    "Poll options cannot exceed {} characters",
+                    MAX_POLL_OPTION_LENGTH
+                )));
+            }
+            if !seen.insert(option.to_lowercase()) {
+                return Err(AppError::ValidationError(
+                    "Poll options must be unique".to_string(),
+                ));
+            }
+        }
+
+        let now = Utc::now();
+        if self.closes_at <= now {
+            return Err(AppError::ValidationError(
+                "Poll closing time must be in the future".to_string(),
+            ));
+        }
+        if self.closes_at > now + chrono::Duration::days(MAX_POLL_DURATION_DAYS) {
+            return Err(AppError::ValidationError(format!(
+                "Polls cannot stay open for more than {} days",
+                MAX_POLL_DURATION_DAYS
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// A poll attached to a post. `vote_counts[i]` holds the votes for `options[i]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Poll {
+    pub post_id: Uuid,
+    pub options: Vec<String>,
+    pub vote_counts: Vec<i32>,
+    pub closes_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Poll {
+    pub fn new(post_id: Uuid, request: CreatePollRequest) -> Result<Self> {
+        request.validate()?;
+
+        let options: Vec<String> = request
+            .options
+            .iter()
+            .map(|option| option.trim().to_string())
+            .collect();
+        let vote_counts = vec![0; options.len()];
+
+        Ok(Poll {
+            post_id,
+            options,
+            vote_counts,
+            closes_at: request.closes_at,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn is_closed(&self) -> bool {
+        Utc::now() >= self.closes_at
+    }
+
+    pub fn total_votes(&self) -> i32 {
+        self.vote_counts.iter().sum()
+    }
+
+    pub fn validate_option(&self, option_index: i32) -> Result<()> {
+        if option_index < 0 || option_index as usize >= self.options.len() {
+            return Err(AppError::ValidationError("Invalid poll option".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    pub content: String,
+    pub mentions: Vec<MentionSpan>,
+    pub like_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCommentRequest {
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    pub content: String,
+}
+
+impl Comment {
+    pub fn new(request: CreateCommentRequest) -> Result<Self> {
+        if request.content.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Comment content cannot be empty".to_string(),
+            ));
+        }
+
+        if request.content.len() > 1000 {
+            return Err(AppError::ValidationError(
+                "Comment content cannot exceed 1000 characters".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+
+        Ok(Comment {
+            id: Uuid::new_v4(),
+            post_id: request.post_id,
+            user_id: request.user_id,
+            parent_comment_id: request.parent_comment_id,
+            content: request.content,
+            mentions: Vec::new(),
+            like_count: 0,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+/// A user suggested to another to follow, with why they were suggested
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FollowSuggestion {
+    pub user_id: Uuid,
+    /// Accounts the viewer follows that follow the user
+    pub mutual_follows: i64,
+    /// Accounts in the viewer's network, followers or followed, that follow the user
+    pub network_followers: i64,
+    pub follows_you: bool,
+    /// The user is in the viewer's phone contacts
+    pub in_contacts: bool,
+    /// Conversations the viewer and the user are both in
+    pub shared_conversations: i64,
+    pub score: f64,
+}
+
+impl FollowSuggestion {
+    /// Score the suggestion from its signals. A matched contact or a
+    /// follower outweighs a few mutual follows; counts are log-scaled so a
+    /// large network does not drown out the rest.
+    pub fn scored(mut self) -> Self {
+        self.score = 2.0 * (self.mutual_follows.max(0) as f64).ln_1p()
+            + (self.network_followers.max(0) as f64).ln_1p()
+            + 1.5 * (self.shared_conversations.max(0) as f64).ln_1p()
+            + if self.in_contacts { 3.0 } else { 0.0 }
+            + if self.follows_you { 2.0 } else { 0.0 };
+        self
+    }
+}
+
+/// Longest post search query, in characters
+pub const MAX_SEARCH_QUERY_LENGTH: usize = 200;
+
+/// Marks the start of a matched term in a search headline before it is
+/// turned into a `SearchSnippet`
+pub const SEARCH_HIGHLIGHT_START: char = '\u{2}';
+
+/// Marks the end of a matched term in a search headline
+pub const SEARCH_HIGHLIGHT_END: char = '\u{3}';
+
+/// A full-text post search: the terms to match and filters on the results
+#[derive(Debug, Clone, Default)]
+pub struct PostSearchQuery {
+    /// Words to match; quoted phrases, `or` and `-word` are understood
+    pub text: String,
+    pub author_id: Option<Uuid>,
+    /// Only posts created at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only posts created before this time
+    pub until: Option<DateTime<Utc>>,
+    pub content_type: Option<PostContentType>,
+    pub reels_only: bool,
+    /// Only posts using this hashtag, lowercased and without the `#`
+    pub hashtag: Option<String>,
+}
+
+impl PostSearchQuery {
+    /// Trim the terms and normalize the hashtag, rejecting empty or overlong
+    /// queries, inverted date ranges and malformed hashtags
+    pub fn normalized(mut self) -> Result<Self> {
+        self.text = self.text.trim().to_string();
+        if self.text.is_empty() {
+            return Err(AppError::ValidationError(
+                "Search query cannot be empty".to_string(),
+            ));
+        }
+        if self.text.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Search query cannot exceed {} characters",
+                MAX_SEARCH_QUERY_LENGTH
+            )));
+        }
+        if let (Some(since), Some(until)) = (self.since, self.until) {
+            if since >= until {
+                return Err(AppError::ValidationError(
+                    "since must be before until".to_string(),
+                ));
+            }
+        }
+        if let Some(hashtag) = self.hashtag.take() {
+            let tag = hashtag.trim().trim_start_matches('#');
+            match extract_hashtags(&format!("#{}", tag)).as_slice() {
+                [parsed] if parsed.chars().count() == tag.chars().count() => {
+                    self.hashtag = Some(parsed.clone());
+                }
+                _ => {
+                    return Err(AppError::ValidationError(format!(
+                        "Invalid hashtag: {}",
+                        hashtag
+                    )))
+                }
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// An excerpt of a matching post with the matched terms marked.
+/// Highlights are `(start, end)` character (not byte) offsets into `text`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchSnippet {
+    pub text: String,
+    pub highlights: Vec<(usize, usize)>,
+}
+
+impl SearchSnippet {
+    /// Build a snippet from a headline whose matches are wrapped in
+    /// `SEARCH_HIGHLIGHT_START` and `SEARCH_HIGHLIGHT_END`
+    pub fn from_headline(headline: &str) -> Self {
+        let mut snippet = SearchSnippet::default();
+        let mut open: Option<usize> = None;
+        let mut len = 0;
+        for c in headline.chars() {
+            match c {
+                SEARCH_HIGHLIGHT_START => open = Some(len),
+                SEARCH_HIGHLIGHT_END => {
+                    if let Some(start) = open.take() {
+                        if start < len {
+                            snippet.highlights.push((start, len));
+                        }
+                    }
+                }
+                _ => {
+                    snippet.text.push(c);
+                    len += 1;
+                }
+            }
+        }
+        snippet
+    }
+}
+
+/// A post matching a search, with its relevance and a highlighted excerpt
+#[derive(Debug, Clone)]
+pub struct PostSearchHit {
+    pub post: Post,
+    pub rank: f32,
+    pub snippet: SearchSnippet,
+}
+
+/// A hashtag matching a search, with how many posts use it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashtagCount {
+    pub tag: String,
+    pub post_count: i64,
+}
+
+/// A post saved by a user. Bookmarks are private and never count toward
+/// a post's public engagement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub post_id: Uuid,
+    pub collection_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Bookmark {
+    pub fn new(user_id: Uuid, post_id: Uuid, collection_id: Option<Uuid>) -> Self {
+        Bookmark {
+            id: Uuid::new_v4(),
+            user_id,
+            post_id,
+            collection_id,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A named, private group of bookmarks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookmarkCollection {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BookmarkCollection {
+    pub fn new(user_id: Uuid, name: String) -> Result<Self> {
+        let name = name.trim().to_string();
+
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Collection name cannot be empty".to_string(),
+            ));
+        }
+
+        if name.chars().count() > 50 {
+            return Err(AppError::ValidationError(
+                "Collection name cannot exceed 50 characters".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+
+        Ok(BookmarkCollection {
+            id: Uuid::new_v4(),
+            user_id,
+            name,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+/// How long a story stays up after it is posted
+pub const STORY_TTL_HOURS: i64 = 24;
+pub const MAX_STORY_CAPTION_LENGTH: usize = 200;
+
+/// An image or video shared outside the post feed that expires after a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Story {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Attachment built from the story's upload; its ID is the upload ID
+    pub media: MediaAttachment,
+    pub caption: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Story {
+    pub fn new(user_id: Uuid, media: MediaAttachment, caption: Option<String>) -> Result<Self> {
+        if !media.is_image() && !media.is_video() {
             return Err(AppError::ValidationError(
-                "Posts cannot have more than 10 media attachments".to_string(),
+                "Stories must be an image or a video".to_string(),
             ));
         }
 
-        // Determine content type
-        let content_type = if request.media_attachments.is_empty() {
-            PostContentType::Text
-        } else {
-            let has_image = request
-                .media_attachments
-                .iter()
-                .any(|m| m.media_type.starts_with("image/"));
-            let has_video = request
-                .media_attachments
-                .iter()
-                .any(|m| m.media_type.starts_with("video/"));
+        if media.is_video() && !media.is_playable_video() {
+            return Err(AppError::ValidationError(
+                "Story videos must be uploaded through the media API".to_string(),
+            ));
+        }
 
-            match (has_image, has_video) {
-                (true, true) => PostContentType::Mixed,
-                (true, false) => PostContentType::Image,
-                (false, true) => PostContentType::Video,
-                (false, false) => PostContentType::Text,
-            }
-        };
+        let caption = caption
+            .map(|caption| caption.trim().to_string())
+            .filter(|caption| !caption.is_empty());
+        if caption
+            .as_ref()
+            .is_some_and(|caption| caption.chars().count() > MAX_STORY_CAPTION_LENGTH)
+        {
+            return Err(AppError::ValidationError(format!(
+                "Story captions cannot exceed {} characters",
+                MAX_STORY_CAPTION_LENGTH
+            )));
+        }
 
         let now = Utc::now();
 
-        Ok(Post {
+        Ok(Story {
             id: Uuid::new_v4(),
-            user_id: request.user_id,
-            content_type,
-            text_content: request.text_content,
-            media_attachments: request.media_attachments,
-            is_reel: request.is_reel,
-            visibility: request.visibility,
-            like_count: 0,
-            comment_count: 0,
-            reshare_count: 0,
+            user_id,
+            media,
+            caption,
             created_at: now,
-            updated_at: now,
+            expires_at: now + chrono::Duration::hours(STORY_TTL_HOURS),
         })
     }
 
-    pub fn increment_like_count(&mut self) {
-        self.like_count += 1;
-        self.updated_at = Utc::now();
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
     }
+}
 
-    pub fn decrement_like_count(&mut self) {
-        if self.like_count > 0 {
-            self.like_count -= 1;
-            self.updated_at = Utc::now();
+/// A user who has seen a story
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryView {
+    pub story_id: Uuid,
+    pub viewer_id: Uuid,
+    pub viewed_at: DateTime<Utc>,
+}
+
+/// What a report points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportTargetType {
+    Post,
+    Comment,
+    Message,
+    User,
+}
+
+impl ReportTargetType {
+    /// Users are suspended rather than hidden
+    pub fn is_content(&self) -> bool {
+        !matches!(self, ReportTargetType::User)
+    }
+}
+
+impl std::fmt::Display for ReportTargetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportTargetType::Post => write!(f, "post"),
+            ReportTargetType::Comment => write!(f, "comment"),
+            ReportTargetType::Message => write!(f, "message"),
+            ReportTargetType::User => write!(f, "user"),
         }
     }
+}
 
-    pub fn increment_comment_count(&mut self) {
-        self.comment_count += 1;
-        self.updated_at = Utc::now();
+impl std::str::FromStr for ReportTargetType {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "post" => Ok(ReportTargetType::Post),
+            "comment" => Ok(ReportTargetType::Comment),
+            "message" => Ok(ReportTargetType::Message),
+            "user" => Ok(ReportTargetType::User),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid report target: {}",
+                s
+            ))),
+        }
     }
+}
 
-    pub fn decrement_comment_count(&mut self) {
-        if self.comment_count > 0 {
-            self.comment_count -= 1;
-            self.updated_at = Utc::now();
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    HateSpeech,
+    Violence,
+    Nudity,
+    Scam,
+    Misinformation,
+    SelfHarm,
+    Other,
+}
+
+impl std::fmt::Display for ReportReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportReason::Spam => write!(f, "spam"),
+            ReportReason::Harassment => write!(f, "harassment"),
+            ReportReason::HateSpeech => write!(f, "hate_speech"),
+            ReportReason::Violence => write!(f, "violence"),
+            ReportReason::Nudity => write!(f, "nudity"),
+            ReportReason::Scam => write!(f, "scam"),
+            ReportReason::Misinformation => write!(f, "misinformation"),
+            ReportReason::SelfHarm => write!(f, "self_harm"),
+            ReportReason::Other => write!(f, "other"),
         }
     }
+}
 
-    pub fn increment_reshare_count(&mut self) {
-        self.reshare_count += 1;
-        self.updated_at = Utc::now();
+impl std::str::FromStr for ReportReason {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "spam" => Ok(ReportReason::Spam),
+            "harassment" => Ok(ReportReason::Harassment),
+            "hate_speech" => Ok(ReportReason::HateSpeech),
+            "violence" => Ok(ReportReason::Violence),
+            "nudity" => Ok(ReportReason::Nudity),
+            "scam" => Ok(ReportReason::Scam),
+            "misinformation" => Ok(ReportReason::Misinformation),
+            "self_harm" => Ok(ReportReason::SelfHarm),
+            "other" => Ok(ReportReason::Other),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid report reason: {}",
+                s
+            ))),
+        }
     }
+}
 
-    pub fn decrement_reshare_count(&mut self) {
-        if self.reshare_count > 0 {
-            self.reshare_count -= 1;
-            self.updated_at = Utc::now();
+pub const MAX_REPORT_DETAILS_LENGTH: usize = 1000;
+
+/// A user's complaint about a post, comment, message or another user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub id: Uuid,
+    pub reporter_id: Uuid,
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Report {
+    pub fn new(
+        reporter_id: Uuid,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        reason: ReportReason,
+        details: Option<String>,
+    ) -> Result<Self> {
+        let details = details
+            .map(|details| details.trim().to_string())
+            .filter(|details| !details.is_empty());
+        if details
+            .as_ref()
+            .is_some_and(|details| details.chars().count() > MAX_REPORT_DETAILS_LENGTH)
+        {
+            return Err(AppError::ValidationError(format!(
+                "Report details cannot exceed {} characters",
+                MAX_REPORT_DETAILS_LENGTH
+            )));
         }
+
+        Ok(Report {
+            id: Uuid::new_v4(),
+            reporter_id,
+            target_type,
+            target_id,
+            reason,
+            details,
+            created_at: Utc::now(),
+        })
     }
+}
 
-    pub fn is_video_content(&self) -> bool {
-        matches!(
-            self.content_type,
-            PostContentType::Video | PostContentType::Mixed
-        )
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModerationCaseStatus {
+    Open,
+    Resolved,
+}
+
+impl std::fmt::Display for ModerationCaseStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModerationCaseStatus::Open => write!(f, "open"),
+            ModerationCaseStatus::Resolved => write!(f, "resolved"),
+        }
     }
+}
 
-    pub fn is_image_content(&self) -> bool {
-        matches!(
-            self.content_type,
-            PostContentType::Image | PostContentType::Mixed
-        )
+impl std::str::FromStr for ModerationCaseStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "open" => Ok(ModerationCaseStatus::Open),
+            "resolved" => Ok(ModerationCaseStatus::Resolved),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid moderation case status: {}",
+                s
+            ))),
+        }
     }
+}
 
-    pub fn has_media(&self) -> bool {
-        !self.media_attachments.is_empty()
+/// All reports against one target, reviewed together by a moderator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationCase {
+    pub id: Uuid,
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    /// Author of the reported content, or the reported user
+    pub target_user_id: Uuid,
+    pub status: ModerationCaseStatus,
+    /// Reports received since the case was last resolved
+    pub report_count: i32,
+    pub is_hidden: bool,
+    /// Content filter rules that sent the target for review
+    pub flagged_rules: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl ModerationCase {
+    pub fn is_open(&self) -> bool {
+        self.status == ModerationCaseStatus::Open
     }
+}
 
-    pub fn get_total_engagement(&self) -> i32 {
-        self.like_count + self.comment_count + self.reshare_count
+pub const MAX_MODERATION_NOTE_LENGTH: usize = 1000;
+pub const MAX_SUSPENSION_DAYS: i64 = 365;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModerationActionType {
+    /// Nothing wrong; hidden content is restored
+    Dismiss,
+    RemoveContent,
+    Warn,
+    SuspendUser {
+        until: DateTime<Utc>,
+    },
+    LockWallet,
+}
+
+impl ModerationActionType {
+    /// Suspend the user for a whole number of days from `now`
+    pub fn suspend_for(days: i64, now: DateTime<Utc>) -> Result<Self> {
+        if !(1..=MAX_SUSPENSION_DAYS).contains(&days) {
+            return Err(AppError::ValidationError(format!(
+                "Suspensions must last between 1 and {} days",
+                MAX_SUSPENSION_DAYS
+            )));
+        }
+
+        Ok(ModerationActionType::SuspendUser {
+            until: now + chrono::Duration::days(days),
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModerationActionType::Dismiss => "dismiss",
+            ModerationActionType::RemoveContent => "remove_content",
+            ModerationActionType::Warn => "warn",
+            ModerationActionType::SuspendUser { .. } => "suspend_user",
+            ModerationActionType::LockWallet => "lock_wallet",
+        }
+    }
+
+    /// Rebuild an action from its stored name and suspension end
+    pub fn from_parts(name: &str, suspended_until: Option<DateTime<Utc>>) -> Result<Self> {
+        match (name, suspended_until) {
+            ("dismiss", _) => Ok(ModerationActionType::Dismiss),
+            ("remove_content", _) => Ok(ModerationActionType::RemoveContent),
+            ("warn", _) => Ok(ModerationActionType::Warn),
+            ("suspend_user", Some(until)) => Ok(ModerationActionType::SuspendUser { until }),
+            ("lock_wallet", _) => Ok(ModerationActionType::LockWallet),
+            _ => Err(AppError::ValidationError(format!(
+                "Invalid moderation action: {}",
+                name
+            ))),
+        }
+    }
+
+    pub fn suspended_until(&self) -> Option<DateTime<Utc>> {
+        match self {
+            ModerationActionType::SuspendUser { until } => Some(*until),
+            _ => None,
+        }
+    }
+}
+
+/// A moderator's decision on a case, kept as an audit record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationAction {
+    pub id: Uuid,
+    pub case_id: Uuid,
+    pub moderator_id: Uuid,
+    pub action: ModerationActionType,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ModerationAction {
+    pub fn new(
+        case_id: Uuid,
+        moderator_id: Uuid,
+        action: ModerationActionType,
+        note: Option<String>,
+    ) -> Result<Self> {
+        let note = note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        if note
+            .as_ref()
+            .is_some_and(|note| note.chars().count() > MAX_MODERATION_NOTE_LENGTH)
+        {
+            return Err(AppError::ValidationError(format!(
+                "Moderation notes cannot exceed {} characters",
+                MAX_MODERATION_NOTE_LENGTH
+            )));
+        }
+
+        Ok(ModerationAction {
+            id: Uuid::new_v4(),
+            case_id,
+            moderator_id,
+            action,
+            note,
+            created_at: Utc::now(),
+        })
     }
 }
 
+/// A conversation as listed for one of its participants
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversationSummary {
+    pub id: Uuid,
+    pub participant_ids: Vec<Uuid>,
+    pub is_group: bool,
+    pub group_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// When the last message was sent, or when the conversation was created
+    pub last_activity_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MessageType {
     Text,
@@ -576,6 +2294,9 @@ pub struct Message {
     pub media_url: Option<String>,
     pub payment_data: Option<PaymentData>,
     pub reply_to_id: Option<Uuid>,
+    /// Story this message replies to, until the story expires
+    pub story_id: Option<Uuid>,
+    pub mentions: Vec<MentionSpan>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -665,6 +2386,8 @@ impl Message {
             media_url: request.media_url,
             payment_data: request.payment_data,
             reply_to_id: request.reply_to_id,
+            story_id: None,
+            mentions: Vec::new(),
             created_at: Utc::now(),
         })
     }
@@ -1086,6 +2809,7 @@ pub enum NotificationType {
     PaymentReceived,
     PaymentSent,
     Mention,
+    PollClosed,
     System,
 }
 
@@ -1099,6 +2823,7 @@ impl std::fmt::Display for NotificationType {
             NotificationType::PaymentReceived => write!(f, "payment_received"),
             NotificationType::PaymentSent => write!(f, "payment_sent"),
             NotificationType::Mention => write!(f, "mention"),
+            NotificationType::PollClosed => write!(f, "poll_closed"),
             NotificationType::System => write!(f, "system"),
         }
     }
@@ -1116,6 +2841,7 @@ impl std::str::FromStr for NotificationType {
             "payment_received" => Ok(NotificationType::PaymentReceived),
             "payment_sent" => Ok(NotificationType::PaymentSent),
             "mention" => Ok(NotificationType::Mention),
+            "poll_closed" => Ok(NotificationType::PollClosed),
             "system" => Ok(NotificationType::System),
             _ => Err(AppError::ValidationError(format!(
                 "Invalid notification type: {}",
@@ -1319,6 +3045,7 @@ pub struct NotificationPreferences {
     pub follow_notifications: bool,
     pub payment_notifications: bool,
     pub mention_notifications: bool,
+    pub poll_notifications: bool,
     pub system_notifications: bool,
 }
 
@@ -1334,6 +3061,7 @@ impl Default for NotificationPreferences {
             follow_notifications: true,
             payment_notifications: true,
             mention_notifications: true,
+            poll_notifications: true,
             system_notifications: true,
         }
     }
@@ -1357,6 +3085,7 @@ impl NotificationPreferences {
                 self.payment_notifications
             }
             NotificationType::Mention => self.mention_notifications,
+            NotificationType::PollClosed => self.poll_notifications,
             NotificationType::System => self.system_notifications,
         }
     }
@@ -1370,6 +3099,7 @@ impl NotificationPreferences {
         self.follow_notifications = false;
         self.payment_notifications = false;
         self.mention_notifications = false;
+        self.poll_notifications = false;
         self.system_notifications = false;
     }
 
@@ -1382,6 +3112,7 @@ impl NotificationPreferences {
         self.follow_notifications = true;
         self.payment_notifications = true;
         self.mention_notifications = true;
+        self.poll_notifications = true;
         self.system_notifications = true;
     }
 }
@@ -1461,6 +3192,7 @@ mod tests {
                 media_attachments: vec![], // Start with no media for simplicity
                 is_reel,
                 visibility,
+                poll: None,
             };
 
             match Post::new(request) {
@@ -1634,6 +3366,7 @@ mod tests {
             display_name: Some("Updated Name".to_string()),
             bio: Some("Updated bio".to_string()),
             avatar_url: Some("https://example.com/avatar.jpg".to_string()),
+            sensitive_media: None,
         };
 
         user.update(update_request).unwrap();
@@ -1645,6 +3378,254 @@ mod tests {
             "https://example.com/avatar.jpg"
         );
     }
+
+    #[test]
+    fn test_post_scheduling() {
+        let mut post = Post::new(CreatePostRequest {
+            user_id: Uuid::new_v4(),
+            text_content: Some("Coming soon".to_string()),
+            media_attachments: vec![],
+            is_reel: false,
+            visibility: PostVisibility::Public,
+            poll: None,
+        })
+        .unwrap();
+        assert!(post.is_published());
+
+        assert!(post
+            .schedule(Utc::now() - chrono::Duration::minutes(1))
+            .is_err());
+
+        let publish_at = Utc::now() + chrono::Duration::hours(1);
+        post.schedule(publish_at).unwrap();
+        assert_eq!(post.status, PostStatus::Scheduled);
+        assert_eq!(post.publish_at, Some(publish_at));
+
+        post.save_as_draft();
+        assert_eq!(post.status, PostStatus::Draft);
+        assert!(post.publish_at.is_none());
+
+        post.publish();
+        assert!(post.is_published());
+    }
+
+    #[test]
+    fn test_post_sensitivity_and_alt_text() {
+        let mut image = MediaAttachment::new(
+            "https://example.com/photo.jpg".to_string(),
+            "image/jpeg".to_string(),
+            1024,
+            Some(800),
+            Some(600),
+            None,
+        )
+        .unwrap();
+        image
+            .set_alt_text(Some("  A dog on a beach  ".to_string()))
+            .unwrap();
+        assert_eq!(image.alt_text.as_deref(), Some("A dog on a beach"));
+        image.set_alt_text(Some("   ".to_string())).unwrap();
+        assert!(image.alt_text.is_none());
+        assert!(image
+            .set_alt_text(Some("a".repeat(MAX_ALT_TEXT_LENGTH + 1)))
+            .is_err());
+
+        let mut post = Post::new(CreatePostRequest {
+            user_id: Uuid::new_v4(),
+            text_content: None,
+            media_attachments: vec![image],
+            is_reel: false,
+            visibility: PostVisibility::Public,
+            poll: None,
+        })
+        .unwrap();
+        assert!(!post.is_sensitive);
+
+        // A content warning marks the post as sensitive on its own
+        post.set_sensitivity(false, Some("Spoilers".to_string()))
+            .unwrap();
+        assert!(post.is_sensitive);
+        assert_eq!(post.content_warning.as_deref(), Some("Spoilers"));
+
+        assert!(post
+            .set_sensitivity(true, Some("a".repeat(MAX_CONTENT_WARNING_LENGTH + 1)))
+            .is_err());
+
+        post.set_sensitivity(false, None).unwrap();
+        assert!(!post.is_sensitive);
+        assert!(post.content_warning.is_none());
+    }
+
+    #[test]
+    fn test_poll_post_validation() {
+        let poll_post = |options: Vec<&str>, closes_in: chrono::Duration| CreatePostRequest {
+            user_id: Uuid::new_v4(),
+            text_content: Some("Tabs or spaces?".to_string()),
+            media_attachments: vec![],
+            is_reel: false,
+            visibility: PostVisibility::Public,
+            poll: Some(CreatePollRequest {
+                options: options.into_iter().map(String::from).collect(),
+                closes_at: Utc::now() + closes_in,
+            }),
+        };
+        let day = chrono::Duration::days(1);
+
+        let post = Post::new(poll_post(vec!["Tabs", "Spaces"], day)).unwrap();
+        assert_eq!(post.content_type, PostContentType::Poll);
+
+        assert!(Post::new(poll_post(vec!["Tabs"], day)).is_err());
+        assert!(Post::new(poll_post(vec!["a", "b", "c", "d", "e"], day)).is_err());
+        assert!(Post::new(poll_post(vec!["Tabs", " tabs "], day)).is_err());
+        assert!(Post::new(poll_post(vec!["Tabs", ""], day)).is_err());
+        assert!(Post::new(poll_post(vec!["Tabs", "Spaces"], -day)).is_err());
+        assert!(Post::new(poll_post(vec!["Tabs", "Spaces"], day * 8)).is_err());
+
+        let poll = Poll::new(
+            post.id,
+            CreatePollRequest {
+                options: vec![" Tabs ".to_string(), "Spaces".to_string()],
+                closes_at: Utc::now() + day,
+            },
+        )
+        .unwrap();
+        assert_eq!(poll.options, vec!["Tabs", "Spaces"]);
+        assert_eq!(poll.vote_counts, vec![0, 0]);
+        assert!(!poll.is_closed());
+        assert!(poll.validate_option(1).is_ok());
+        assert!(poll.validate_option(2).is_err());
+        assert!(poll.validate_option(-1).is_err());
+    }
+
+    #[test]
+    fn test_story_validation() {
+        let media = |media_type: &str| {
+            MediaAttachment::new(
+                "https://cdn.test/story".to_string(),
+                media_type.to_string(),
+                1024,
+                Some(1080),
+                Some(1920),
+                None,
+            )
+            .unwrap()
+        };
+        let user_id = Uuid::new_v4();
+
+        let story = Story::new(user_id, media("image/jpeg"), Some("  hi  ".to_string())).unwrap();
+        assert_eq!(story.caption.as_deref(), Some("hi"));
+        assert_eq!(
+            story.expires_at - story.created_at,
+            chrono::Duration::hours(STORY_TTL_HOURS)
+        );
+        assert!(!story.is_expired(story.created_at));
+        assert!(story.is_expired(story.expires_at));
+
+        let blank = Story::new(user_id, media("image/png"), Some("   ".to_string())).unwrap();
+        assert!(blank.caption.is_none());
+
+        assert!(Story::new(user_id, media("audio/mpeg"), None).is_err());
+        assert!(Story::new(user_id, media("image/png"), Some("a".repeat(201))).is_err());
+
+        // Videos must have been probed by the media API
+        assert!(Story::new(user_id, media("video/mp4"), None).is_err());
+        let mut video = media("video/mp4");
+        video.duration = Some(10);
+        video.video_codec = Some("h264".to_string());
+        assert!(Story::new(user_id, video, None).is_ok());
+    }
+
+    #[test]
+    fn test_extract_mentions() {
+        let mentions = extract_mentions("hey @Alice and @bob_99, mail me@example.com @x");
+
+        assert_eq!(mentions.len(), 2);
+        assert_eq!(mentions[0].username, "alice");
+        assert_eq!((mentions[0].start, mentions[0].end), (4, 10));
+        assert_eq!(mentions[1].username, "bob_99");
+    }
+
+    #[test]
+    fn test_extract_mentions_uses_char_offsets() {
+        let mentions = extract_mentions("héllo @carol");
+
+        assert_eq!(mentions.len(), 1);
+        assert_eq!((mentions[0].start, mentions[0].end), (6, 12));
+    }
+
+    #[test]
+    fn test_extract_hashtags() {
+        let tags = extract_hashtags("#Rust and #rust_lang, issue#12 #2024 #Café ##double #rust");
+
+        assert_eq!(tags, vec!["rust", "rust_lang", "café"]);
+    }
+
+    #[test]
+    fn test_search_snippet_from_headline() {
+        let headline = "Morning \u{2}run\u{3} by the \u{2}café\u{3}, then \u{2}\u{3}home";
+        let snippet = SearchSnippet::from_headline(headline);
+
+        assert_eq!(snippet.text, "Morning run by the café, then home");
+        assert_eq!(snippet.highlights, vec![(8, 11), (19, 23)]);
+    }
+
+    #[test]
+    fn test_post_search_query_normalization() {
+        let query = PostSearchQuery {
+            text: "  rust async  ".to_string(),
+            hashtag: Some("#RustLang".to_string()),
+            ..Default::default()
+        }
+        .normalized()
+        .unwrap();
+        assert_eq!(query.text, "rust async");
+        assert_eq!(query.hashtag.as_deref(), Some("rustlang"));
+
+        let invalid = [
+            PostSearchQuery::default(),
+            PostSearchQuery {
+                text: "a".repeat(MAX_SEARCH_QUERY_LENGTH + 1),
+                ..Default::default()
+            },
+            PostSearchQuery {
+                text: "rust".to_string(),
+                hashtag: Some("rust lang".to_string()),
+                ..Default::default()
+            },
+            PostSearchQuery {
+                text: "rust".to_string(),
+                since: Some(Utc::now()),
+                until: Some(Utc::now() - chrono::Duration::days(1)),
+                ..Default::default()
+            },
+        ];
+        for query in invalid {
+            assert!(matches!(
+                query.normalized(),
+                Err(AppError::ValidationError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_comment_validation() {
+        let request = CreateCommentRequest {
+            post_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            parent_comment_id: None,
+            content: "   ".to_string(),
+        };
+        assert!(Comment::new(request).is_err());
+
+        let request = CreateCommentRequest {
+            post_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            parent_comment_id: None,
+            content: "Nice post @alice".to_string(),
+        };
+        let comment = Comment::new(request).unwrap();
+        assert!(comment.mentions.is_empty());
+    }
 }
 // **Feature: view-social-mvp, Property 8: Feed content filtering**
 // **Validates: Requirements 3.1**
@@ -1704,6 +3685,7 @@ proptest! {
                     media_attachments: vec![],
                     is_reel: false,
                     visibility: PostVisibility::Public,
+                    poll: None,
                 };
 
                 if let Ok(post) = Post::new(post_request) {
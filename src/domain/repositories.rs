@@ -1,11 +1,17 @@
 use crate::domain::entities::{
-    DeviceToken, Message, MessageRead, Notification, NotificationPreferences, Post, Transaction,
-    User, Wallet,
+    Bookmark, BookmarkCollection, Comment, ConversationSummary, DailyEngagement,
+    DailyFollowerStats, DeviceToken, EngagementEvent, EngagementTotals, FeedFeedback,
+    FollowSuggestion, HashtagCount, MediaUpload, Mention, Message, MessageRead, ModerationAction,
+    ModerationCase, Notification, NotificationPreferences, Poll, Post, PostSearchHit,
+    PostSearchQuery, PostViewEvent, PostViewStats, Report, ReportTargetType, Story, StoryView,
+    TimelineEntry, Transaction, User, Wallet,
 };
 use crate::domain::errors::Result;
+use crate::domain::value_objects::{Cursor, PageStart};
 use async_trait::async_trait;
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Repository trait for User entity operations
@@ -17,6 +23,9 @@ pub trait UserRepository: Send + Sync {
     /// Find user by ID
     async fn find_by_id(&self, id: Uuid) -> Result<Option<User>>;
 
+    /// Find users by ID in one query, skipping IDs that don't exist
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>>;
+
     /// Find user by username
     async fn find_by_username(&self, username: &str) -> Result<Option<User>>;
 
@@ -41,11 +50,25 @@ pub trait UserRepository: Send + Sync {
     /// Find users by search query (username or display name)
     async fn search(&self, query: &str, limit: i64, offset: i64) -> Result<Vec<User>>;
 
-    /// Get user followers
-    async fn get_followers(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<User>>;
+    /// Get user followers, most recent follow first, each with when they followed
+    async fn get_followers(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>>;
+
+    /// Get users that a user is following, most recent follow first, each
+    /// with when they were followed
+    async fn get_following(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>>;
 
-    /// Get users that a user is following
-    async fn get_following(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<User>>;
+    /// Get a user's follower count without loading the rest of the user
+    async fn follower_count(&self, user_id: Uuid) -> Result<Option<i32>>;
 
     /// Check if user A follows user B
     async fn is_following(&self, follower_id: Uuid, following_id: Uuid) -> Result<bool>;
@@ -55,6 +78,27 @@ pub trait UserRepository: Send + Sync {
 
     /// Unfollow a user
     async fn unfollow(&self, follower_id: Uuid, following_id: Uuid) -> Result<()>;
+
+    /// Check if user A has blocked user B
+    async fn has_blocked(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<bool>;
+
+    /// Mute a user, keeping their posts out of the muter's feeds
+    async fn mute(&self, user_id: Uuid, muted_user_id: Uuid) -> Result<()>;
+
+    /// Unmute a user
+    async fn unmute(&self, user_id: Uuid, muted_user_id: Uuid) -> Result<()>;
+
+    /// IDs of the users a user has muted
+    async fn muted_user_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>>;
+
+    /// IDs of the users a user has blocked or been blocked by
+    async fn blocked_user_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>>;
+
+    /// Which of `user_ids` a user has blocked or been blocked by
+    async fn blocked_ids(&self, user_id: Uuid, user_ids: &[Uuid]) -> Result<HashSet<Uuid>>;
+
+    /// Which of `user_ids` a user follows
+    async fn followed_ids(&self, follower_id: Uuid, user_ids: &[Uuid]) -> Result<HashSet<Uuid>>;
 }
 
 /// Repository trait for Post entity operations
@@ -66,27 +110,76 @@ pub trait PostRepository: Send + Sync {
     /// Find post by ID
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Post>>;
 
+    /// Find posts by ID in one query, skipping IDs that don't exist
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Post>>;
+
     /// Update post
     async fn update(&self, post: &Post) -> Result<Post>;
 
     /// Delete post by ID
     async fn delete(&self, id: Uuid) -> Result<()>;
 
-    /// Get user's feed (posts from followed users)
-    async fn find_feed(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<Post>>;
+    /// Get user's feed (posts from followed users), newest first
+    async fn find_feed(&self, user_id: Uuid, page: PageStart, limit: i64) -> Result<Vec<Post>>;
 
-    /// Get posts by user ID
+    /// Get the newest feed posts by followed authors with at least
+    /// `min_followers` followers, whose posts are not fanned out on write,
+    /// starting after the cursor
+    async fn find_feed_from_popular(
+        &self,
+        user_id: Uuid,
+        min_followers: i32,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<Vec<Post>>;
+
+    /// Get a user's published posts, pinned posts first and the rest newest first
     async fn find_by_user_id(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<Post>>;
 
     /// Get public posts (for discovery)
     async fn find_public(&self, limit: i64, offset: i64) -> Result<Vec<Post>>;
 
-    /// Get reels only
-    async fn find_reels(&self, user_id: Option<Uuid>, limit: i64, offset: i64)
-        -> Result<Vec<Post>>;
+    /// Get public posts published since `since` by accounts the viewer does
+    /// not follow and has not blocked, been blocked by or muted. Each post
+    /// comes with how many accounts the viewer follows liked it; posts those
+    /// accounts liked come first, then the most engaged.
+    async fn find_explore_candidates(
+        &self,
+        viewer_id: Uuid,
+        since: DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<(Post, i64)>>;
+
+    /// Get the posts a user liked since `since`, most recently liked first
+    async fn find_liked_by_user(
+        &self,
+        user_id: Uuid,
+        since: DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<Post>>;
 
-    /// Search posts by content
-    async fn search(&self, query: &str, limit: i64, offset: i64) -> Result<Vec<Post>>;
+    /// Get reels with at least one server-probed, playable video, newest first
+    async fn find_reels(
+        &self,
+        user_id: Option<Uuid>,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<Post>>;
+
+    /// Full-text search over published posts' text and alt text, most
+    /// relevant first. Only posts that are public, the viewer's own, or
+    /// followers-only from accounts the viewer follows are returned.
+    async fn search(
+        &self,
+        viewer_id: Option<Uuid>,
+        query: &PostSearchQuery,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PostSearchHit>>;
+
+    /// Get up to `limit` hashtags starting with `prefix` used in recent
+    /// public posts, most used first
+    async fn search_hashtags(&self, prefix: &str, limit: i64) -> Result<Vec<HashtagCount>>;
 
     /// Increment engagement count (likes, comments, reshares)
     async fn increment_like_count(&self, post_id: Uuid) -> Result<()>;
@@ -99,6 +192,18 @@ pub trait PostRepository: Send + Sync {
     /// Check if user has liked a post
     async fn has_user_liked(&self, user_id: Uuid, post_id: Uuid) -> Result<bool>;
 
+    /// Of the given posts, the ones the user has liked
+    async fn liked_post_ids(&self, user_id: Uuid, post_ids: &[Uuid]) -> Result<HashSet<Uuid>>;
+
+    /// How many of the given authors' posts the user has liked or commented
+    /// on since `since`, by author; authors with none are left out
+    async fn count_interactions_with_authors(
+        &self,
+        user_id: Uuid,
+        author_ids: &[Uuid],
+        since: DateTime<chrono::Utc>,
+    ) -> Result<HashMap<Uuid, i64>>;
+
     /// Like a post
     async fn like_post(&self, user_id: Uuid, post_id: Uuid) -> Result<()>;
 
@@ -107,6 +212,428 @@ pub trait PostRepository: Send + Sync {
 
     /// Get post likes
     async fn get_post_likes(&self, post_id: Uuid, limit: i64, offset: i64) -> Result<Vec<User>>;
+
+    /// Get posts that mention a user and are visible to them, newest first
+    async fn find_mentioning_user(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<Post>>;
+
+    /// Get a user's drafts and scheduled posts
+    async fn find_unpublished_by_user(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Post>>;
+
+    /// Get scheduled posts whose publish time has passed
+    async fn find_due_scheduled(&self, now: DateTime<chrono::Utc>, limit: i64)
+        -> Result<Vec<Post>>;
+
+    /// Publish a scheduled post, returning None if it was already published or rescheduled
+    async fn publish_scheduled(
+        &self,
+        post_id: Uuid,
+        now: DateTime<chrono::Utc>,
+    ) -> Result<Option<Post>>;
+
+    /// Pin a post to its author's profile, returning false if the author
+    /// already has `max_pinned` pinned posts
+    async fn pin(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        max_pinned: i64,
+        now: DateTime<chrono::Utc>,
+    ) -> Result<bool>;
+
+    /// Unpin a post
+    async fn unpin(&self, post_id: Uuid) -> Result<()>;
+}
+
+/// Repository trait for Comment entity operations
+#[async_trait]
+pub trait CommentRepository: Send + Sync {
+    /// Create a new comment
+    async fn create(&self, comment: &Comment) -> Result<Comment>;
+
+    /// Find comment by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Comment>>;
+
+    /// Get comments on a post, oldest first, starting after the cursor
+    async fn find_by_post(
+        &self,
+        post_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<Comment>>;
+
+    /// Delete comment by ID
+    async fn delete(&self, id: Uuid) -> Result<()>;
+}
+
+/// Repository trait for Bookmark and BookmarkCollection operations
+#[async_trait]
+pub trait BookmarkRepository: Send + Sync {
+    /// Save a bookmark, moving it to the given collection if it already exists
+    async fn upsert(&self, bookmark: &Bookmark) -> Result<Bookmark>;
+
+    /// Remove a user's bookmark of a post
+    async fn remove(&self, user_id: Uuid, post_id: Uuid) -> Result<()>;
+
+    /// Get a user's bookmarks, newest first, starting after the cursor
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        collection_id: Option<Uuid>,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<Vec<Bookmark>>;
+
+    /// Check if user has bookmarked a post
+    async fn is_bookmarked(&self, user_id: Uuid, post_id: Uuid) -> Result<bool>;
+
+    /// Create a new collection
+    async fn create_collection(
+        &self,
+        collection: &BookmarkCollection,
+    ) -> Result<BookmarkCollection>;
+
+    /// Find collection by ID
+    async fn find_collection_by_id(&self, id: Uuid) -> Result<Option<BookmarkCollection>>;
+
+    /// Get all collections owned by a user
+    async fn find_collections_by_user(&self, user_id: Uuid) -> Result<Vec<BookmarkCollection>>;
+
+    /// Delete a collection, keeping its bookmarks
+    async fn delete_collection(&self, id: Uuid) -> Result<()>;
+}
+
+/// Short-lived store for view events that deduplicates views and accumulates
+/// counters until they are rolled up into the post view stats
+#[async_trait]
+pub trait PendingViewRepository: Send + Sync {
+    /// Add an event to its post's pending counters. The event only counts as
+    /// a view if the viewer has not viewed the post within the dedup window;
+    /// returns whether it did.
+    async fn record(&self, event: &PostViewEvent, dedup_window_secs: u64) -> Result<bool>;
+
+    /// Remove and return the pending counters of up to `limit` posts
+    async fn take_pending(&self, limit: usize) -> Result<Vec<PostViewStats>>;
+
+    /// Add counters back after they could not be rolled up
+    async fn restore(&self, stats: &PostViewStats) -> Result<()>;
+}
+
+/// Posts each user has been shown in their feeds. A feed session starts at
+/// a feed's first page; posts shown during the session only count as seen
+/// from the next session on, so later pages rank the same way.
+#[async_trait]
+pub trait ImpressionRepository: Send + Sync {
+    /// Record that the user was shown the posts. Posts already recorded keep
+    /// the time they were first shown.
+    async fn record(&self, user_id: Uuid, post_ids: &[Uuid], at: DateTime<Utc>) -> Result<()>;
+
+    /// Of the given posts, the ones the user was shown before `before`
+    async fn seen_before(
+        &self,
+        user_id: Uuid,
+        post_ids: &[Uuid],
+        before: DateTime<Utc>,
+    ) -> Result<HashSet<Uuid>>;
+
+    /// Start a new feed session for the user
+    async fn start_session(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<()>;
+
+    /// When the user's current feed session started, if one is running
+    async fn session_started_at(&self, user_id: Uuid) -> Result<Option<DateTime<Utc>>>;
+}
+
+/// Repository trait for the negative feedback users give on feed posts
+#[async_trait]
+pub trait FeedFeedbackRepository: Send + Sync {
+    /// Save feedback, refreshing its time if the user already gave the same
+    /// feedback on the post
+    async fn upsert(&self, feedback: &FeedFeedback) -> Result<()>;
+
+    /// Remove every kind of feedback a user gave on a post
+    async fn remove(&self, user_id: Uuid, post_id: Uuid) -> Result<()>;
+
+    /// Get a user's feedback given since `since`, newest first
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<FeedFeedback>>;
+}
+
+/// Repository trait for what follow suggestions are built from beyond follows
+#[async_trait]
+pub trait SuggestionRepository: Send + Sync {
+    /// IDs of the users in a user's phone contacts, matched by the contact's
+    /// account or phone number
+    async fn matched_contact_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>>;
+
+    /// Stop suggesting a user to a user
+    async fn dismiss(&self, user_id: Uuid, dismissed_user_id: Uuid) -> Result<()>;
+
+    /// IDs of the users a user dismissed from their suggestions
+    async fn dismissed_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>>;
+}
+
+/// Store for precomputed follow suggestions and for the users active enough
+/// to have theirs refreshed
+#[async_trait]
+pub trait SuggestionCacheRepository: Send + Sync {
+    /// Get a user's stored suggestions, best first, or None if none are stored
+    async fn find(&self, user_id: Uuid) -> Result<Option<Vec<FollowSuggestion>>>;
+
+    /// Replace a user's stored suggestions
+    async fn save(&self, user_id: Uuid, suggestions: &[FollowSuggestion]) -> Result<()>;
+
+    /// Record that a user looked at their suggestions
+    async fn mark_active(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<()>;
+
+    /// Get up to `limit` users who looked at their suggestions since `since`,
+    /// most recent first
+    async fn active_since(&self, since: DateTime<Utc>, limit: usize) -> Result<Vec<Uuid>>;
+}
+
+/// Store for push-based home timelines, holding the newest post IDs from the
+/// accounts each user follows
+#[async_trait]
+pub trait TimelineRepository: Send + Sync {
+    /// Get up to `limit` entries of a user's timeline, newest first, or None
+    /// if no timeline is stored for the user
+    async fn find(&self, user_id: Uuid, limit: usize) -> Result<Option<Vec<TimelineEntry>>>;
+
+    /// Replace a user's timeline, keeping the newest `max_items` entries
+    async fn replace(
+        &self,
+        user_id: Uuid,
+        entries: &[TimelineEntry],
+        max_items: usize,
+    ) -> Result<()>;
+
+    /// Add entries to the stored timelines of the given users, keeping the
+    /// newest `max_items` of each. Users without a stored timeline are
+    /// skipped; theirs is rebuilt the next time they read it.
+    async fn push(
+        &self,
+        user_ids: &[Uuid],
+        entries: &[TimelineEntry],
+        max_items: usize,
+    ) -> Result<()>;
+
+    /// Remove an entry from the given users' timelines
+    async fn remove(&self, user_ids: &[Uuid], entry: &TimelineEntry) -> Result<()>;
+
+    /// Remove every post by an author from a user's timeline
+    async fn remove_author(&self, user_id: Uuid, author_id: Uuid) -> Result<()>;
+}
+
+/// Repository trait for rolled-up post view stats
+#[async_trait]
+pub trait PostViewRepository: Send + Sync {
+    /// Add pending counters to the stored totals and to the post's insights
+    /// for `day`, and refresh the post's view count. Counters for posts that
+    /// no longer exist are dropped.
+    async fn apply(&self, delta: &PostViewStats, day: NaiveDate) -> Result<()>;
+
+    /// Get the stored totals for a post
+    async fn find_by_post(&self, post_id: Uuid) -> Result<Option<PostViewStats>>;
+}
+
+/// Repository trait for the daily insight rollups
+#[async_trait]
+pub trait InsightsRepository: Send + Sync {
+    /// Add an engagement event to the rollup for `day`
+    async fn record(&self, event: &EngagementEvent, day: NaiveDate) -> Result<()>;
+
+    /// Get a post's engagement for each day in `from..=to` that had any
+    async fn find_post_daily(
+        &self,
+        post_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyEngagement>>;
+
+    /// Get the engagement summed over all of an author's posts for each day
+    /// in `from..=to` that had any
+    async fn find_author_daily(
+        &self,
+        author_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyEngagement>>;
+
+    /// Get an author's posts with the most likes and comments in `from..=to`
+    async fn find_top_posts(
+        &self,
+        author_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, EngagementTotals)>>;
+
+    /// Get a user's follower changes for each day in `from..=to` that had any
+    async fn find_follower_daily(
+        &self,
+        user_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyFollowerStats>>;
+}
+
+/// Repository trait for reports and the moderation queue
+#[async_trait]
+pub trait ModerationRepository: Send + Sync {
+    /// Check whether a user may work the moderation queue
+    async fn is_moderator(&self, user_id: Uuid) -> Result<bool>;
+
+    /// Attach a report to the target's case, opening or reopening it as
+    /// needed. Returns the case and whether the report was new; a reporter's
+    /// repeated reports of the same target are not counted again.
+    async fn file_report(
+        &self,
+        report: &Report,
+        target_user_id: Uuid,
+    ) -> Result<(ModerationCase, bool)>;
+
+    /// Find a case by ID
+    async fn find_case(&self, case_id: Uuid) -> Result<Option<ModerationCase>>;
+
+    /// Get open cases, most reported first
+    async fn find_open_cases(&self, limit: i64, offset: i64) -> Result<Vec<ModerationCase>>;
+
+    /// Get every report filed against a case's target
+    async fn find_reports(&self, case_id: Uuid) -> Result<Vec<Report>>;
+
+    /// Get the actions taken on a case, oldest first
+    async fn find_actions(&self, case_id: Uuid) -> Result<Vec<ModerationAction>>;
+
+    /// Hide or restore the case's target content
+    async fn set_content_hidden(&self, case: &ModerationCase, hidden: bool) -> Result<()>;
+
+    /// Open a case for content the filter flagged, or reopen the existing
+    /// one, adding the rules that matched
+    async fn flag_for_review(
+        &self,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        target_user_id: Uuid,
+        rules: &[String],
+    ) -> Result<ModerationCase>;
+
+    /// Record an action and resolve its case
+    async fn record_action(&self, action: &ModerationAction) -> Result<()>;
+
+    /// Block a user from signing in until the given time
+    async fn suspend_user(&self, user_id: Uuid, until: DateTime<chrono::Utc>) -> Result<()>;
+}
+
+/// Repository trait for Poll operations
+#[async_trait]
+pub trait PollRepository: Send + Sync {
+    /// Create the poll for a post
+    async fn create(&self, poll: &Poll) -> Result<Poll>;
+
+    /// Find the poll attached to a post
+    async fn find_by_post_id(&self, post_id: Uuid) -> Result<Option<Poll>>;
+
+    /// Find the polls attached to any of the given posts
+    async fn find_by_post_ids(&self, post_ids: &[Uuid]) -> Result<Vec<Poll>>;
+
+    /// Record a user's vote and bump the option's count in one transaction.
+    /// Fails with Conflict if the user already voted and ValidationError if the poll is closed.
+    async fn vote(&self, post_id: Uuid, user_id: Uuid, option_index: i32) -> Result<Poll>;
+
+    /// Get the option a user voted for, if any
+    async fn find_user_vote(&self, post_id: Uuid, user_id: Uuid) -> Result<Option<i32>>;
+
+    /// Get the options a user voted for in the given posts' polls, keyed by post ID
+    async fn find_user_votes(&self, post_ids: &[Uuid], user_id: Uuid)
+        -> Result<HashMap<Uuid, i32>>;
+
+    /// Get closed polls whose authors have not been notified yet
+    async fn find_closed_unnotified(
+        &self,
+        now: DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<Poll>>;
+
+    /// Mark a closed poll as notified, returning false if it already was
+    async fn mark_closed_notified(&self, post_id: Uuid) -> Result<bool>;
+}
+
+/// Repository trait for Mention operations
+#[async_trait]
+pub trait MentionRepository: Send + Sync {
+    /// Store mentions, ignoring duplicates for the same source and user
+    async fn create_many(&self, mentions: &[Mention]) -> Result<()>;
+}
+
+/// Repository trait for MediaUpload session operations
+#[async_trait]
+pub trait MediaUploadRepository: Send + Sync {
+    /// Create a new upload session
+    async fn create(&self, upload: &MediaUpload) -> Result<MediaUpload>;
+
+    /// Find upload session by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<MediaUpload>>;
+
+    /// Store the completed state of a pending upload, including its final size,
+    /// dimensions and renditions. Returns None if it was not pending,
+    /// so concurrent completions only succeed once.
+    async fn complete(&self, upload: &MediaUpload) -> Result<Option<MediaUpload>>;
+
+    /// Delete an upload session
+    async fn delete(&self, id: Uuid) -> Result<()>;
+}
+
+/// Repository trait for Story and StoryView operations
+#[async_trait]
+pub trait StoryRepository: Send + Sync {
+    /// Create a new story
+    async fn create(&self, story: &Story) -> Result<Story>;
+
+    /// Find story by ID, whether or not it has expired
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Story>>;
+
+    /// Get the unexpired stories of the viewer and the users they follow, oldest
+    /// first. Authors on either side of a block with the viewer are left out.
+    async fn find_feed_stories(
+        &self,
+        viewer_id: Uuid,
+        now: DateTime<chrono::Utc>,
+    ) -> Result<Vec<Story>>;
+
+    /// Delete a story together with its views
+    async fn delete(&self, id: Uuid) -> Result<()>;
+
+    /// Record that a user saw a story, keeping the first time they did
+    async fn record_view(&self, view: &StoryView) -> Result<()>;
+
+    /// Of the given stories, return the IDs the viewer has seen
+    async fn find_seen_story_ids(&self, viewer_id: Uuid, story_ids: &[Uuid]) -> Result<Vec<Uuid>>;
+
+    /// Get a story's views, most recent first, starting after the cursor
+    async fn find_views(
+        &self,
+        story_id: Uuid,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<Vec<StoryView>>;
+
+    /// Count the users who have seen a story
+    async fn count_views(&self, story_id: Uuid) -> Result<i64>;
+
+    /// Get stories that have expired, oldest first
+    async fn find_expired(&self, now: DateTime<chrono::Utc>, limit: i64) -> Result<Vec<Story>>;
 }
 
 /// Repository trait for Conversation entity operations
@@ -128,13 +655,14 @@ pub trait ConversationRepository: Send + Sync {
         id: Uuid,
     ) -> Result<Option<(Uuid, Vec<Uuid>, bool, Option<String>, DateTime<chrono::Utc>)>>;
 
-    /// Get all conversations for a user
+    /// Get a user's conversations, most recently active first. Page cursors
+    /// are keyed on the last activity time.
     async fn find_by_user(
         &self,
         user_id: Uuid,
+        page: PageStart,
         limit: i64,
-        offset: i64,
-    ) -> Result<Vec<(Uuid, Vec<Uuid>, bool, Option<String>, DateTime<chrono::Utc>)>>;
+    ) -> Result<Vec<ConversationSummary>>;
 
     /// Check if user is participant in conversation
     async fn is_participant(&self, conversation_id: Uuid, user_id: Uuid) -> Result<bool>;
@@ -225,6 +753,9 @@ pub trait WalletRepository: Send + Sync {
     /// Find wallet by ID
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Wallet>>;
 
+    /// Find wallets by ID in one query, skipping IDs that don't exist
+    async fn find_wallets_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Wallet>>;
+
     /// Find wallet by user ID
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<Wallet>>;
 
@@ -264,12 +795,12 @@ pub trait WalletRepository: Send + Sync {
     /// Update transaction status
     async fn update_transaction(&self, transaction: &Transaction) -> Result<Transaction>;
 
-    /// Get transaction history for wallet
+    /// Get transaction history for wallet, newest first
     async fn get_transaction_history(
         &self,
         wallet_id: Uuid,
+        page: PageStart,
         limit: i64,
-        offset: i64,
     ) -> Result<Vec<Transaction>>;
 
     /// Get pending transactions for wallet
@@ -300,12 +831,12 @@ pub trait NotificationRepository: Send + Sync {
     /// Delete notification by ID
     async fn delete(&self, id: Uuid) -> Result<()>;
 
-    /// Get notifications for a user with pagination
+    /// Get notifications for a user, newest first
     async fn find_by_user_id(
         &self,
         user_id: Uuid,
+        page: PageStart,
         limit: i64,
-        offset: i64,
     ) -> Result<Vec<Notification>>;
 
     /// Get unread notifications for a user
@@ -402,6 +933,10 @@ impl UserRepository for MockUserRepository {
         Ok(None)
     }
 
+    async fn find_by_ids(&self, _ids: &[Uuid]) -> Result<Vec<User>> {
+        Ok(vec![])
+    }
+
     async fn find_by_username(&self, _username: &str) -> Result<Option<User>> {
         Ok(None)
     }
@@ -434,14 +969,28 @@ impl UserRepository for MockUserRepository {
         Ok(vec![])
     }
 
-    async fn get_followers(&self, _user_id: Uuid, _limit: i64, _offset: i64) -> Result<Vec<User>> {
+    async fn get_followers(
+        &self,
+        _user_id: Uuid,
+        _page: PageStart,
+        _limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>> {
         Ok(vec![])
     }
 
-    async fn get_following(&self, _user_id: Uuid, _limit: i64, _offset: i64) -> Result<Vec<User>> {
+    async fn get_following(
+        &self,
+        _user_id: Uuid,
+        _page: PageStart,
+        _limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>> {
         Ok(vec![])
     }
 
+    async fn follower_count(&self, _user_id: Uuid) -> Result<Option<i32>> {
+        Ok(None)
+    }
+
     async fn is_following(&self, _follower_id: Uuid, _following_id: Uuid) -> Result<bool> {
         Ok(false)
     }
@@ -453,4 +1002,32 @@ impl UserRepository for MockUserRepository {
     async fn unfollow(&self, _follower_id: Uuid, _following_id: Uuid) -> Result<()> {
         Ok(())
     }
+
+    async fn has_blocked(&self, _blocker_id: Uuid, _blocked_id: Uuid) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn mute(&self, _user_id: Uuid, _muted_user_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn unmute(&self, _user_id: Uuid, _muted_user_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn muted_user_ids(&self, _user_id: Uuid) -> Result<HashSet<Uuid>> {
+        Ok(HashSet::new())
+    }
+
+    async fn blocked_user_ids(&self, _user_id: Uuid) -> Result<HashSet<Uuid>> {
+        Ok(HashSet::new())
+    }
+
+    async fn blocked_ids(&self, _user_id: Uuid, _user_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        Ok(HashSet::new())
+    }
+
+    async fn followed_ids(&self, _follower_id: Uuid, _user_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        Ok(HashSet::new())
+    }
 }
@@ -1,7 +1,9 @@
 use crate::domain::errors::{AppError, Result};
+use chrono::{DateTime, TimeZone, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Username(String);
@@ -213,3 +215,95 @@ impl fmt::Display for Bio {
         write!(f, "{}", self.0)
     }
 }
+
+/// Opaque keyset pagination cursor pointing at the last item of a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(created_at: DateTime<Utc>, id: Uuid) -> Self {
+        Self { created_at, id }
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.created_at.timestamp_micros(), self.id)
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self> {
+        let invalid = || AppError::ValidationError("Invalid cursor".to_string());
+
+        if !cursor.len().is_multiple_of(2) || !cursor.is_ascii() {
+            return Err(invalid());
+        }
+
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16))
+            .collect::<std::result::Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+        let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+        let (micros, id) = raw.split_once(':').ok_or_else(invalid)?;
+        let micros: i64 = micros.parse().map_err(|_| invalid())?;
+        let created_at = Utc.timestamp_micros(micros).single().ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+
+        Ok(Self { created_at, id })
+    }
+
+    /// Trim a page fetched with one item more than `limit`, returning the
+    /// cursor of its last kept item when more items follow
+    pub fn for_next_page<T>(
+        items: &mut Vec<T>,
+        limit: i64,
+        key: impl Fn(&T) -> Cursor,
+    ) -> Option<Cursor> {
+        if items.len() as i64 <= limit {
+            return None;
+        }
+        items.truncate(limit as usize);
+        items.last().map(key)
+    }
+}
+
+/// Where a page of a list starts. Cursors are the supported way to page;
+/// offsets are kept for older clients but drift as items are added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageStart {
+    /// Right after the item the cursor points at
+    After(Cursor),
+    /// Deprecated: after skipping this many items
+    Offset(i64),
+}
+
+impl PageStart {
+    pub const FIRST: PageStart = PageStart::Offset(0);
+
+    /// Read the `cursor` and `offset` query parameters, preferring the cursor
+    pub fn from_query(cursor: Option<&str>, offset: i64) -> Result<Self> {
+        match cursor {
+            Some(cursor) => Ok(PageStart::After(Cursor::decode(cursor)?)),
+            None => Ok(PageStart::Offset(offset.max(0))),
+        }
+    }
+
+    pub fn cursor(&self) -> Option<Cursor> {
+        match self {
+            PageStart::After(cursor) => Some(*cursor),
+            PageStart::Offset(_) => None,
+        }
+    }
+
+    pub fn offset(&self) -> i64 {
+        match self {
+            PageStart::After(_) => 0,
+            PageStart::Offset(offset) => *offset,
+        }
+    }
+}
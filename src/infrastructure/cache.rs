@@ -1,6 +1,8 @@
 use crate::domain::errors::{AppError, Result};
+use chrono::NaiveDate;
 use redis::{Client, Commands, Connection};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Redis cache implementation for session and feed caching
@@ -69,6 +71,23 @@ impl RedisCache {
         Ok(())
     }
 
+    /// Delete every key matching a glob pattern, walking the keyspace with
+    /// SCAN so Redis is not blocked
+    pub fn delete_matching(&self, pattern: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let keys: Vec<String> = conn
+            .scan_match::<_, String>(pattern)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to scan cache keys: {}", e)))?
+            .collect();
+
+        for chunk in keys.chunks(500) {
+            conn.del::<_, ()>(chunk).map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete cache values: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+
     /// Check if a key exists in cache
     pub fn exists(&self, key: &str) -> Result<bool> {
         let mut conn = self.get_connection()?;
@@ -210,6 +229,284 @@ impl RedisCache {
         Ok(removed)
     }
 
+    /// Set a marker key only if it does not exist yet; returns whether it was set
+    pub fn set_if_absent(&self, key: &str, ttl_seconds: u64) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+        let result: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_seconds)
+            .query(&mut conn)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to set cache marker: {}", e)))?;
+        Ok(result.is_some())
+    }
+
+    /// Increment integer and float fields of a hash in one round trip
+    pub fn hash_increment(
+        &self,
+        key: &str,
+        ints: &[(&str, i64)],
+        floats: &[(&str, f64)],
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for (field, by) in ints {
+            pipe.hincr(key, *field, *by).ignore();
+        }
+        for (field, by) in floats {
+            pipe.hincr(key, *field, *by).ignore();
+        }
+        pipe.query::<()>(&mut conn).map_err(|e| {
+            AppError::DatabaseError(format!("Failed to increment cache hash: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// Remove a hash and return its fields
+    pub fn hash_take(&self, key: &str) -> Result<HashMap<String, String>> {
+        let mut conn = self.get_connection()?;
+        let (fields,): (HashMap<String, String>,) = redis::pipe()
+            .atomic()
+            .hgetall(key)
+            .del(key)
+            .ignore()
+            .query(&mut conn)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to take cache hash: {}", e)))?;
+        Ok(fields)
+    }
+
+    /// Add a member to a set
+    pub fn set_add(&self, key: &str, member: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        conn.sadd::<_, _, ()>(key, member)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to add to cache set: {}", e)))?;
+        Ok(())
+    }
+
+    /// Remove and return up to `count` arbitrary members of a set
+    pub fn set_pop(&self, key: &str, count: usize) -> Result<Vec<String>> {
+        let mut conn = self.get_connection()?;
+        let members: Vec<String> = redis::cmd("SPOP")
+            .arg(key)
+            .arg(count)
+            .query(&mut conn)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to pop from cache set: {}", e)))?;
+        Ok(members)
+    }
+
+    /// Get up to `count` members of a sorted set with their scores, highest score first
+    pub fn sorted_set_range_rev(&self, key: &str, count: usize) -> Result<Vec<(String, f64)>> {
+        if count == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut conn = self.get_connection()?;
+        let members: Vec<(String, f64)> = conn
+            .zrevrange_withscores(key, 0, count as isize - 1)
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to get cache sorted set range: {}", e))
+            })?;
+        Ok(members)
+    }
+
+    /// Replace a sorted set with the given members, keeping the `max_length`
+    /// highest scored, and set its expiration
+    pub fn sorted_set_replace(
+        &self,
+        key: &str,
+        members: &[(f64, String)],
+        max_length: usize,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let mut pipe = redis::pipe();
+        pipe.atomic().del(key).ignore();
+        if !members.is_empty() {
+            pipe.zadd_multiple(key, members)
+                .ignore()
+                .zremrangebyrank(key, 0, -(max_length as isize) - 1)
+                .ignore()
+                .expire(key, ttl_seconds as usize)
+                .ignore();
+        }
+        pipe.query::<()>(&mut conn).map_err(|e| {
+            AppError::DatabaseError(format!("Failed to replace cache sorted set: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// Add members to each of the given sorted sets that already exist,
+    /// trimming each to its `max_length` highest scored members
+    pub fn sorted_set_add_existing(
+        &self,
+        keys: &[String],
+        members: &[(f64, String)],
+        max_length: usize,
+    ) -> Result<()> {
+        if keys.is_empty() || members.is_empty() {
+            return Ok(());
+        }
+
+        // Checked and written in one script so a set expiring in between is
+        // not recreated with only the new members
+        let script = redis::Script::new(
+            r"
+            local max_length = tonumber(ARGV[1])
+            for _, key in ipairs(KEYS) do
+                if redis.call('EXISTS', key) == 1 then
+                    for i = 2, #ARGV, 2 do
+                        redis.call('ZADD', key, ARGV[i], ARGV[i + 1])
+                    end
+                    redis.call('ZREMRANGEBYRANK', key, 0, -max_length - 1)
+                end
+            end
+            return 0
+            ",
+        );
+
+        let mut invocation = script.prepare_invoke();
+        for key in keys {
+            invocation.key(key);
+        }
+        invocation.arg(max_length);
+        for (score, member) in members {
+            invocation.arg(*score).arg(member);
+        }
+
+        let mut conn = self.get_connection()?;
+        invocation.invoke::<()>(&mut conn).map_err(|e| {
+            AppError::DatabaseError(format!("Failed to add to cache sorted sets: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// Add members to a sorted set, creating it if needed. Members already
+    /// in the set keep their score. Keeps the `max_length` highest scored and
+    /// resets the set's expiration.
+    pub fn sorted_set_add_new(
+        &self,
+        key: &str,
+        members: &[(f64, String)],
+        max_length: usize,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.get_connection()?;
+        redis::pipe()
+            .atomic()
+            .cmd("ZADD")
+            .arg(key)
+            .arg("NX")
+            .arg(members)
+            .ignore()
+            .zremrangebyrank(key, 0, -(max_length as isize) - 1)
+            .ignore()
+            .expire(key, ttl_seconds as usize)
+            .ignore()
+            .query::<()>(&mut conn)
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to add to cache sorted set: {}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Add members to a sorted set, updating the scores of members already
+    /// in it, and reset the set's expiration
+    pub fn sorted_set_add(
+        &self,
+        key: &str,
+        members: &[(f64, String)],
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.get_connection()?;
+        redis::pipe()
+            .atomic()
+            .zadd_multiple(key, members)
+            .ignore()
+            .expire(key, ttl_seconds as usize)
+            .ignore()
+            .query::<()>(&mut conn)
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to add to cache sorted set: {}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Get up to `count` members of a sorted set scored at least `min_score`,
+    /// highest score first
+    pub fn sorted_set_range_by_score_rev(
+        &self,
+        key: &str,
+        min_score: f64,
+        count: usize,
+    ) -> Result<Vec<String>> {
+        if count == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut conn = self.get_connection()?;
+        let members: Vec<String> = conn
+            .zrevrangebyscore_limit(key, "+inf", min_score, 0, count as isize)
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to get cache sorted set range: {}", e))
+            })?;
+        Ok(members)
+    }
+
+    /// Remove the members of a sorted set scored below `max_score`
+    pub fn sorted_set_remove_below(&self, key: &str, max_score: f64) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        conn.zrembyscore::<_, _, _, ()>(key, "-inf", format!("({}", max_score))
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to trim cache sorted set: {}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Get the scores of the given members of a sorted set, None for members
+    /// that are not in it
+    pub fn sorted_set_scores(&self, key: &str, members: &[String]) -> Result<Vec<Option<f64>>> {
+        if members.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut conn = self.get_connection()?;
+        let mut pipe = redis::pipe();
+        for member in members {
+            pipe.zscore(key, member);
+        }
+        pipe.query(&mut conn).map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get cache sorted set scores: {}", e))
+        })
+    }
+
+    /// Remove members from each of the given sorted sets
+    pub fn sorted_set_remove(&self, keys: &[String], members: &[String]) -> Result<()> {
+        if keys.is_empty() || members.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.get_connection()?;
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.zrem(key, members).ignore();
+        }
+        pipe.query::<()>(&mut conn).map_err(|e| {
+            AppError::DatabaseError(format!("Failed to remove from cache sorted set: {}", e))
+        })?;
+        Ok(())
+    }
+
     /// Clear all items from cache (use with caution)
     pub fn flush_all(&self) -> Result<()> {
         let mut conn = self.get_connection()?;
@@ -334,11 +631,56 @@ impl CacheKeys {
         format!("followers:user:{}", user_id)
     }
 
+    /// Generate home timeline key
+    pub fn home_timeline(user_id: Uuid) -> String {
+        format!("timeline:home:{}", user_id)
+    }
+
+    /// Generate key for the posts a user has been shown in feeds
+    pub fn feed_impressions(user_id: Uuid) -> String {
+        format!("feed:impressions:{}", user_id)
+    }
+
+    /// Generate key for when a user's current feed session started
+    pub fn feed_session(user_id: Uuid) -> String {
+        format!("feed:session:{}", user_id)
+    }
+
+    /// Generate key for a user's precomputed follow suggestions
+    pub fn follow_suggestions(user_id: Uuid) -> String {
+        format!("suggestions:user:{}", user_id)
+    }
+
+    /// Generate key for the users who recently looked at their suggestions
+    pub fn suggestion_active_users() -> String {
+        "suggestions:active".to_string()
+    }
+
     /// Generate trending posts cache key
     pub fn trending_posts() -> String {
         "trending:posts".to_string()
     }
 
+    /// Generate view dedup marker key for a viewer of a post
+    pub fn post_view_seen(post_id: Uuid, viewer_id: Uuid) -> String {
+        format!("views:seen:{}:{}", post_id, viewer_id)
+    }
+
+    /// Generate daily reach marker key for a viewer of a post
+    pub fn post_view_reach(post_id: Uuid, viewer_id: Uuid, day: NaiveDate) -> String {
+        format!("views:reach:{}:{}:{}", post_id, viewer_id, day)
+    }
+
+    /// Generate pending view counters key for a post
+    pub fn post_views_pending(post_id: Uuid) -> String {
+        format!("views:pending:{}", post_id)
+    }
+
+    /// Generate key for the set of posts with pending view counters
+    pub fn pending_view_posts() -> String {
+        "views:pending".to_string()
+    }
+
     /// Generate rate limit cache key
     pub fn rate_limit(user_id: Uuid, endpoint: &str) -> String {
         format!("rate_limit:{}:{}", user_id, endpoint)
@@ -465,6 +807,25 @@ impl CacheConfig {
     /// Maximum feed items to cache
     pub const MAX_FEED_ITEMS: usize = 100;
 
+    /// Home timeline TTL (7 days), refreshed whenever the timeline is read
+    pub const TIMELINE_TTL: u64 = 7 * 24 * 60 * 60;
+
+    /// Feed impressions TTL (14 days), refreshed whenever posts are shown
+    pub const IMPRESSIONS_TTL: u64 = 14 * 24 * 60 * 60;
+
+    /// Most recent feed impressions kept per user
+    pub const MAX_IMPRESSIONS: usize = 2000;
+
+    /// How long a feed session lasts after its first page (30 minutes)
+    pub const FEED_SESSION_TTL: u64 = 30 * 60;
+
+    /// Precomputed follow suggestions TTL (1 day); active users' are
+    /// refreshed well before they expire
+    pub const SUGGESTIONS_TTL: u64 = 24 * 60 * 60;
+
+    /// How long a user counts as active for suggestion refreshes (7 days)
+    pub const SUGGESTION_ACTIVE_TTL: u64 = 7 * 24 * 60 * 60;
+
     /// Maximum conversation messages to cache
     pub const MAX_CONVERSATION_MESSAGES: usize = 50;
 }
@@ -0,0 +1,566 @@
+//! Keyword and link filter for user-written text
+//!
+//! Rules are read from a JSON file and can be reloaded while the server is
+//! running. Text and blocked words are both folded before matching: letters
+//! are lowercased, look-alike letters from other scripts and common leetspeak
+//! substitutions map to plain ASCII, and invisible characters are dropped.
+//! A blocked word only matches whole words, so "class" does not trip "ass".
+use crate::domain::errors::{AppError, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::SystemTime;
+
+/// Where a piece of text is going; rules can be limited to some fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterField {
+    Post,
+    Comment,
+    Message,
+    Bio,
+    DisplayName,
+}
+
+impl FilterField {
+    fn label(&self) -> &'static str {
+        match self {
+            FilterField::Post => "Post",
+            FilterField::Comment => "Comment",
+            FilterField::Message => "Message",
+            FilterField::Bio => "Bio",
+            FilterField::DisplayName => "Display name",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    /// Refuse the text
+    Reject,
+    /// Replace the match with asterisks
+    Mask,
+    /// Accept the text and queue it for moderator review
+    Flag,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FilterRule {
+    pub name: String,
+    pub action: FilterAction,
+    #[serde(default)]
+    pub words: Vec<String>,
+    /// Blocked domains; subdomains are blocked too
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Fields the rule applies to; all of them when empty
+    #[serde(default)]
+    pub fields: Vec<FilterField>,
+}
+
+/// Contents of the rules file
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FilterRuleSet {
+    pub rules: Vec<FilterRule>,
+}
+
+/// Text that passed the filter, with masked matches replaced
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenedText {
+    pub text: String,
+    /// Names of the flag rules the text matched
+    pub flagged_rules: Vec<String>,
+}
+
+impl ScreenedText {
+    pub fn is_flagged(&self) -> bool {
+        !self.flagged_rules.is_empty()
+    }
+}
+
+struct CompiledRule {
+    name: String,
+    action: FilterAction,
+    words: Vec<Vec<char>>,
+    domains: Vec<String>,
+    fields: Vec<FilterField>,
+}
+
+impl CompiledRule {
+    fn compile(rule: FilterRule) -> Result<Self> {
+        if rule.name.trim().is_empty() {
+            return Err(AppError::ConfigurationError(
+                "Content filter rules need a name".to_string(),
+            ));
+        }
+
+        let words = rule
+            .words
+            .iter()
+            .map(|word| word.chars().filter_map(fold_char).collect::<Vec<char>>())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let domains = rule
+            .domains
+            .iter()
+            .map(|domain| fold_domain(domain.trim().trim_start_matches("www.")))
+            .filter(|domain| !domain.is_empty())
+            .collect();
+
+        Ok(Self {
+            name: rule.name,
+            action: rule.action,
+            words,
+            domains,
+            fields: rule.fields,
+        })
+    }
+
+    fn applies_to(&self, field: FilterField) -> bool {
+        self.fields.is_empty() || self.fields.contains(&field)
+    }
+}
+
+/// Content filter whose rules can be swapped while it is in use
+pub struct ContentFilter {
+    rules: RwLock<Arc<Vec<CompiledRule>>>,
+    /// File the rules were loaded from, if any
+    source: Option<PathBuf>,
+    source_modified: Mutex<Option<SystemTime>>,
+}
+
+impl ContentFilter {
+    pub fn new(rule_set: FilterRuleSet) -> Result<Self> {
+        Ok(Self {
+            rules: RwLock::new(Arc::new(compile(rule_set)?)),
+            source: None,
+            source_modified: Mutex::new(None),
+        })
+    }
+
+    /// Load rules from a JSON file that is checked again on every reload
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let (rules, modified) = load(&path)?;
+        Ok(Self {
+            rules: RwLock::new(Arc::new(rules)),
+            source: Some(path),
+            source_modified: Mutex::new(modified),
+        })
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.current().len()
+    }
+
+    /// Reload the rules file if it changed since it was last read. A file
+    /// that fails to load leaves the current rules in place.
+    pub fn reload_if_changed(&self) -> Result<bool> {
+        let path = match &self.source {
+            Some(path) => path,
+            None => return Ok(false),
+        };
+
+        let modified = modified_at(path)?;
+        if modified.is_some() && modified == *self.source_modified.lock().unwrap() {
+            return Ok(false);
+        }
+
+        let (rules, modified) = load(path)?;
+        *self.rules.write().unwrap() = Arc::new(rules);
+        *self.source_modified.lock().unwrap() = modified;
+        Ok(true)
+    }
+
+    /// Run text through the rules for a field. Fails if a reject rule
+    /// matches; otherwise returns the text with mask rule matches hidden.
+    pub fn screen(&self, field: FilterField, text: &str) -> Result<ScreenedText> {
+        let rules = self.current();
+        let folded = fold_text(text);
+        let links = find_links(text);
+
+        let mut masked: Vec<(usize, usize)> = Vec::new();
+        let mut flagged_rules: Vec<String> = Vec::new();
+        for rule in rules.iter().filter(|rule| rule.applies_to(field)) {
+            let mut spans = Vec::new();
+            for word in &rule.words {
+                spans.extend(find_word(&folded, word));
+            }
+            for (start, end, host) in &links {
+                if rule
+                    .domains
+                    .iter()
+                    .any(|domain| host == domain || host.ends_with(&format!(".{}", domain)))
+                {
+                    spans.push((*start, *end));
+                }
+            }
+            if spans.is_empty() {
+                continue;
+            }
+
+            match rule.action {
+                FilterAction::Reject => {
+                    return Err(AppError::ValidationError(format!(
+                        "{} contains content that is not allowed",
+                        field.label()
+                    )))
+                }
+                FilterAction::Mask => masked.extend(spans),
+                FilterAction::Flag => {
+                    if !flagged_rules.contains(&rule.name) {
+                        flagged_rules.push(rule.name.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(ScreenedText {
+            text: mask(text, masked),
+            flagged_rules,
+        })
+    }
+
+    fn current(&self) -> Arc<Vec<CompiledRule>> {
+        self.rules.read().unwrap().clone()
+    }
+}
+
+fn compile(rule_set: FilterRuleSet) -> Result<Vec<CompiledRule>> {
+    rule_set
+        .rules
+        .into_iter()
+        .map(CompiledRule::compile)
+        .collect()
+}
+
+fn modified_at(path: &Path) -> Result<Option<SystemTime>> {
+    let metadata = std::fs::metadata(path).map_err(|e| {
+        AppError::ConfigurationError(format!(
+            "Failed to read content filter rules {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    Ok(metadata.modified().ok())
+}
+
+fn load(path: &Path) -> Result<(Vec<CompiledRule>, Option<SystemTime>)> {
+    let modified = modified_at(path)?;
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        AppError::ConfigurationError(format!(
+            "Failed to read content filter rules {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let rule_set: FilterRuleSet = serde_json::from_str(&contents).map_err(|e| {
+        AppError::ConfigurationError(format!(
+            "Invalid content filter rules {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    Ok((compile(rule_set)?, modified))
+}
+
+/// A folded character, the byte range of the original character it came
+/// from, and whether the original was a letter or digit
+type FoldedChar = (char, usize, usize, bool);
+
+fn fold_text(text: &str) -> Vec<FoldedChar> {
+    text.char_indices()
+        .filter_map(|(start, c)| {
+            fold_char(c).map(|folded| (folded, start, start + c.len_utf8(), c.is_alphanumeric()))
+        })
+        .collect()
+}
+
+/// Fold a character for word matching, or drop it if it is invisible
+fn fold_char(c: char) -> Option<char> {
+    let c = fold_letter(c)?;
+    Some(match c {
+        '0' => 'o',
+        // l, 1 and i are written for each other often enough to treat as one
+        '1' | 'l' | '|' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' | '+' => 't',
+        '8' => 'b',
+        '9' => 'g',
+        c => c,
+    })
+}
+
+/// Lowercase a character and map look-alikes from other scripts to ASCII
+fn fold_letter(c: char) -> Option<char> {
+    let c = match c {
+        // Zero-width characters, soft hyphens and combining accents
+        '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}' => return None,
+        '\u{0300}'..='\u{036F}' => return None,
+        // Fullwidth ASCII
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        c => c,
+    };
+    let c = c.to_lowercase().next().unwrap_or(c);
+
+    Some(match c {
+        // Cyrillic
+        'а' => 'a',
+        'в' => 'b',
+        'с' => 'c',
+        'ԁ' => 'd',
+        'е' | 'ё' => 'e',
+        'һ' | 'н' => 'h',
+        'і' | 'ї' => 'i',
+        'ј' => 'j',
+        'к' => 'k',
+        'м' => 'm',
+        'о' => 'o',
+        'р' => 'p',
+        'ԛ' => 'q',
+        'ѕ' => 's',
+        'т' => 't',
+        'у' => 'y',
+        'ԝ' => 'w',
+        'х' => 'x',
+        // Greek
+        'α' => 'a',
+        'β' => 'b',
+        'ε' => 'e',
+        'η' => 'n',
+        'ι' => 'i',
+        'κ' => 'k',
+        'ν' => 'v',
+        'ο' => 'o',
+        'ρ' => 'p',
+        'τ' => 't',
+        'υ' => 'u',
+        'χ' => 'x',
+        // Accented Latin
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ı' => 'i',
+        'ñ' | 'ń' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => 'o',
+        'ś' | 'š' => 's',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    })
+}
+
+/// Fold a domain name; leetspeak is left alone since digits are common in domains
+fn fold_domain(domain: &str) -> String {
+    domain.chars().filter_map(fold_letter).collect()
+}
+
+/// Find whole-word occurrences of a folded word, as byte ranges of the original text
+fn find_word(folded: &[FoldedChar], word: &[char]) -> Vec<(usize, usize)> {
+    // Boundaries look at the original text, so "darn!" still ends at the "!"
+    let is_word_char = |index: usize| folded[index].3;
+
+    let mut spans = Vec::new();
+    if word.len() > folded.len() {
+        return spans;
+    }
+    for start in 0..=folded.len() - word.len() {
+        let end = start + word.len();
+        if folded[start..end]
+            .iter()
+            .map(|(c, _, _, _)| c)
+            .eq(word.iter())
+            && (start == 0 || !is_word_char(start - 1))
+            && (end == folded.len() || !is_word_char(end))
+        {
+            spans.push((folded[start].1, folded[end - 1].2));
+        }
+    }
+    spans
+}
+
+/// Find links in the original text as byte ranges and their folded host names
+fn find_links(text: &str) -> Vec<(usize, usize, String)> {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| {
+        Regex::new(r"(?i)(?:https?://)?((?:[\p{L}\p{N}-]+\.)+[\p{L}\p{N}-]{2,})(?:[/?#:][^\s]*)?")
+            .unwrap()
+    });
+
+    link.captures_iter(text)
+        .filter_map(|captures| {
+            let whole = captures.get(0)?;
+            let host = fold_domain(captures.get(1)?.as_str());
+            Some((whole.start(), whole.end(), host))
+        })
+        .collect()
+}
+
+/// Replace each byte range with one asterisk per character
+fn mask(text: &str, mut spans: Vec<(usize, usize)>) -> String {
+    if spans.is_empty() {
+        return text.to_string();
+    }
+    spans.sort_unstable();
+
+    let mut masked = String::with_capacity(text.len());
+    let mut position = 0;
+    for (start, end) in spans {
+        let start = start.max(position);
+        if start >= end {
+            continue;
+        }
+        masked.push_str(&text[position..start]);
+        masked.extend(text[start..end].chars().map(|_| '*'));
+        position = end;
+    }
+    masked.push_str(&text[position..]);
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, action: FilterAction, words: &[&str], domains: &[&str]) -> FilterRule {
+        FilterRule {
+            name: name.to_string(),
+            action,
+            words: words.iter().map(|word| word.to_string()).collect(),
+            domains: domains.iter().map(|domain| domain.to_string()).collect(),
+            fields: Vec::new(),
+        }
+    }
+
+    fn filter(rules: Vec<FilterRule>) -> ContentFilter {
+        ContentFilter::new(FilterRuleSet { rules }).unwrap()
+    }
+
+    #[test]
+    fn test_blocked_words_match_through_leetspeak_and_confusables() {
+        let filter = filter(vec![rule("slurs", FilterAction::Reject, &["badword"], &[])]);
+
+        for text in [
+            "what a BADWORD",
+            "what a b4dw0rd",
+            "what a bаdwоrd", // Cyrillic а and о
+            "what a ｂａｄｗｏｒｄ",
+            "what a bad\u{200B}word",
+        ] {
+            assert!(
+                matches!(
+                    filter.screen(FilterField::Post, text),
+                    Err(AppError::ValidationError(_))
+                ),
+                "{} should be rejected",
+                text
+            );
+        }
+
+        // Only whole words match
+        assert!(filter
+            .screen(FilterField::Post, "badwords are fine")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_mask_and_flag_actions() {
+        let filter = filter(vec![
+            rule("profanity", FilterAction::Mask, &["darn"], &[]),
+            rule("scams", FilterAction::Flag, &["free crypto"], &[]),
+        ]);
+
+        let screened = filter
+            .screen(FilterField::Comment, "Darn, d4rn! Free   crypto here")
+            .unwrap();
+        assert_eq!(screened.text, "****, ****! Free   crypto here");
+        assert!(!screened.is_flagged());
+
+        let screened = filter
+            .screen(FilterField::Comment, "get free crypto now")
+            .unwrap();
+        assert_eq!(screened.text, "get free crypto now");
+        assert_eq!(screened.flagged_rules, vec!["scams".to_string()]);
+    }
+
+    #[test]
+    fn test_blocked_domains_include_subdomains() {
+        let filter = filter(vec![rule(
+            "phishing",
+            FilterAction::Mask,
+            &[],
+            &["evil.example"],
+        )]);
+
+        let screened = filter
+            .screen(
+                FilterField::Message,
+                "see https://login.evil.example/reset?x=1 or evil.example and good.example",
+            )
+            .unwrap();
+        assert_eq!(
+            screened.text,
+            "see ************************************ or ************ and good.example"
+        );
+
+        // Look-alike letters in the host still match
+        let screened = filter
+            .screen(FilterField::Message, "https://еvil.example")
+            .unwrap();
+        assert!(!screened.text.contains("example"));
+    }
+
+    #[test]
+    fn test_rules_only_apply_to_their_fields() {
+        let mut names = rule("reserved", FilterAction::Reject, &["admin"], &[]);
+        names.fields = vec![FilterField::DisplayName];
+        let filter = filter(vec![names]);
+
+        assert!(filter.screen(FilterField::DisplayName, "Admin").is_err());
+        assert!(filter.screen(FilterField::Post, "ask an admin").is_ok());
+    }
+
+    #[test]
+    fn test_reload_picks_up_changed_rules_file() {
+        let path =
+            std::env::temp_dir().join(format!("content-filter-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, r#"{"rules": []}"#).unwrap();
+        let filter = ContentFilter::from_file(&path).unwrap();
+        assert!(filter.screen(FilterField::Bio, "spam").is_ok());
+        assert!(!filter.reload_if_changed().unwrap());
+
+        std::fs::write(
+            &path,
+            r#"{"rules": [{"name": "spam", "action": "reject", "words": ["spam"]}]}"#,
+        )
+        .unwrap();
+        // Make sure the modification time moves even on coarse clocks
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(filter.reload_if_changed().unwrap());
+        assert!(filter.screen(FilterField::Bio, "spam").is_err());
+
+        // A broken file keeps the rules that were loaded
+        std::fs::write(&path, "not json").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later + std::time::Duration::from_secs(5))
+            .unwrap();
+        assert!(filter.reload_if_changed().is_err());
+        assert!(filter.screen(FilterField::Bio, "spam").is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database model for bookmarks table
+#[derive(FromRow)]
+pub struct BookmarkModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub post_id: Uuid,
+    pub collection_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Database model for bookmark_collections table
+#[derive(FromRow)]
+pub struct BookmarkCollectionModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
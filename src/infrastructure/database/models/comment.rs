@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database model for comments table
+#[derive(FromRow)]
+pub struct CommentModel {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    pub content: String,
+    pub mentions: serde_json::Value,
+    pub like_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -16,3 +16,11 @@ pub struct ConversationModel {
 pub struct ParticipantModel {
     pub user_id: Uuid,
 }
+
+/// A conversation in a user's list, with when it was last active
+#[derive(FromRow)]
+pub struct ConversationListRow {
+    #[sqlx(flatten)]
+    pub conversation: ConversationModel,
+    pub last_activity_at: DateTime<Utc>,
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database model for feed_feedback table
+#[derive(FromRow)]
+pub struct FeedFeedbackModel {
+    pub user_id: Uuid,
+    pub post_id: Uuid,
+    pub author_id: Uuid,
+    pub kind: String,
+    pub hashtags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,38 @@
+use chrono::NaiveDate;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Engagement counters from post_daily_stats, summed per day or per post
+#[derive(FromRow)]
+pub struct EngagementTotalsModel {
+    pub impressions: i64,
+    pub follower_impressions: i64,
+    pub reach: i64,
+    pub follower_reach: i64,
+    pub likes: i64,
+    pub comments: i64,
+}
+
+/// Engagement counters from post_daily_stats for one day
+#[derive(FromRow)]
+pub struct DailyEngagementModel {
+    pub day: NaiveDate,
+    #[sqlx(flatten)]
+    pub totals: EngagementTotalsModel,
+}
+
+/// Engagement counters from post_daily_stats for one post
+#[derive(FromRow)]
+pub struct PostEngagementModel {
+    pub post_id: Uuid,
+    #[sqlx(flatten)]
+    pub totals: EngagementTotalsModel,
+}
+
+/// Database model for user_daily_stats table
+#[derive(FromRow)]
+pub struct DailyFollowerStatsModel {
+    pub day: NaiveDate,
+    pub followers_gained: i64,
+    pub followers_lost: i64,
+}
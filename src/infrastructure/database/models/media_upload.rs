@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database model for media_uploads table
+#[derive(FromRow)]
+pub struct MediaUploadModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub storage_key: String,
+    pub media_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration: Option<i32>,
+    pub status: String,
+    pub url: Option<String>,
+    pub renditions: serde_json::Value,
+    pub blurhash: Option<String>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
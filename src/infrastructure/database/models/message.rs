@@ -13,6 +13,8 @@ pub struct MessageModel {
     pub media_url: Option<String>,
     pub payment_data: Option<serde_json::Value>,
     pub reply_to_id: Option<Uuid>,
+    pub story_id: Option<Uuid>,
+    pub mentions: serde_json::Value,
     pub created_at: DateTime<Utc>,
 }
 
@@ -1,13 +1,33 @@
+pub mod bookmark;
+pub mod comment;
 pub mod conversation;
+pub mod feed_feedback;
+pub mod insights;
+pub mod media_upload;
 pub mod message;
+pub mod moderation;
+pub mod poll;
 pub mod post;
+pub mod post_view;
+pub mod story;
 pub mod transaction;
 pub mod user;
 pub mod wallet;
 
-pub use conversation::{ConversationModel, ParticipantModel};
+pub use bookmark::{BookmarkCollectionModel, BookmarkModel};
+pub use comment::CommentModel;
+pub use conversation::{ConversationListRow, ConversationModel, ParticipantModel};
+pub use feed_feedback::FeedFeedbackModel;
+pub use insights::{
+    DailyEngagementModel, DailyFollowerStatsModel, EngagementTotalsModel, PostEngagementModel,
+};
+pub use media_upload::MediaUploadModel;
 pub use message::{MessageModel, MessageReadModel};
-pub use post::PostModel;
+pub use moderation::{ModerationActionModel, ModerationCaseModel, ReportModel};
+pub use poll::PollModel;
+pub use post::{ExploreCandidateRow, HashtagCountRow, PostModel, PostSearchRow};
+pub use post_view::PostViewStatsModel;
+pub use story::{StoryModel, StoryViewModel};
 pub use transaction::TransactionModel;
-pub use user::UserModel;
+pub use user::{FollowListRow, UserModel};
 pub use wallet::WalletModel;
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database model for moderation_cases table
+#[derive(FromRow)]
+pub struct ModerationCaseModel {
+    pub id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub target_user_id: Uuid,
+    pub status: String,
+    pub report_count: i32,
+    pub is_hidden: bool,
+    pub flagged_rules: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Database model for reports table
+#[derive(FromRow)]
+pub struct ReportModel {
+    pub id: Uuid,
+    pub reporter_id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub reason: String,
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Database model for moderation_actions table
+#[derive(FromRow)]
+pub struct ModerationActionModel {
+    pub id: Uuid,
+    pub case_id: Uuid,
+    pub moderator_id: Uuid,
+    pub action: String,
+    pub suspended_until: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database model for polls table
+#[derive(FromRow)]
+pub struct PollModel {
+    pub post_id: Uuid,
+    pub options: Vec<String>,
+    pub vote_counts: Vec<i32>,
+    pub closes_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
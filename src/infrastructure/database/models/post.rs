@@ -10,11 +10,44 @@ pub struct PostModel {
     pub content_type: String,
     pub text_content: Option<String>,
     pub media_attachments: serde_json::Value,
+    pub mentions: serde_json::Value,
     pub is_reel: bool,
     pub visibility: String,
+    pub status: String,
+    pub publish_at: Option<DateTime<Utc>>,
     pub like_count: i32,
     pub comment_count: i32,
     pub reshare_count: i32,
+    pub view_count: i64,
+    pub avg_completion_percent: f64,
+    pub is_hidden: bool,
+    pub is_sensitive: bool,
+    pub content_warning: Option<String>,
+    pub pinned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// An explore feed candidate, with how many accounts the viewer follows liked it
+#[derive(FromRow)]
+pub struct ExploreCandidateRow {
+    #[sqlx(flatten)]
+    pub post: PostModel,
+    pub followed_likes: i64,
+}
+
+/// A post matching a full-text search, with its rank and highlighted headline
+#[derive(FromRow)]
+pub struct PostSearchRow {
+    #[sqlx(flatten)]
+    pub post: PostModel,
+    pub rank: f32,
+    pub headline: String,
+}
+
+/// A hashtag and how many posts use it
+#[derive(FromRow)]
+pub struct HashtagCountRow {
+    pub tag: String,
+    pub post_count: i64,
+}
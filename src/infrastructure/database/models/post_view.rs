@@ -0,0 +1,17 @@
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database model for post_view_stats table
+#[derive(FromRow)]
+pub struct PostViewStatsModel {
+    pub post_id: Uuid,
+    pub impression_count: i64,
+    pub view_count: i64,
+    pub replay_count: i64,
+    pub completed_count: i64,
+    pub total_watch_ms: i64,
+    pub completion_percent_sum: f64,
+    pub reach_count: i64,
+    pub follower_impression_count: i64,
+    pub follower_reach_count: i64,
+}
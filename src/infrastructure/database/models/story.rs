@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database model for stories table
+#[derive(FromRow)]
+pub struct StoryModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub media: serde_json::Value,
+    pub caption: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Database model for story_views table
+#[derive(FromRow)]
+pub struct StoryViewModel {
+    pub story_id: Uuid,
+    pub viewer_id: Uuid,
+    pub viewed_at: DateTime<Utc>,
+}
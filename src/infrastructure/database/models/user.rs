@@ -18,6 +18,16 @@ pub struct UserModel {
     pub phone_verified: bool,
     pub follower_count: i32,
     pub following_count: i32,
+    pub suspended_until: Option<DateTime<Utc>>,
+    pub sensitive_media: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// A user in a follower or following list, with when the follow happened
+#[derive(FromRow)]
+pub struct FollowListRow {
+    #[sqlx(flatten)]
+    pub user: UserModel,
+    pub followed_at: DateTime<Utc>,
+}
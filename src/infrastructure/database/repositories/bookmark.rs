@@ -0,0 +1,173 @@
+use crate::domain::entities::{Bookmark, BookmarkCollection};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::BookmarkRepository;
+use crate::domain::value_objects::Cursor;
+use crate::infrastructure::database::models::{BookmarkCollectionModel, BookmarkModel};
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// PostgreSQL implementation of BookmarkRepository
+pub struct PostgresBookmarkRepository {
+    pool: PgPool,
+}
+
+impl PostgresBookmarkRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn to_domain(model: BookmarkModel) -> Bookmark {
+        Bookmark {
+            id: model.id,
+            user_id: model.user_id,
+            post_id: model.post_id,
+            collection_id: model.collection_id,
+            created_at: model.created_at,
+        }
+    }
+
+    fn collection_to_domain(model: BookmarkCollectionModel) -> BookmarkCollection {
+        BookmarkCollection {
+            id: model.id,
+            user_id: model.user_id,
+            name: model.name,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[async_trait]
+impl BookmarkRepository for PostgresBookmarkRepository {
+    async fn upsert(&self, bookmark: &Bookmark) -> Result<Bookmark> {
+        let model: BookmarkModel = sqlx::query_as(
+            "INSERT INTO bookmarks (id, user_id, post_id, collection_id, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, post_id) DO UPDATE SET collection_id = EXCLUDED.collection_id
+            RETURNING id, user_id, post_id, collection_id, created_at",
+        )
+        .bind(bookmark.id)
+        .bind(bookmark.user_id)
+        .bind(bookmark.post_id)
+        .bind(bookmark.collection_id)
+        .bind(bookmark.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save bookmark: {}", e)))?;
+
+        Ok(Self::to_domain(model))
+    }
+
+    async fn remove(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM bookmarks WHERE user_id = $1 AND post_id = $2")
+            .bind(user_id)
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to remove bookmark: {}", e)))?;
+        Ok(())
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        collection_id: Option<Uuid>,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<Vec<Bookmark>> {
+        let models: Vec<BookmarkModel> = sqlx::query_as(
+            "SELECT id, user_id, post_id, collection_id, created_at FROM bookmarks
+            WHERE user_id = $1
+              AND ($2::uuid IS NULL OR collection_id = $2)
+              AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $5",
+        )
+        .bind(user_id)
+        .bind(collection_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch bookmarks: {}", e)))?;
+
+        Ok(models.into_iter().map(Self::to_domain).collect())
+    }
+
+    async fn is_bookmarked(&self, user_id: Uuid, post_id: Uuid) -> Result<bool> {
+        let row: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM bookmarks WHERE user_id = $1 AND post_id = $2)",
+        )
+        .bind(user_id)
+        .bind(post_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to check bookmark: {}", e)))?;
+
+        Ok(row.0)
+    }
+
+    async fn create_collection(
+        &self,
+        collection: &BookmarkCollection,
+    ) -> Result<BookmarkCollection> {
+        let model: BookmarkCollectionModel = sqlx::query_as(
+            "INSERT INTO bookmark_collections (id, user_id, name, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, user_id, name, created_at, updated_at",
+        )
+        .bind(collection.id)
+        .bind(collection.user_id)
+        .bind(&collection.name)
+        .bind(collection.created_at)
+        .bind(collection.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                AppError::Conflict("Collection name already exists".to_string())
+            }
+            _ => AppError::DatabaseError(format!("Failed to create collection: {}", e)),
+        })?;
+
+        Ok(Self::collection_to_domain(model))
+    }
+
+    async fn find_collection_by_id(&self, id: Uuid) -> Result<Option<BookmarkCollection>> {
+        let model: Option<BookmarkCollectionModel> = sqlx::query_as(
+            "SELECT id, user_id, name, created_at, updated_at FROM bookmark_collections WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find collection: {}", e)))?;
+
+        Ok(model.map(Self::collection_to_domain))
+    }
+
+    async fn find_collections_by_user(&self, user_id: Uuid) -> Result<Vec<BookmarkCollection>> {
+        let models: Vec<BookmarkCollectionModel> = sqlx::query_as(
+            "SELECT id, user_id, name, created_at, updated_at FROM bookmark_collections
+            WHERE user_id = $1
+            ORDER BY created_at ASC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch collections: {}", e)))?;
+
+        Ok(models.into_iter().map(Self::collection_to_domain).collect())
+    }
+
+    async fn delete_collection(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM bookmark_collections WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete collection: {}", e)))?;
+        Ok(())
+    }
+}
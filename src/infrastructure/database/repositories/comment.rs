@@ -0,0 +1,116 @@
+use crate::domain::entities::Comment;
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::CommentRepository;
+use crate::domain::value_objects::PageStart;
+use crate::infrastructure::database::models::CommentModel;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// PostgreSQL implementation of CommentRepository
+pub struct PostgresCommentRepository {
+    pool: PgPool,
+}
+
+impl PostgresCommentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn to_domain(model: CommentModel) -> Result<Comment> {
+        let mentions = serde_json::from_value(model.mentions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to deserialize mentions: {}", e))
+        })?;
+
+        Ok(Comment {
+            id: model.id,
+            post_id: model.post_id,
+            user_id: model.user_id,
+            parent_comment_id: model.parent_comment_id,
+            content: model.content,
+            mentions,
+            like_count: model.like_count,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        })
+    }
+}
+
+#[async_trait]
+impl CommentRepository for PostgresCommentRepository {
+    async fn create(&self, comment: &Comment) -> Result<Comment> {
+        let mentions_json = serde_json::to_value(&comment.mentions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize mentions: {}", e))
+        })?;
+
+        let model: CommentModel = sqlx::query_as(
+            "INSERT INTO comments (id, post_id, user_id, parent_comment_id, content, mentions, like_count, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id, post_id, user_id, parent_comment_id, content, mentions, like_count, created_at, updated_at",
+        )
+        .bind(comment.id)
+        .bind(comment.post_id)
+        .bind(comment.user_id)
+        .bind(comment.parent_comment_id)
+        .bind(&comment.content)
+        .bind(mentions_json)
+        .bind(comment.like_count)
+        .bind(comment.created_at)
+        .bind(comment.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create comment: {}", e)))?;
+
+        Self::to_domain(model)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Comment>> {
+        let model: Option<CommentModel> = sqlx::query_as(
+            "SELECT id, post_id, user_id, parent_comment_id, content, mentions, like_count, created_at, updated_at
+            FROM comments WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find comment: {}", e)))?;
+
+        model.map(Self::to_domain).transpose()
+    }
+
+    async fn find_by_post(
+        &self,
+        post_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<Comment>> {
+        let after = page.cursor();
+        let models: Vec<CommentModel> = sqlx::query_as(
+            "SELECT id, post_id, user_id, parent_comment_id, content, mentions, like_count, created_at, updated_at
+            FROM comments
+            WHERE post_id = $1 AND NOT is_hidden
+              AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $4 OFFSET $5",
+        )
+        .bind(post_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
+        .bind(limit)
+        .bind(page.offset())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch comments: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM comments WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete comment: {}", e)))?;
+        Ok(())
+    }
+}
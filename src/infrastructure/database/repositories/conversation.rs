@@ -1,6 +1,10 @@
+use crate::domain::entities::ConversationSummary;
 use crate::domain::errors::{AppError, Result};
 use crate::domain::repositories::ConversationRepository;
-use crate::infrastructure::database::models::{ConversationModel, ParticipantModel};
+use crate::domain::value_objects::PageStart;
+use crate::infrastructure::database::models::{
+    ConversationListRow, ConversationModel, ParticipantModel,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::PgPool;
@@ -107,20 +111,26 @@ impl ConversationRepository for PostgresConversationRepository {
     async fn find_by_user(
         &self,
         user_id: Uuid,
+        page: PageStart,
         limit: i64,
-        offset: i64,
-    ) -> Result<Vec<(Uuid, Vec<Uuid>, bool, Option<String>, DateTime<Utc>)>> {
-        let models: Vec<ConversationModel> = sqlx::query_as(
-            "SELECT DISTINCT c.id, c.conversation_type, c.title, c.created_at
+    ) -> Result<Vec<ConversationSummary>> {
+        let after = page.cursor();
+        let rows: Vec<ConversationListRow> = sqlx::query_as(
+            "SELECT DISTINCT c.id, c.conversation_type, c.title, c.created_at,
+                COALESCE(c.last_message_at, c.created_at) AS last_activity_at
             FROM conversations c
             INNER JOIN conversation_participants cp ON c.id = cp.conversation_id
             WHERE cp.user_id = $1 AND cp.left_at IS NULL
-            ORDER BY c.last_message_at DESC
-            LIMIT $2 OFFSET $3",
+              AND ($2::timestamptz IS NULL
+                OR (COALESCE(c.last_message_at, c.created_at), c.id) < ($2, $3))
+            ORDER BY last_activity_at DESC, c.id DESC
+            LIMIT $4 OFFSET $5",
         )
         .bind(user_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
         .bind(limit)
-        .bind(offset)
+        .bind(page.offset())
         .fetch_all(&self.pool)
         .await
         .map_err(|e| {
@@ -128,7 +138,8 @@ impl ConversationRepository for PostgresConversationRepository {
         })?;
 
         let mut conversations = Vec::new();
-        for model in models {
+        for row in rows {
+            let model = row.conversation;
             let participants: Vec<ParticipantModel> = sqlx::query_as(
                 "SELECT user_id FROM conversation_participants WHERE conversation_id = $1 AND left_at IS NULL")
                 .bind(model.id)
@@ -139,13 +150,14 @@ impl ConversationRepository for PostgresConversationRepository {
             let participant_ids: Vec<Uuid> = participants.iter().map(|p| p.user_id).collect();
             let is_group = model.conversation_type == "group";
 
-            conversations.push((
-                model.id,
+            conversations.push(ConversationSummary {
+                id: model.id,
                 participant_ids,
                 is_group,
-                model.title,
-                model.created_at,
-            ));
+                group_name: model.title,
+                created_at: model.created_at,
+                last_activity_at: row.last_activity_at,
+            });
         }
 
         Ok(conversations)
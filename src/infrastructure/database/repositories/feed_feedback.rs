@@ -0,0 +1,89 @@
+use crate::domain::entities::FeedFeedback;
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::FeedFeedbackRepository;
+use crate::infrastructure::database::models::FeedFeedbackModel;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// PostgreSQL implementation of FeedFeedbackRepository
+pub struct PostgresFeedFeedbackRepository {
+    pool: PgPool,
+}
+
+impl PostgresFeedFeedbackRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn to_domain(model: FeedFeedbackModel) -> Result<FeedFeedback> {
+        Ok(FeedFeedback {
+            user_id: model.user_id,
+            post_id: model.post_id,
+            author_id: model.author_id,
+            kind: model.kind.parse()?,
+            hashtags: model.hashtags,
+            created_at: model.created_at,
+        })
+    }
+}
+
+#[async_trait]
+impl FeedFeedbackRepository for PostgresFeedFeedbackRepository {
+    async fn upsert(&self, feedback: &FeedFeedback) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO feed_feedback (user_id, post_id, author_id, kind, hashtags, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id, post_id, kind) DO UPDATE SET created_at = EXCLUDED.created_at",
+        )
+        .bind(feedback.user_id)
+        .bind(feedback.post_id)
+        .bind(feedback.author_id)
+        .bind(feedback.kind.to_string())
+        .bind(&feedback.hashtags)
+        .bind(feedback.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save feed feedback: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn remove(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM feed_feedback WHERE user_id = $1 AND post_id = $2")
+            .bind(user_id)
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to remove feed feedback: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<FeedFeedback>> {
+        let models: Vec<FeedFeedbackModel> = sqlx::query_as(
+            "SELECT user_id, post_id, author_id, kind, hashtags, created_at
+            FROM feed_feedback
+            WHERE user_id = $1 AND created_at >= $2
+            ORDER BY created_at DESC
+            LIMIT $3",
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find feed feedback: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+}
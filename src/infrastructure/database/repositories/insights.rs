@@ -0,0 +1,231 @@
+use crate::domain::entities::{
+    DailyEngagement, DailyFollowerStats, EngagementEvent, EngagementTotals,
+};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::InsightsRepository;
+use crate::infrastructure::database::models::{
+    DailyEngagementModel, DailyFollowerStatsModel, EngagementTotalsModel, PostEngagementModel,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Summed engagement columns of post_daily_stats; SUM over BIGINT is NUMERIC
+const TOTALS_COLUMNS: &str = "SUM(impressions)::BIGINT AS impressions,
+    SUM(follower_impressions)::BIGINT AS follower_impressions,
+    SUM(reach)::BIGINT AS reach,
+    SUM(follower_reach)::BIGINT AS follower_reach,
+    SUM(likes)::BIGINT AS likes,
+    SUM(comments)::BIGINT AS comments";
+
+/// PostgreSQL implementation of InsightsRepository
+pub struct PostgresInsightsRepository {
+    pool: PgPool,
+}
+
+impl PostgresInsightsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn totals_to_domain(model: EngagementTotalsModel) -> EngagementTotals {
+        EngagementTotals {
+            impressions: model.impressions,
+            follower_impressions: model.follower_impressions,
+            reach: model.reach,
+            follower_reach: model.follower_reach,
+            likes: model.likes,
+            comments: model.comments,
+        }
+    }
+
+    fn daily_to_domain(model: DailyEngagementModel) -> DailyEngagement {
+        DailyEngagement {
+            day: model.day,
+            totals: Self::totals_to_domain(model.totals),
+        }
+    }
+
+    async fn add_post_counts(
+        &self,
+        post_id: Uuid,
+        day: NaiveDate,
+        likes: i64,
+        comments: i64,
+    ) -> Result<()> {
+        // Selecting from posts skips events for posts that were just deleted
+        sqlx::query(
+            "INSERT INTO post_daily_stats (post_id, author_id, day, likes, comments)
+            SELECT id, user_id, $2, $3, $4 FROM posts WHERE id = $1
+            ON CONFLICT (post_id, day) DO UPDATE SET
+                likes = post_daily_stats.likes + EXCLUDED.likes,
+                comments = post_daily_stats.comments + EXCLUDED.comments",
+        )
+        .bind(post_id)
+        .bind(day)
+        .bind(likes)
+        .bind(comments)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update post insights: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn add_follower_counts(
+        &self,
+        user_id: Uuid,
+        day: NaiveDate,
+        gained: i64,
+        lost: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO user_daily_stats (user_id, day, followers_gained, followers_lost)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, day) DO UPDATE SET
+                followers_gained = user_daily_stats.followers_gained + EXCLUDED.followers_gained,
+                followers_lost = user_daily_stats.followers_lost + EXCLUDED.followers_lost",
+        )
+        .bind(user_id)
+        .bind(day)
+        .bind(gained)
+        .bind(lost)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to update follower insights: {}", e))
+        })?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InsightsRepository for PostgresInsightsRepository {
+    async fn record(&self, event: &EngagementEvent, day: NaiveDate) -> Result<()> {
+        match *event {
+            EngagementEvent::PostLiked(post_id) => self.add_post_counts(post_id, day, 1, 0).await,
+            EngagementEvent::PostUnliked(post_id) => {
+                self.add_post_counts(post_id, day, -1, 0).await
+            }
+            EngagementEvent::PostCommented(post_id) => {
+                self.add_post_counts(post_id, day, 0, 1).await
+            }
+            EngagementEvent::UserFollowed(user_id) => {
+                self.add_follower_counts(user_id, day, 1, 0).await
+            }
+            EngagementEvent::UserUnfollowed(user_id) => {
+                self.add_follower_counts(user_id, day, 0, 1).await
+            }
+        }
+    }
+
+    async fn find_post_daily(
+        &self,
+        post_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyEngagement>> {
+        let models: Vec<DailyEngagementModel> = sqlx::query_as(&format!(
+            "SELECT day, {}
+            FROM post_daily_stats
+            WHERE post_id = $1 AND day BETWEEN $2 AND $3
+            GROUP BY day
+            ORDER BY day",
+            TOTALS_COLUMNS
+        ))
+        .bind(post_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find post insights: {}", e)))?;
+
+        Ok(models.into_iter().map(Self::daily_to_domain).collect())
+    }
+
+    async fn find_author_daily(
+        &self,
+        author_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyEngagement>> {
+        let models: Vec<DailyEngagementModel> = sqlx::query_as(&format!(
+            "SELECT day, {}
+            FROM post_daily_stats
+            WHERE author_id = $1 AND day BETWEEN $2 AND $3
+            GROUP BY day
+            ORDER BY day",
+            TOTALS_COLUMNS
+        ))
+        .bind(author_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find author insights: {}", e)))?;
+
+        Ok(models.into_iter().map(Self::daily_to_domain).collect())
+    }
+
+    async fn find_top_posts(
+        &self,
+        author_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, EngagementTotals)>> {
+        let models: Vec<PostEngagementModel> = sqlx::query_as(&format!(
+            "SELECT post_id, {}
+            FROM post_daily_stats
+            WHERE author_id = $1 AND day BETWEEN $2 AND $3
+            GROUP BY post_id
+            ORDER BY SUM(likes + comments) DESC, SUM(impressions) DESC, post_id
+            LIMIT $4",
+            TOTALS_COLUMNS
+        ))
+        .bind(author_id)
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find top posts: {}", e)))?;
+
+        Ok(models
+            .into_iter()
+            .map(|model| (model.post_id, Self::totals_to_domain(model.totals)))
+            .collect())
+    }
+
+    async fn find_follower_daily(
+        &self,
+        user_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyFollowerStats>> {
+        let models: Vec<DailyFollowerStatsModel> = sqlx::query_as(
+            "SELECT day, followers_gained, followers_lost
+            FROM user_daily_stats
+            WHERE user_id = $1 AND day BETWEEN $2 AND $3
+            ORDER BY day",
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find follower insights: {}", e)))?;
+
+        Ok(models
+            .into_iter()
+            .map(|model| DailyFollowerStats {
+                day: model.day,
+                followers_gained: model.followers_gained,
+                followers_lost: model.followers_lost,
+            })
+            .collect())
+    }
+}
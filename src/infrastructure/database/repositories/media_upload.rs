@@ -0,0 +1,139 @@
+use crate::domain::entities::{MediaUpload, MediaUploadStatus};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::MediaUploadRepository;
+use crate::infrastructure::database::models::MediaUploadModel;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// PostgreSQL implementation of MediaUploadRepository
+pub struct PostgresMediaUploadRepository {
+    pool: PgPool,
+}
+
+impl PostgresMediaUploadRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn to_domain(model: MediaUploadModel) -> Result<MediaUpload> {
+        let renditions = serde_json::from_value(model.renditions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to deserialize renditions: {}", e))
+        })?;
+
+        Ok(MediaUpload {
+            id: model.id,
+            user_id: model.user_id,
+            storage_key: model.storage_key,
+            media_type: model.media_type,
+            size: model.size,
+            width: model.width,
+            height: model.height,
+            duration: model.duration,
+            status: model.status.parse().unwrap_or(MediaUploadStatus::Pending),
+            url: model.url,
+            renditions,
+            blurhash: model.blurhash,
+            video_codec: model.video_codec,
+            audio_codec: model.audio_codec,
+            expires_at: model.expires_at,
+            created_at: model.created_at,
+            completed_at: model.completed_at,
+        })
+    }
+
+    fn renditions_json(upload: &MediaUpload) -> Result<serde_json::Value> {
+        serde_json::to_value(&upload.renditions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize renditions: {}", e))
+        })
+    }
+}
+
+#[async_trait]
+impl MediaUploadRepository for PostgresMediaUploadRepository {
+    async fn create(&self, upload: &MediaUpload) -> Result<MediaUpload> {
+        let renditions_json = Self::renditions_json(upload)?;
+
+        let model: MediaUploadModel = sqlx::query_as(
+            "INSERT INTO media_uploads (id, user_id, storage_key, media_type, size, width, height, duration, status, url, renditions, blurhash, video_codec, audio_codec, expires_at, created_at, completed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            RETURNING id, user_id, storage_key, media_type, size, width, height, duration, status, url, renditions, blurhash, video_codec, audio_codec, expires_at, created_at, completed_at",
+        )
+        .bind(upload.id)
+        .bind(upload.user_id)
+        .bind(&upload.storage_key)
+        .bind(&upload.media_type)
+        .bind(upload.size)
+        .bind(upload.width)
+        .bind(upload.height)
+        .bind(upload.duration)
+        .bind(upload.status.to_string())
+        .bind(&upload.url)
+        .bind(renditions_json)
+        .bind(&upload.blurhash)
+        .bind(&upload.video_codec)
+        .bind(&upload.audio_codec)
+        .bind(upload.expires_at)
+        .bind(upload.created_at)
+        .bind(upload.completed_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create media upload: {}", e)))?;
+
+        Self::to_domain(model)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<MediaUpload>> {
+        let model: Option<MediaUploadModel> = sqlx::query_as(
+            "SELECT id, user_id, storage_key, media_type, size, width, height, duration, status, url, renditions, blurhash, video_codec, audio_codec, expires_at, created_at, completed_at
+            FROM media_uploads WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find media upload: {}", e)))?;
+
+        model.map(Self::to_domain).transpose()
+    }
+
+    async fn complete(&self, upload: &MediaUpload) -> Result<Option<MediaUpload>> {
+        let renditions_json = Self::renditions_json(upload)?;
+
+        let model: Option<MediaUploadModel> = sqlx::query_as(
+            "UPDATE media_uploads SET status = $2, url = $3, size = $4, width = $5, height = $6, duration = $7, renditions = $8, blurhash = $9, video_codec = $10, audio_codec = $11, completed_at = $12
+            WHERE id = $1 AND status = 'pending'
+            RETURNING id, user_id, storage_key, media_type, size, width, height, duration, status, url, renditions, blurhash, video_codec, audio_codec, expires_at, created_at, completed_at",
+        )
+        .bind(upload.id)
+        .bind(upload.status.to_string())
+        .bind(&upload.url)
+        .bind(upload.size)
+        .bind(upload.width)
+        .bind(upload.height)
+        .bind(upload.duration)
+        .bind(renditions_json)
+        .bind(&upload.blurhash)
+        .bind(&upload.video_codec)
+        .bind(&upload.audio_codec)
+        .bind(upload.completed_at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to complete media upload: {}", e))
+        })?;
+
+        model.map(Self::to_domain).transpose()
+    }
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM media_uploads WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete media upload: {}", e))
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::domain::entities::Mention;
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::MentionRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+/// PostgreSQL implementation of MentionRepository
+pub struct PostgresMentionRepository {
+    pool: PgPool,
+}
+
+impl PostgresMentionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MentionRepository for PostgresMentionRepository {
+    async fn create_many(&self, mentions: &[Mention]) -> Result<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start transaction: {}", e))
+            })?;
+
+        for mention in mentions {
+            sqlx::query(
+                "INSERT INTO mentions (id, mentioned_user_id, author_id, source_type, source_id, post_id, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (source_type, source_id, mentioned_user_id) DO NOTHING")
+                .bind(mention.id)
+                .bind(mention.mentioned_user_id)
+                .bind(mention.author_id)
+                .bind(mention.source.to_string())
+                .bind(mention.source_id)
+                .bind(mention.post_id)
+                .bind(mention.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create mention: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit mentions: {}", e)))?;
+
+        Ok(())
+    }
+}
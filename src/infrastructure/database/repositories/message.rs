@@ -37,6 +37,10 @@ impl PostgresMessageRepository {
             None
         };
 
+        let mentions = serde_json::from_value(model.mentions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to deserialize mentions: {}", e))
+        })?;
+
         Ok(Message {
             id: model.id,
             conversation_id: model.conversation_id,
@@ -46,6 +50,8 @@ impl PostgresMessageRepository {
             media_url: model.media_url,
             payment_data,
             reply_to_id: model.reply_to_id,
+            story_id: model.story_id,
+            mentions,
             created_at: model.created_at,
         })
     }
@@ -71,10 +77,14 @@ impl MessageRepository for PostgresMessageRepository {
             None
         };
 
+        let mentions_json = serde_json::to_value(&message.mentions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize mentions: {}", e))
+        })?;
+
         let model: MessageModel = sqlx::query_as(
-            "INSERT INTO messages (id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            RETURNING id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at")
+            "INSERT INTO messages (id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at")
             .bind(message.id)
             .bind(message.conversation_id)
             .bind(message.sender_id)
@@ -83,6 +93,8 @@ impl MessageRepository for PostgresMessageRepository {
             .bind(&message.media_url)
             .bind(payment_json)
             .bind(message.reply_to_id)
+            .bind(message.story_id)
+            .bind(mentions_json)
             .bind(message.created_at)
             .bind(message.created_at)
         .fetch_one(&self.pool)
@@ -104,7 +116,7 @@ impl MessageRepository for PostgresMessageRepository {
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Message>> {
         let model: Option<MessageModel> = sqlx::query_as(
-            "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at FROM messages WHERE id = $1")
+            "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at FROM messages WHERE id = $1")
             .bind(id)
             .fetch_optional(&self.pool)
             .await
@@ -135,7 +147,7 @@ impl MessageRepository for PostgresMessageRepository {
             "UPDATE messages
             SET message_type = $2, content = $3, media_url = $4, payment_data = $5, reply_to_id = $6, updated_at = $7
             WHERE id = $1
-            RETURNING id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at")
+            RETURNING id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at")
             .bind(message.id)
             .bind(message_type_str)
             .bind(&message.content)
@@ -177,8 +189,8 @@ impl MessageRepository for PostgresMessageRepository {
 
             if let Some(before_row) = before_row {
                 sqlx::query_as(
-                    "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at FROM messages
-                    WHERE conversation_id = $1 AND created_at < $2
+                    "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at FROM messages
+                    WHERE conversation_id = $1 AND created_at < $2 AND NOT is_hidden
                     ORDER BY created_at DESC
                     LIMIT $3")
                     .bind(conversation_id)
@@ -192,8 +204,8 @@ impl MessageRepository for PostgresMessageRepository {
             }
         } else {
             sqlx::query_as(
-                "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at FROM messages
-                WHERE conversation_id = $1
+                "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at FROM messages
+                WHERE conversation_id = $1 AND NOT is_hidden
                 ORDER BY created_at DESC
                 LIMIT $2")
                 .bind(conversation_id)
@@ -305,8 +317,8 @@ impl MessageRepository for PostgresMessageRepository {
         offset: i64,
     ) -> Result<Vec<Message>> {
         let models: Vec<MessageModel> = sqlx::query_as(
-            "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at FROM messages
-            WHERE conversation_id = $1 AND message_type = $2
+            "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at FROM messages
+            WHERE conversation_id = $1 AND message_type = $2 AND NOT is_hidden
             ORDER BY created_at DESC
             LIMIT $3 OFFSET $4")
             .bind(conversation_id)
@@ -322,8 +334,8 @@ impl MessageRepository for PostgresMessageRepository {
 
     async fn find_latest_in_conversation(&self, conversation_id: Uuid) -> Result<Option<Message>> {
         let model: Option<MessageModel> = sqlx::query_as(
-            "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at FROM messages
-            WHERE conversation_id = $1
+            "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at FROM messages
+            WHERE conversation_id = $1 AND NOT is_hidden
             ORDER BY created_at DESC
             LIMIT 1")
             .bind(conversation_id)
@@ -342,8 +354,8 @@ impl MessageRepository for PostgresMessageRepository {
         offset: i64,
     ) -> Result<Vec<Message>> {
         let models: Vec<MessageModel> = sqlx::query_as(
-            "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, created_at FROM messages
-            WHERE conversation_id = $1 AND content ILIKE $2
+            "SELECT id, conversation_id, sender_id, message_type, content, media_url, payment_data, reply_to_id, story_id, mentions, created_at FROM messages
+            WHERE conversation_id = $1 AND content ILIKE $2 AND NOT is_hidden
             ORDER BY created_at DESC
             LIMIT $3 OFFSET $4")
             .bind(conversation_id)
@@ -1,16 +1,38 @@
+pub mod bookmark;
+pub mod comment;
 pub mod conversation;
+pub mod feed_feedback;
+pub mod insights;
+pub mod media_upload;
+pub mod mention;
 pub mod message;
+pub mod moderation;
 pub mod notification;
+pub mod poll;
 pub mod post;
+pub mod post_view;
+pub mod story;
+pub mod suggestion;
 pub mod user;
 pub mod wallet;
 
+pub use bookmark::PostgresBookmarkRepository;
+pub use comment::PostgresCommentRepository;
 pub use conversation::PostgresConversationRepository;
+pub use feed_feedback::PostgresFeedFeedbackRepository;
+pub use insights::PostgresInsightsRepository;
+pub use media_upload::PostgresMediaUploadRepository;
+pub use mention::PostgresMentionRepository;
 pub use message::PostgresMessageRepository;
+pub use moderation::PostgresModerationRepository;
 pub use notification::{
     InMemoryNotificationPreferencesRepository, PostgresDeviceTokenRepository,
     PostgresNotificationRepository,
 };
+pub use poll::PostgresPollRepository;
 pub use post::PostgresPostRepository;
+pub use post_view::PostgresPostViewRepository;
+pub use story::PostgresStoryRepository;
+pub use suggestion::PostgresSuggestionRepository;
 pub use user::PostgresUserRepository;
 pub use wallet::PostgresWalletRepository;
@@ -0,0 +1,351 @@
+use crate::domain::entities::{
+    ModerationAction, ModerationActionType, ModerationCase, Report, ReportTargetType,
+};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::ModerationRepository;
+use crate::infrastructure::database::models::{
+    ModerationActionModel, ModerationCaseModel, ReportModel,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const CASE_COLUMNS: &str = "id, target_type, target_id, target_user_id, status, report_count, is_hidden, flagged_rules, created_at, updated_at, resolved_at";
+
+/// PostgreSQL implementation of ModerationRepository
+pub struct PostgresModerationRepository {
+    pool: PgPool,
+}
+
+impl PostgresModerationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn case_to_domain(model: ModerationCaseModel) -> Result<ModerationCase> {
+        Ok(ModerationCase {
+            id: model.id,
+            target_type: model.target_type.parse()?,
+            target_id: model.target_id,
+            target_user_id: model.target_user_id,
+            status: model.status.parse()?,
+            report_count: model.report_count,
+            is_hidden: model.is_hidden,
+            flagged_rules: model.flagged_rules,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            resolved_at: model.resolved_at,
+        })
+    }
+
+    fn report_to_domain(model: ReportModel) -> Result<Report> {
+        Ok(Report {
+            id: model.id,
+            reporter_id: model.reporter_id,
+            target_type: model.target_type.parse()?,
+            target_id: model.target_id,
+            reason: model.reason.parse()?,
+            details: model.details,
+            created_at: model.created_at,
+        })
+    }
+
+    fn action_to_domain(model: ModerationActionModel) -> Result<ModerationAction> {
+        Ok(ModerationAction {
+            id: model.id,
+            case_id: model.case_id,
+            moderator_id: model.moderator_id,
+            action: ModerationActionType::from_parts(&model.action, model.suspended_until)?,
+            note: model.note,
+            created_at: model.created_at,
+        })
+    }
+
+    /// Table holding the content a report can point at
+    fn content_table(target_type: ReportTargetType) -> Result<&'static str> {
+        match target_type {
+            ReportTargetType::Post => Ok("posts"),
+            ReportTargetType::Comment => Ok("comments"),
+            ReportTargetType::Message => Ok("messages"),
+            ReportTargetType::User => Err(AppError::ValidationError(
+                "Users cannot be hidden".to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl ModerationRepository for PostgresModerationRepository {
+    async fn is_moderator(&self, user_id: Uuid) -> Result<bool> {
+        let row: (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM moderators WHERE user_id = $1)")
+                .bind(user_id)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to check moderator: {}", e))
+                })?;
+
+        Ok(row.0)
+    }
+
+    async fn file_report(
+        &self,
+        report: &Report,
+        target_user_id: Uuid,
+    ) -> Result<(ModerationCase, bool)> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start transaction: {}", e))
+            })?;
+
+        // The no-op update makes RETURNING yield the existing case
+        let (case_id,): (Uuid,) = sqlx::query_as(
+            "INSERT INTO moderation_cases (id, target_type, target_id, target_user_id, status, report_count, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, 'open', 0, $5, $5)
+            ON CONFLICT (target_type, target_id) DO UPDATE SET target_user_id = moderation_cases.target_user_id
+            RETURNING id",
+        )
+        .bind(Uuid::new_v4())
+        .bind(report.target_type.to_string())
+        .bind(report.target_id)
+        .bind(target_user_id)
+        .bind(report.created_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to open moderation case: {}", e)))?;
+
+        let inserted = sqlx::query(
+            "INSERT INTO reports (id, case_id, reporter_id, target_type, target_id, reason, details, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (reporter_id, target_type, target_id) DO NOTHING",
+        )
+        .bind(report.id)
+        .bind(case_id)
+        .bind(report.reporter_id)
+        .bind(report.target_type.to_string())
+        .bind(report.target_id)
+        .bind(report.reason.to_string())
+        .bind(&report.details)
+        .bind(report.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to file report: {}", e)))?
+        .rows_affected()
+            > 0;
+
+        let model: ModerationCaseModel = if inserted {
+            sqlx::query_as(&format!(
+                "UPDATE moderation_cases
+                SET report_count = report_count + 1, status = 'open', resolved_at = NULL, updated_at = $2
+                WHERE id = $1
+                RETURNING {}",
+                CASE_COLUMNS
+            ))
+            .bind(case_id)
+            .bind(report.created_at)
+            .fetch_one(&mut *tx)
+            .await
+        } else {
+            sqlx::query_as(&format!(
+                "SELECT {} FROM moderation_cases WHERE id = $1",
+                CASE_COLUMNS
+            ))
+            .bind(case_id)
+            .fetch_one(&mut *tx)
+            .await
+        }
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update moderation case: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok((Self::case_to_domain(model)?, inserted))
+    }
+
+    async fn find_case(&self, case_id: Uuid) -> Result<Option<ModerationCase>> {
+        let model: Option<ModerationCaseModel> = sqlx::query_as(&format!(
+            "SELECT {} FROM moderation_cases WHERE id = $1",
+            CASE_COLUMNS
+        ))
+        .bind(case_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find moderation case: {}", e)))?;
+
+        model.map(Self::case_to_domain).transpose()
+    }
+
+    async fn find_open_cases(&self, limit: i64, offset: i64) -> Result<Vec<ModerationCase>> {
+        let models: Vec<ModerationCaseModel> = sqlx::query_as(&format!(
+            "SELECT {} FROM moderation_cases
+            WHERE status = 'open'
+            ORDER BY report_count DESC, created_at ASC
+            LIMIT $1 OFFSET $2",
+            CASE_COLUMNS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch moderation queue: {}", e)))?;
+
+        models.into_iter().map(Self::case_to_domain).collect()
+    }
+
+    async fn find_reports(&self, case_id: Uuid) -> Result<Vec<Report>> {
+        let models: Vec<ReportModel> = sqlx::query_as(
+            "SELECT id, reporter_id, target_type, target_id, reason, details, created_at
+            FROM reports
+            WHERE case_id = $1
+            ORDER BY created_at ASC",
+        )
+        .bind(case_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch reports: {}", e)))?;
+
+        models.into_iter().map(Self::report_to_domain).collect()
+    }
+
+    async fn find_actions(&self, case_id: Uuid) -> Result<Vec<ModerationAction>> {
+        let models: Vec<ModerationActionModel> = sqlx::query_as(
+            "SELECT id, case_id, moderator_id, action, suspended_until, note, created_at
+            FROM moderation_actions
+            WHERE case_id = $1
+            ORDER BY created_at ASC",
+        )
+        .bind(case_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch moderation actions: {}", e))
+        })?;
+
+        models.into_iter().map(Self::action_to_domain).collect()
+    }
+
+    async fn set_content_hidden(&self, case: &ModerationCase, hidden: bool) -> Result<()> {
+        let table = Self::content_table(case.target_type)?;
+
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start transaction: {}", e))
+            })?;
+
+        sqlx::query(&format!(
+            "UPDATE {} SET is_hidden = $2 WHERE id = $1",
+            table
+        ))
+        .bind(case.target_id)
+        .bind(hidden)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to hide content: {}", e)))?;
+
+        sqlx::query("UPDATE moderation_cases SET is_hidden = $2, updated_at = $3 WHERE id = $1")
+            .bind(case.id)
+            .bind(hidden)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to update moderation case: {}", e))
+            })?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn flag_for_review(
+        &self,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        target_user_id: Uuid,
+        rules: &[String],
+    ) -> Result<ModerationCase> {
+        let now = Utc::now();
+        let model: ModerationCaseModel = sqlx::query_as(&format!(
+            "INSERT INTO moderation_cases (id, target_type, target_id, target_user_id, status, report_count, flagged_rules, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, 'open', 0, $5, $6, $6)
+            ON CONFLICT (target_type, target_id) DO UPDATE
+            SET flagged_rules = ARRAY(SELECT DISTINCT unnest(moderation_cases.flagged_rules || EXCLUDED.flagged_rules)),
+                status = 'open', resolved_at = NULL, updated_at = EXCLUDED.updated_at
+            RETURNING {}",
+            CASE_COLUMNS
+        ))
+        .bind(Uuid::new_v4())
+        .bind(target_type.to_string())
+        .bind(target_id)
+        .bind(target_user_id)
+        .bind(rules)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to flag content for review: {}", e)))?;
+
+        Self::case_to_domain(model)
+    }
+
+    async fn record_action(&self, action: &ModerationAction) -> Result<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start transaction: {}", e))
+            })?;
+
+        sqlx::query(
+            "INSERT INTO moderation_actions (id, case_id, moderator_id, action, suspended_until, note, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(action.id)
+        .bind(action.case_id)
+        .bind(action.moderator_id)
+        .bind(action.action.name())
+        .bind(action.action.suspended_until())
+        .bind(&action.note)
+        .bind(action.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to record moderation action: {}", e))
+        })?;
+
+        // Reports filed after this point count towards the threshold afresh
+        sqlx::query(
+            "UPDATE moderation_cases
+            SET status = 'resolved', report_count = 0, resolved_at = $2, updated_at = $2
+            WHERE id = $1",
+        )
+        .bind(action.case_id)
+        .bind(action.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to resolve moderation case: {}", e))
+        })?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn suspend_user(&self, user_id: Uuid, until: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE users SET suspended_until = $2, updated_at = $3 WHERE id = $1")
+            .bind(user_id)
+            .bind(until)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to suspend user: {}", e)))?;
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use crate::domain::errors::{AppError, Result};
 use crate::domain::repositories::{
     DeviceTokenRepository, NotificationPreferencesRepository, NotificationRepository,
 };
+use crate::domain::value_objects::PageStart;
 use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -43,8 +44,8 @@ impl NotificationRepository for PostgresNotificationRepository {
     async fn find_by_user_id(
         &self,
         _user_id: Uuid,
+        _page: PageStart,
         _limit: i64,
-        _offset: i64,
     ) -> Result<Vec<Notification>> {
         // TODO: Implement actual database operations
         Ok(vec![])
@@ -0,0 +1,189 @@
+use crate::domain::entities::Poll;
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::PollRepository;
+use crate::infrastructure::database::models::PollModel;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// PostgreSQL implementation of PollRepository
+pub struct PostgresPollRepository {
+    pool: PgPool,
+}
+
+impl PostgresPollRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn to_domain(model: PollModel) -> Poll {
+        Poll {
+            post_id: model.post_id,
+            options: model.options,
+            vote_counts: model.vote_counts,
+            closes_at: model.closes_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[async_trait]
+impl PollRepository for PostgresPollRepository {
+    async fn create(&self, poll: &Poll) -> Result<Poll> {
+        let model: PollModel = sqlx::query_as(
+            "INSERT INTO polls (post_id, options, vote_counts, closes_at, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING post_id, options, vote_counts, closes_at, created_at",
+        )
+        .bind(poll.post_id)
+        .bind(&poll.options)
+        .bind(&poll.vote_counts)
+        .bind(poll.closes_at)
+        .bind(poll.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create poll: {}", e)))?;
+
+        Ok(Self::to_domain(model))
+    }
+
+    async fn find_by_post_id(&self, post_id: Uuid) -> Result<Option<Poll>> {
+        let model: Option<PollModel> = sqlx::query_as(
+            "SELECT post_id, options, vote_counts, closes_at, created_at FROM polls WHERE post_id = $1",
+        )
+        .bind(post_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find poll: {}", e)))?;
+
+        Ok(model.map(Self::to_domain))
+    }
+
+    async fn find_by_post_ids(&self, post_ids: &[Uuid]) -> Result<Vec<Poll>> {
+        if post_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let models: Vec<PollModel> = sqlx::query_as(
+            "SELECT post_id, options, vote_counts, closes_at, created_at FROM polls WHERE post_id = ANY($1)",
+        )
+        .bind(post_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find polls: {}", e)))?;
+
+        Ok(models.into_iter().map(Self::to_domain).collect())
+    }
+
+    async fn vote(&self, post_id: Uuid, user_id: Uuid, option_index: i32) -> Result<Poll> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start transaction: {}", e))
+            })?;
+
+        // The primary key on (post_id, user_id) enforces one vote per user
+        sqlx::query(
+            "INSERT INTO poll_votes (post_id, user_id, option_index, created_at)
+            VALUES ($1, $2, $3, NOW())",
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .bind(option_index)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                AppError::Conflict("Already voted in this poll".to_string())
+            }
+            _ => AppError::DatabaseError(format!("Failed to record vote: {}", e)),
+        })?;
+
+        // Postgres arrays are 1-based
+        let model: Option<PollModel> = sqlx::query_as(
+            "UPDATE polls
+            SET vote_counts[$2 + 1] = vote_counts[$2 + 1] + 1
+            WHERE post_id = $1 AND closes_at > NOW()
+            RETURNING post_id, options, vote_counts, closes_at, created_at",
+        )
+        .bind(post_id)
+        .bind(option_index)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update poll counts: {}", e)))?;
+
+        // Dropping the transaction rolls back the vote
+        let model = model.ok_or_else(|| AppError::ValidationError("Poll is closed".to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit vote: {}", e)))?;
+
+        Ok(Self::to_domain(model))
+    }
+
+    async fn find_user_vote(&self, post_id: Uuid, user_id: Uuid) -> Result<Option<i32>> {
+        let row: Option<(i32,)> = sqlx::query_as(
+            "SELECT option_index FROM poll_votes WHERE post_id = $1 AND user_id = $2",
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find vote: {}", e)))?;
+
+        Ok(row.map(|r| r.0))
+    }
+
+    async fn find_user_votes(
+        &self,
+        post_ids: &[Uuid],
+        user_id: Uuid,
+    ) -> Result<HashMap<Uuid, i32>> {
+        if post_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(Uuid, i32)> = sqlx::query_as(
+            "SELECT post_id, option_index FROM poll_votes WHERE post_id = ANY($1) AND user_id = $2",
+        )
+        .bind(post_ids)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find votes: {}", e)))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn find_closed_unnotified(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Poll>> {
+        let models: Vec<PollModel> = sqlx::query_as(
+            "SELECT post_id, options, vote_counts, closes_at, created_at FROM polls
+            WHERE closes_at <= $1 AND closed_notified_at IS NULL
+            ORDER BY closes_at ASC
+            LIMIT $2",
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch closed polls: {}", e)))?;
+
+        Ok(models.into_iter().map(Self::to_domain).collect())
+    }
+
+    async fn mark_closed_notified(&self, post_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE polls SET closed_notified_at = NOW()
+            WHERE post_id = $1 AND closed_notified_at IS NULL",
+        )
+        .bind(post_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update poll: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
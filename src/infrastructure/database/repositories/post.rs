@@ -1,13 +1,24 @@
-use crate::domain::entities::{Post, PostVisibility, User};
+use crate::domain::entities::{
+    HashtagCount, Post, PostSearchHit, PostSearchQuery, PostStatus, PostVisibility, SearchSnippet,
+    User, SEARCH_HIGHLIGHT_END, SEARCH_HIGHLIGHT_START,
+};
 use crate::domain::errors::{AppError, Result};
 use crate::domain::repositories::PostRepository;
-use crate::domain::value_objects::{Bio, DisplayName, Email, PhoneNumber, Username};
-use crate::infrastructure::database::models::{PostModel, UserModel};
+use crate::domain::value_objects::{
+    Bio, Cursor, DisplayName, Email, PageStart, PhoneNumber, Username,
+};
+use crate::infrastructure::database::models::{
+    ExploreCandidateRow, HashtagCountRow, PostModel, PostSearchRow, UserModel,
+};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+/// How far back hashtag search counts posts
+const HASHTAG_SEARCH_WINDOW_DAYS: i64 = 90;
+
 /// PostgreSQL implementation of PostRepository
 pub struct PostgresPostRepository {
     pool: PgPool,
@@ -25,11 +36,16 @@ impl PostgresPostRepository {
                 AppError::SerializationError(format!("Failed to deserialize media: {}", e))
             })?;
 
+        let mentions = serde_json::from_value(model.mentions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to deserialize mentions: {}", e))
+        })?;
+
         let content_type = match model.content_type.as_str() {
             "text" => crate::domain::entities::PostContentType::Text,
             "image" => crate::domain::entities::PostContentType::Image,
             "video" => crate::domain::entities::PostContentType::Video,
             "mixed" => crate::domain::entities::PostContentType::Mixed,
+            "poll" => crate::domain::entities::PostContentType::Poll,
             _ => crate::domain::entities::PostContentType::Text,
         };
 
@@ -40,17 +56,28 @@ impl PostgresPostRepository {
             _ => PostVisibility::Public,
         };
 
+        let status = model.status.parse().unwrap_or(PostStatus::Published);
+
         Ok(Post {
             id: model.id,
             user_id: model.user_id,
             content_type,
             text_content: model.text_content,
             media_attachments,
+            mentions,
             is_reel: model.is_reel,
             visibility,
+            status,
+            publish_at: model.publish_at,
             like_count: model.like_count,
             comment_count: model.comment_count,
             reshare_count: model.reshare_count,
+            view_count: model.view_count,
+            avg_completion_percent: model.avg_completion_percent,
+            is_hidden: model.is_hidden,
+            is_sensitive: model.is_sensitive,
+            content_warning: model.content_warning,
+            pinned_at: model.pinned_at,
             created_at: model.created_at,
             updated_at: model.updated_at,
         })
@@ -71,6 +98,8 @@ impl PostgresPostRepository {
             phone_verified: model.phone_verified,
             follower_count: model.follower_count,
             following_count: model.following_count,
+            suspended_until: model.suspended_until,
+            sensitive_media: model.sensitive_media.parse()?,
             created_at: model.created_at,
             updated_at: model.updated_at,
         })
@@ -84,11 +113,16 @@ impl PostRepository for PostgresPostRepository {
             AppError::SerializationError(format!("Failed to serialize media: {}", e))
         })?;
 
+        let mentions_json = serde_json::to_value(&post.mentions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize mentions: {}", e))
+        })?;
+
         let content_type_str = match post.content_type {
             crate::domain::entities::PostContentType::Text => "text",
             crate::domain::entities::PostContentType::Image => "image",
             crate::domain::entities::PostContentType::Video => "video",
             crate::domain::entities::PostContentType::Mixed => "mixed",
+            crate::domain::entities::PostContentType::Poll => "poll",
         };
 
         let visibility_str = match post.visibility {
@@ -98,18 +132,23 @@ impl PostRepository for PostgresPostRepository {
         };
 
         sqlx::query(
-            "INSERT INTO posts (id, user_id, content_type, text_content, media_attachments, is_reel, visibility, like_count, comment_count, reshare_count, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)")
+            "INSERT INTO posts (id, user_id, content_type, text_content, media_attachments, mentions, is_reel, visibility, status, publish_at, like_count, comment_count, reshare_count, is_sensitive, content_warning, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)")
             .bind(post.id)
             .bind(post.user_id)
             .bind(content_type_str)
             .bind(&post.text_content)
             .bind(media_json)
+            .bind(mentions_json)
             .bind(post.is_reel)
             .bind(visibility_str)
+            .bind(post.status.to_string())
+            .bind(post.publish_at)
             .bind(post.like_count)
             .bind(post.comment_count)
             .bind(post.reshare_count)
+            .bind(post.is_sensitive)
+            .bind(&post.content_warning)
             .bind(post.created_at)
             .bind(post.updated_at)
         .execute(&self.pool)
@@ -129,6 +168,20 @@ impl PostRepository for PostgresPostRepository {
         model.map(Self::to_domain).transpose()
     }
 
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Post>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let models: Vec<PostModel> = sqlx::query_as("SELECT * FROM posts WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to find posts by ids: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
     async fn update(&self, post: &Post) -> Result<Post> {
         let media_json = serde_json::to_value(&post.media_attachments).map_err(|e| {
             AppError::SerializationError(format!("Failed to serialize media: {}", e))
@@ -139,6 +192,7 @@ impl PostRepository for PostgresPostRepository {
             crate::domain::entities::PostContentType::Image => "image",
             crate::domain::entities::PostContentType::Video => "video",
             crate::domain::entities::PostContentType::Mixed => "mixed",
+            crate::domain::entities::PostContentType::Poll => "poll",
         };
 
         let visibility_str = match post.visibility {
@@ -147,10 +201,16 @@ impl PostRepository for PostgresPostRepository {
             PostVisibility::Private => "private",
         };
 
+        let mentions_json = serde_json::to_value(&post.mentions).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize mentions: {}", e))
+        })?;
+
         let model: PostModel = sqlx::query_as(
             "UPDATE posts 
             SET content_type = $2, text_content = $3, media_attachments = $4, is_reel = $5, 
-                visibility = $6, like_count = $7, comment_count = $8, reshare_count = $9, updated_at = $10
+                visibility = $6, like_count = $7, comment_count = $8, reshare_count = $9, updated_at = $10,
+                mentions = $11, status = $12, publish_at = $13, created_at = $14,
+                is_sensitive = $15, content_warning = $16
             WHERE id = $1
             RETURNING *")
             .bind(post.id)
@@ -163,6 +223,12 @@ impl PostRepository for PostgresPostRepository {
             .bind(post.comment_count)
             .bind(post.reshare_count)
             .bind(post.updated_at)
+            .bind(mentions_json)
+            .bind(post.status.to_string())
+            .bind(post.publish_at)
+            .bind(post.created_at)
+            .bind(post.is_sensitive)
+            .bind(&post.content_warning)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to update post: {}", e)))?;
@@ -179,17 +245,22 @@ impl PostRepository for PostgresPostRepository {
         Ok(())
     }
 
-    async fn find_feed(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<Post>> {
+    async fn find_feed(&self, user_id: Uuid, page: PageStart, limit: i64) -> Result<Vec<Post>> {
+        let after = page.cursor();
         let models: Vec<PostModel> = sqlx::query_as(
             "SELECT p.* FROM posts p
             INNER JOIN follows f ON p.user_id = f.following_id
             WHERE f.follower_id = $1 AND p.visibility IN ('public', 'followers')
-            ORDER BY p.created_at DESC
-            LIMIT $2 OFFSET $3",
+              AND p.status = 'published'
+              AND ($2::timestamptz IS NULL OR (p.created_at, p.id) < ($2, $3))
+            ORDER BY p.created_at DESC, p.id DESC
+            LIMIT $4 OFFSET $5",
         )
         .bind(user_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
         .bind(limit)
-        .bind(offset)
+        .bind(page.offset())
         .fetch_all(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to fetch feed: {}", e)))?;
@@ -197,11 +268,43 @@ impl PostRepository for PostgresPostRepository {
         models.into_iter().map(Self::to_domain).collect()
     }
 
+    async fn find_feed_from_popular(
+        &self,
+        user_id: Uuid,
+        min_followers: i32,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let models: Vec<PostModel> = sqlx::query_as(
+            "SELECT p.* FROM posts p
+            INNER JOIN follows f ON p.user_id = f.following_id
+            INNER JOIN users u ON u.id = p.user_id
+            WHERE f.follower_id = $1 AND u.follower_count >= $2
+              AND p.visibility IN ('public', 'followers')
+              AND p.status = 'published'
+              AND ($3::timestamptz IS NULL OR (p.created_at, p.id) < ($3, $4))
+            ORDER BY p.created_at DESC, p.id DESC
+            LIMIT $5",
+        )
+        .bind(user_id)
+        .bind(min_followers)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch feed from popular authors: {}", e))
+        })?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
     async fn find_by_user_id(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<Post>> {
         let models: Vec<PostModel> = sqlx::query_as(
             "SELECT * FROM posts
-            WHERE user_id = $1
-            ORDER BY created_at DESC
+            WHERE user_id = $1 AND status = 'published'
+            ORDER BY pinned_at DESC NULLS LAST, created_at DESC
             LIMIT $2 OFFSET $3",
         )
         .bind(user_id)
@@ -217,7 +320,7 @@ impl PostRepository for PostgresPostRepository {
     async fn find_public(&self, limit: i64, offset: i64) -> Result<Vec<Post>> {
         let models: Vec<PostModel> = sqlx::query_as(
             "SELECT * FROM posts
-            WHERE visibility = 'public'
+            WHERE visibility = 'public' AND status = 'published'
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2",
         )
@@ -230,34 +333,119 @@ impl PostRepository for PostgresPostRepository {
         models.into_iter().map(Self::to_domain).collect()
     }
 
+    async fn find_explore_candidates(
+        &self,
+        viewer_id: Uuid,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<(Post, i64)>> {
+        let rows: Vec<ExploreCandidateRow> = sqlx::query_as(
+            "SELECT p.*, COALESCE(fl.followed_likes, 0) AS followed_likes
+            FROM posts p
+            LEFT JOIN (
+                SELECT pl.post_id, COUNT(*) AS followed_likes
+                FROM post_likes pl
+                INNER JOIN follows f ON f.following_id = pl.user_id AND f.follower_id = $1
+                WHERE pl.created_at >= $2
+                GROUP BY pl.post_id
+            ) fl ON fl.post_id = p.id
+            WHERE p.visibility = 'public' AND p.status = 'published'
+              AND p.created_at >= $2 AND p.user_id <> $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM follows f WHERE f.follower_id = $1 AND f.following_id = p.user_id)
+              AND NOT EXISTS (
+                  SELECT 1 FROM mutes m WHERE m.user_id = $1 AND m.muted_user_id = p.user_id)
+              AND NOT EXISTS (
+                  SELECT 1 FROM contacts c
+                  WHERE c.is_blocked = true
+                    AND ((c.user_id = $1 AND c.contact_user_id = p.user_id)
+                         OR (c.user_id = p.user_id AND c.contact_user_id = $1)))
+            ORDER BY followed_likes DESC,
+                     p.like_count + p.comment_count + p.reshare_count * 2 DESC,
+                     p.created_at DESC
+            LIMIT $3",
+        )
+        .bind(viewer_id)
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch explore candidates: {}", e))
+        })?;
+
+        rows.into_iter()
+            .map(|row| Ok((Self::to_domain(row.post)?, row.followed_likes)))
+            .collect()
+    }
+
+    async fn find_liked_by_user(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let models: Vec<PostModel> = sqlx::query_as(
+            "SELECT p.* FROM posts p
+            INNER JOIN post_likes pl ON pl.post_id = p.id
+            WHERE pl.user_id = $1 AND pl.created_at >= $2 AND p.status = 'published'
+            ORDER BY pl.created_at DESC
+            LIMIT $3",
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch liked posts: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
     async fn find_reels(
         &self,
         user_id: Option<Uuid>,
+        page: PageStart,
         limit: i64,
-        offset: i64,
     ) -> Result<Vec<Post>> {
+        let after = page.cursor();
         let models: Vec<PostModel> = if let Some(uid) = user_id {
             sqlx::query_as(
                 "SELECT p.* FROM posts p
                 INNER JOIN follows f ON p.user_id = f.following_id
                 WHERE f.follower_id = $1 AND p.is_reel = true AND p.visibility IN ('public', 'followers')
-                ORDER BY p.created_at DESC
-                LIMIT $2 OFFSET $3")
+                  AND p.status = 'published'
+                  AND EXISTS (
+                    SELECT 1 FROM jsonb_array_elements(p.media_attachments) media
+                    WHERE media->>'media_type' LIKE 'video/%' AND media->>'video_codec' IS NOT NULL
+                  )
+                  AND ($2::timestamptz IS NULL OR (p.created_at, p.id) < ($2, $3))
+                ORDER BY p.created_at DESC, p.id DESC
+                LIMIT $4 OFFSET $5")
                 .bind(uid)
+                .bind(after.map(|c| c.created_at))
+                .bind(after.map(|c| c.id))
                 .bind(limit)
-                .bind(offset)
+                .bind(page.offset())
             .fetch_all(&self.pool)
             .await
             .map_err(|e| AppError::DatabaseError(format!("Failed to fetch reels: {}", e)))?
         } else {
             sqlx::query_as(
                 "SELECT * FROM posts
-                WHERE is_reel = true AND visibility = 'public'
-                ORDER BY created_at DESC
-                LIMIT $1 OFFSET $2",
+                WHERE is_reel = true AND visibility = 'public' AND status = 'published'
+                  AND EXISTS (
+                    SELECT 1 FROM jsonb_array_elements(media_attachments) media
+                    WHERE media->>'media_type' LIKE 'video/%' AND media->>'video_codec' IS NOT NULL
+                  )
+                  AND ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2))
+                ORDER BY created_at DESC, id DESC
+                LIMIT $3 OFFSET $4",
             )
+            .bind(after.map(|c| c.created_at))
+            .bind(after.map(|c| c.id))
             .bind(limit)
-            .bind(offset)
+            .bind(page.offset())
             .fetch_all(&self.pool)
             .await
             .map_err(|e| AppError::DatabaseError(format!("Failed to fetch public reels: {}", e)))?
@@ -266,21 +454,106 @@ impl PostRepository for PostgresPostRepository {
         models.into_iter().map(Self::to_domain).collect()
     }
 
-    async fn search(&self, query: &str, limit: i64, offset: i64) -> Result<Vec<Post>> {
-        let models: Vec<PostModel> = sqlx::query_as(
-            "SELECT * FROM posts
-            WHERE text_content ILIKE $1 AND visibility = 'public'
-            ORDER BY created_at DESC
-            LIMIT $2 OFFSET $3",
+    async fn search(
+        &self,
+        viewer_id: Option<Uuid>,
+        query: &PostSearchQuery,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PostSearchHit>> {
+        // The headline covers the text and alt text so matches in either are shown
+        let headline_options = format!(
+            "StartSel={}, StopSel={}, MaxWords=35, MinWords=15, MaxFragments=2",
+            SEARCH_HIGHLIGHT_START, SEARCH_HIGHLIGHT_END
+        );
+        let rows: Vec<PostSearchRow> = sqlx::query_as(
+            "SELECT p.*, ts_rank_cd(p.search_vector, q.query) AS rank,
+                ts_headline(
+                    'english',
+                    concat_ws(' ', p.text_content, (
+                        SELECT string_agg(alt, ' ')
+                        FROM jsonb_array_elements_text(
+                            jsonb_path_query_array(p.media_attachments, 'strict $[*].alt_text')) alt
+                    )),
+                    q.query,
+                    $2
+                ) AS headline
+            FROM posts p, websearch_to_tsquery('english', $1) q(query)
+            WHERE p.search_vector @@ q.query
+              AND p.status = 'published' AND p.is_hidden = false
+              AND (p.visibility = 'public'
+                   OR p.user_id = $3
+                   OR (p.visibility = 'followers' AND EXISTS (
+                       SELECT 1 FROM follows f WHERE f.follower_id = $3 AND f.following_id = p.user_id)))
+              AND ($3::uuid IS NULL OR NOT EXISTS (
+                  SELECT 1 FROM contacts c
+                  WHERE c.is_blocked = true
+                    AND ((c.user_id = $3 AND c.contact_user_id = p.user_id)
+                         OR (c.user_id = p.user_id AND c.contact_user_id = $3))))
+              AND ($4::uuid IS NULL OR p.user_id = $4)
+              AND ($5::timestamptz IS NULL OR p.created_at >= $5)
+              AND ($6::timestamptz IS NULL OR p.created_at < $6)
+              AND ($7::text IS NULL OR p.content_type::text = $7)
+              AND (NOT $8 OR p.is_reel = true)
+              AND ($9::text IS NULL OR p.text_content ~* ('(^|[^[:alnum:]_#])#' || $9 || '($|[^[:alnum:]_])'))
+            ORDER BY rank DESC, p.created_at DESC, p.id DESC
+            LIMIT $10 OFFSET $11",
         )
-        .bind(format!("%{}%", query))
+        .bind(&query.text)
+        .bind(headline_options)
+        .bind(viewer_id)
+        .bind(query.author_id)
+        .bind(query.since)
+        .bind(query.until)
+        .bind(query.content_type.as_ref().map(|c| c.to_string()))
+        .bind(query.reels_only)
+        .bind(&query.hashtag)
         .bind(limit)
         .bind(offset)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to search posts: {}", e)))?;
 
-        models.into_iter().map(Self::to_domain).collect()
+        rows.into_iter()
+            .map(|row| {
+                Ok(PostSearchHit {
+                    post: Self::to_domain(row.post)?,
+                    rank: row.rank,
+                    snippet: SearchSnippet::from_headline(&row.headline),
+                })
+            })
+            .collect()
+    }
+
+    async fn search_hashtags(&self, prefix: &str, limit: i64) -> Result<Vec<HashtagCount>> {
+        // Tags are counted over recent public posts; the trigram index on
+        // text_content narrows the scan to posts containing the prefix
+        let since = Utc::now() - chrono::Duration::days(HASHTAG_SEARCH_WINDOW_DAYS);
+        let rows: Vec<HashtagCountRow> = sqlx::query_as(
+            "SELECT lower(m[1]) AS tag, COUNT(DISTINCT p.id) AS post_count
+            FROM posts p, regexp_matches(p.text_content, '(?:^|[^[:alnum:]_#])#([[:alnum:]_]+)', 'g') m
+            WHERE p.visibility = 'public' AND p.status = 'published' AND p.is_hidden = false
+              AND p.created_at >= $2
+              AND p.text_content ILIKE ('%#' || $1 || '%')
+              AND starts_with(lower(m[1]), $1) AND m[1] ~ '[[:alpha:]]'
+            GROUP BY lower(m[1])
+            ORDER BY post_count DESC, tag
+            LIMIT $3",
+        )
+        .bind(prefix)
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to search hashtags: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| HashtagCount {
+                tag: row.tag,
+                post_count: row.post_count,
+            })
+            .collect())
     }
 
     async fn increment_like_count(&self, post_id: Uuid) -> Result<()> {
@@ -366,6 +639,56 @@ impl PostRepository for PostgresPostRepository {
         Ok(row.0)
     }
 
+    async fn liked_post_ids(&self, user_id: Uuid, post_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        if post_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT post_id FROM post_likes WHERE user_id = $1 AND post_id = ANY($2)",
+        )
+        .bind(user_id)
+        .bind(post_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to check like status: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn count_interactions_with_authors(
+        &self,
+        user_id: Uuid,
+        author_ids: &[Uuid],
+        since: DateTime<Utc>,
+    ) -> Result<HashMap<Uuid, i64>> {
+        if author_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(Uuid, i64)> = sqlx::query_as(
+            r#"
+            SELECT p.user_id, COUNT(*)
+            FROM (
+                SELECT post_id FROM post_likes WHERE user_id = $1 AND created_at >= $3
+                UNION ALL
+                SELECT post_id FROM comments WHERE user_id = $1 AND created_at >= $3
+            ) interactions
+            JOIN posts p ON p.id = interactions.post_id
+            WHERE p.user_id = ANY($2)
+            GROUP BY p.user_id
+            "#,
+        )
+        .bind(user_id)
+        .bind(author_ids)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to count interactions: {}", e)))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
     async fn like_post(&self, user_id: Uuid, post_id: Uuid) -> Result<()> {
         sqlx::query(
             "INSERT INTO post_likes (user_id, post_id, created_at) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING")
@@ -405,4 +728,145 @@ impl PostRepository for PostgresPostRepository {
 
         models.into_iter().map(Self::user_model_to_domain).collect()
     }
+
+    async fn find_mentioning_user(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let after = page.cursor();
+        let models: Vec<PostModel> = sqlx::query_as(
+            "SELECT p.* FROM posts p
+            INNER JOIN mentions m ON m.source_id = p.id AND m.source_type = 'post'
+            WHERE m.mentioned_user_id = $1 AND p.status = 'published'
+              AND (p.visibility = 'public'
+                   OR p.user_id = $1
+                   OR (p.visibility = 'followers' AND EXISTS(
+                       SELECT 1 FROM follows f WHERE f.follower_id = $1 AND f.following_id = p.user_id)))
+              AND NOT EXISTS (
+                  SELECT 1 FROM contacts c
+                  WHERE c.is_blocked = true
+                    AND ((c.user_id = $1 AND c.contact_user_id = p.user_id)
+                         OR (c.user_id = p.user_id AND c.contact_user_id = $1)))
+              AND ($2::timestamptz IS NULL OR (p.created_at, p.id) < ($2, $3))
+            ORDER BY p.created_at DESC, p.id DESC
+            LIMIT $4 OFFSET $5",
+        )
+        .bind(user_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
+        .bind(limit)
+        .bind(page.offset())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch mentioning posts: {}", e))
+        })?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn find_unpublished_by_user(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Post>> {
+        let models: Vec<PostModel> = sqlx::query_as(
+            "SELECT * FROM posts
+            WHERE user_id = $1 AND status IN ('draft', 'scheduled')
+            ORDER BY COALESCE(publish_at, updated_at) DESC
+            LIMIT $2 OFFSET $3",
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch drafts: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn find_due_scheduled(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Post>> {
+        let models: Vec<PostModel> = sqlx::query_as(
+            "SELECT * FROM posts
+            WHERE status = 'scheduled' AND publish_at <= $1
+            ORDER BY publish_at ASC
+            LIMIT $2",
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch scheduled posts: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn publish_scheduled(&self, post_id: Uuid, now: DateTime<Utc>) -> Result<Option<Post>> {
+        // Guarded on status so concurrent schedulers publish each post only once
+        let model: Option<PostModel> = sqlx::query_as(
+            "UPDATE posts
+            SET status = 'published', publish_at = NULL, created_at = $2, updated_at = $2
+            WHERE id = $1 AND status = 'scheduled' AND publish_at <= $2
+            RETURNING *",
+        )
+        .bind(post_id)
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to publish post: {}", e)))?;
+
+        model.map(Self::to_domain).transpose()
+    }
+
+    async fn pin(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        max_pinned: i64,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start transaction: {}", e))
+            })?;
+
+        // Lock the author so concurrent pins can't both slip under the limit
+        sqlx::query("SELECT id FROM users WHERE id = $1 FOR UPDATE")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to lock user: {}", e)))?;
+
+        let result = sqlx::query(
+            "UPDATE posts SET pinned_at = $3
+            WHERE id = $1 AND user_id = $2 AND pinned_at IS NULL
+              AND (SELECT COUNT(*) FROM posts WHERE user_id = $2 AND pinned_at IS NOT NULL) < $4",
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .bind(now)
+        .bind(max_pinned)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to pin post: {}", e)))?;
+
+        tx.commit().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to commit pin transaction: {}", e))
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn unpin(&self, post_id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE posts SET pinned_at = NULL WHERE id = $1")
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to unpin post: {}", e)))?;
+        Ok(())
+    }
 }
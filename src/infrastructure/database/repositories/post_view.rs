@@ -0,0 +1,130 @@
+use crate::domain::entities::PostViewStats;
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::PostViewRepository;
+use crate::infrastructure::database::models::PostViewStatsModel;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// PostgreSQL implementation of PostViewRepository
+pub struct PostgresPostViewRepository {
+    pool: PgPool,
+}
+
+impl PostgresPostViewRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn to_domain(model: PostViewStatsModel) -> PostViewStats {
+        PostViewStats {
+            post_id: model.post_id,
+            impression_count: model.impression_count,
+            view_count: model.view_count,
+            replay_count: model.replay_count,
+            completed_count: model.completed_count,
+            total_watch_ms: model.total_watch_ms,
+            completion_percent_sum: model.completion_percent_sum,
+            reach_count: model.reach_count,
+            follower_impression_count: model.follower_impression_count,
+            follower_reach_count: model.follower_reach_count,
+        }
+    }
+}
+
+#[async_trait]
+impl PostViewRepository for PostgresPostViewRepository {
+    async fn apply(&self, delta: &PostViewStats, day: NaiveDate) -> Result<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start transaction: {}", e))
+            })?;
+
+        // Selecting from posts skips counters for posts deleted since the view
+        let totals: Option<PostViewStatsModel> = sqlx::query_as(
+            "INSERT INTO post_view_stats (post_id, impression_count, view_count, replay_count, completed_count, total_watch_ms, completion_percent_sum, reach_count, follower_impression_count, follower_reach_count, updated_at)
+            SELECT id, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW() FROM posts WHERE id = $1
+            ON CONFLICT (post_id) DO UPDATE SET
+                impression_count = post_view_stats.impression_count + EXCLUDED.impression_count,
+                view_count = post_view_stats.view_count + EXCLUDED.view_count,
+                replay_count = post_view_stats.replay_count + EXCLUDED.replay_count,
+                completed_count = post_view_stats.completed_count + EXCLUDED.completed_count,
+                total_watch_ms = post_view_stats.total_watch_ms + EXCLUDED.total_watch_ms,
+                completion_percent_sum = post_view_stats.completion_percent_sum + EXCLUDED.completion_percent_sum,
+                reach_count = post_view_stats.reach_count + EXCLUDED.reach_count,
+                follower_impression_count = post_view_stats.follower_impression_count + EXCLUDED.follower_impression_count,
+                follower_reach_count = post_view_stats.follower_reach_count + EXCLUDED.follower_reach_count,
+                updated_at = NOW()
+            RETURNING post_id, impression_count, view_count, replay_count, completed_count, total_watch_ms, completion_percent_sum, reach_count, follower_impression_count, follower_reach_count",
+        )
+        .bind(delta.post_id)
+        .bind(delta.impression_count)
+        .bind(delta.view_count)
+        .bind(delta.replay_count)
+        .bind(delta.completed_count)
+        .bind(delta.total_watch_ms)
+        .bind(delta.completion_percent_sum)
+        .bind(delta.reach_count)
+        .bind(delta.follower_impression_count)
+        .bind(delta.follower_reach_count)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update view stats: {}", e)))?;
+
+        let totals = match totals {
+            Some(model) => Self::to_domain(model),
+            None => return Ok(()),
+        };
+
+        // updated_at is left alone so views do not read as edits
+        sqlx::query("UPDATE posts SET view_count = $2, avg_completion_percent = $3 WHERE id = $1")
+            .bind(totals.post_id)
+            .bind(totals.view_count)
+            .bind(totals.avg_completion_percent())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to update post view count: {}", e))
+            })?;
+
+        sqlx::query(
+            "INSERT INTO post_daily_stats (post_id, author_id, day, impressions, follower_impressions, reach, follower_reach)
+            SELECT id, user_id, $2, $3, $4, $5, $6 FROM posts WHERE id = $1
+            ON CONFLICT (post_id, day) DO UPDATE SET
+                impressions = post_daily_stats.impressions + EXCLUDED.impressions,
+                follower_impressions = post_daily_stats.follower_impressions + EXCLUDED.follower_impressions,
+                reach = post_daily_stats.reach + EXCLUDED.reach,
+                follower_reach = post_daily_stats.follower_reach + EXCLUDED.follower_reach",
+        )
+        .bind(delta.post_id)
+        .bind(day)
+        .bind(delta.impression_count)
+        .bind(delta.follower_impression_count)
+        .bind(delta.reach_count)
+        .bind(delta.follower_reach_count)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update post insights: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit view stats: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_by_post(&self, post_id: Uuid) -> Result<Option<PostViewStats>> {
+        let model: Option<PostViewStatsModel> = sqlx::query_as(
+            "SELECT post_id, impression_count, view_count, replay_count, completed_count, total_watch_ms, completion_percent_sum, reach_count, follower_impression_count, follower_reach_count
+            FROM post_view_stats WHERE post_id = $1",
+        )
+        .bind(post_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find view stats: {}", e)))?;
+
+        Ok(model.map(Self::to_domain))
+    }
+}
@@ -0,0 +1,194 @@
+use crate::domain::entities::{Story, StoryView};
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::StoryRepository;
+use crate::domain::value_objects::Cursor;
+use crate::infrastructure::database::models::{StoryModel, StoryViewModel};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// PostgreSQL implementation of StoryRepository
+pub struct PostgresStoryRepository {
+    pool: PgPool,
+}
+
+impl PostgresStoryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database model to domain entity
+    fn to_domain(model: StoryModel) -> Result<Story> {
+        let media = serde_json::from_value(model.media).map_err(|e| {
+            AppError::SerializationError(format!("Failed to deserialize story media: {}", e))
+        })?;
+
+        Ok(Story {
+            id: model.id,
+            user_id: model.user_id,
+            media,
+            caption: model.caption,
+            created_at: model.created_at,
+            expires_at: model.expires_at,
+        })
+    }
+
+    fn view_to_domain(model: StoryViewModel) -> StoryView {
+        StoryView {
+            story_id: model.story_id,
+            viewer_id: model.viewer_id,
+            viewed_at: model.viewed_at,
+        }
+    }
+}
+
+#[async_trait]
+impl StoryRepository for PostgresStoryRepository {
+    async fn create(&self, story: &Story) -> Result<Story> {
+        let media_json = serde_json::to_value(&story.media).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize story media: {}", e))
+        })?;
+
+        let model: StoryModel = sqlx::query_as(
+            "INSERT INTO stories (id, user_id, media, caption, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, user_id, media, caption, created_at, expires_at",
+        )
+        .bind(story.id)
+        .bind(story.user_id)
+        .bind(media_json)
+        .bind(&story.caption)
+        .bind(story.created_at)
+        .bind(story.expires_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create story: {}", e)))?;
+
+        Self::to_domain(model)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Story>> {
+        let model: Option<StoryModel> = sqlx::query_as(
+            "SELECT id, user_id, media, caption, created_at, expires_at FROM stories WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find story: {}", e)))?;
+
+        model.map(Self::to_domain).transpose()
+    }
+
+    async fn find_feed_stories(&self, viewer_id: Uuid, now: DateTime<Utc>) -> Result<Vec<Story>> {
+        let models: Vec<StoryModel> = sqlx::query_as(
+            "SELECT s.id, s.user_id, s.media, s.caption, s.created_at, s.expires_at
+            FROM stories s
+            WHERE s.expires_at > $2
+              AND (s.user_id = $1 OR EXISTS(
+                  SELECT 1 FROM follows f WHERE f.follower_id = $1 AND f.following_id = s.user_id
+              ))
+              AND NOT EXISTS(
+                  SELECT 1 FROM contacts c
+                  WHERE c.is_blocked = true
+                    AND ((c.user_id = s.user_id AND c.contact_user_id = $1)
+                      OR (c.user_id = $1 AND c.contact_user_id = s.user_id))
+              )
+            ORDER BY s.created_at ASC, s.id ASC",
+        )
+        .bind(viewer_id)
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch story feed: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM stories WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete story: {}", e)))?;
+        Ok(())
+    }
+
+    async fn record_view(&self, view: &StoryView) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO story_views (story_id, viewer_id, viewed_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (story_id, viewer_id) DO NOTHING",
+        )
+        .bind(view.story_id)
+        .bind(view.viewer_id)
+        .bind(view.viewed_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record story view: {}", e)))?;
+        Ok(())
+    }
+
+    async fn find_seen_story_ids(&self, viewer_id: Uuid, story_ids: &[Uuid]) -> Result<Vec<Uuid>> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT story_id FROM story_views WHERE viewer_id = $1 AND story_id = ANY($2)",
+        )
+        .bind(viewer_id)
+        .bind(story_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch seen stories: {}", e)))?;
+
+        Ok(rows.into_iter().map(|(story_id,)| story_id).collect())
+    }
+
+    async fn find_views(
+        &self,
+        story_id: Uuid,
+        after: Option<Cursor>,
+        limit: i64,
+    ) -> Result<Vec<StoryView>> {
+        let models: Vec<StoryViewModel> = sqlx::query_as(
+            "SELECT story_id, viewer_id, viewed_at FROM story_views
+            WHERE story_id = $1
+              AND ($2::timestamptz IS NULL OR (viewed_at, viewer_id) < ($2, $3))
+            ORDER BY viewed_at DESC, viewer_id DESC
+            LIMIT $4",
+        )
+        .bind(story_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch story views: {}", e)))?;
+
+        Ok(models.into_iter().map(Self::view_to_domain).collect())
+    }
+
+    async fn count_views(&self, story_id: Uuid) -> Result<i64> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM story_views WHERE story_id = $1")
+            .bind(story_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to count story views: {}", e)))?;
+
+        Ok(row.0)
+    }
+
+    async fn find_expired(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Story>> {
+        let models: Vec<StoryModel> = sqlx::query_as(
+            "SELECT id, user_id, media, caption, created_at, expires_at FROM stories
+            WHERE expires_at <= $1
+            ORDER BY expires_at ASC
+            LIMIT $2",
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch expired stories: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+}
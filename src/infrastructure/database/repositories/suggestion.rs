@@ -0,0 +1,64 @@
+use crate::domain::errors::{AppError, Result};
+use crate::domain::repositories::SuggestionRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// PostgreSQL implementation of SuggestionRepository
+pub struct PostgresSuggestionRepository {
+    pool: PgPool,
+}
+
+impl PostgresSuggestionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SuggestionRepository for PostgresSuggestionRepository {
+    async fn matched_contact_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>> {
+        // Contacts saved by phone number alone match the account with that number
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT DISTINCT u.id FROM contacts c
+            JOIN users u ON u.id = c.contact_user_id
+                OR (c.contact_user_id IS NULL AND u.phone_number = c.phone_number)
+            WHERE c.user_id = $1 AND c.is_blocked IS NOT TRUE AND u.id <> $1",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to match contacts: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn dismiss(&self, user_id: Uuid, dismissed_user_id: Uuid) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO dismissed_suggestions (user_id, dismissed_user_id) VALUES ($1, $2)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(user_id)
+        .bind(dismissed_user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to dismiss suggestion: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn dismissed_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT dismissed_user_id FROM dismissed_suggestions WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch dismissed suggestions: {}", e))
+        })?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+}
@@ -1,11 +1,12 @@
 use crate::domain::entities::User;
 use crate::domain::errors::{AppError, Result};
 use crate::domain::repositories::UserRepository;
-use crate::domain::value_objects::{Bio, DisplayName, Email, PhoneNumber, Username};
-use crate::infrastructure::database::models::UserModel;
+use crate::domain::value_objects::{Bio, DisplayName, Email, PageStart, PhoneNumber, Username};
+use crate::infrastructure::database::models::{FollowListRow, UserModel};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
+use std::collections::HashSet;
 use uuid::Uuid;
 
 /// PostgreSQL implementation of UserRepository
@@ -34,6 +35,8 @@ impl PostgresUserRepository {
             phone_verified: model.phone_verified,
             follower_count: model.follower_count,
             following_count: model.following_count,
+            suspended_until: model.suspended_until,
+            sensitive_media: model.sensitive_media.parse()?,
             created_at: model.created_at,
             updated_at: model.updated_at,
         })
@@ -78,6 +81,20 @@ impl UserRepository for PostgresUserRepository {
         model.map(Self::to_domain).transpose()
     }
 
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let models: Vec<UserModel> = sqlx::query_as("SELECT * FROM users WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to find users by id: {}", e)))?;
+
+        models.into_iter().map(Self::to_domain).collect()
+    }
+
     async fn find_by_username(&self, username: &str) -> Result<Option<User>> {
         let model: Option<UserModel> = sqlx::query_as("SELECT * FROM users WHERE username = $1")
             .bind(username)
@@ -117,7 +134,8 @@ impl UserRepository for PostgresUserRepository {
         let model: UserModel = sqlx::query_as(
             "UPDATE users 
             SET username = $2, email = $3, phone_number = $4, password_hash = $5, display_name = $6, bio = $7, 
-                avatar_url = $8, is_verified = $9, email_verified = $10, phone_verified = $11, follower_count = $12, following_count = $13, updated_at = $14
+                avatar_url = $8, is_verified = $9, email_verified = $10, phone_verified = $11, follower_count = $12, following_count = $13, updated_at = $14,
+                sensitive_media = $15
             WHERE id = $1
             RETURNING *")
             .bind(user.id)
@@ -134,6 +152,7 @@ impl UserRepository for PostgresUserRepository {
             .bind(user.follower_count)
             .bind(user.following_count)
             .bind(user.updated_at)
+            .bind(user.sensitive_media.to_string())
         .fetch_one(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to update user: {}", e)))?;
@@ -191,40 +210,74 @@ impl UserRepository for PostgresUserRepository {
         models.into_iter().map(Self::to_domain).collect()
     }
 
-    async fn get_followers(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<User>> {
-        let models: Vec<UserModel> = sqlx::query_as(
-            "SELECT u.* FROM users u
+    async fn get_followers(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>> {
+        let after = page.cursor();
+        let rows: Vec<FollowListRow> = sqlx::query_as(
+            "SELECT u.*, f.created_at AS followed_at FROM users u
             INNER JOIN follows f ON u.id = f.follower_id
             WHERE f.following_id = $1
-            ORDER BY f.created_at DESC
-            LIMIT $2 OFFSET $3",
+              AND ($2::timestamptz IS NULL OR (f.created_at, u.id) < ($2, $3))
+            ORDER BY f.created_at DESC, u.id DESC
+            LIMIT $4 OFFSET $5",
         )
         .bind(user_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
         .bind(limit)
-        .bind(offset)
+        .bind(page.offset())
         .fetch_all(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to get followers: {}", e)))?;
 
-        models.into_iter().map(Self::to_domain).collect()
+        rows.into_iter()
+            .map(|row| Ok((Self::to_domain(row.user)?, row.followed_at)))
+            .collect()
     }
 
-    async fn get_following(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<User>> {
-        let models: Vec<UserModel> = sqlx::query_as(
-            "SELECT u.* FROM users u
+    async fn get_following(
+        &self,
+        user_id: Uuid,
+        page: PageStart,
+        limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>> {
+        let after = page.cursor();
+        let rows: Vec<FollowListRow> = sqlx::query_as(
+            "SELECT u.*, f.created_at AS followed_at FROM users u
             INNER JOIN follows f ON u.id = f.following_id
             WHERE f.follower_id = $1
-            ORDER BY f.created_at DESC
-            LIMIT $2 OFFSET $3",
+              AND ($2::timestamptz IS NULL OR (f.created_at, u.id) < ($2, $3))
+            ORDER BY f.created_at DESC, u.id DESC
+            LIMIT $4 OFFSET $5",
         )
         .bind(user_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
         .bind(limit)
-        .bind(offset)
+        .bind(page.offset())
         .fetch_all(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to get following: {}", e)))?;
 
-        models.into_iter().map(Self::to_domain).collect()
+        rows.into_iter()
+            .map(|row| Ok((Self::to_domain(row.user)?, row.followed_at)))
+            .collect()
+    }
+
+    async fn follower_count(&self, user_id: Uuid) -> Result<Option<i32>> {
+        let row: Option<(i32,)> = sqlx::query_as("SELECT follower_count FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to fetch follower count: {}", e))
+            })?;
+
+        Ok(row.map(|r| r.0))
     }
 
     async fn is_following(&self, follower_id: Uuid, following_id: Uuid) -> Result<bool> {
@@ -320,4 +373,105 @@ impl UserRepository for PostgresUserRepository {
 
         Ok(())
     }
+
+    async fn has_blocked(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<bool> {
+        let row: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM contacts WHERE user_id = $1 AND contact_user_id = $2 AND is_blocked = true)",
+        )
+        .bind(blocker_id)
+        .bind(blocked_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to check block status: {}", e)))?;
+
+        Ok(row.0)
+    }
+
+    async fn mute(&self, user_id: Uuid, muted_user_id: Uuid) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO mutes (user_id, muted_user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        )
+        .bind(user_id)
+        .bind(muted_user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to mute user: {}", e)))?;
+        Ok(())
+    }
+
+    async fn unmute(&self, user_id: Uuid, muted_user_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM mutes WHERE user_id = $1 AND muted_user_id = $2")
+            .bind(user_id)
+            .bind(muted_user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to unmute user: {}", e)))?;
+        Ok(())
+    }
+
+    async fn muted_user_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>> {
+        let rows: Vec<(Uuid,)> =
+            sqlx::query_as("SELECT muted_user_id FROM mutes WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to fetch muted users: {}", e))
+                })?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn blocked_user_ids(&self, user_id: Uuid) -> Result<HashSet<Uuid>> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT contact_user_id FROM contacts
+            WHERE user_id = $1 AND is_blocked = true AND contact_user_id IS NOT NULL
+            UNION
+            SELECT user_id FROM contacts WHERE contact_user_id = $1 AND is_blocked = true",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch blocked users: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn blocked_ids(&self, user_id: Uuid, user_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        if user_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT contact_user_id FROM contacts
+            WHERE user_id = $1 AND is_blocked = true AND contact_user_id = ANY($2)
+            UNION
+            SELECT user_id FROM contacts
+            WHERE contact_user_id = $1 AND is_blocked = true AND user_id = ANY($2)",
+        )
+        .bind(user_id)
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch blocked users: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn followed_ids(&self, follower_id: Uuid, user_ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        if user_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT following_id FROM follows WHERE follower_id = $1 AND following_id = ANY($2)",
+        )
+        .bind(follower_id)
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch follows: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
 }
@@ -3,6 +3,7 @@ use crate::domain::entities::{
 };
 use crate::domain::errors::{AppError, Result};
 use crate::domain::repositories::WalletRepository;
+use crate::domain::value_objects::PageStart;
 use crate::infrastructure::database::models::{TransactionModel, WalletModel};
 use async_trait::async_trait;
 use chrono::Utc;
@@ -110,6 +111,20 @@ impl WalletRepository for PostgresWalletRepository {
         model.map(Self::wallet_to_domain).transpose()
     }
 
+    async fn find_wallets_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Wallet>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let models: Vec<WalletModel> = sqlx::query_as("SELECT * FROM wallets WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to find wallets: {}", e)))?;
+
+        models.into_iter().map(Self::wallet_to_domain).collect()
+    }
+
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<Wallet>> {
         let model: Option<WalletModel> = sqlx::query_as("SELECT * FROM wallets WHERE user_id = $1")
             .bind(user_id)
@@ -342,18 +357,22 @@ impl WalletRepository for PostgresWalletRepository {
     async fn get_transaction_history(
         &self,
         wallet_id: Uuid,
+        page: PageStart,
         limit: i64,
-        offset: i64,
     ) -> Result<Vec<Transaction>> {
+        let after = page.cursor();
         let models: Vec<TransactionModel> = sqlx::query_as(
             "SELECT * FROM transactions
-            WHERE sender_wallet_id = $1 OR receiver_wallet_id = $1
-            ORDER BY created_at DESC
-            LIMIT $2 OFFSET $3",
+            WHERE (sender_wallet_id = $1 OR receiver_wallet_id = $1)
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4 OFFSET $5",
         )
         .bind(wallet_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
         .bind(limit)
-        .bind(offset)
+        .bind(page.offset())
         .fetch_all(&self.pool)
         .await
         .map_err(|e| {
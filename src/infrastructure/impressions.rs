@@ -0,0 +1,77 @@
+//! Redis feed impressions
+//!
+//! Each user's impressions are a sorted set of post IDs scored by when the
+//! post was first shown, in milliseconds, capped to the most recent ones so a
+//! heavy scroller's set stays small. The current feed session is a plain key
+//! holding its start time that expires shortly after the user stops paging.
+use crate::domain::errors::Result;
+use crate::domain::repositories::ImpressionRepository;
+use crate::infrastructure::cache::{CacheConfig, CacheKeys, RedisCache};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub struct RedisImpressionRepository {
+    cache: RedisCache,
+}
+
+impl RedisImpressionRepository {
+    pub fn new(cache: RedisCache) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl ImpressionRepository for RedisImpressionRepository {
+    async fn record(&self, user_id: Uuid, post_ids: &[Uuid], at: DateTime<Utc>) -> Result<()> {
+        let score = at.timestamp_millis() as f64;
+        let members: Vec<(f64, String)> = post_ids
+            .iter()
+            .map(|post_id| (score, post_id.to_string()))
+            .collect();
+        self.cache.sorted_set_add_new(
+            &CacheKeys::feed_impressions(user_id),
+            &members,
+            CacheConfig::MAX_IMPRESSIONS,
+            CacheConfig::IMPRESSIONS_TTL,
+        )
+    }
+
+    async fn seen_before(
+        &self,
+        user_id: Uuid,
+        post_ids: &[Uuid],
+        before: DateTime<Utc>,
+    ) -> Result<HashSet<Uuid>> {
+        let members: Vec<String> = post_ids.iter().map(Uuid::to_string).collect();
+        let scores = self
+            .cache
+            .sorted_set_scores(&CacheKeys::feed_impressions(user_id), &members)?;
+        let before = before.timestamp_millis() as f64;
+
+        Ok(post_ids
+            .iter()
+            .zip(scores)
+            .filter(|(_, score)| score.is_some_and(|score| score < before))
+            .map(|(post_id, _)| *post_id)
+            .collect())
+    }
+
+    async fn start_session(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<()> {
+        self.cache.set(
+            &CacheKeys::feed_session(user_id),
+            &at.timestamp_millis(),
+            CacheConfig::FEED_SESSION_TTL,
+        )
+    }
+
+    async fn session_started_at(&self, user_id: Uuid) -> Result<Option<DateTime<Utc>>> {
+        let key = CacheKeys::feed_session(user_id);
+        let Some(millis) = self.cache.get::<i64>(&key)? else {
+            return Ok(None);
+        };
+        self.cache.expire(&key, CacheConfig::FEED_SESSION_TTL)?;
+        Ok(DateTime::<Utc>::from_timestamp_millis(millis))
+    }
+}
@@ -0,0 +1,121 @@
+//! Running the external ffmpeg tools on the async runtime: a temporary copy of
+//! the upload for them to read, and a deadline after which they are killed.
+use super::ProbeError;
+use crate::domain::entities::media_extension;
+use std::path::{Path, PathBuf};
+use std::process::{Output, Stdio};
+use std::time::Duration;
+use tokio::process::Command;
+use uuid::Uuid;
+
+/// A file in the temporary directory, removed when dropped
+pub(super) struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Reserve a uniquely named path, without creating the file
+    pub(super) fn new(prefix: &str, extension: &str) -> Self {
+        Self(
+            std::env::temp_dir()
+                .join(format!("{}-{}", prefix, Uuid::new_v4()))
+                .with_extension(extension),
+        )
+    }
+
+    /// Write a copy of an upload, named with the extension of its media type
+    /// so the tools can tell the container apart
+    pub(super) async fn write_media(
+        prefix: &str,
+        data: &[u8],
+        media_type: &str,
+    ) -> Result<Self, ProbeError> {
+        let extension = media_extension(media_type)
+            .ok_or_else(|| ProbeError::Unsupported(format!("Unsupported media: {}", media_type)))?;
+        let file = Self::new(prefix, extension);
+        tokio::fs::write(&file.0, data).await.map_err(|e| {
+            ProbeError::Invalid(format!("Failed to write media for decoding: {}", e))
+        })?;
+        Ok(file)
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Run a command to completion, collecting its output. The process is killed
+/// if it is still running after `timeout`.
+pub(super) async fn run(command: &mut Command, timeout: Duration) -> Result<Output, ProbeError> {
+    let program = command
+        .as_std()
+        .get_program()
+        .to_string_lossy()
+        .into_owned();
+    let output = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output();
+
+    match tokio::time::timeout(timeout, output).await {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(e)) => Err(ProbeError::Unsupported(format!(
+            "Failed to run {}: {}",
+            program, e
+        ))),
+        Err(_) => Err(ProbeError::Invalid(
+            "Timed out decoding the media".to_string(),
+        )),
+    }
+}
+
+/// Whether a tool can be run at all, checked once at startup
+pub(super) async fn is_available(binary: &Path) -> bool {
+    run(
+        Command::new(binary).arg("-version"),
+        Duration::from_secs(10),
+    )
+    .await
+    .is_ok_and(|output| output.status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_kills_commands_past_the_timeout() {
+        let started = std::time::Instant::now();
+        let result = run(Command::new("sleep").arg("10"), Duration::from_millis(100)).await;
+        assert!(matches!(result, Err(ProbeError::Invalid(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_missing_binary_is_unavailable() {
+        let missing = Path::new("/nonexistent/ffprobe");
+        assert!(!is_available(missing).await);
+        assert!(matches!(
+            run(&mut Command::new(missing), Duration::from_secs(1)).await,
+            Err(ProbeError::Unsupported(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_temp_file_removed_on_drop() {
+        let file = TempFile::write_media("view-test", b"data", "video/mp4")
+            .await
+            .unwrap();
+        let path = file.path().to_path_buf();
+        assert_eq!(path.extension().unwrap(), "mp4");
+        assert!(path.exists());
+        drop(file);
+        assert!(!path.exists());
+    }
+}
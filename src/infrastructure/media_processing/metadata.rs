@@ -0,0 +1,434 @@
+//! Container-level rewriting: removal of metadata such as EXIF (including GPS),
+//! XMP, IPTC and text comments, keeping only the EXIF orientation of JPEGs.
+use super::ImageError;
+
+/// Every PNG file starts with this signature
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// Marker bytes of JPEG segments that carry no entropy-coded data
+const JPEG_STANDALONE_MARKERS: std::ops::RangeInclusive<u8> = 0xd0..=0xd9;
+
+/// EXIF orientation tag
+const ORIENTATION_TAG: u16 = 0x0112;
+
+/// A chunk type and its body
+type Chunk<'a> = ([u8; 4], &'a [u8]);
+
+/// EXIF orientation (1-8) of a JPEG, defaulting to 1 (upright)
+pub fn jpeg_orientation(data: &[u8]) -> u16 {
+    jpeg_segments(data)
+        .ok()
+        .and_then(|segments| {
+            segments.into_iter().find_map(|(marker, body)| {
+                (marker == 0xe1 && body.starts_with(b"Exif\0\0"))
+                    .then(|| exif_orientation(&body[6..]))
+                    .flatten()
+            })
+        })
+        .filter(|orientation| (1..=8).contains(orientation))
+        .unwrap_or(1)
+}
+
+/// Read the orientation tag from IFD0 of a TIFF structure
+fn exif_orientation(tiff: &[u8]) -> Option<u16> {
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let u16_at = |pos: usize| {
+        tiff.get(pos..pos + 2).map(|b| {
+            if little_endian {
+                u16::from_le_bytes([b[0], b[1]])
+            } else {
+                u16::from_be_bytes([b[0], b[1]])
+            }
+        })
+    };
+    let u32_at = |pos: usize| {
+        tiff.get(pos..pos + 4).map(|b| {
+            if little_endian {
+                u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+            } else {
+                u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+            }
+        })
+    };
+
+    let ifd = u32_at(4)? as usize;
+    let entries = u16_at(ifd)? as usize;
+    (0..entries).find_map(|i| {
+        let entry = ifd + 2 + i * 12;
+        (u16_at(entry)? == ORIENTATION_TAG).then(|| u16_at(entry + 8))?
+    })
+}
+
+/// Minimal EXIF APP1 payload holding only an orientation tag
+fn orientation_exif(orientation: u16) -> Vec<u8> {
+    let mut body = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01".to_vec();
+    body.extend_from_slice(&ORIENTATION_TAG.to_be_bytes());
+    // Type SHORT, count 1, value padded to four bytes, then no next IFD
+    body.extend_from_slice(&[0, 3, 0, 0, 0, 1]);
+    body.extend_from_slice(&orientation.to_be_bytes());
+    body.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+    body
+}
+
+/// Remove privacy-sensitive metadata while leaving the image data untouched
+pub fn strip_metadata(data: &[u8], media_type: &str) -> Result<Vec<u8>, ImageError> {
+    match media_type {
+        "image/jpeg" => strip_jpeg(data),
+        "image/png" => strip_png(data),
+        "image/gif" => strip_gif(data),
+        "image/webp" => strip_webp(data),
+        _ => Err(ImageError::Unsupported(media_type.to_string())),
+    }
+}
+
+/// A JPEG header segment as (marker, body)
+type Segment<'a> = (u8, &'a [u8]);
+
+/// Split a JPEG into its header segments, stopping at the first scan
+fn jpeg_segments(data: &[u8]) -> Result<Vec<Segment<'_>>, ImageError> {
+    Ok(jpeg_layout(data)?.0)
+}
+
+/// Header segments of a JPEG and the offset of its first scan (SOS) marker
+fn jpeg_layout(data: &[u8]) -> Result<(Vec<Segment<'_>>, usize), ImageError> {
+    let truncated = || ImageError::Invalid("Truncated JPEG file".to_string());
+    if !data.starts_with(&[0xff, 0xd8]) {
+        return Err(ImageError::Invalid("Not a JPEG file".to_string()));
+    }
+
+    let mut segments = Vec::new();
+    let mut pos = 2;
+    loop {
+        // Markers may be preceded by fill bytes
+        while data.get(pos) == Some(&0xff) && data.get(pos + 1) == Some(&0xff) {
+            pos += 1;
+        }
+        if data.get(pos) != Some(&0xff) {
+            return Err(ImageError::Invalid("Invalid JPEG marker".to_string()));
+        }
+        let marker = *data.get(pos + 1).ok_or_else(truncated)?;
+        if marker == 0xda {
+            return Ok((segments, pos));
+        }
+        if JPEG_STANDALONE_MARKERS.contains(&marker) || marker == 0x01 {
+            pos += 2;
+            continue;
+        }
+        let len = data
+            .get(pos + 2..pos + 4)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+            .filter(|&len| len >= 2)
+            .ok_or_else(truncated)?;
+        let body = data.get(pos + 4..pos + 2 + len).ok_or_else(truncated)?;
+        segments.push((marker, body));
+        pos += 2 + len;
+    }
+}
+
+fn strip_jpeg(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let (segments, scan_start) = jpeg_layout(data)?;
+    let orientation = jpeg_orientation(data);
+
+    let mut out = vec![0xff, 0xd8];
+    let mut orientation_written = orientation == 1;
+    for (marker, body) in segments {
+        // Keep JFIF (APP0), ICC profiles (APP2) and Adobe color info (APP14);
+        // drop EXIF/XMP (APP1), IPTC (APP13), other application data and comments
+        let keep = match marker {
+            0xe0 | 0xe2 | 0xee => true,
+            0xe1 | 0xe3..=0xed | 0xef | 0xfe => false,
+            _ => true,
+        };
+        if !orientation_written && marker != 0xe0 {
+            // Keep the orientation so viewers still display the image upright
+            push_jpeg_segment(&mut out, 0xe1, &orientation_exif(orientation));
+            orientation_written = true;
+        }
+        if keep {
+            push_jpeg_segment(&mut out, marker, body);
+        }
+    }
+    out.extend_from_slice(&data[scan_start..]);
+    Ok(out)
+}
+
+fn push_jpeg_segment(out: &mut Vec<u8>, marker: u8, body: &[u8]) {
+    out.extend_from_slice(&[0xff, marker]);
+    out.extend_from_slice(&((body.len() + 2) as u16).to_be_bytes());
+    out.extend_from_slice(body);
+}
+
+/// Split a PNG file into its chunks, stopping at IEND
+fn png_chunks(data: &[u8]) -> Result<Vec<Chunk<'_>>, ImageError> {
+    if !data.starts_with(&PNG_SIGNATURE) {
+        return Err(ImageError::Invalid("Not a PNG file".to_string()));
+    }
+
+    let mut chunks = Vec::new();
+    let mut pos = PNG_SIGNATURE.len();
+    while pos + 8 <= data.len() {
+        let len =
+            u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        let kind = [data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]];
+        let body = data
+            .get(pos + 8..pos + 8 + len)
+            .ok_or_else(|| ImageError::Invalid("Truncated PNG chunk".to_string()))?;
+        chunks.push((kind, body));
+        // Skip the chunk and its CRC
+        pos += 12 + len;
+        if &kind == b"IEND" {
+            break;
+        }
+    }
+    Ok(chunks)
+}
+
+fn strip_png(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    // Chunk bodies borrow from `data`, so each chunk (with its CRC) can be copied as-is
+    let mut out = data[..8].to_vec();
+    for (kind, body) in png_chunks(data)? {
+        if matches!(&kind, b"eXIf" | b"tEXt" | b"zTXt" | b"iTXt" | b"tIME") {
+            continue;
+        }
+        let start = body.as_ptr() as usize - data.as_ptr() as usize - 8;
+        let end = (start + 12 + body.len()).min(data.len());
+        out.extend_from_slice(&data[start..end]);
+    }
+    Ok(out)
+}
+
+/// Split a RIFF/WebP file into its chunks
+fn riff_chunks(data: &[u8]) -> Result<Vec<Chunk<'_>>, ImageError> {
+    if data.len() < 12 || &data[..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return Err(ImageError::Invalid("Not a WebP file".to_string()));
+    }
+    let mut chunks = Vec::new();
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let kind = [data[pos], data[pos + 1], data[pos + 2], data[pos + 3]];
+        let len = u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]])
+            as usize;
+        let body = data
+            .get(pos + 8..pos + 8 + len)
+            .ok_or_else(|| ImageError::Invalid("Truncated WebP chunk".to_string()))?;
+        chunks.push((kind, body));
+        // Chunks are padded to an even length
+        pos += 8 + len + (len & 1);
+    }
+    Ok(chunks)
+}
+
+fn strip_webp(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let mut body = b"WEBP".to_vec();
+    for (kind, chunk) in riff_chunks(data)? {
+        if &kind == b"EXIF" || &kind == b"XMP " {
+            continue;
+        }
+        let mut chunk = chunk.to_vec();
+        if &kind == b"VP8X" && !chunk.is_empty() {
+            // Clear the EXIF and XMP presence flags
+            chunk[0] &= !(0x08 | 0x04);
+        }
+        body.extend_from_slice(&kind);
+        body.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+        body.extend_from_slice(&chunk);
+        if chunk.len() & 1 == 1 {
+            body.push(0);
+        }
+    }
+
+    let mut out = b"RIFF".to_vec();
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend_from_slice(&body);
+    Ok(out)
+}
+
+fn strip_gif(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let truncated = || ImageError::Invalid("Truncated GIF file".to_string());
+    if data.len() < 13 || !(data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")) {
+        return Err(ImageError::Invalid("Not a GIF file".to_string()));
+    }
+
+    let color_table = |flags: u8| {
+        if flags & 0x80 != 0 {
+            3 * (1usize << ((flags & 0x07) + 1))
+        } else {
+            0
+        }
+    };
+    // Length of a run of data sub-blocks, including the terminator
+    let sub_blocks = |mut pos: usize| -> Result<usize, ImageError> {
+        let start = pos;
+        loop {
+            let len = *data.get(pos).ok_or_else(truncated)? as usize;
+            pos += 1 + len;
+            if len == 0 {
+                return Ok(pos - start);
+            }
+        }
+    };
+
+    let header_len = 13 + color_table(data[10]);
+    let mut out = data.get(..header_len).ok_or_else(truncated)?.to_vec();
+    let mut pos = header_len;
+    loop {
+        // Some encoders omit the trailer, which decoders tolerate
+        match data.get(pos).copied().unwrap_or(0x3b) {
+            0x3b => {
+                out.push(0x3b);
+                return Ok(out);
+            }
+            0x21 => {
+                let label = *data.get(pos + 1).ok_or_else(truncated)?;
+                let len = 2 + sub_blocks(pos + 2)?;
+                // Drop comments and application extensions other than looping control
+                let is_loop =
+                    label == 0xff && data.get(pos + 3..pos + 14) == Some(b"NETSCAPE2.0".as_slice());
+                if label != 0xfe && (label != 0xff || is_loop) {
+                    out.extend_from_slice(data.get(pos..pos + len).ok_or_else(truncated)?);
+                }
+                pos += len;
+            }
+            0x2c => {
+                let flags = *data.get(pos + 9).ok_or_else(truncated)?;
+                // Descriptor, local color table and LZW minimum code size
+                let header = 10 + color_table(flags) + 1;
+                let len = header + sub_blocks(pos + header)?;
+                out.extend_from_slice(data.get(pos..pos + len).ok_or_else(truncated)?);
+                pos += len;
+            }
+            _ => return Err(ImageError::Invalid("Invalid GIF block".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::build_png;
+    use super::*;
+
+    /// Smallest useful JPEG header: SOI, APP0, an EXIF APP1 with GPS-like data, a comment,
+    /// a frame header for a 3x2 image, then the scan
+    fn jpeg_with_exif(orientation: u16) -> Vec<u8> {
+        let mut exif = b"Exif\0\0II\x2a\0\x08\0\0\0\x02\0".to_vec();
+        // Orientation entry
+        exif.extend_from_slice(&ORIENTATION_TAG.to_le_bytes());
+        exif.extend_from_slice(&[3, 0, 1, 0, 0, 0]);
+        exif.extend_from_slice(&orientation.to_le_bytes());
+        exif.extend_from_slice(&[0, 0]);
+        // GPS IFD pointer entry
+        exif.extend_from_slice(&[0x25, 0x88, 4, 0, 1, 0, 0, 0, 0x30, 0, 0, 0]);
+        exif.extend_from_slice(&[0, 0, 0, 0]);
+
+        let mut data = vec![0xff, 0xd8];
+        push_jpeg_segment(&mut data, 0xe0, b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0");
+        push_jpeg_segment(&mut data, 0xe1, &exif);
+        push_jpeg_segment(&mut data, 0xfe, b"secret comment");
+        push_jpeg_segment(&mut data, 0xc0, &[8, 0, 2, 0, 3, 1, 1, 0x11, 0]);
+        data.extend_from_slice(&[0xff, 0xda, 0, 8, 1, 1, 0, 0, 63, 0, 0x12, 0x34, 0xff, 0xd9]);
+        data
+    }
+
+    #[test]
+    fn test_jpeg_orientation() {
+        assert_eq!(jpeg_orientation(&jpeg_with_exif(6)), 6);
+        assert_eq!(jpeg_orientation(&jpeg_with_exif(1)), 1);
+    }
+
+    #[test]
+    fn test_strip_jpeg_keeps_only_orientation() {
+        let data = jpeg_with_exif(6);
+        let stripped = strip_metadata(&data, "image/jpeg").unwrap();
+
+        let segments = jpeg_segments(&stripped).unwrap();
+        let markers: Vec<u8> = segments.iter().map(|(marker, _)| *marker).collect();
+        assert_eq!(markers, vec![0xe0, 0xe1, 0xc0]);
+        assert_eq!(segments[1].1, orientation_exif(6).as_slice());
+        assert_eq!(jpeg_orientation(&stripped), 6);
+        assert!(!stripped.windows(6).any(|w| w == b"secret"));
+        assert!(stripped.ends_with(&[0x12, 0x34, 0xff, 0xd9]));
+
+        let upright = strip_metadata(&jpeg_with_exif(1), "image/jpeg").unwrap();
+        let markers: Vec<u8> = jpeg_segments(&upright)
+            .unwrap()
+            .iter()
+            .map(|(marker, _)| *marker)
+            .collect();
+        assert_eq!(markers, vec![0xe0, 0xc0]);
+    }
+
+    #[test]
+    fn test_strip_png_text_chunks() {
+        let data = build_png(
+            1,
+            1,
+            png::ColorType::Grayscale,
+            &[("GPS", "somewhere")],
+            &[(b"eXIf", b"MM\0\x2a"), (b"gAMA", &[0, 0, 0xb1, 0x8f])],
+            &[128],
+        );
+        let stripped = strip_metadata(&data, "image/png").unwrap();
+        let kinds: Vec<[u8; 4]> = png_chunks(&stripped)
+            .unwrap()
+            .iter()
+            .map(|(kind, _)| *kind)
+            .collect();
+        assert_eq!(kinds, vec![*b"IHDR", *b"gAMA", *b"IDAT", *b"IEND"]);
+        let decoded = image::load_from_memory(&stripped).unwrap().to_luma8();
+        assert_eq!(decoded.dimensions(), (1, 1));
+        assert_eq!(decoded.get_pixel(0, 0).0, [128]);
+    }
+
+    #[test]
+    fn test_strip_webp_metadata_chunks() {
+        let mut vp8x = vec![0x08 | 0x04, 0, 0, 0];
+        vp8x.extend_from_slice(&[99, 0, 0, 49, 0, 0]);
+        let mut body = b"WEBP".to_vec();
+        for (kind, chunk) in [
+            (b"VP8X", vp8x.as_slice()),
+            (b"EXIF", b"gps".as_slice()),
+            (b"VP8L", &[0x2f, 0, 0, 0, 0]),
+            (b"XMP ", b"<x/>"),
+        ] {
+            body.extend_from_slice(kind);
+            body.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+            body.extend_from_slice(chunk);
+            if chunk.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+        let mut data = b"RIFF".to_vec();
+        data.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        data.extend_from_slice(&body);
+
+        let stripped = strip_metadata(&data, "image/webp").unwrap();
+        let chunks = riff_chunks(&stripped).unwrap();
+        let kinds: Vec<[u8; 4]> = chunks.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, vec![*b"VP8X", *b"VP8L"]);
+        assert_eq!(chunks[0].1[0], 0);
+        assert_eq!(
+            u32::from_le_bytes([stripped[4], stripped[5], stripped[6], stripped[7]]) as usize,
+            stripped.len() - 8
+        );
+    }
+
+    #[test]
+    fn test_strip_gif_comments() {
+        let mut data = b"GIF89a\x02\0\x01\0\x80\0\0".to_vec();
+        data.extend_from_slice(&[0, 0, 0, 255, 255, 255]);
+        // Looping extension, comment, image, trailer
+        data.extend_from_slice(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\0\0\0");
+        data.extend_from_slice(b"\x21\xfe\x05hello\0");
+        data.extend_from_slice(&[0x2c, 0, 0, 0, 0, 2, 0, 1, 0, 0, 2, 2, 0x44, 0x01, 0]);
+        data.push(0x3b);
+
+        let stripped = strip_metadata(&data, "image/gif").unwrap();
+        assert!(!stripped.windows(5).any(|w| w == b"hello"));
+        assert!(stripped.windows(11).any(|w| w == b"NETSCAPE2.0"));
+        assert_eq!(stripped.len(), data.len() - 9);
+    }
+}
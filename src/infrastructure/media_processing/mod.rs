@@ -0,0 +1,421 @@
+//! Processing for uploaded media: image type sniffing, metadata stripping,
+//! resized renditions and blurhash placeholders, and probing of video and audio
+//! containers for their real duration, dimensions and codecs.
+//!
+//! Images are decoded with the `image` crate under fixed size and allocation
+//! limits, so oversized or malicious uploads fail instead of exhausting memory.
+//! Image processing is CPU-bound and synchronous; callers on the async runtime
+//! should run it with `tokio::task::spawn_blocking`. Video and audio are read by
+//! the external ffprobe and ffmpeg binaries, run as async child processes.
+mod command;
+mod metadata;
+mod poster;
+mod probe;
+
+pub use poster::{FfmpegFrameExtractor, PosterFrameExtractor};
+pub use probe::{parse_ffprobe_output, FfprobeMediaProber, MediaProber};
+
+use crate::domain::entities::RenditionKind;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, RgbImage};
+use std::io::Cursor;
+use thiserror::Error;
+
+/// Largest width or height that will be decoded
+pub const MAX_DECODE_DIMENSION: u32 = 4096;
+
+/// Most memory a single image decode may allocate
+const MAX_DECODE_ALLOC: u64 = 256 * 1024 * 1024;
+
+/// Longest side of the thumbnail rendition
+pub const THUMBNAIL_MAX_DIMENSION: u32 = 320;
+
+/// Longest side of the rendition shown in feeds
+pub const FEED_MAX_DIMENSION: u32 = 1080;
+
+/// JPEG quality used for every JPEG rendition
+pub const RENDITION_JPEG_QUALITY: u8 = 82;
+
+/// WebP quality used for every WebP rendition
+pub const RENDITION_WEBP_QUALITY: f32 = 80.0;
+
+/// Blurhash components along the x and y axes
+const BLURHASH_COMPONENTS: (u32, u32) = (4, 3);
+
+/// The blurhash only captures coarse color, so it is computed from a tiny copy
+const BLURHASH_SOURCE_DIMENSION: u32 = 32;
+
+#[derive(Error, Debug)]
+pub enum ImageError {
+    #[error("Invalid image: {0}")]
+    Invalid(String),
+
+    #[error("Unsupported image encoding: {0}")]
+    Unsupported(String),
+}
+
+impl From<image::ImageError> for ImageError {
+    fn from(e: image::ImageError) -> Self {
+        match e {
+            image::ImageError::Limits(_) => ImageError::Invalid(format!(
+                "Image is larger than {0}x{0} pixels",
+                MAX_DECODE_DIMENSION
+            )),
+            image::ImageError::Unsupported(e) => ImageError::Unsupported(e.to_string()),
+            e => ImageError::Invalid(e.to_string()),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ProbeError {
+    #[error("Invalid media file: {0}")]
+    Invalid(String),
+
+    #[error("Unsupported media encoding: {0}")]
+    Unsupported(String),
+}
+
+/// A resized copy of an image, encoded and ready to store
+#[derive(Debug, Clone)]
+pub struct EncodedRendition {
+    pub kind: RenditionKind,
+    pub data: Vec<u8>,
+    pub media_type: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Result of processing an uploaded image
+#[derive(Debug, Clone)]
+pub struct ProcessedImage {
+    /// Media type detected from the file contents
+    pub media_type: &'static str,
+    /// The original file with privacy-sensitive metadata removed
+    pub original: Vec<u8>,
+    /// Dimensions as displayed, after applying the EXIF orientation
+    pub width: u32,
+    pub height: u32,
+    /// A JPEG and a WebP copy at thumbnail, feed and full size
+    pub renditions: Vec<EncodedRendition>,
+    pub blurhash: Option<String>,
+}
+
+/// What a video or audio container says about its contents
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbedMedia {
+    /// Media type detected from the file contents
+    pub media_type: &'static str,
+    pub duration_ms: u64,
+    /// Display size of the video track, after applying any rotation
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Codec of the first video track; None for audio files
+    pub video_codec: Option<&'static str>,
+    pub audio_codec: Option<&'static str>,
+}
+
+/// Detect an image type from its leading magic bytes
+pub fn sniff_image_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some("image/jpeg")
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Build the renditions shown in place of a video from one of its frames.
+/// The full-size frame becomes the poster rendition.
+pub fn process_poster_frame(frame: &[u8]) -> Result<ProcessedImage, ImageError> {
+    let mut processed = process_image(frame)?;
+    for rendition in &mut processed.renditions {
+        if rendition.kind == RenditionKind::Full {
+            rendition.kind = RenditionKind::Poster;
+        }
+    }
+    Ok(processed)
+}
+
+/// Strip metadata from an uploaded image and build its renditions.
+/// Images are decoded (the first frame of animations), turned upright and
+/// encoded as JPEG and WebP at thumbnail, feed and full size.
+pub fn process_image(data: &[u8]) -> Result<ProcessedImage, ImageError> {
+    let media_type = sniff_image_type(data)
+        .ok_or_else(|| ImageError::Invalid("Unrecognized image format".to_string()))?;
+    let original = metadata::strip_metadata(data, media_type)?;
+    let image = decode(data, media_type)?;
+
+    let thumbnail = resize_to_fit(&image, THUMBNAIL_MAX_DIMENSION);
+    let tiny = resize_to_fit(&thumbnail, BLURHASH_SOURCE_DIMENSION).to_rgba8();
+    let blurhash = blurhash::encode(
+        BLURHASH_COMPONENTS.0,
+        BLURHASH_COMPONENTS.1,
+        tiny.width(),
+        tiny.height(),
+        tiny.as_raw(),
+    )
+    .ok();
+
+    let feed = resize_to_fit(&image, FEED_MAX_DIMENSION);
+    let mut renditions = Vec::with_capacity(6);
+    for (kind, rendition) in [
+        (RenditionKind::Thumbnail, &thumbnail),
+        (RenditionKind::Feed, &feed),
+        (RenditionKind::Full, &image),
+    ] {
+        renditions.push(EncodedRendition {
+            kind,
+            data: encode_jpeg(rendition)?,
+            media_type: "image/jpeg",
+            width: rendition.width(),
+            height: rendition.height(),
+        });
+        renditions.push(EncodedRendition {
+            kind,
+            data: encode_webp(rendition)?,
+            media_type: "image/webp",
+            width: rendition.width(),
+            height: rendition.height(),
+        });
+    }
+
+    Ok(ProcessedImage {
+        media_type,
+        original,
+        width: image.width(),
+        height: image.height(),
+        renditions,
+        blurhash,
+    })
+}
+
+/// Decode an image within the size and allocation limits and turn it upright
+/// according to its EXIF orientation
+fn decode(data: &[u8], media_type: &str) -> Result<DynamicImage, ImageError> {
+    let format = ImageFormat::from_mime_type(media_type)
+        .ok_or_else(|| ImageError::Unsupported(media_type.to_string()))?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DECODE_DIMENSION);
+    limits.max_image_height = Some(MAX_DECODE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    reader.limits(limits);
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Scale an image down so neither side exceeds `max_dimension`, keeping its
+/// aspect ratio. Smaller images are left as they are.
+fn resize_to_fit(image: &DynamicImage, max_dimension: u32) -> DynamicImage {
+    if image.width() <= max_dimension && image.height() <= max_dimension {
+        return image.clone();
+    }
+    image.resize(max_dimension, max_dimension, FilterType::Lanczos3)
+}
+
+/// JPEG has no transparency, so transparent pixels are composited onto white
+fn flatten(image: &DynamicImage) -> RgbImage {
+    if !image.color().has_alpha() {
+        return image.to_rgb8();
+    }
+    let rgba = image.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |c: u8| ((c as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+fn encode_jpeg(image: &DynamicImage) -> Result<Vec<u8>, ImageError> {
+    let mut data = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut data, RENDITION_JPEG_QUALITY)
+        .encode_image(&flatten(image))?;
+    Ok(data)
+}
+
+fn encode_webp(image: &DynamicImage) -> Result<Vec<u8>, ImageError> {
+    let encoded = if image.color().has_alpha() {
+        let rgba = image.to_rgba8();
+        webp::Encoder::from_rgba(rgba.as_raw(), rgba.width(), rgba.height())
+            .encode(RENDITION_WEBP_QUALITY)
+            .to_vec()
+    } else {
+        let rgb = image.to_rgb8();
+        webp::Encoder::from_rgb(rgb.as_raw(), rgb.width(), rgb.height())
+            .encode(RENDITION_WEBP_QUALITY)
+            .to_vec()
+    };
+    Ok(encoded)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    pub(crate) use super::probe::tests::{mp3_report, mp4_report, webm_report};
+    use super::*;
+
+    /// 8-bit PNG with the given text entries and extra chunks ahead of its pixels
+    pub(crate) fn build_png(
+        width: u32,
+        height: u32,
+        color: png::ColorType,
+        text: &[(&str, &str)],
+        chunks: &[(&[u8; 4], &[u8])],
+        pixels: &[u8],
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, width, height);
+        encoder.set_color(color);
+        encoder.set_depth(png::BitDepth::Eight);
+        for (keyword, value) in text {
+            encoder
+                .add_text_chunk(keyword.to_string(), value.to_string())
+                .unwrap();
+        }
+        let mut writer = encoder.write_header().unwrap();
+        for (kind, body) in chunks {
+            writer
+                .write_chunk(png::chunk::ChunkType(**kind), body)
+                .unwrap();
+        }
+        writer.write_image_data(pixels).unwrap();
+        writer.finish().unwrap();
+        data
+    }
+
+    /// Valid RGB PNG of the given size with a horizontal gradient
+    pub(crate) fn sample_png(width: u32, height: u32) -> Vec<u8> {
+        let row: Vec<u8> = (0..width)
+            .flat_map(|x| [(x * 255 / width) as u8, 64, 192])
+            .collect();
+        build_png(
+            width,
+            height,
+            png::ColorType::Rgb,
+            &[("Comment", "taken at home")],
+            &[],
+            &row.repeat(height as usize),
+        )
+    }
+
+    #[test]
+    fn test_sniff_image_type() {
+        assert_eq!(sniff_image_type(&sample_png(1, 1)), Some("image/png"));
+        assert_eq!(
+            sniff_image_type(&[0xff, 0xd8, 0xff, 0xe0]),
+            Some("image/jpeg")
+        );
+        assert_eq!(sniff_image_type(b"GIF89a\x01\0"), Some("image/gif"));
+        assert_eq!(
+            sniff_image_type(b"RIFF\x04\0\0\0WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(sniff_image_type(b"not an image"), None);
+    }
+
+    #[test]
+    fn test_process_png_builds_renditions() {
+        let processed = process_image(&sample_png(1600, 400)).unwrap();
+        assert_eq!(processed.media_type, "image/png");
+        assert_eq!((processed.width, processed.height), (1600, 400));
+        assert!(!processed.original.windows(4).any(|w| w == b"tEXt"));
+
+        let sizes: Vec<(RenditionKind, u32, u32)> = processed
+            .renditions
+            .iter()
+            .map(|r| (r.kind, r.width, r.height))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                (RenditionKind::Thumbnail, 320, 80),
+                (RenditionKind::Thumbnail, 320, 80),
+                (RenditionKind::Feed, 1080, 270),
+                (RenditionKind::Feed, 1080, 270),
+                (RenditionKind::Full, 1600, 400),
+                (RenditionKind::Full, 1600, 400),
+            ]
+        );
+        for rendition in &processed.renditions {
+            assert_eq!(
+                sniff_image_type(&rendition.data),
+                Some(rendition.media_type)
+            );
+            let decoded = image::load_from_memory(&rendition.data).unwrap();
+            assert_eq!(
+                (decoded.width(), decoded.height()),
+                (rendition.width, rendition.height)
+            );
+        }
+        assert_eq!(processed.blurhash.as_ref().map(|h| h.len()), Some(28));
+    }
+
+    #[test]
+    fn test_process_rejects_corrupt_images() {
+        assert!(matches!(
+            process_image(b"plain text"),
+            Err(ImageError::Invalid(_))
+        ));
+        let png = sample_png(8, 8);
+        assert!(process_image(&png[..png.len() - 30]).is_err());
+    }
+
+    #[test]
+    fn test_process_rejects_oversized_images() {
+        let row = vec![0u8; 3 * (MAX_DECODE_DIMENSION as usize + 1)];
+        let png = build_png(
+            MAX_DECODE_DIMENSION + 1,
+            1,
+            png::ColorType::Rgb,
+            &[],
+            &[],
+            &row,
+        );
+        assert!(matches!(process_image(&png), Err(ImageError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_process_poster_frame() {
+        let processed = process_poster_frame(&sample_png(640, 360)).unwrap();
+        let kinds: Vec<RenditionKind> = processed.renditions.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RenditionKind::Thumbnail,
+                RenditionKind::Thumbnail,
+                RenditionKind::Feed,
+                RenditionKind::Feed,
+                RenditionKind::Poster,
+                RenditionKind::Poster
+            ]
+        );
+        assert!(processed.blurhash.is_some());
+    }
+
+    #[test]
+    fn test_process_gif_builds_renditions() {
+        let mut gif = Vec::new();
+        image::codecs::gif::GifEncoder::new(&mut gif)
+            .encode(&[255; 3 * 2 * 4], 3, 2, image::ExtendedColorType::Rgba8)
+            .unwrap();
+        // Put a comment ahead of the trailer
+        gif.pop();
+        gif.extend_from_slice(b"\x21\xfe\x03abc\0\x3b");
+        let processed = process_image(&gif).unwrap();
+        assert_eq!(processed.media_type, "image/gif");
+        assert_eq!((processed.width, processed.height), (3, 2));
+        assert_eq!(processed.original.len(), gif.len() - 7);
+        let media_types: Vec<&str> = processed.renditions.iter().map(|r| r.media_type).collect();
+        assert_eq!(media_types, ["image/jpeg", "image/webp"].repeat(3));
+        assert!(processed.blurhash.is_some());
+    }
+}
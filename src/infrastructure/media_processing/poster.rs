@@ -0,0 +1,94 @@
+//! Poster frames for uploaded videos. Decoding video is left to an external
+//! ffmpeg binary; the frame it writes is turned into renditions like any image.
+use super::command::{self, TempFile};
+use super::ProbeError;
+use async_trait::async_trait;
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::{info, warn};
+
+/// How long ffmpeg gets to decode a single frame
+const FFMPEG_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Decodes a single frame of a video
+#[async_trait]
+pub trait PosterFrameExtractor: Send + Sync {
+    /// Decode the frame shown `at_ms` milliseconds in as a PNG
+    async fn extract_frame(
+        &self,
+        video: &[u8],
+        media_type: &str,
+        at_ms: u64,
+    ) -> Result<Vec<u8>, ProbeError>;
+}
+
+/// Extracts frames by running ffmpeg on a temporary copy of the video
+pub struct FfmpegFrameExtractor {
+    binary: PathBuf,
+}
+
+impl FfmpegFrameExtractor {
+    pub fn new(binary: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: binary.into(),
+        }
+    }
+
+    /// Use the ffmpeg binary named by FFMPEG_PATH, or `ffmpeg` on the PATH.
+    /// Returns None when it cannot be run, in which case videos get no poster.
+    pub async fn from_env() -> Option<Self> {
+        let binary = env::var("FFMPEG_PATH")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| "ffmpeg".to_string());
+
+        if command::is_available(binary.as_ref()).await {
+            info!("Using {} for video poster frames", binary);
+            Some(Self::new(binary))
+        } else {
+            warn!(
+                "{} is not available, videos will not get poster frames",
+                binary
+            );
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl PosterFrameExtractor for FfmpegFrameExtractor {
+    async fn extract_frame(
+        &self,
+        video: &[u8],
+        media_type: &str,
+        at_ms: u64,
+    ) -> Result<Vec<u8>, ProbeError> {
+        let input = TempFile::write_media("view-poster", video, media_type).await?;
+        let output = TempFile::new("view-poster", "png");
+
+        // Seeking before the input is fast, and ffmpeg applies any rotation itself
+        let status = command::run(
+            Command::new(&self.binary)
+                .args(["-v", "error", "-nostdin", "-y", "-ss"])
+                .arg(format!("{}.{:03}", at_ms / 1000, at_ms % 1000))
+                .arg("-i")
+                .arg(input.path())
+                .args(["-frames:v", "1", "-pix_fmt", "rgb24", "-c:v", "png"])
+                .arg(output.path()),
+            FFMPEG_TIMEOUT,
+        )
+        .await?
+        .status;
+
+        if !status.success() {
+            return Err(ProbeError::Invalid(
+                "Video frames could not be decoded".to_string(),
+            ));
+        }
+        tokio::fs::read(output.path())
+            .await
+            .map_err(|_| ProbeError::Invalid("Video frames could not be decoded".to_string()))
+    }
+}
@@ -0,0 +1,432 @@
+//! Video and audio probing. Containers are read by an external ffprobe binary,
+//! whose JSON report is mapped to the duration, dimensions and codecs we store.
+use super::command::{self, TempFile};
+use super::{ProbeError, ProbedMedia};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::{info, warn};
+
+/// How long ffprobe gets to read a single file
+const FFPROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Reads what a video or audio container says about its contents
+#[async_trait]
+pub trait MediaProber: Send + Sync {
+    async fn probe(&self, data: &[u8], media_type: &str) -> Result<ProbedMedia, ProbeError>;
+}
+
+/// Probes media by running ffprobe on a temporary copy of the file
+pub struct FfprobeMediaProber {
+    binary: PathBuf,
+}
+
+impl FfprobeMediaProber {
+    pub fn new(binary: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: binary.into(),
+        }
+    }
+
+    /// Use the ffprobe binary named by FFPROBE_PATH, or `ffprobe` on the PATH.
+    /// Returns None when it cannot be run, in which case video and audio uploads
+    /// are refused.
+    pub async fn from_env() -> Option<Self> {
+        let binary = env::var("FFPROBE_PATH")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| "ffprobe".to_string());
+
+        if command::is_available(binary.as_ref()).await {
+            info!("Using {} to probe video and audio uploads", binary);
+            Some(Self::new(binary))
+        } else {
+            warn!(
+                "{} is not available, video and audio uploads are disabled",
+                binary
+            );
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl MediaProber for FfprobeMediaProber {
+    async fn probe(&self, data: &[u8], media_type: &str) -> Result<ProbedMedia, ProbeError> {
+        let input = TempFile::write_media("view-probe", data, media_type).await?;
+        let output = command::run(
+            Command::new(&self.binary)
+                .args([
+                    "-v",
+                    "error",
+                    "-print_format",
+                    "json",
+                    "-show_format",
+                    "-show_streams",
+                ])
+                .arg(input.path()),
+            FFPROBE_TIMEOUT,
+        )
+        .await?;
+
+        if !output.status.success() {
+            return Err(ProbeError::Invalid(
+                "Unrecognized video or audio format".to_string(),
+            ));
+        }
+        parse_ffprobe_output(&output.stdout)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeOutput {
+    #[serde(default)]
+    streams: Vec<FfprobeStream>,
+    format: Option<FfprobeFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeStream {
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    #[serde(default)]
+    disposition: HashMap<String, i64>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+    #[serde(default)]
+    side_data_list: Vec<FfprobeSideData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeSideData {
+    rotation: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeFormat {
+    format_name: String,
+    duration: Option<String>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+}
+
+impl FfprobeStream {
+    /// Degrees the video is rotated by for display, from either the display
+    /// matrix side data or the older `rotate` tag
+    fn rotation(&self) -> i64 {
+        self.side_data_list
+            .iter()
+            .find_map(|side_data| side_data.rotation)
+            .map(|rotation| rotation.round() as i64)
+            .or_else(|| self.tags.get("rotate").and_then(|r| r.parse().ok()))
+            .unwrap_or(0)
+    }
+}
+
+/// Map ffprobe's `-print_format json -show_format -show_streams` report to
+/// the media type, duration, display size and codecs of the file.
+/// Videos must have a video track and audio files an audio track, in a codec
+/// clients can play.
+pub fn parse_ffprobe_output(json: &[u8]) -> Result<ProbedMedia, ProbeError> {
+    let report: FfprobeOutput = serde_json::from_slice(json)
+        .map_err(|e| ProbeError::Invalid(format!("Unreadable probe output: {}", e)))?;
+    let format = report
+        .format
+        .ok_or_else(|| ProbeError::Invalid("Unrecognized video or audio format".to_string()))?;
+
+    let media_type = container_media_type(&format)?;
+    let mut probed = ProbedMedia {
+        media_type,
+        duration_ms: format
+            .duration
+            .as_deref()
+            .and_then(|duration| duration.parse::<f64>().ok())
+            .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+            .map_or(0, |seconds| (seconds * 1000.0).round() as u64),
+        width: None,
+        height: None,
+        video_codec: None,
+        audio_codec: None,
+    };
+
+    for stream in &report.streams {
+        let codec = stream.codec_name.as_deref().unwrap_or_default();
+        match stream.codec_type.as_deref() {
+            // Cover art in audio files is reported as a one-frame video stream
+            Some("video") if stream.disposition.get("attached_pic") == Some(&1) => {}
+            Some("video") if probed.video_codec.is_none() => {
+                probed.video_codec = Some(video_codec(codec)?);
+                let (width, height) = (stream.width, stream.height);
+                if stream.rotation().rem_euclid(180) == 90 {
+                    probed.width = height;
+                    probed.height = width;
+                } else {
+                    probed.width = width;
+                    probed.height = height;
+                }
+            }
+            Some("audio") if probed.audio_codec.is_none() => {
+                probed.audio_codec = Some(audio_codec(codec)?);
+            }
+            _ => {}
+        }
+    }
+
+    if probed.media_type.starts_with("video/") {
+        if probed.video_codec.is_none() {
+            return Err(ProbeError::Invalid("Video has no video track".to_string()));
+        }
+        if !matches!((probed.width, probed.height), (Some(w), Some(h)) if w > 0 && h > 0) {
+            return Err(ProbeError::Invalid(
+                "Could not read video dimensions".to_string(),
+            ));
+        }
+    } else if probed.audio_codec.is_none() {
+        return Err(ProbeError::Invalid("Audio has no audio track".to_string()));
+    }
+    if probed.duration_ms == 0 {
+        return Err(ProbeError::Invalid(
+            "Could not read media duration".to_string(),
+        ));
+    }
+
+    Ok(probed)
+}
+
+fn container_media_type(format: &FfprobeFormat) -> Result<&'static str, ProbeError> {
+    let names: Vec<&str> = format.format_name.split(',').collect();
+    if names.contains(&"mov") || names.contains(&"mp4") {
+        // MP4 and QuickTime share a demuxer; QuickTime files declare the "qt  " brand
+        let brand = format.tags.get("major_brand").map(|brand| brand.trim());
+        Ok(if brand == Some("qt") {
+            "video/quicktime"
+        } else {
+            "video/mp4"
+        })
+    } else if names.contains(&"webm") {
+        Ok("video/webm")
+    } else if names.contains(&"mp3") {
+        Ok("audio/mpeg")
+    } else if names.contains(&"wav") {
+        Ok("audio/wav")
+    } else if names.contains(&"ogg") {
+        Ok("audio/ogg")
+    } else {
+        Err(ProbeError::Unsupported(format!(
+            "Unsupported container: {}",
+            format.format_name
+        )))
+    }
+}
+
+fn video_codec(codec: &str) -> Result<&'static str, ProbeError> {
+    match codec {
+        "h264" => Ok("h264"),
+        "hevc" => Ok("hevc"),
+        "av1" => Ok("av1"),
+        "vp8" => Ok("vp8"),
+        "vp9" => Ok("vp9"),
+        "mpeg4" => Ok("mpeg4"),
+        _ => Err(unsupported_codec("video", codec)),
+    }
+}
+
+fn audio_codec(codec: &str) -> Result<&'static str, ProbeError> {
+    match codec {
+        "aac" => Ok("aac"),
+        "opus" => Ok("opus"),
+        "vorbis" => Ok("vorbis"),
+        "mp3" => Ok("mp3"),
+        "ac3" => Ok("ac3"),
+        "eac3" => Ok("eac3"),
+        codec if codec.starts_with("pcm_") => Ok("pcm"),
+        _ => Err(unsupported_codec("audio", codec)),
+    }
+}
+
+fn unsupported_codec(kind: &str, codec: &str) -> ProbeError {
+    ProbeError::Unsupported(format!("Unsupported {} codec: {}", kind, codec))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// ffprobe's report for an H.264/AAC MP4 of the given display size and length
+    pub(crate) fn mp4_report(width: u32, height: u32, duration_ms: u32) -> Vec<u8> {
+        report(
+            "mov,mp4,m4a,3gp,3g2,mj2",
+            "isom",
+            duration_ms,
+            vec![
+                json!({"codec_type": "video", "codec_name": "h264", "width": width, "height": height}),
+                json!({"codec_type": "audio", "codec_name": "aac"}),
+            ],
+        )
+    }
+
+    /// ffprobe's report for a VP9/Opus WebM of the given length
+    pub(crate) fn webm_report(duration_ms: u32) -> Vec<u8> {
+        report(
+            "matroska,webm",
+            "",
+            duration_ms,
+            vec![
+                json!({"codec_type": "video", "codec_name": "vp9", "width": 1280, "height": 720}),
+                json!({"codec_type": "audio", "codec_name": "opus"}),
+            ],
+        )
+    }
+
+    /// ffprobe's report for an MP3 with cover art
+    pub(crate) fn mp3_report(duration_ms: u32) -> Vec<u8> {
+        report(
+            "mp3",
+            "",
+            duration_ms,
+            vec![
+                json!({"codec_type": "audio", "codec_name": "mp3"}),
+                json!({
+                    "codec_type": "video",
+                    "codec_name": "mjpeg",
+                    "width": 500,
+                    "height": 500,
+                    "disposition": {"default": 0, "attached_pic": 1}
+                }),
+            ],
+        )
+    }
+
+    fn report(
+        format_name: &str,
+        brand: &str,
+        duration_ms: u32,
+        streams: Vec<serde_json::Value>,
+    ) -> Vec<u8> {
+        json!({
+            "streams": streams,
+            "format": {
+                "format_name": format_name,
+                "duration": format!("{:.6}", duration_ms as f64 / 1000.0),
+                "tags": {"major_brand": brand}
+            }
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_parse_mp4() {
+        let probed = parse_ffprobe_output(&mp4_report(720, 1280, 42_400)).unwrap();
+        assert_eq!(
+            probed,
+            ProbedMedia {
+                media_type: "video/mp4",
+                duration_ms: 42_400,
+                width: Some(720),
+                height: Some(1280),
+                video_codec: Some("h264"),
+                audio_codec: Some("aac"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_quicktime_applies_rotation() {
+        let data = report(
+            "mov,mp4,m4a,3gp,3g2,mj2",
+            "qt  ",
+            3000,
+            vec![json!({
+                "codec_type": "video",
+                "codec_name": "hevc",
+                "width": 1920,
+                "height": 1080,
+                "side_data_list": [{"side_data_type": "Display Matrix", "rotation": -90}]
+            })],
+        );
+        let probed = parse_ffprobe_output(&data).unwrap();
+        assert_eq!(probed.media_type, "video/quicktime");
+        assert_eq!((probed.width, probed.height), (Some(1080), Some(1920)));
+        assert_eq!(probed.video_codec, Some("hevc"));
+        assert_eq!(probed.audio_codec, None);
+    }
+
+    #[test]
+    fn test_parse_webm_and_audio() {
+        let webm = parse_ffprobe_output(&webm_report(5000)).unwrap();
+        assert_eq!(webm.media_type, "video/webm");
+        assert_eq!(webm.video_codec, Some("vp9"));
+        assert_eq!(webm.audio_codec, Some("opus"));
+
+        // Cover art does not make an MP3 a video
+        let mp3 = parse_ffprobe_output(&mp3_report(5000)).unwrap();
+        assert_eq!(mp3.media_type, "audio/mpeg");
+        assert_eq!((mp3.width, mp3.height), (None, None));
+        assert_eq!(mp3.video_codec, None);
+        assert_eq!(mp3.audio_codec, Some("mp3"));
+
+        let wav = report(
+            "wav",
+            "",
+            1000,
+            vec![json!({"codec_type": "audio", "codec_name": "pcm_s16le"})],
+        );
+        assert_eq!(parse_ffprobe_output(&wav).unwrap().audio_codec, Some("pcm"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unusable_media() {
+        assert!(matches!(
+            parse_ffprobe_output(&mp4_report(720, 1280, 0)),
+            Err(ProbeError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse_ffprobe_output(&mp4_report(0, 0, 1000)),
+            Err(ProbeError::Invalid(_))
+        ));
+
+        let no_video = report(
+            "mov,mp4,m4a,3gp,3g2,mj2",
+            "M4A ",
+            1000,
+            vec![json!({"codec_type": "audio", "codec_name": "aac"})],
+        );
+        assert!(matches!(
+            parse_ffprobe_output(&no_video),
+            Err(ProbeError::Invalid(_))
+        ));
+
+        let unknown_codec = report(
+            "matroska,webm",
+            "",
+            1000,
+            vec![json!({"codec_type": "video", "codec_name": "theora", "width": 2, "height": 2})],
+        );
+        assert!(matches!(
+            parse_ffprobe_output(&unknown_codec),
+            Err(ProbeError::Unsupported(_))
+        ));
+
+        let unknown_container = report("flv", "", 1000, vec![]);
+        assert!(matches!(
+            parse_ffprobe_output(&unknown_container),
+            Err(ProbeError::Unsupported(_))
+        ));
+        assert!(matches!(
+            parse_ffprobe_output(b"{}"),
+            Err(ProbeError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse_ffprobe_output(b"not json"),
+            Err(ProbeError::Invalid(_))
+        ));
+    }
+}
@@ -1,6 +1,12 @@
 // Infrastructure layer - External concerns
 pub mod cache;
+pub mod content_filter;
 pub mod database;
 pub mod email;
+pub mod impressions;
+pub mod media_processing;
 pub mod sms;
 pub mod storage;
+pub mod suggestions;
+pub mod timeline;
+pub mod view_counter;
@@ -1,2 +1,692 @@
-// Storage implementations will be created in later tasks
-// This file serves as a placeholder for the infrastructure layer structure
+//! Object storage for uploaded media
+//!
+//! `MediaStorage` hides where media bytes live. The local backend writes to a
+//! directory, while the S3 backend talks to any S3-compatible service (AWS S3,
+//! MinIO) through the AWS SDK and hands clients presigned URLs so large files
+//! never pass through the API.
+//!
+//! Stored files are never public. Clients get media URLs signed by
+//! `MediaUrlSigner` that expire shortly after the API handed them out, and the
+//! file route only serves (or redirects to) objects for a valid signature.
+use crate::domain::errors::{AppError, Result};
+use async_trait::async_trait;
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::env;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Longest lifetime S3 accepts for a presigned URL (7 days)
+const MAX_PRESIGN_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Lifetime of the presigned URLs clients are redirected to for downloads
+const DOWNLOAD_PRESIGN_SECONDS: i64 = 5 * 60;
+
+/// How long a signed media URL handed to a client stays valid, at least
+pub const MEDIA_URL_LIFETIME_SECONDS: i64 = 60 * 60;
+
+/// Signed media URL expiries are rounded up to this many seconds, so the URL
+/// for a file stays the same (and cacheable) for a while
+const MEDIA_URL_EXPIRY_ROUNDING_SECONDS: i64 = 15 * 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageProvider {
+    Local,
+    S3,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageConfig {
+    pub provider: StorageProvider,
+    /// Directory used by the local backend
+    pub upload_path: String,
+    /// Base URL of the API route stored objects are served from
+    pub public_url: String,
+    /// Key media URLs are signed with; the JWT secret is used when unset
+    pub url_secret: Option<String>,
+    pub endpoint: Option<String>,
+    pub region: String,
+    pub bucket: Option<String>,
+    pub access_key: Option<String>,
+    pub secret_key: Option<String>,
+}
+
+impl StorageConfig {
+    pub fn from_env() -> Result<Self> {
+        let provider = match env::var("STORAGE_PROVIDER")
+            .unwrap_or_else(|_| "local".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "local" => StorageProvider::Local,
+            "s3" | "minio" => StorageProvider::S3,
+            _ => {
+                return Err(AppError::ConfigurationError(
+                    "Invalid STORAGE_PROVIDER".to_string(),
+                ))
+            }
+        };
+
+        let endpoint = optional_var("STORAGE_ENDPOINT");
+        let bucket = optional_var("STORAGE_BUCKET");
+
+        // Files are always served through the API so access can be checked
+        let public_url = optional_var("STORAGE_PUBLIC_URL").unwrap_or_else(|| {
+            format!(
+                "{}/api/v1/media/files",
+                env::var("BASE_URL")
+                    .unwrap_or_else(|_| "http://localhost:3000".to_string())
+                    .trim_end_matches('/')
+            )
+        });
+
+        Ok(Self {
+            provider,
+            upload_path: env::var("UPLOAD_PATH").unwrap_or_else(|_| "./uploads".to_string()),
+            public_url,
+            url_secret: optional_var("MEDIA_URL_SECRET"),
+            endpoint,
+            region: env::var("STORAGE_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            bucket,
+            access_key: optional_var("STORAGE_ACCESS_KEY"),
+            secret_key: optional_var("STORAGE_SECRET_KEY"),
+        })
+    }
+}
+
+/// Read an optional variable, treating an empty value as unset
+fn optional_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+/// A URL the client uploads a file to directly, bypassing the API
+#[derive(Debug, Clone)]
+pub struct PresignedUpload {
+    pub url: String,
+    pub method: String,
+    /// Headers the client must send unchanged, as they are covered by the signature
+    pub headers: Vec<(String, String)>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Storage backend for media objects addressed by key
+#[async_trait]
+pub trait MediaStorage: Send + Sync {
+    /// Create a URL the client can upload to directly, or None if uploads
+    /// must go through the API
+    async fn presign_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<PresignedUpload>>;
+
+    /// Store an object
+    async fn put_object(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<()>;
+
+    /// Read an object, if it exists
+    async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Size of a stored object in bytes, if it exists
+    async fn object_size(&self, key: &str) -> Result<Option<i64>>;
+
+    /// Delete an object. Deleting a missing object is not an error.
+    async fn delete_object(&self, key: &str) -> Result<()>;
+
+    /// Create a short-lived URL the client can download an object from
+    /// directly, or None if the API must serve the bytes itself
+    async fn presign_download(&self, key: &str) -> Result<Option<String>>;
+
+    /// Unsigned URL of the API route an object is served from. This is what
+    /// gets stored; clients only ever see it signed by `MediaUrlSigner`.
+    fn public_url(&self, key: &str) -> String;
+}
+
+/// Create the storage backend selected by the configuration
+pub fn create_media_storage(config: StorageConfig) -> Result<Arc<dyn MediaStorage>> {
+    match config.provider {
+        StorageProvider::Local => {
+            info!("Using local media storage at {}", config.upload_path);
+            Ok(Arc::new(LocalMediaStorage::new(
+                config.upload_path,
+                config.public_url,
+            )))
+        }
+        StorageProvider::S3 => {
+            let missing = |name: &str| AppError::ConfigurationError(format!("{} not set", name));
+            let endpoint = config.endpoint.ok_or_else(|| missing("STORAGE_ENDPOINT"))?;
+            let storage = S3MediaStorage::new(
+                &endpoint,
+                config.bucket.ok_or_else(|| missing("STORAGE_BUCKET"))?,
+                S3Credentials {
+                    access_key: config
+                        .access_key
+                        .ok_or_else(|| missing("STORAGE_ACCESS_KEY"))?,
+                    secret_key: config
+                        .secret_key
+                        .ok_or_else(|| missing("STORAGE_SECRET_KEY"))?,
+                    region: config.region,
+                },
+                config.public_url,
+            )?;
+            info!("Using S3-compatible media storage at {}", endpoint);
+            Ok(Arc::new(storage))
+        }
+    }
+}
+
+/// Media storage on the local filesystem, served by the API
+pub struct LocalMediaStorage {
+    root: PathBuf,
+    public_url: String,
+}
+
+impl LocalMediaStorage {
+    pub fn new(root: impl Into<PathBuf>, public_url: String) -> Self {
+        Self {
+            root: root.into(),
+            public_url,
+        }
+    }
+
+    /// Resolve a key inside the storage root, rejecting anything that could escape it
+    fn path_for(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        if key.is_empty()
+            || !relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(AppError::ValidationError("Invalid storage key".to_string()));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl MediaStorage for LocalMediaStorage {
+    async fn presign_upload(
+        &self,
+        _key: &str,
+        _content_type: &str,
+        _expires_at: DateTime<Utc>,
+    ) -> Result<Option<PresignedUpload>> {
+        Ok(None)
+    }
+
+    async fn put_object(&self, key: &str, _content_type: &str, data: Vec<u8>) -> Result<()> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                AppError::ExternalServiceError(format!("Failed to create media directory: {}", e))
+            })?;
+        }
+
+        tokio::fs::write(&path, data).await.map_err(|e| {
+            AppError::ExternalServiceError(format!("Failed to store media object: {}", e))
+        })
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::ExternalServiceError(format!(
+                "Failed to read media object: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn object_size(&self, key: &str) -> Result<Option<i64>> {
+        match tokio::fs::metadata(self.path_for(key)?).await {
+            Ok(metadata) => Ok(Some(metadata.len() as i64)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::ExternalServiceError(format!(
+                "Failed to read media object: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::ExternalServiceError(format!(
+                "Failed to delete media object: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn presign_download(&self, _key: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url.trim_end_matches('/'), key)
+    }
+}
+
+/// Credentials and region used to sign S3 requests
+#[derive(Debug, Clone)]
+pub struct S3Credentials {
+    pub access_key: String,
+    pub secret_key: String,
+    pub region: String,
+}
+
+/// Media storage on an S3-compatible service, using path-style addressing
+/// so it works with MinIO as well as AWS
+pub struct S3MediaStorage {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+    public_url: String,
+}
+
+impl S3MediaStorage {
+    pub fn new(
+        endpoint: &str,
+        bucket: String,
+        credentials: S3Credentials,
+        public_url: String,
+    ) -> Result<Self> {
+        let has_host = reqwest::Url::parse(endpoint)
+            .map_err(|e| AppError::ConfigurationError(format!("Invalid STORAGE_ENDPOINT: {}", e)))?
+            .host_str()
+            .is_some();
+        if !has_host {
+            return Err(AppError::ConfigurationError(
+                "STORAGE_ENDPOINT must include a host".to_string(),
+            ));
+        }
+
+        let config = aws_sdk_s3::Config::builder()
+            .endpoint_url(endpoint)
+            .region(Region::new(credentials.region))
+            .credentials_provider(Credentials::new(
+                credentials.access_key,
+                credentials.secret_key,
+                None,
+                None,
+                "storage-config",
+            ))
+            .force_path_style(true)
+            .build();
+
+        Ok(Self {
+            client: aws_sdk_s3::Client::from_conf(config),
+            bucket,
+            public_url,
+        })
+    }
+}
+
+fn presigning_config(expires_in: i64) -> Result<PresigningConfig> {
+    PresigningConfig::expires_in(std::time::Duration::from_secs(expires_in as u64))
+        .map_err(|e| AppError::ExternalServiceError(format!("Failed to presign request: {}", e)))
+}
+
+fn storage_error(action: &str, error: impl std::fmt::Display) -> AppError {
+    AppError::ExternalServiceError(format!("Failed to {}: {}", action, error))
+}
+
+#[async_trait]
+impl MediaStorage for S3MediaStorage {
+    async fn presign_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<PresignedUpload>> {
+        let expires_in = (expires_at - Utc::now())
+            .num_seconds()
+            .clamp(1, MAX_PRESIGN_SECONDS);
+        let presigned = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .presigned(presigning_config(expires_in)?)
+            .await
+            .map_err(|e| storage_error("presign upload", e))?;
+
+        Ok(Some(PresignedUpload {
+            url: presigned.uri().to_string(),
+            method: presigned.method().to_string(),
+            headers: presigned
+                .headers()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            expires_at,
+        }))
+    }
+
+    async fn put_object(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| storage_error("store media object", e.into_service_error()))?;
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let output = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) => {
+                let e = e.into_service_error();
+                return if e.is_no_such_key() {
+                    Ok(None)
+                } else {
+                    Err(storage_error("read media object", e))
+                };
+            }
+        };
+
+        let data = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| storage_error("read media object", e))?;
+        Ok(Some(data.into_bytes().to_vec()))
+    }
+
+    async fn object_size(&self, key: &str) -> Result<Option<i64>> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => output.content_length().map(Some).ok_or_else(|| {
+                AppError::ExternalServiceError(
+                    "Object storage did not report the object size".to_string(),
+                )
+            }),
+            Err(e) => {
+                let e = e.into_service_error();
+                if e.is_not_found() {
+                    Ok(None)
+                } else {
+                    Err(storage_error("inspect media object", e))
+                }
+            }
+        }
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        // S3 reports success for keys that do not exist
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| storage_error("delete media object", e.into_service_error()))?;
+        Ok(())
+    }
+
+    async fn presign_download(&self, key: &str) -> Result<Option<String>> {
+        let presigned = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(presigning_config(DOWNLOAD_PRESIGN_SECONDS)?)
+            .await
+            .map_err(|e| storage_error("presign download", e))?;
+        Ok(Some(presigned.uri().to_string()))
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url.trim_end_matches('/'), key)
+    }
+}
+
+/// Signs the media URLs handed to clients, and checks those signatures when a
+/// file is requested. A signature covers the storage key and an expiry time.
+#[derive(Clone)]
+pub struct MediaUrlSigner {
+    secret: Arc<Vec<u8>>,
+    /// Stored URLs under this base are signed; anything else is left alone
+    public_url: String,
+}
+
+impl MediaUrlSigner {
+    pub fn new(secret: &str, public_url: &str) -> Self {
+        Self {
+            secret: Arc::new(secret.as_bytes().to_vec()),
+            public_url: format!("{}/", public_url.trim_end_matches('/')),
+        }
+    }
+
+    /// Add an expiring signature to a stored media URL. URLs that do not
+    /// point at our storage, such as links sent in messages, are returned as-is.
+    pub fn sign(&self, url: &str) -> String {
+        self.sign_at(url, Utc::now())
+    }
+
+    fn sign_at(&self, url: &str, now: DateTime<Utc>) -> String {
+        let Some(key) = url.strip_prefix(&self.public_url) else {
+            return url.to_string();
+        };
+        let earliest = now.timestamp() + MEDIA_URL_LIFETIME_SECONDS;
+        let expires = (earliest + MEDIA_URL_EXPIRY_ROUNDING_SECONDS - 1)
+            / MEDIA_URL_EXPIRY_ROUNDING_SECONDS
+            * MEDIA_URL_EXPIRY_ROUNDING_SECONDS;
+        format!(
+            "{}?expires={}&signature={}",
+            url,
+            expires,
+            hex::encode(self.mac(key, expires).finalize().into_bytes())
+        )
+    }
+
+    /// Whether a signature for a key is genuine and has not expired
+    pub fn verify(&self, key: &str, expires: i64, signature: &str, now: DateTime<Utc>) -> bool {
+        if expires < now.timestamp() {
+            return false;
+        }
+        // Longer-lived signatures were never handed out
+        if expires
+            > (now + Duration::seconds(MEDIA_URL_LIFETIME_SECONDS)).timestamp()
+                + MEDIA_URL_EXPIRY_ROUNDING_SECONDS
+        {
+            return false;
+        }
+        hex::decode(signature)
+            .is_ok_and(|signature| self.mac(key, expires).verify_slice(&signature).is_ok())
+    }
+
+    fn mac(&self, key: &str, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(key.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires.to_string().as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[tokio::test]
+    async fn test_s3_presigned_urls() {
+        let storage = S3MediaStorage::new(
+            "http://localhost:9000",
+            "media".to_string(),
+            S3Credentials {
+                access_key: "minioadmin".to_string(),
+                secret_key: "minioadmin".to_string(),
+                region: "us-east-1".to_string(),
+            },
+            "http://localhost:3000/api/v1/media/files".to_string(),
+        )
+        .unwrap();
+
+        let upload = storage
+            .presign_upload(
+                "uploads/a.png",
+                "image/png",
+                Utc::now() + Duration::minutes(30),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(upload.method, "PUT");
+        assert!(upload
+            .url
+            .starts_with("http://localhost:9000/media/uploads/a.png?"));
+        assert!(upload
+            .url
+            .contains("X-Amz-SignedHeaders=content-type%3Bhost"));
+        assert!(
+            upload
+                .headers
+                .iter()
+                .any(|(name, value)| name.eq_ignore_ascii_case("content-type")
+                    && value == "image/png")
+        );
+
+        let download = storage
+            .presign_download("uploads/a.png")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(download.starts_with("http://localhost:9000/media/uploads/a.png?"));
+        assert!(download.contains("X-Amz-Expires=300"));
+        assert_eq!(
+            storage.public_url("uploads/a.png"),
+            "http://localhost:3000/api/v1/media/files/uploads/a.png"
+        );
+    }
+
+    #[test]
+    fn test_media_url_signatures() {
+        let signer = MediaUrlSigner::new("secret", "https://api.test/media/files/");
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 3, 0).unwrap();
+
+        let signed = signer.sign_at("https://api.test/media/files/uploads/a.png", now);
+        let (url, query) = signed.split_once('?').unwrap();
+        assert_eq!(url, "https://api.test/media/files/uploads/a.png");
+        let params: std::collections::HashMap<&str, &str> = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .collect();
+        let expires: i64 = params["expires"].parse().unwrap();
+        let signature = params["signature"];
+        // An hour from now, rounded up to the quarter hour
+        assert_eq!(
+            expires,
+            Utc.with_ymd_and_hms(2026, 1, 1, 13, 15, 0)
+                .unwrap()
+                .timestamp()
+        );
+
+        // The URL stays the same within the rounding window
+        assert_eq!(
+            signer.sign_at(
+                "https://api.test/media/files/uploads/a.png",
+                now + Duration::minutes(10)
+            ),
+            signed
+        );
+
+        assert!(signer.verify("uploads/a.png", expires, signature, now));
+        assert!(!signer.verify("uploads/b.png", expires, signature, now));
+        assert!(!signer.verify("uploads/a.png", expires + 900, signature, now));
+        assert!(!signer.verify("uploads/a.png", expires, "not hex", now));
+        assert!(!signer.verify(
+            "uploads/a.png",
+            expires,
+            signature,
+            now + Duration::hours(2)
+        ));
+        assert!(
+            !MediaUrlSigner::new("other", "https://api.test/media/files").verify(
+                "uploads/a.png",
+                expires,
+                signature,
+                now
+            )
+        );
+
+        // Links that do not point at our storage are left alone
+        assert_eq!(
+            signer.sign_at("https://example.com/cat.png", now),
+            "https://example.com/cat.png"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_round_trip() {
+        let root = std::env::temp_dir().join(format!("media-storage-{}", uuid::Uuid::new_v4()));
+        let storage = LocalMediaStorage::new(&root, "http://localhost:3000/media".to_string());
+
+        assert!(storage
+            .presign_upload("uploads/a.png", "image/png", Utc::now())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(storage
+            .presign_download("uploads/a.png")
+            .await
+            .unwrap()
+            .is_none());
+
+        storage
+            .put_object("uploads/a.png", "image/png", vec![1, 2, 3])
+            .await
+            .unwrap();
+        assert_eq!(storage.object_size("uploads/a.png").await.unwrap(), Some(3));
+        assert_eq!(
+            storage.get_object("uploads/a.png").await.unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(
+            storage.public_url("uploads/a.png"),
+            "http://localhost:3000/media/uploads/a.png"
+        );
+
+        storage.delete_object("uploads/a.png").await.unwrap();
+        assert_eq!(storage.object_size("uploads/a.png").await.unwrap(), None);
+        assert!(storage.delete_object("uploads/a.png").await.is_ok());
+
+        let _ = tokio::fs::remove_dir_all(&root).await;
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_rejects_escaping_keys() {
+        let storage = LocalMediaStorage::new("/tmp/media", "http://localhost".to_string());
+
+        for key in ["../etc/passwd", "/etc/passwd", "uploads/../../x", ""] {
+            assert!(storage.get_object(key).await.is_err(), "{}", key);
+        }
+    }
+}
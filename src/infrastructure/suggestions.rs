@@ -0,0 +1,60 @@
+//! Redis follow suggestions
+//!
+//! Each user's suggestions are stored as one serialized list, best first.
+//! Users who look at their suggestions are kept in a sorted set scored by
+//! when they last did, in milliseconds, so the periodic refresh can find
+//! the active ones; members older than the active window are pruned
+//! whenever the set is read.
+use crate::domain::entities::FollowSuggestion;
+use crate::domain::errors::Result;
+use crate::domain::repositories::SuggestionCacheRepository;
+use crate::infrastructure::cache::{CacheConfig, CacheKeys, RedisCache};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub struct RedisSuggestionCacheRepository {
+    cache: RedisCache,
+}
+
+impl RedisSuggestionCacheRepository {
+    pub fn new(cache: RedisCache) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl SuggestionCacheRepository for RedisSuggestionCacheRepository {
+    async fn find(&self, user_id: Uuid) -> Result<Option<Vec<FollowSuggestion>>> {
+        self.cache.get(&CacheKeys::follow_suggestions(user_id))
+    }
+
+    async fn save(&self, user_id: Uuid, suggestions: &[FollowSuggestion]) -> Result<()> {
+        self.cache.set(
+            &CacheKeys::follow_suggestions(user_id),
+            &suggestions,
+            CacheConfig::SUGGESTIONS_TTL,
+        )
+    }
+
+    async fn mark_active(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<()> {
+        self.cache.sorted_set_add(
+            &CacheKeys::suggestion_active_users(),
+            &[(at.timestamp_millis() as f64, user_id.to_string())],
+            CacheConfig::SUGGESTION_ACTIVE_TTL,
+        )
+    }
+
+    async fn active_since(&self, since: DateTime<Utc>, limit: usize) -> Result<Vec<Uuid>> {
+        let key = CacheKeys::suggestion_active_users();
+        let since = since.timestamp_millis() as f64;
+        self.cache.sorted_set_remove_below(&key, since)?;
+
+        Ok(self
+            .cache
+            .sorted_set_range_by_score_rev(&key, since, limit)?
+            .iter()
+            .filter_map(|member| Uuid::parse_str(member).ok())
+            .collect())
+    }
+}
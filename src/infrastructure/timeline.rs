@@ -0,0 +1,114 @@
+//! Redis home timelines
+//!
+//! Each user's timeline is a sorted set scored by post creation time in
+//! milliseconds. Members are `author_id:post_id` so an unfollow can drop an
+//! author's posts without looking them up. Timelines expire when their user
+//! stops reading them and are rebuilt from Postgres on the next read.
+use crate::domain::entities::TimelineEntry;
+use crate::domain::errors::Result;
+use crate::domain::repositories::TimelineRepository;
+use crate::infrastructure::cache::{CacheConfig, CacheKeys, RedisCache};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub struct RedisTimelineRepository {
+    cache: RedisCache,
+}
+
+impl RedisTimelineRepository {
+    pub fn new(cache: RedisCache) -> Self {
+        Self { cache }
+    }
+}
+
+fn to_member(entry: &TimelineEntry) -> (f64, String) {
+    (
+        entry.created_at.timestamp_millis() as f64,
+        format!("{}:{}", entry.author_id, entry.post_id),
+    )
+}
+
+fn parse_member(member: &str, score: f64) -> Option<TimelineEntry> {
+    let (author_id, post_id) = member.split_once(':')?;
+    Some(TimelineEntry {
+        post_id: Uuid::parse_str(post_id).ok()?,
+        author_id: Uuid::parse_str(author_id).ok()?,
+        created_at: DateTime::<Utc>::from_timestamp_millis(score as i64)?,
+    })
+}
+
+#[async_trait]
+impl TimelineRepository for RedisTimelineRepository {
+    async fn find(&self, user_id: Uuid, limit: usize) -> Result<Option<Vec<TimelineEntry>>> {
+        let key = CacheKeys::home_timeline(user_id);
+        if !self.cache.exists(&key)? {
+            return Ok(None);
+        }
+
+        let members = self.cache.sorted_set_range_rev(&key, limit)?;
+        self.cache.expire(&key, CacheConfig::TIMELINE_TTL)?;
+
+        Ok(Some(
+            members
+                .iter()
+                .filter_map(|(member, score)| parse_member(member, *score))
+                .collect(),
+        ))
+    }
+
+    async fn replace(
+        &self,
+        user_id: Uuid,
+        entries: &[TimelineEntry],
+        max_items: usize,
+    ) -> Result<()> {
+        let members: Vec<(f64, String)> = entries.iter().map(to_member).collect();
+        self.cache.sorted_set_replace(
+            &CacheKeys::home_timeline(user_id),
+            &members,
+            max_items,
+            CacheConfig::TIMELINE_TTL,
+        )
+    }
+
+    async fn push(
+        &self,
+        user_ids: &[Uuid],
+        entries: &[TimelineEntry],
+        max_items: usize,
+    ) -> Result<()> {
+        let keys: Vec<String> = user_ids
+            .iter()
+            .map(|user_id| CacheKeys::home_timeline(*user_id))
+            .collect();
+        let members: Vec<(f64, String)> = entries.iter().map(to_member).collect();
+        self.cache
+            .sorted_set_add_existing(&keys, &members, max_items)
+    }
+
+    async fn remove(&self, user_ids: &[Uuid], entry: &TimelineEntry) -> Result<()> {
+        let keys: Vec<String> = user_ids
+            .iter()
+            .map(|user_id| CacheKeys::home_timeline(*user_id))
+            .collect();
+        let (_, member) = to_member(entry);
+        self.cache.sorted_set_remove(&keys, &[member])
+    }
+
+    async fn remove_author(&self, user_id: Uuid, author_id: Uuid) -> Result<()> {
+        let key = CacheKeys::home_timeline(user_id);
+        let prefix = format!("{}:", author_id);
+
+        // Timelines are capped, so reading the whole set is cheap
+        let members: Vec<String> = self
+            .cache
+            .sorted_set_range_rev(&key, CacheConfig::MAX_FEED_ITEMS)?
+            .into_iter()
+            .map(|(member, _)| member)
+            .filter(|member| member.starts_with(&prefix))
+            .collect();
+
+        self.cache.sorted_set_remove(&[key], &members)
+    }
+}
@@ -0,0 +1,133 @@
+//! Redis buffer for post view events
+//!
+//! Every event bumps a per-post hash of counters, and the post id is added to
+//! a set so the rollup job knows which hashes to drain. A short-lived marker
+//! per viewer and post decides whether the event also counts as a view, and a
+//! marker per viewer, post and day whether it adds to the post's reach.
+use crate::domain::entities::{PostViewEvent, PostViewStats};
+use crate::domain::errors::Result;
+use crate::domain::repositories::PendingViewRepository;
+use crate::infrastructure::cache::{CacheKeys, RedisCache};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const IMPRESSIONS_FIELD: &str = "impressions";
+const VIEWS_FIELD: &str = "views";
+const REPLAYS_FIELD: &str = "replays";
+const COMPLETED_FIELD: &str = "completed";
+const WATCH_MS_FIELD: &str = "watch_ms";
+const COMPLETION_SUM_FIELD: &str = "completion_sum";
+const REACH_FIELD: &str = "reach";
+const FOLLOWER_IMPRESSIONS_FIELD: &str = "follower_impressions";
+const FOLLOWER_REACH_FIELD: &str = "follower_reach";
+
+/// Reach markers outlive their day so late events still find them
+const REACH_MARKER_TTL_SECS: u64 = 2 * 24 * 60 * 60;
+
+pub struct RedisPendingViewRepository {
+    cache: RedisCache,
+}
+
+impl RedisPendingViewRepository {
+    pub fn new(cache: RedisCache) -> Self {
+        Self { cache }
+    }
+
+    fn increment(&self, stats: &PostViewStats) -> Result<()> {
+        self.cache.hash_increment(
+            &CacheKeys::post_views_pending(stats.post_id),
+            &[
+                (IMPRESSIONS_FIELD, stats.impression_count),
+                (VIEWS_FIELD, stats.view_count),
+                (REPLAYS_FIELD, stats.replay_count),
+                (COMPLETED_FIELD, stats.completed_count),
+                (WATCH_MS_FIELD, stats.total_watch_ms),
+                (REACH_FIELD, stats.reach_count),
+                (FOLLOWER_IMPRESSIONS_FIELD, stats.follower_impression_count),
+                (FOLLOWER_REACH_FIELD, stats.follower_reach_count),
+            ],
+            &[(COMPLETION_SUM_FIELD, stats.completion_percent_sum)],
+        )?;
+
+        // Added after the counters so a concurrent rollup never drops the post
+        // while it still has counters waiting
+        self.cache
+            .set_add(&CacheKeys::pending_view_posts(), &stats.post_id.to_string())
+    }
+}
+
+fn parse_stats(post_id: Uuid, fields: &HashMap<String, String>) -> PostViewStats {
+    let int = |field: &str| {
+        fields
+            .get(field)
+            .and_then(|value| value.parse::<i64>().ok())
+            .unwrap_or(0)
+    };
+
+    PostViewStats {
+        post_id,
+        impression_count: int(IMPRESSIONS_FIELD),
+        view_count: int(VIEWS_FIELD),
+        replay_count: int(REPLAYS_FIELD),
+        completed_count: int(COMPLETED_FIELD),
+        total_watch_ms: int(WATCH_MS_FIELD),
+        completion_percent_sum: fields
+            .get(COMPLETION_SUM_FIELD)
+            .and_then(|value| value.parse::<f64>().ok())
+            .unwrap_or(0.0),
+        reach_count: int(REACH_FIELD),
+        follower_impression_count: int(FOLLOWER_IMPRESSIONS_FIELD),
+        follower_reach_count: int(FOLLOWER_REACH_FIELD),
+    }
+}
+
+#[async_trait]
+impl PendingViewRepository for RedisPendingViewRepository {
+    async fn record(&self, event: &PostViewEvent, dedup_window_secs: u64) -> Result<bool> {
+        let counted = self.cache.set_if_absent(
+            &CacheKeys::post_view_seen(event.post_id, event.viewer_id),
+            dedup_window_secs,
+        )?;
+        let reached = self.cache.set_if_absent(
+            &CacheKeys::post_view_reach(
+                event.post_id,
+                event.viewer_id,
+                event.occurred_at.date_naive(),
+            ),
+            REACH_MARKER_TTL_SECS,
+        )?;
+
+        self.increment(&PostViewStats::for_event(event, counted, reached))?;
+
+        Ok(counted)
+    }
+
+    async fn take_pending(&self, limit: usize) -> Result<Vec<PostViewStats>> {
+        let post_ids = self
+            .cache
+            .set_pop(&CacheKeys::pending_view_posts(), limit)?;
+
+        let mut pending = Vec::with_capacity(post_ids.len());
+        for post_id in post_ids {
+            let post_id = match Uuid::parse_str(&post_id) {
+                Ok(post_id) => post_id,
+                Err(_) => continue,
+            };
+
+            let fields = self
+                .cache
+                .hash_take(&CacheKeys::post_views_pending(post_id))?;
+            let stats = parse_stats(post_id, &fields);
+            if !stats.is_empty() {
+                pending.push(stats);
+            }
+        }
+
+        Ok(pending)
+    }
+
+    async fn restore(&self, stats: &PostViewStats) -> Result<()> {
+        self.increment(stats)
+    }
+}
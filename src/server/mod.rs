@@ -1,5 +1,6 @@
 pub mod config;
 pub mod router;
+pub mod scheduler;
 pub mod state;
 
 use crate::config::Config;
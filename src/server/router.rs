@@ -32,6 +32,10 @@ mod tests {
             database_url: "postgresql://test".to_string(),
             redis_url: "redis://test".to_string(),
             jwt_secret: "test-secret".to_string(),
+            report_hide_threshold: 5,
+            content_filter_rules_path: None,
+            feed_ranker: "engagement".to_string(),
+            feed_ranking_weights_path: None,
         };
 
         // Note: This will fail if database is not available
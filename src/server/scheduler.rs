@@ -0,0 +1,173 @@
+use crate::api::websocket::{ConnectionManager, WebSocketEvent};
+use crate::application::services::{
+    PollService, PostSchedulerService, PostViewService, PublishedPost, StoryService,
+    SuggestionService,
+};
+use crate::infrastructure::content_filter::ContentFilter;
+use chrono::Utc;
+use std::sync::Arc;
+
+/// How often the scheduler looks for posts and polls that are due
+const PUBLISH_INTERVAL_SECS: u64 = 30;
+
+/// How often expired stories are cleaned up
+const STORY_CLEANUP_INTERVAL_SECS: u64 = 300;
+
+/// How often pending view counters are rolled up into Postgres
+const VIEW_ROLLUP_INTERVAL_SECS: u64 = 60;
+
+/// Number of posts whose view counters are rolled up per batch
+const VIEW_ROLLUP_BATCH_SIZE: usize = 500;
+
+/// How often active users' follow suggestions are recomputed
+const SUGGESTION_REFRESH_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+/// How often the content filter rules file is checked for changes
+const CONTENT_FILTER_RELOAD_INTERVAL_SECS: u64 = 30;
+
+/// Start a background task that publishes scheduled posts once they are due
+/// and pushes them to the author's followers over WebSocket
+pub fn start_post_publisher(
+    scheduler: Arc<PostSchedulerService>,
+    connection_manager: ConnectionManager,
+) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(PUBLISH_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+
+            match scheduler.publish_due_posts(Utc::now()).await {
+                Ok(published) => {
+                    for published_post in &published {
+                        fan_out(&connection_manager, published_post).await;
+                    }
+                    if !published.is_empty() {
+                        tracing::info!("Published {} scheduled posts", published.len());
+                    }
+                }
+                Err(e) => tracing::error!("Failed to publish scheduled posts: {}", e),
+            }
+        }
+    });
+}
+
+/// Start a background task that tells authors when their polls close
+pub fn start_poll_close_notifier(poll_service: Arc<PollService>) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(PUBLISH_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+
+            match poll_service.notify_closed_polls(Utc::now()).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Sent {} poll closed notifications", count),
+                Err(e) => tracing::error!("Failed to notify closed polls: {}", e),
+            }
+        }
+    });
+}
+
+/// Start a background task that removes expired stories and their media
+pub fn start_story_cleanup(story_service: Arc<StoryService>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(
+            STORY_CLEANUP_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+
+            match story_service.delete_expired_stories(Utc::now()).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Removed {} expired stories", count),
+                Err(e) => tracing::error!("Failed to remove expired stories: {}", e),
+            }
+        }
+    });
+}
+
+/// Start a background task that drains pending view counters into post stats
+pub fn start_view_rollup(view_service: Arc<PostViewService>) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(VIEW_ROLLUP_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+
+            // Keep draining while full batches come back
+            let mut total = 0;
+            loop {
+                match view_service
+                    .flush_pending_views(Utc::now(), VIEW_ROLLUP_BATCH_SIZE)
+                    .await
+                {
+                    Ok(count) => {
+                        total += count;
+                        if count < VIEW_ROLLUP_BATCH_SIZE {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to roll up post views: {}", e);
+                        break;
+                    }
+                }
+            }
+
+            if total > 0 {
+                tracing::info!("Rolled up views for {} posts", total);
+            }
+        }
+    });
+}
+
+/// Start a background task that recomputes the follow suggestions of users
+/// who looked at theirs recently, so requests are served from Redis
+pub fn start_suggestion_refresh(suggestion_service: Arc<SuggestionService>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(
+            SUGGESTION_REFRESH_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+
+            match suggestion_service.refresh_active_users(Utc::now()).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Refreshed follow suggestions for {} users", count),
+                Err(e) => tracing::error!("Failed to refresh follow suggestions: {}", e),
+            }
+        }
+    });
+}
+
+/// Start a background task that reloads the content filter rules when their file changes
+pub fn start_content_filter_reload(filter: Arc<ContentFilter>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(
+            CONTENT_FILTER_RELOAD_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+
+            // A broken file keeps the previous rules in place
+            match filter.reload_if_changed() {
+                Ok(true) => tracing::info!("Reloaded {} content filter rules", filter.rule_count()),
+                Ok(false) => {}
+                Err(e) => tracing::error!("Failed to reload content filter rules: {}", e),
+            }
+        }
+    });
+}
+
+/// Notify the followers of a newly published post
+async fn fan_out(connection_manager: &ConnectionManager, published: &PublishedPost) {
+    let event = WebSocketEvent::PostPublished {
+        post_id: published.post.id,
+        user_id: published.post.user_id,
+    };
+
+    connection_manager
+        .send_to_users(&published.follower_ids, event)
+        .await;
+}
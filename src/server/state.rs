@@ -1,18 +1,45 @@
 use crate::api::handlers::auth_handlers::AuthState;
+use crate::api::handlers::bookmark_handlers::BookmarkState;
+use crate::api::handlers::insights_handlers::InsightsState;
+use crate::api::handlers::media_handlers::MediaState;
 use crate::api::handlers::message_handlers::MessageState;
+use crate::api::handlers::moderation_handlers::ModerationState;
 use crate::api::handlers::notification_handlers::NotificationState;
 use crate::api::handlers::payment_handlers::PaymentState;
 use crate::api::handlers::post_handlers::PostState;
+use crate::api::handlers::search_handlers::SearchState;
+use crate::api::handlers::story_handlers::StoryState;
+use crate::api::handlers::user_handlers::UserState;
 use crate::api::websocket::WebSocketState;
-use crate::application::services::NotificationService;
+use crate::application::ranking::ranker_by_name;
+use crate::application::services::{
+    BookmarkService, ContentFilterService, FeedGenerationService, InsightsService, MediaService,
+    MentionService, ModerationService, NotificationService, PinnedPostService, PollService,
+    PostHydrationService, PostSchedulerService, PostViewService, PostVisibilityService,
+    SearchService, StoryService, SuggestionService, TimelineService, UserManagementService,
+};
 use crate::application::verification::VerificationService;
 use crate::config::Config;
 use crate::domain::auth::JwtService;
+use crate::infrastructure::cache::RedisCache;
+use crate::infrastructure::content_filter::{ContentFilter, FilterRuleSet};
 use crate::infrastructure::database::repositories::{
-    InMemoryNotificationPreferencesRepository, PostgresConversationRepository,
-    PostgresDeviceTokenRepository, PostgresMessageRepository, PostgresNotificationRepository,
-    PostgresPostRepository, PostgresUserRepository, PostgresWalletRepository,
+    InMemoryNotificationPreferencesRepository, PostgresBookmarkRepository,
+    PostgresCommentRepository, PostgresConversationRepository, PostgresDeviceTokenRepository,
+    PostgresFeedFeedbackRepository, PostgresInsightsRepository, PostgresMediaUploadRepository,
+    PostgresMentionRepository, PostgresMessageRepository, PostgresModerationRepository,
+    PostgresNotificationRepository, PostgresPollRepository, PostgresPostRepository,
+    PostgresPostViewRepository, PostgresStoryRepository, PostgresSuggestionRepository,
+    PostgresUserRepository, PostgresWalletRepository,
+};
+use crate::infrastructure::impressions::RedisImpressionRepository;
+use crate::infrastructure::media_processing::{
+    FfmpegFrameExtractor, FfprobeMediaProber, MediaProber, PosterFrameExtractor,
 };
+use crate::infrastructure::storage::{create_media_storage, MediaUrlSigner, StorageConfig};
+use crate::infrastructure::suggestions::RedisSuggestionCacheRepository;
+use crate::infrastructure::timeline::RedisTimelineRepository;
+use crate::infrastructure::view_counter::RedisPendingViewRepository;
 use anyhow::Result;
 use std::sync::Arc;
 
@@ -21,9 +48,16 @@ use std::sync::Arc;
 pub struct AppState {
     pub auth_state: AuthState,
     pub post_state: PostState,
+    pub bookmark_state: BookmarkState,
+    pub search_state: SearchState,
+    pub story_state: StoryState,
+    pub insights_state: InsightsState,
+    pub media_state: MediaState,
     pub message_state: MessageState,
+    pub moderation_state: ModerationState,
     pub payment_state: PaymentState,
     pub notification_state: NotificationState,
+    pub user_state: UserState,
     pub ws_state: WebSocketState,
 }
 
@@ -49,6 +83,18 @@ impl AppState {
             as Arc<dyn crate::domain::repositories::MessageRepository>;
         let wallet_repo = Arc::new(PostgresWalletRepository::new(pool.clone()))
             as Arc<dyn crate::domain::repositories::WalletRepository>;
+        let comment_repo = Arc::new(PostgresCommentRepository::new(pool.clone()))
+            as Arc<dyn crate::domain::repositories::CommentRepository>;
+        let mention_repo = Arc::new(PostgresMentionRepository::new(pool.clone()))
+            as Arc<dyn crate::domain::repositories::MentionRepository>;
+        let bookmark_repo = Arc::new(PostgresBookmarkRepository::new(pool.clone()))
+            as Arc<dyn crate::domain::repositories::BookmarkRepository>;
+        let poll_repo = Arc::new(PostgresPollRepository::new(pool.clone()))
+            as Arc<dyn crate::domain::repositories::PollRepository>;
+        let media_upload_repo = Arc::new(PostgresMediaUploadRepository::new(pool.clone()))
+            as Arc<dyn crate::domain::repositories::MediaUploadRepository>;
+        let story_repo = Arc::new(PostgresStoryRepository::new(pool.clone()))
+            as Arc<dyn crate::domain::repositories::StoryRepository>;
 
         // Initialize notification repositories
         let notification_repo = Arc::new(PostgresNotificationRepository::new(pool.clone()))
@@ -69,9 +115,6 @@ impl AppState {
                 anyhow::anyhow!("Failed to initialize verification service: {}", e)
             })?);
 
-        // Initialize auth state
-        let auth_state = AuthState::new(user_repo.clone(), jwt_service, verification_service);
-
         tracing::info!("✅ Authentication and verification services initialized");
 
         // Create WebSocket state and start cleanup task
@@ -80,18 +123,283 @@ impl AppState {
 
         tracing::info!("✅ WebSocket connection manager initialized");
 
+        // Initialize notification service
+        let notification_service = Arc::new(NotificationService::new(
+            notification_repo,
+            device_token_repo,
+            preferences_repo,
+            user_repo.clone(),
+        ));
+
+        tracing::info!("✅ Notification service initialized");
+
+        // Initialize mention service
+        let mention_service = Arc::new(MentionService::new(
+            user_repo.clone(),
+            post_repo.clone(),
+            mention_repo,
+            notification_service.clone(),
+        ));
+
+        // Initialize the post visibility policy shared by every post read path
+        let visibility_service = Arc::new(PostVisibilityService::new(
+            post_repo.clone(),
+            user_repo.clone(),
+        ));
+
+        // Feeds, reels and trending posts are cached in Redis
+        let feed_cache = RedisCache::new(&config.redis_url)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize Redis: {}", e))?;
+
+        // Home timelines are pushed to followers' Redis sorted sets on publish
+        let timeline_cache = RedisCache::new(&config.redis_url)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize Redis: {}", e))?;
+        let timeline_service = Arc::new(TimelineService::new(
+            Arc::new(RedisTimelineRepository::new(timeline_cache)),
+            post_repo.clone(),
+            user_repo.clone(),
+        ));
+        // Algorithmic feeds are ranked by the configured ranker
+        let feed_ranker = ranker_by_name(
+            &config.feed_ranker,
+            config.feed_ranking_weights_path.as_deref(),
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to load feed ranker: {}", e))?;
+        tracing::info!("Ranking feeds with the {} ranker", feed_ranker.name());
+        // Posts shown in feeds are remembered so refreshes surface new ones
+        let impression_cache = RedisCache::new(&config.redis_url)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize Redis: {}", e))?;
+        let feed_service = Arc::new(
+            FeedGenerationService::new(
+                post_repo.clone(),
+                user_repo.clone(),
+                Some(feed_cache),
+                Some(timeline_service),
+            )
+            .with_ranker(feed_ranker)
+            .with_impressions(Arc::new(RedisImpressionRepository::new(impression_cache)))
+            .with_feedback(Arc::new(PostgresFeedFeedbackRepository::new(pool.clone()))),
+        );
+
+        // Initialize post scheduler and start publishing due posts
+        let scheduler_service = Arc::new(PostSchedulerService::new(
+            post_repo.clone(),
+            user_repo.clone(),
+            feed_service.clone(),
+            mention_service.clone(),
+        ));
+        crate::server::scheduler::start_post_publisher(
+            scheduler_service.clone(),
+            ws_state.connection_manager.clone(),
+        );
+
+        tracing::info!("✅ Post scheduler started");
+
+        // Initialize poll service and start announcing closed polls
+        let poll_service = Arc::new(PollService::new(
+            poll_repo,
+            post_repo.clone(),
+            visibility_service.clone(),
+            notification_service.clone(),
+        ));
+        crate::server::scheduler::start_poll_close_notifier(poll_service.clone());
+
+        // Initialize media storage and upload sessions
+        let storage_config = StorageConfig::from_env()
+            .map_err(|e| anyhow::anyhow!("Failed to load storage configuration: {}", e))?;
+        let media_urls = MediaUrlSigner::new(
+            storage_config
+                .url_secret
+                .as_deref()
+                .unwrap_or(&config.jwt_secret),
+            &storage_config.public_url,
+        );
+        let media_storage = create_media_storage(storage_config)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize media storage: {}", e))?;
+        let media_prober = FfprobeMediaProber::from_env()
+            .await
+            .map(|ffprobe| Arc::new(ffprobe) as Arc<dyn MediaProber>);
+        let poster_frames = FfmpegFrameExtractor::from_env()
+            .await
+            .map(|ffmpeg| Arc::new(ffmpeg) as Arc<dyn PosterFrameExtractor>);
+        let media_service = Arc::new(MediaService::new(
+            media_upload_repo,
+            media_storage,
+            media_prober,
+            poster_frames,
+        ));
+
+        tracing::info!("✅ Media storage initialized");
+
+        // Initialize stories and start removing expired ones
+        let story_service = Arc::new(StoryService::new(
+            story_repo,
+            user_repo.clone(),
+            conversation_repo.clone(),
+            message_repo.clone(),
+            media_service.clone(),
+        ));
+        crate::server::scheduler::start_story_cleanup(story_service.clone());
+
+        // Buffer view events in Redis and roll them up into Postgres
+        let view_cache = RedisCache::new(&config.redis_url)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize Redis: {}", e))?;
+        let view_service = Arc::new(PostViewService::new(
+            visibility_service.clone(),
+            user_repo.clone(),
+            Arc::new(RedisPendingViewRepository::new(view_cache)),
+            Arc::new(PostgresPostViewRepository::new(pool.clone())),
+        ));
+        crate::server::scheduler::start_view_rollup(view_service.clone());
+
+        // Follow suggestions are precomputed into Redis for active users
+        let suggestion_cache = RedisCache::new(&config.redis_url)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize Redis: {}", e))?;
+        let suggestion_service = Arc::new(SuggestionService::new(
+            user_repo.clone(),
+            conversation_repo.clone(),
+            Arc::new(PostgresSuggestionRepository::new(pool.clone())),
+            Some(Arc::new(RedisSuggestionCacheRepository::new(
+                suggestion_cache,
+            ))),
+        ));
+        crate::server::scheduler::start_suggestion_refresh(suggestion_service.clone());
+
+        let insights_service = Arc::new(InsightsService::new(
+            Arc::new(PostgresInsightsRepository::new(pool.clone())),
+            post_repo.clone(),
+            user_repo.clone(),
+        ));
+
+        // Reports and the moderation queue act on content, accounts and wallets
+        let moderation_service = Arc::new(ModerationService::new(
+            Arc::new(PostgresModerationRepository::new(pool.clone())),
+            visibility_service.clone(),
+            post_repo.clone(),
+            feed_service.clone(),
+            comment_repo.clone(),
+            conversation_repo.clone(),
+            message_repo.clone(),
+            user_repo.clone(),
+            wallet_repo.clone(),
+            notification_service.clone(),
+            config.report_hide_threshold,
+        ));
+
+        // Text filter for posts, comments, messages and profiles
+        let content_filter = match &config.content_filter_rules_path {
+            Some(path) => {
+                let filter = Arc::new(
+                    ContentFilter::from_file(path)
+                        .map_err(|e| anyhow::anyhow!("Failed to load content filter: {}", e))?,
+                );
+                tracing::info!("Loaded {} content filter rules", filter.rule_count());
+                crate::server::scheduler::start_content_filter_reload(filter.clone());
+                filter
+            }
+            None => Arc::new(
+                ContentFilter::new(FilterRuleSet::default())
+                    .map_err(|e| anyhow::anyhow!("Failed to create content filter: {}", e))?,
+            ),
+        };
+        let content_filter = Arc::new(ContentFilterService::new(
+            content_filter,
+            moderation_service.clone(),
+        ));
+
+        // Initialize auth state
+        let auth_state = AuthState::new(
+            user_repo.clone(),
+            jwt_service,
+            verification_service,
+            content_filter.clone(),
+        );
+
+        // Batch-loads authors, likes and polls for every page of posts
+        let hydration_service = Arc::new(PostHydrationService::new(
+            post_repo.clone(),
+            user_repo.clone(),
+            poll_service.clone(),
+        ));
+
         // Create domain-specific states
+        let bookmark_state = BookmarkState {
+            bookmark_service: Arc::new(BookmarkService::new(
+                bookmark_repo,
+                post_repo.clone(),
+                visibility_service.clone(),
+            )),
+            hydration_service: hydration_service.clone(),
+            media_urls: media_urls.clone(),
+        };
+
+        let search_state = SearchState {
+            search_service: Arc::new(SearchService::new(
+                post_repo.clone(),
+                user_repo.clone(),
+                visibility_service.clone(),
+            )),
+            hydration_service: hydration_service.clone(),
+            media_urls: media_urls.clone(),
+        };
+
+        let story_state = StoryState {
+            story_service,
+            user_repo: user_repo.clone(),
+            content_filter: content_filter.clone(),
+            connection_manager: ws_state.connection_manager.clone(),
+            media_urls: media_urls.clone(),
+        };
+
+        let pinned_post_service = Arc::new(PinnedPostService::new(
+            post_repo.clone(),
+            visibility_service.clone(),
+        ));
+
+        let user_state = UserState {
+            user_service: Arc::new(UserManagementService::new(
+                user_repo.clone(),
+                wallet_repo.clone(),
+            )),
+            insights_service: insights_service.clone(),
+            content_filter: content_filter.clone(),
+            feed_service: feed_service.clone(),
+            suggestion_service,
+        };
+
         let post_state = PostState {
             post_repo,
             user_repo: user_repo.clone(),
+            comment_repo,
+            feed_service,
+            hydration_service,
+            mention_service: mention_service.clone(),
+            scheduler_service,
+            poll_service,
+            visibility_service,
+            media_service: media_service.clone(),
+            view_service,
+            insights_service: insights_service.clone(),
+            content_filter: content_filter.clone(),
+            pinned_post_service,
             connection_manager: ws_state.connection_manager.clone(),
+            media_urls: media_urls.clone(),
         };
 
         let message_state = MessageState {
             conversation_repo,
             message_repo,
             user_repo: user_repo.clone(),
+            mention_service,
+            media_service: media_service.clone(),
+            content_filter,
             connection_manager: ws_state.connection_manager.clone(),
+            media_urls: media_urls.clone(),
+        };
+
+        let media_state = MediaState {
+            media_service,
+            media_urls,
         };
 
         let payment_state = PaymentState {
@@ -100,22 +408,19 @@ impl AppState {
             connection_manager: ws_state.connection_manager.clone(),
         };
 
-        // Initialize notification service
-        let notification_service = Arc::new(NotificationService::new(
-            notification_repo,
-            device_token_repo,
-            preferences_repo,
-            user_repo.clone(),
-        ));
-
-        tracing::info!("✅ Notification service initialized");
-
         Ok(Self {
             auth_state,
             post_state,
+            bookmark_state,
+            search_state,
+            story_state,
+            insights_state: InsightsState { insights_service },
+            media_state,
             message_state,
+            moderation_state: ModerationState { moderation_service },
             payment_state,
             notification_state: notification_service,
+            user_state,
             ws_state,
         })
     }
@@ -21,6 +21,7 @@ mod tests {
             media_attachments: vec![],
             is_reel: false,
             visibility: PostVisibility::Public,
+            poll: None,
         };
 
         let result = Post::new(valid_request);
@@ -33,6 +34,7 @@ mod tests {
             media_attachments: vec![],
             is_reel: false,
             visibility: PostVisibility::Public,
+            poll: None,
         };
 
         let result = Post::new(invalid_request);
@@ -44,8 +46,8 @@ mod tests {
         // Test that reels require video content under 60 seconds
         let user_id = Uuid::new_v4();
 
-        // Valid reel with video under 60 seconds
-        let media = MediaAttachment::new(
+        // A video whose metadata the client supplied cannot be a reel
+        let mut media = MediaAttachment::new(
             "https://example.com/video.mp4".to_string(),
             "video/mp4".to_string(),
             1024 * 1024, // 1MB
@@ -55,19 +57,33 @@ mod tests {
         )
         .unwrap();
 
+        let unprobed_reel = CreatePostRequest {
+            user_id,
+            text_content: Some("Check out my reel!".to_string()),
+            media_attachments: vec![media.clone()],
+            is_reel: true,
+            visibility: PostVisibility::Public,
+            poll: None,
+        };
+        assert!(Post::new(unprobed_reel).is_err());
+
+        // Valid reel with a probed video under 60 seconds
+        media.video_codec = Some("h264".to_string());
+
         let valid_reel = CreatePostRequest {
             user_id,
             text_content: Some("Check out my reel!".to_string()),
             media_attachments: vec![media.clone()],
             is_reel: true,
             visibility: PostVisibility::Public,
+            poll: None,
         };
 
         let result = Post::new(valid_reel);
         assert!(result.is_ok());
 
         // Invalid reel with video over 60 seconds
-        let long_media = MediaAttachment::new(
+        let mut long_media = MediaAttachment::new(
             "https://example.com/long-video.mp4".to_string(),
             "video/mp4".to_string(),
             5 * 1024 * 1024, // 5MB
@@ -76,6 +92,7 @@ mod tests {
             Some(90), // 90 seconds - too long
         )
         .unwrap();
+        long_media.video_codec = Some("h264".to_string());
 
         let invalid_reel = CreatePostRequest {
             user_id,
@@ -83,6 +100,7 @@ mod tests {
             media_attachments: vec![long_media],
             is_reel: true,
             visibility: PostVisibility::Public,
+            poll: None,
         };
 
         let result = Post::new(invalid_reel);
@@ -139,6 +157,7 @@ mod tests {
             media_attachments: vec![],
             is_reel: false,
             visibility: PostVisibility::Public,
+            poll: None,
         };
 
         let post = Post::new(text_post).unwrap();
@@ -164,6 +183,7 @@ mod tests {
             media_attachments: vec![image],
             is_reel: false,
             visibility: PostVisibility::Public,
+            poll: None,
         };
 
         let post = Post::new(image_post).unwrap();
@@ -189,6 +209,7 @@ mod tests {
                 media_attachments: vec![],
                 is_reel: false,
                 visibility: visibility.clone(),
+                poll: None,
             };
 
             let post = Post::new(request).unwrap();
@@ -207,6 +228,7 @@ mod tests {
             media_attachments: vec![],
             is_reel: false,
             visibility: PostVisibility::Public,
+            poll: None,
         };
 
         let post = Post::new(request).unwrap();
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use proptest::prelude::*;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
@@ -10,6 +11,7 @@ use view_social_backend::domain::entities::{
 };
 use view_social_backend::domain::errors::Result;
 use view_social_backend::domain::repositories::{UserRepository, WalletRepository};
+use view_social_backend::domain::value_objects::PageStart;
 
 // Mock implementations for testing
 struct MockUserRepository {
@@ -35,6 +37,11 @@ impl UserRepository for MockUserRepository {
         Ok(self.users.lock().unwrap().get(&id).cloned())
     }
 
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>> {
+        let users = self.users.lock().unwrap();
+        Ok(ids.iter().filter_map(|id| users.get(id).cloned()).collect())
+    }
+
     async fn find_by_username(&self, username: &str) -> Result<Option<User>> {
         Ok(self
             .users
@@ -97,14 +104,28 @@ impl UserRepository for MockUserRepository {
         Ok(vec![])
     }
 
-    async fn get_followers(&self, _user_id: Uuid, _limit: i64, _offset: i64) -> Result<Vec<User>> {
+    async fn get_followers(
+        &self,
+        _user_id: Uuid,
+        _page: PageStart,
+        _limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>> {
         Ok(vec![])
     }
 
-    async fn get_following(&self, _user_id: Uuid, _limit: i64, _offset: i64) -> Result<Vec<User>> {
+    async fn get_following(
+        &self,
+        _user_id: Uuid,
+        _page: PageStart,
+        _limit: i64,
+    ) -> Result<Vec<(User, DateTime<Utc>)>> {
         Ok(vec![])
     }
 
+    async fn follower_count(&self, _user_id: Uuid) -> Result<Option<i32>> {
+        Ok(None)
+    }
+
     async fn is_following(&self, _follower_id: Uuid, _following_id: Uuid) -> Result<bool> {
         Ok(false)
     }
@@ -116,6 +137,42 @@ impl UserRepository for MockUserRepository {
     async fn unfollow(&self, _follower_id: Uuid, _following_id: Uuid) -> Result<()> {
         Ok(())
     }
+
+    async fn has_blocked(&self, _blocker_id: Uuid, _blocked_id: Uuid) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn mute(&self, _user_id: Uuid, _muted_user_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn unmute(&self, _user_id: Uuid, _muted_user_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn muted_user_ids(&self, _user_id: Uuid) -> Result<std::collections::HashSet<Uuid>> {
+        Ok(std::collections::HashSet::new())
+    }
+
+    async fn blocked_user_ids(&self, _user_id: Uuid) -> Result<std::collections::HashSet<Uuid>> {
+        Ok(std::collections::HashSet::new())
+    }
+
+    async fn blocked_ids(
+        &self,
+        _user_id: Uuid,
+        _user_ids: &[Uuid],
+    ) -> Result<std::collections::HashSet<Uuid>> {
+        Ok(std::collections::HashSet::new())
+    }
+
+    async fn followed_ids(
+        &self,
+        _follower_id: Uuid,
+        _user_ids: &[Uuid],
+    ) -> Result<std::collections::HashSet<Uuid>> {
+        Ok(std::collections::HashSet::new())
+    }
 }
 
 struct MockWalletRepository {
@@ -150,6 +207,14 @@ impl WalletRepository for MockWalletRepository {
         Ok(self.wallets.lock().unwrap().get(&id).cloned())
     }
 
+    async fn find_wallets_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Wallet>> {
+        let wallets = self.wallets.lock().unwrap();
+        Ok(ids
+            .iter()
+            .filter_map(|id| wallets.get(id).cloned())
+            .collect())
+    }
+
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<Wallet>> {
         if let Some(wallet_id) = self.user_wallets.lock().unwrap().get(&user_id) {
             Ok(self.wallets.lock().unwrap().get(wallet_id).cloned())
@@ -225,8 +290,8 @@ impl WalletRepository for MockWalletRepository {
     async fn get_transaction_history(
         &self,
         _wallet_id: Uuid,
+        _page: PageStart,
         _limit: i64,
-        _offset: i64,
     ) -> Result<Vec<view_social_backend::domain::entities::Transaction>> {
         Ok(vec![])
     }